@@ -1,5 +1,9 @@
+pub(crate) mod content_lint;
 pub(crate) mod dto;
+pub(crate) mod embed_provider;
 pub mod handlers;
 pub(crate) mod imgix_url;
+pub(crate) mod json_ld;
 pub(crate) mod markdown;
+pub(crate) mod prose_lint;
 pub mod response;
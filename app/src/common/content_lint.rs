@@ -0,0 +1,444 @@
+use crate::common::embed_provider::url_host;
+use crate::common::markdown::{markdown_options, wiki_link_slug};
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::{Arena, parse_document};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// リント結果の重大度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintSeverity {
+    /// 公開は可能だが修正が望ましい
+    Warning,
+    /// 公開を拒否する（明示的に無視した場合を除く）
+    Error,
+}
+
+/// リントルールの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintRule {
+    /// 存在しない記事への内部リンク
+    BrokenInternalLink,
+    /// 代替テキストのない画像
+    MissingAltText,
+    /// imgix以外のホストにある画像
+    ExternalImageHost,
+    /// 見出しレベルの飛び越し（h2の次にh4など）
+    SkippedHeadingLevel,
+    /// 説明文が空
+    EmptyDescription,
+}
+
+impl LintRule {
+    /// ルールの重大度
+    pub fn severity(self) -> LintSeverity {
+        match self {
+            LintRule::BrokenInternalLink | LintRule::EmptyDescription => LintSeverity::Error,
+            LintRule::MissingAltText
+            | LintRule::ExternalImageHost
+            | LintRule::SkippedHeadingLevel => LintSeverity::Warning,
+        }
+    }
+}
+
+/// リントで検出した問題
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// 本文中の位置（1始まりの行番号）。本文以外の問題はNone
+    pub line: Option<usize>,
+}
+
+impl LintIssue {
+    fn new(rule: LintRule, message: String, line: Option<usize>) -> Self {
+        Self {
+            rule,
+            severity: rule.severity(),
+            message,
+            line,
+        }
+    }
+}
+
+/// リンターが参照するサイト固有の設定
+#[derive(Debug, Clone, Copy)]
+pub struct LintConfig<'a> {
+    /// 本文画像を配信するimgixのドメイン（例: "blog-romira.imgix.net"）
+    pub imgix_domain: &'a str,
+    /// サイトのURL（絶対URLの内部リンク判定に使う。例: "https://blog.romira.dev"）
+    pub site_url: &'a str,
+}
+
+/// 問題一覧にエラーが含まれるか
+pub fn has_lint_errors(issues: &[LintIssue]) -> bool {
+    issues
+        .iter()
+        .any(|issue| issue.severity == LintSeverity::Error)
+}
+
+/// 内部リンク（`/articles/{slug}`）のURLからslugを取り出す
+fn internal_article_slug<'a>(url: &'a str, site_url: &str) -> Option<&'a str> {
+    let site_url = site_url.trim_end_matches('/');
+    let path = match url.strip_prefix(site_url) {
+        Some(path) if !site_url.is_empty() && path.starts_with('/') => path,
+        _ => url,
+    };
+    let slug = path
+        .strip_prefix("/articles/")?
+        .split(['/', '?', '#'])
+        .next()?;
+    (!slug.is_empty()).then_some(slug)
+}
+
+/// 本文中の内部リンク（ウィキリンクを含む）先の記事slugを重複なく抽出
+pub fn extract_internal_article_slugs(markdown: &str, site_url: &str) -> Vec<String> {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut slugs = Vec::new();
+    for node in root.descendants() {
+        let data = node.data();
+        let slug = match &data.value {
+            NodeValue::Link(link) => internal_article_slug(&link.url, site_url),
+            NodeValue::WikiLink(link) => wiki_link_slug(&link.url),
+            _ => None,
+        };
+        if let Some(slug) = slug
+            && !slugs.iter().any(|s| s == slug)
+        {
+            slugs.push(slug.to_string());
+        }
+    }
+    slugs
+}
+
+/// 生HTML中の `<img>` タグ（src属性とalt属性の有無）
+struct HtmlImage {
+    src: Option<String>,
+    has_alt: bool,
+}
+
+/// タグ文字列から属性値を取り出す（引用符付きのみ対応）
+fn html_attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(pos) = lower[search_from..].find(name) {
+        let start = search_from + pos;
+        search_from = start + name.len();
+        let preceded_by_space = lower[..start].ends_with(|c: char| c.is_ascii_whitespace());
+        let rest = lower[search_from..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        return value.find(quote).map(|end| value[..end].to_string());
+    }
+    None
+}
+
+/// 生HTMLから `<img>` タグを抽出
+fn extract_html_images(html: &str) -> Vec<HtmlImage> {
+    let lower = html.to_ascii_lowercase();
+    let mut images = Vec::new();
+    let mut search_from = 0;
+    while let Some(pos) = lower[search_from..].find("<img") {
+        let start = search_from + pos;
+        let end = lower[start..]
+            .find('>')
+            .map_or(html.len(), |end| start + end);
+        let tag = &html[start..end];
+        images.push(HtmlImage {
+            src: html_attribute(tag, "src"),
+            has_alt: html_attribute(tag, "alt").is_some(),
+        });
+        search_from = end;
+    }
+    images
+}
+
+/// 画像の代替テキスト（子ノードのテキスト）を連結
+fn image_alt_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut alt = String::new();
+    for descendant in node.descendants() {
+        match &descendant.data().value {
+            NodeValue::Text(text) => alt.push_str(text),
+            NodeValue::Code(code) => alt.push_str(&code.literal),
+            _ => {}
+        }
+    }
+    alt
+}
+
+/// 画像URLのホストを検査
+fn check_image_host(url: &str, config: &LintConfig, line: usize, issues: &mut Vec<LintIssue>) {
+    // 相対パスの画像は自サイト配信なので対象外
+    let Some(host) = url_host(url) else {
+        return;
+    };
+    if !host.eq_ignore_ascii_case(config.imgix_domain) {
+        issues.push(LintIssue::new(
+            LintRule::ExternalImageHost,
+            format!("画像がimgix以外のホストにあります: {host}"),
+            Some(line),
+        ));
+    }
+}
+
+/// 記事本文と説明文の構造をチェック
+///
+/// `existing_slugs` には本文中の内部リンク先のうち、公開済みの記事が存在するslugを渡す。
+/// `public_slugs` にはそのうち一般公開の記事のslugを渡す（ウィキリンクは一般公開の記事にしかリンクしないため）。
+pub fn lint_article(
+    body: &str,
+    description: Option<&str>,
+    config: &LintConfig,
+    existing_slugs: &HashSet<String>,
+    public_slugs: &HashSet<String>,
+) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    if description.is_none_or(|description| description.trim().is_empty()) {
+        issues.push(LintIssue::new(
+            LintRule::EmptyDescription,
+            "説明文（description）が空です".to_string(),
+            None,
+        ));
+    }
+
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, body, &options);
+
+    // 記事タイトルがh1なので、本文の見出しはh2から始まる想定
+    let mut previous_heading_level = 1;
+
+    for node in root.descendants() {
+        let line = node.data().sourcepos.start.line;
+        match &node.data().value {
+            NodeValue::Heading(heading) => {
+                let level = heading.level;
+                if level > previous_heading_level + 1 {
+                    issues.push(LintIssue::new(
+                        LintRule::SkippedHeadingLevel,
+                        format!(
+                            "見出しレベルが h{previous_heading_level} から h{level} に飛んでいます"
+                        ),
+                        Some(line),
+                    ));
+                }
+                previous_heading_level = level;
+            }
+            NodeValue::Link(link) => {
+                if let Some(slug) = internal_article_slug(&link.url, config.site_url)
+                    && !existing_slugs.contains(slug)
+                {
+                    issues.push(LintIssue::new(
+                        LintRule::BrokenInternalLink,
+                        format!("リンク先の記事が見つかりません: /articles/{slug}"),
+                        Some(line),
+                    ));
+                }
+            }
+            NodeValue::WikiLink(link) => {
+                if let Some(slug) = wiki_link_slug(&link.url)
+                    && !public_slugs.contains(slug)
+                {
+                    issues.push(LintIssue::new(
+                        LintRule::BrokenInternalLink,
+                        format!("リンク先の一般公開の記事が見つかりません: [[{slug}]]"),
+                        Some(line),
+                    ));
+                }
+            }
+            NodeValue::Image(link) => {
+                if image_alt_text(node).trim().is_empty() {
+                    issues.push(LintIssue::new(
+                        LintRule::MissingAltText,
+                        "画像に代替テキスト（alt）がありません".to_string(),
+                        Some(line),
+                    ));
+                }
+                check_image_host(&link.url, config, line, &mut issues);
+            }
+            NodeValue::HtmlBlock(block) => {
+                lint_html_images(&block.literal, config, line, &mut issues);
+            }
+            NodeValue::HtmlInline(html) => {
+                lint_html_images(html, config, line, &mut issues);
+            }
+            _ => {}
+        }
+    }
+
+    issues
+}
+
+/// 生HTML中の `<img>` タグをチェック
+fn lint_html_images(html: &str, config: &LintConfig, line: usize, issues: &mut Vec<LintIssue>) {
+    for image in extract_html_images(html) {
+        if !image.has_alt {
+            issues.push(LintIssue::new(
+                LintRule::MissingAltText,
+                "画像に代替テキスト（alt）がありません".to_string(),
+                Some(line),
+            ));
+        }
+        if let Some(src) = image.src {
+            check_image_host(&src, config, line, issues);
+        }
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: LintConfig = LintConfig {
+        imgix_domain: "example.imgix.net",
+        site_url: "https://blog.example.com",
+    };
+
+    fn lint(body: &str, existing_slugs: &[&str]) -> Vec<LintIssue> {
+        lint_with_public(body, existing_slugs, existing_slugs)
+    }
+
+    fn lint_with_public(
+        body: &str,
+        existing_slugs: &[&str],
+        public_slugs: &[&str],
+    ) -> Vec<LintIssue> {
+        let existing_slugs = existing_slugs.iter().map(|s| s.to_string()).collect();
+        let public_slugs = public_slugs.iter().map(|s| s.to_string()).collect();
+        lint_article(
+            body,
+            Some("説明文"),
+            &CONFIG,
+            &existing_slugs,
+            &public_slugs,
+        )
+    }
+
+    fn rules(issues: &[LintIssue]) -> Vec<LintRule> {
+        issues.iter().map(|issue| issue.rule).collect()
+    }
+
+    #[test]
+    fn 問題のない記事では何も検出されないこと() {
+        let body = "## 見出し\n\n![図](https://example.imgix.net/a.png)\n\n[前回](/articles/prev)\n\n### 小見出し\n";
+        assert_eq!(lint(body, &["prev"]), vec![]);
+    }
+
+    #[test]
+    fn 説明文が空ならエラーになること() {
+        let issues = lint_article(
+            "本文",
+            Some("  "),
+            &CONFIG,
+            &HashSet::new(),
+            &HashSet::new(),
+        );
+        assert_eq!(rules(&issues), vec![LintRule::EmptyDescription]);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+        assert_eq!(issues[0].line, None);
+
+        let issues = lint_article("本文", None, &CONFIG, &HashSet::new(), &HashSet::new());
+        assert_eq!(rules(&issues), vec![LintRule::EmptyDescription]);
+    }
+
+    #[test]
+    fn 存在しない記事への内部リンクがエラーになること() {
+        let body = "本文\n\n[リンク](/articles/missing#section)と[外部](https://example.com/articles/foo)と[絶対](https://blog.example.com/articles/prev/)";
+        let issues = lint(body, &["prev"]);
+
+        assert_eq!(rules(&issues), vec![LintRule::BrokenInternalLink]);
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("/articles/missing"));
+    }
+
+    #[test]
+    fn 存在しない記事へのウィキリンクがエラーになること() {
+        let body = "[[prev]]と[[prev|前回の記事]]\n\n[[missing|未公開の記事]]";
+        let issues = lint(body, &["prev"]);
+
+        assert_eq!(rules(&issues), vec![LintRule::BrokenInternalLink]);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("[[missing]]"));
+    }
+
+    #[test]
+    fn 限定公開の記事へのウィキリンクがエラーになり通常のリンクはエラーにならないこと() {
+        let body = "[前回](/articles/unlisted)\n\n[[unlisted]]";
+        let issues = lint_with_public(body, &["unlisted"], &[]);
+
+        assert_eq!(rules(&issues), vec![LintRule::BrokenInternalLink]);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("[[unlisted]]"));
+    }
+
+    #[test]
+    fn 代替テキストのない画像が警告になること() {
+        let body = "![](https://example.imgix.net/a.png)\n\n<img src=\"https://example.imgix.net/b.png\">\n\n<img src=\"https://example.imgix.net/c.png\" alt=\"\">";
+        let issues = lint(body, &[]);
+
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::MissingAltText, LintRule::MissingAltText]
+        );
+        assert_eq!(issues[0].line, Some(1));
+        assert_eq!(issues[1].line, Some(3));
+        assert_eq!(issues[0].severity, LintSeverity::Warning);
+    }
+
+    #[test]
+    fn imgix以外のホストの画像が警告になること() {
+        let body = "![a](https://example.com/a.png)\n\n![b](/static/b.png) <img src='https://other.imgix.net/c.png' alt='c'>";
+        let issues = lint(body, &[]);
+
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::ExternalImageHost, LintRule::ExternalImageHost]
+        );
+        assert!(issues[0].message.contains("example.com"));
+        assert!(issues[1].message.contains("other.imgix.net"));
+    }
+
+    #[test]
+    fn 見出しレベルの飛び越しが警告になること() {
+        let body = "### いきなりh3\n\n## h2\n\n#### h4\n\n## h2\n";
+        let issues = lint(body, &[]);
+
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::SkippedHeadingLevel, LintRule::SkippedHeadingLevel]
+        );
+        assert_eq!(issues[0].line, Some(1));
+        assert_eq!(issues[1].line, Some(5));
+        assert!(issues[1].message.contains("h2 から h4"));
+    }
+
+    #[test]
+    fn extract_internal_article_slugsで内部リンクのslugが重複なく抽出されること() {
+        let body = "[a](/articles/foo) [b](https://blog.example.com/articles/bar?x=1) [c](/articles/foo#top) [d](/about) [[baz|ラベル]] [[foo]]";
+        assert_eq!(
+            extract_internal_article_slugs(body, CONFIG.site_url),
+            vec!["foo".to_string(), "bar".to_string(), "baz".to_string()]
+        );
+    }
+
+    #[test]
+    fn has_lint_errorsでエラーの有無が判定できること() {
+        let warning = LintIssue::new(LintRule::MissingAltText, String::new(), Some(1));
+        let error = LintIssue::new(LintRule::EmptyDescription, String::new(), None);
+
+        assert!(!has_lint_errors(std::slice::from_ref(&warning)));
+        assert!(has_lint_errors(&[warning, error]));
+    }
+}
@@ -9,15 +9,56 @@ pub struct HomePageArticleDto {
     pub(crate) category: Vec<RwSignal<String>>,
     pub(crate) first_published_at: RwSignal<String>,
     pub(crate) article_source: ArticleSource,
+    /// トップページの「おすすめ」に固定表示する記事か
+    #[serde(default)]
+    pub(crate) featured: bool,
+    /// 記事の絵文字（Zennのアイキャッチ絵文字）
+    #[serde(default)]
+    pub(crate) emoji: Option<String>,
+    /// 同じ記事の他の掲載先（ソースをまたいで重複する記事を1件にまとめた場合）
+    #[serde(default)]
+    pub(crate) also_on: Vec<AlsoOnDto>,
 }
 
+/// 同じ記事の他の掲載先
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlsoOnDto {
+    pub(crate) article_source: ArticleSource,
+    pub(crate) url: String,
+}
+
+/// トップページの記事一覧
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopPageArticlesDto {
+    /// おすすめを先頭に並べた記事
+    pub(crate) articles: Vec<HomePageArticleDto>,
+    /// 直近の取得に失敗した外部記事ソースの名前（前回取得できた記事を表示している）
+    pub(crate) degraded_sources: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) enum ArticleSource {
     WordPress,
     Qiita,
+    Zenn,
+    /// RSS / Atom フィード（設定したソース名）
+    Feed(String),
     Local,
 }
 
+impl ArticleSource {
+    /// 記事カードに表示するソース名（ローカル記事は表示しない）
+    pub(crate) fn label(&self) -> Option<&str> {
+        match self {
+            ArticleSource::WordPress => Some("PR TIMES"),
+            ArticleSource::Qiita => Some("Qiita"),
+            ArticleSource::Zenn => Some("Zenn"),
+            ArticleSource::Feed(name) => Some(name),
+            ArticleSource::Local => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HomePageAuthorDto {
     pub(crate) name: RwSignal<String>,
@@ -29,12 +70,25 @@ pub struct HomePageAuthorDto {
 pub struct ArticlePageDto {
     pub(crate) article_detail_dto: ArticleDetailDto,
     pub(crate) article_meta_dto: ArticleMetaDto,
+    /// 本文の下に表示する関連記事
+    pub(crate) related_articles: Vec<HomePageArticleDto>,
+}
+
+/// カテゴリページのDTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryPageDto {
+    pub(crate) name: String,
+    pub(crate) slug: String,
+    /// カテゴリに属する公開記事（公開日時の降順）
+    pub(crate) articles: Vec<HomePageArticleDto>,
 }
 
 /// 記事取得の結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ArticleResponse {
     Found(Box<ArticlePageDto>),
+    /// パスワード保護された記事で、閲覧許可Cookieがない（記事のslug）
+    Protected(String),
     Redirect(String),
     NotFound(()),
 }
@@ -48,6 +102,15 @@ pub struct ArticleDetailDto {
     pub(crate) category: Vec<RwSignal<String>>,
     pub(crate) first_published_at: RwSignal<String>,
     pub(crate) first_published_at_iso: RwSignal<String>,
+    /// この記事を参照している記事（バックリンク）
+    pub(crate) backlinks: Vec<ArticleLinkDto>,
+}
+
+/// 記事間リンクで表示する記事
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleLinkDto {
+    pub(crate) title: String,
+    pub(crate) src: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,4 +123,11 @@ pub struct ArticleMetaDto {
     pub(crate) og_image_url: RwSignal<String>,
     pub(crate) published_at: RwSignal<String>,
     pub(crate) first_published_at: RwSignal<String>,
+    /// 正規URL（SEO設定で上書きしていなければ記事ページのURL）
+    pub(crate) canonical_url: RwSignal<String>,
+    /// robotsメタタグの値（未設定なら出力しない）
+    pub(crate) robots: RwSignal<Option<String>>,
+    pub(crate) og_title: RwSignal<String>,
+    pub(crate) og_description: RwSignal<String>,
+    pub(crate) twitter_card: RwSignal<String>,
 }
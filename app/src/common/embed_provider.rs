@@ -0,0 +1,323 @@
+//! 埋め込みプロバイダーのレジストリ
+//!
+//! 本文中の単独行URLをoEmbedで埋め込みに変換する際のプロバイダー定義と、
+//! サニタイズ時に許可するiframe・クラスのポリシーを一元管理する。
+//! プロバイダーは環境変数 `EMBED_PROVIDERS`（JSON配列）で定義し、未設定なら `EmbedProviders::default()` の定義を使う。
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 埋め込みプロバイダーの定義（`EMBED_PROVIDERS` のJSON配列の1要素）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EmbedProvider {
+    /// プロバイダー名
+    pub name: String,
+    /// 埋め込み対象とするURLのホスト（`*.` で始まるものはサブドメインにマッチ）
+    pub url_hosts: Vec<String>,
+    /// oEmbedエンドポイント
+    pub oembed_endpoint: String,
+    /// iframeの src として許可するホスト
+    #[serde(default)]
+    pub iframe_hosts: Vec<String>,
+    /// `iframe_hosts` のiframeに共通の属性に加えて許可する属性
+    #[serde(default)]
+    pub iframe_attributes: Vec<String>,
+    /// 埋め込みHTMLで保持するクラス（タグ名 → クラス名）
+    #[serde(default)]
+    pub allowed_classes: HashMap<String, Vec<String>>,
+}
+
+/// iframeに共通で許可する属性
+const COMMON_IFRAME_ATTRIBUTES: &[&str] = &[
+    "src",
+    "width",
+    "height",
+    "title",
+    "allow",
+    "allowfullscreen",
+    "loading",
+    "referrerpolicy",
+    "sandbox",
+    "frameborder",
+];
+
+/// 有効な埋め込みプロバイダーの一覧
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbedProviders {
+    providers: Arc<Vec<EmbedProvider>>,
+}
+
+impl Default for EmbedProviders {
+    /// 既定のプロバイダー（YouTube / X / CodePen / Speaker Deck）
+    fn default() -> Self {
+        fn strings(values: &[&str]) -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        }
+
+        Self::new(vec![
+            EmbedProvider {
+                name: "YouTube".to_string(),
+                url_hosts: strings(&[
+                    "youtube.com",
+                    "www.youtube.com",
+                    "m.youtube.com",
+                    "youtu.be",
+                ]),
+                oembed_endpoint: "https://www.youtube.com/oembed".to_string(),
+                iframe_hosts: strings(&["www.youtube.com", "www.youtube-nocookie.com"]),
+                iframe_attributes: vec![],
+                allowed_classes: HashMap::new(),
+            },
+            EmbedProvider {
+                name: "X".to_string(),
+                url_hosts: strings(&[
+                    "x.com",
+                    "twitter.com",
+                    "www.twitter.com",
+                    "mobile.twitter.com",
+                ]),
+                oembed_endpoint: "https://publish.twitter.com/oembed".to_string(),
+                iframe_hosts: strings(&["platform.twitter.com"]),
+                iframe_attributes: strings(&["scrolling"]),
+                // scriptは除去されるため、ポストは引用として表示する（クラスはスタイルとwidgets.jsの目印に残す）
+                allowed_classes: HashMap::from([(
+                    "blockquote".to_string(),
+                    strings(&["twitter-tweet"]),
+                )]),
+            },
+            EmbedProvider {
+                name: "CodePen".to_string(),
+                url_hosts: strings(&["codepen.io"]),
+                oembed_endpoint: "https://codepen.io/api/oembed".to_string(),
+                iframe_hosts: strings(&["codepen.io"]),
+                iframe_attributes: strings(&["scrolling", "allowtransparency"]),
+                allowed_classes: HashMap::new(),
+            },
+            EmbedProvider {
+                name: "Speaker Deck".to_string(),
+                url_hosts: strings(&["speakerdeck.com"]),
+                oembed_endpoint: "https://speakerdeck.com/oembed.json".to_string(),
+                iframe_hosts: strings(&["speakerdeck.com"]),
+                iframe_attributes: vec![],
+                allowed_classes: HashMap::new(),
+            },
+        ])
+    }
+}
+
+impl EmbedProviders {
+    pub fn new(providers: Vec<EmbedProvider>) -> Self {
+        Self {
+            providers: Arc::new(providers),
+        }
+    }
+
+    /// `EMBED_PROVIDERS` のJSON配列から読み込む
+    ///
+    /// 未設定（空文字列）なら既定のプロバイダーを使い、JSONが不正ならエラーログを出して既定のプロバイダーを使う。
+    pub fn from_config(json: &str) -> Self {
+        if json.trim().is_empty() {
+            return Self::default();
+        }
+        match serde_json::from_str::<Vec<EmbedProvider>>(json) {
+            Ok(providers) => Self::new(providers),
+            Err(err) => {
+                tracing::error!(
+                    error = err.to_string(),
+                    "Used default embed providers instead of invalid EMBED_PROVIDERS"
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// URLを埋め込みに変換するプロバイダーを検索
+    pub fn find(&self, url: &str) -> Option<&EmbedProvider> {
+        let host = url_host(url)?;
+        self.providers.iter().find(|provider| {
+            provider
+                .url_hosts
+                .iter()
+                .any(|pattern| host_matches(pattern, host))
+        })
+    }
+
+    /// iframeの src のホストを許可しているプロバイダー
+    fn find_by_iframe_host(&self, host: &str) -> Option<&EmbedProvider> {
+        self.providers.iter().find(|provider| {
+            provider
+                .iframe_hosts
+                .iter()
+                .any(|pattern| host_matches(pattern, host))
+        })
+    }
+
+    /// iframeの src のホストがいずれかのプロバイダーで許可されているか判定
+    pub fn is_allowed_iframe_host(&self, host: &str) -> bool {
+        self.find_by_iframe_host(host).is_some()
+    }
+
+    /// サニタイズでiframeに許可する属性（全プロバイダーの和集合）
+    ///
+    /// iframeごとには `is_allowed_iframe_attribute` で src のホストに応じて絞り込む。
+    pub fn allowed_iframe_attributes(&self) -> Vec<&str> {
+        let mut attributes = COMMON_IFRAME_ATTRIBUTES.to_vec();
+        for provider in self.providers.iter() {
+            for attribute in &provider.iframe_attributes {
+                if !attributes.contains(&attribute.as_str()) {
+                    attributes.push(attribute);
+                }
+            }
+        }
+        attributes
+    }
+
+    /// src のホストが `host` のiframeに属性を許可するか判定（共通の属性と、そのホストのプロバイダーの属性）
+    pub fn is_allowed_iframe_attribute(&self, host: Option<&str>, attribute: &str) -> bool {
+        COMMON_IFRAME_ATTRIBUTES.contains(&attribute)
+            || host
+                .and_then(|host| self.find_by_iframe_host(host))
+                .is_some_and(|provider| {
+                    provider
+                        .iframe_attributes
+                        .iter()
+                        .any(|allowed| allowed == attribute)
+                })
+    }
+
+    /// サニタイズで保持する埋め込みのクラス（タグ名, クラス名）
+    pub fn allowed_classes(&self) -> Vec<(&str, &str)> {
+        self.providers
+            .iter()
+            .flat_map(|provider| &provider.allowed_classes)
+            .flat_map(|(tag, classes)| {
+                classes
+                    .iter()
+                    .map(move |class| (tag.as_str(), class.as_str()))
+            })
+            .collect()
+    }
+}
+
+/// ホストがパターンにマッチするか判定
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.')),
+        None => pattern == host,
+    }
+}
+
+/// URLのホスト名を取り出す（ポートと認証情報は除く）
+pub(crate) fn url_host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '\\', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_hostでホスト名が取り出せること() {
+        assert_eq!(
+            url_host("https://www.youtube.com/watch?v=abc"),
+            Some("www.youtube.com")
+        );
+        assert_eq!(
+            url_host("http://user@example.com:8080/"),
+            Some("example.com")
+        );
+        assert_eq!(url_host("mailto:foo@example.com"), None);
+        assert_eq!(
+            url_host("https://evil.example\\@www.youtube.com/"),
+            Some("evil.example")
+        );
+    }
+
+    #[test]
+    fn host_matchesでワイルドカードがサブドメインのみにマッチすること() {
+        assert!(host_matches("*.example.com", "a.example.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(!host_matches("*.example.com", "evilexample.com"));
+        assert!(host_matches("example.com", "example.com"));
+    }
+
+    #[test]
+    fn findでメディアurlのプロバイダーが見つかること() {
+        let providers = EmbedProviders::default();
+        let provider = providers.find("https://youtu.be/dQw4w9WgXcQ").unwrap();
+        assert_eq!(provider.name, "YouTube");
+        let provider = providers.find("https://x.com/user/status/1").unwrap();
+        assert_eq!(provider.name, "X");
+        assert!(providers.find("https://example.com/").is_none());
+    }
+
+    #[test]
+    fn is_allowed_iframe_hostでレジストリのホストのみ許可されること() {
+        let providers = EmbedProviders::default();
+        assert!(providers.is_allowed_iframe_host("www.youtube-nocookie.com"));
+        assert!(providers.is_allowed_iframe_host("codepen.io"));
+        assert!(!providers.is_allowed_iframe_host("evil.example.com"));
+        assert!(!providers.is_allowed_iframe_host("www.youtube.com.evil.example"));
+    }
+
+    #[test]
+    fn from_configで設定したプロバイダーだけ使うこと() {
+        let providers = EmbedProviders::from_config(
+            r#"[{
+                "name": "Vimeo",
+                "url_hosts": ["vimeo.com"],
+                "oembed_endpoint": "https://vimeo.com/api/oembed.json",
+                "iframe_hosts": ["player.vimeo.com"],
+                "iframe_attributes": ["scrolling"]
+            }]"#,
+        );
+
+        assert_eq!(
+            providers
+                .find("https://vimeo.com/1")
+                .unwrap()
+                .oembed_endpoint,
+            "https://vimeo.com/api/oembed.json"
+        );
+        assert!(providers.find("https://youtu.be/abc").is_none());
+        assert!(providers.is_allowed_iframe_host("player.vimeo.com"));
+        assert!(!providers.is_allowed_iframe_host("www.youtube.com"));
+        assert_eq!(
+            EmbedProviders::from_config("not json"),
+            EmbedProviders::default()
+        );
+        assert_eq!(EmbedProviders::from_config(""), EmbedProviders::default());
+    }
+
+    #[test]
+    fn is_allowed_iframe_attributeでホストのプロバイダーの属性のみ許可されること() {
+        let providers = EmbedProviders::default();
+        assert!(providers.is_allowed_iframe_attribute(Some("codepen.io"), "allowtransparency"));
+        assert!(providers.is_allowed_iframe_attribute(Some("platform.twitter.com"), "scrolling"));
+        assert!(!providers.is_allowed_iframe_attribute(Some("www.youtube.com"), "scrolling"));
+        assert!(providers.is_allowed_iframe_attribute(Some("www.youtube.com"), "src"));
+        assert!(!providers.is_allowed_iframe_attribute(None, "scrolling"));
+    }
+
+    #[test]
+    fn allowed_iframe_attributesに重複がないこと() {
+        let providers = EmbedProviders::default();
+        let attributes = providers.allowed_iframe_attributes();
+        let mut deduped = attributes.clone();
+        deduped.sort_unstable();
+        deduped.dedup();
+        assert_eq!(attributes.len(), deduped.len());
+        assert!(attributes.contains(&"src"));
+        assert!(attributes.contains(&"scrolling"));
+    }
+}
@@ -1,7 +1,10 @@
 pub mod admin;
 pub mod auth;
 
-use crate::common::dto::{ArticlePageDto, ArticleResponse, HomePageArticleDto, HomePageAuthorDto};
+use crate::common::dto::{
+    ArticlePageDto, ArticleResponse, CategoryPageDto, HomePageArticleDto, HomePageAuthorDto,
+    TopPageArticlesDto,
+};
 use crate::constants::ROMIRA_NEWT_AUTHOR_ID;
 use crate::error::{GetArticleError, GetArticlesError, GetAuthorError};
 use leptos::prelude::*;
@@ -14,59 +17,42 @@ use tracing::instrument;
 #[instrument]
 #[server(input = GetUrl, endpoint = "get_articles_handler")]
 pub(crate) async fn get_articles_handler()
--> Result<Vec<HomePageArticleDto>, ServerFnError<GetArticlesError>> {
+-> Result<TopPageArticlesDto, ServerFnError<GetArticlesError>> {
     use crate::AppState;
     use crate::common::response::set_top_page_cache_control;
-    use leptos_axum::ResponseOptions;
+    use crate::server::article_dedup::{local_article_links, merge_duplicate_articles};
+    use crate::server::featured::pin_featured_articles;
+    use std::collections::HashMap;
 
     let app_state = expect_context::<AppState>();
-    let wordpress_article_service = app_state.word_press_article_service;
-    let qiita_article_service = app_state.qiita_article_service;
+    // 外部記事はバックグラウンドで取得済みのキャッシュだけを読む
+    let external_article_cache = app_state.external_article_cache();
+    let external_articles = external_article_cache.articles();
+    let degraded_sources = external_article_cache.degraded_sources();
+    let syndication_service = app_state.syndication_service().clone();
     let published_article_service = app_state.published_article_service;
-    let response = expect_context::<ResponseOptions>();
+    let featured_item_service = app_state.featured_item_service;
 
     // キャッシュコントロールを設定（既に設定済みならスキップ）
     set_top_page_cache_control();
 
-    let mut articles = Vec::new();
-
-    let wordpress_articles = wordpress_article_service.fetch_articles().await;
-    let wordpress_articles = match wordpress_articles {
-        Ok(articles) => articles,
-        Err(err) => {
-            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
-            tracing::error!(
-                error = err.to_string(),
-                "Failed to get articles from WordPressArticleService",
-            );
-            return Err(ServerFnError::from(
-                GetArticlesError::WordPressArticleService(
-                    "Failed to get articles from WordPressArticleService".to_string(),
-                ),
-            ));
-        }
-    };
-
-    let qiita_articles = qiita_article_service.fetch_articles().await;
-    let qiita_articles = match qiita_articles {
-        Ok(articles) => articles,
-        Err(err) => {
-            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
-            tracing::error!(
-                error = err.to_string(),
-                "Failed to get articles from QiitaArticleService",
-            );
-            return Err(ServerFnError::from(GetArticlesError::QiitaArticleService(
-                "Failed to get articles from QiitaArticleService".to_string(),
-            )));
-        }
-    };
-
-    articles.extend(wordpress_articles.into_iter().map(HomePageArticleDto::from));
-    articles.extend(qiita_articles.into_iter().map(HomePageArticleDto::from));
+    let mut articles: Vec<HomePageArticleDto> = external_articles
+        .into_iter()
+        .map(HomePageArticleDto::from)
+        .collect();
 
+    let mut local_links = HashMap::new();
     match published_article_service.fetch_all().await {
         Ok(local_articles) => {
+            // 転載状況は取得できなくても取り込み元URLやタイトルで重複を判定する
+            let syndications = syndication_service
+                .fetch_synced()
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(error = err.to_string(), "Failed to get syndications");
+                    Vec::new()
+                });
+            local_links = local_article_links(&local_articles, &syndications);
             articles.extend(local_articles.into_iter().map(HomePageArticleDto::from));
         }
         Err(err) => {
@@ -75,8 +61,21 @@ pub(crate) async fn get_articles_handler()
     }
 
     articles.sort_unstable_by_key(|a| Reverse(a.first_published_at.get()));
+    let articles = merge_duplicate_articles(articles, &local_links);
 
-    Ok(articles)
+    // おすすめは取得できなくても記事一覧は表示する
+    let featured_items = featured_item_service
+        .fetch_active()
+        .await
+        .unwrap_or_else(|err| {
+            tracing::warn!(error = err.to_string(), "Failed to get featured items");
+            Vec::new()
+        });
+
+    Ok(TopPageArticlesDto {
+        articles: pin_featured_articles(articles, &featured_items),
+        degraded_sources,
+    })
 }
 
 #[instrument]
@@ -111,6 +110,61 @@ pub(crate) async fn get_author_handler() -> Result<HomePageAuthorDto, ServerFnEr
     Ok(author.into())
 }
 
+/// カテゴリに属する公開記事を取得（該当する記事がなければ `None`）
+#[instrument]
+#[server(input = GetUrl, endpoint = "get_category_articles_handler")]
+pub(crate) async fn get_category_articles_handler(
+    slug: String,
+) -> Result<Option<CategoryPageDto>, ServerFnError<GetArticleError>> {
+    use crate::AppState;
+    use crate::common::response::set_top_page_cache_control;
+    use leptos_axum::ResponseOptions;
+
+    let app_state = expect_context::<AppState>();
+    let published_article_service = app_state.published_article_service;
+    let response = expect_context::<ResponseOptions>();
+
+    // 記事一覧と同じタイミングで更新されるため、トップページと同じキャッシュ設定にする
+    set_top_page_cache_control();
+
+    let articles = match published_article_service.fetch_all().await {
+        Ok(articles) => articles,
+        Err(err) => {
+            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            tracing::error!(error = err.to_string(), "Failed to get category articles");
+            return Err(ServerFnError::from(GetArticleError::DatabaseError(
+                "Failed to get category articles".to_string(),
+            )));
+        }
+    };
+
+    let articles: Vec<_> = articles
+        .into_iter()
+        .filter(|article| {
+            article
+                .categories
+                .iter()
+                .any(|category| category.slug == slug)
+        })
+        .collect();
+    let Some(name) = articles.first().and_then(|article| {
+        article
+            .categories
+            .iter()
+            .find(|category| category.slug == slug)
+            .map(|category| category.name.clone())
+    }) else {
+        response.set_status(StatusCode::NOT_FOUND);
+        return Ok(None);
+    };
+
+    Ok(Some(CategoryPageDto {
+        name,
+        slug,
+        articles: articles.into_iter().map(HomePageArticleDto::from).collect(),
+    }))
+}
+
 #[instrument]
 #[server(input = GetUrl, endpoint = "get_article_handler")]
 pub(crate) async fn get_article_handler(
@@ -120,10 +174,12 @@ pub(crate) async fn get_article_handler(
     use crate::common::dto::ArticleResponse;
     use crate::common::response::set_article_page_cache_control;
     use crate::constants::get_newt_redirect_slug;
+    use crate::server::http::response::set_private_article_page_cache_control;
+    use blog_romira_dev_cms::ArticleVisibility;
     use leptos_axum::ResponseOptions;
 
     let app_state = expect_context::<AppState>();
-    let published_article_service = app_state.published_article_service;
+    let published_article_service = app_state.published_article_service.clone();
     let response = expect_context::<ResponseOptions>();
 
     // キャッシュコントロールを設定
@@ -132,9 +188,45 @@ pub(crate) async fn get_article_handler(
     // 1. DB記事をslugで検索
     match published_article_service.fetch_by_slug(&id).await {
         Ok(Some(article)) => {
-            return Ok(ArticleResponse::Found(Box::new(ArticlePageDto::from(
-                article,
-            ))));
+            let visibility = article.article.visibility;
+            if !visibility.is_public() {
+                set_private_article_page_cache_control(&response);
+            }
+            if visibility == ArticleVisibility::Protected
+                && !is_article_unlocked(&app_state, &article.article.slug).await
+            {
+                return Ok(ArticleResponse::Protected(article.article.slug));
+            }
+            let render_context = app_state
+                .article_render_service
+                .build_context(&article.article.body, Some(format!("article:{}", id)))
+                .await;
+            // バックリンクは取得できなくても記事自体は表示する
+            let backlinks = app_state
+                .article_link_service
+                .fetch_backlinks(&article.article.slug)
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(error = err.to_string(), "Failed to fetch backlinks");
+                    Vec::new()
+                });
+            // 関連記事も取得できなくても記事自体は表示する
+            let related_articles = app_state
+                .related_article_service
+                .fetch_for_article(article.article.id)
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(error = err.to_string(), "Failed to fetch related articles");
+                    Vec::new()
+                });
+            return Ok(ArticleResponse::Found(Box::new(
+                ArticlePageDto::from_published(
+                    article,
+                    &render_context,
+                    backlinks,
+                    related_articles,
+                ),
+            )));
         }
         Ok(None) => {
             // DB記事が見つからない場合、リダイレクトマッピングを確認
@@ -166,6 +258,115 @@ pub(crate) async fn get_article_handler(
     response.set_status(StatusCode::NOT_FOUND);
     Ok(ArticleResponse::NotFound(()))
 }
+/// パスワード保護された記事の閲覧許可Cookieがリクエストに含まれるか
+#[cfg(feature = "ssr")]
+async fn is_article_unlocked(app_state: &crate::AppState, slug: &str) -> bool {
+    use axum::http::HeaderMap;
+
+    let password_hash = match app_state
+        .published_article_service
+        .fetch_password_hash(slug)
+        .await
+    {
+        Ok(Some(password_hash)) => password_hash,
+        Ok(None) => return false,
+        Err(err) => {
+            tracing::error!(error = err.to_string(), "Failed to get password hash");
+            return false;
+        }
+    };
+    let Ok(headers) = leptos_axum::extract::<HeaderMap>().await else {
+        return false;
+    };
+    app_state.article_access_service().is_unlocked(
+        &headers,
+        slug,
+        &password_hash,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// パスワード保護された記事のパスワードを検証し、一致すれば閲覧許可Cookieを発行する
+///
+/// 一致しない場合（記事が存在しない・保護されていない場合を含む）は `false` を返す。
+/// クライアントのIPと記事ごとの試行回数が上限を超えた場合は検証せず429を返す。
+#[instrument(skip(password))]
+#[server(endpoint = "unlock_article")]
+pub(crate) async fn unlock_article_handler(
+    slug: String,
+    password: String,
+) -> Result<bool, ServerFnError<GetArticleError>> {
+    use crate::AppState;
+    use crate::server::http::response::set_private_article_page_cache_control;
+    use axum::http::header::SET_COOKIE;
+    use axum::http::{HeaderMap, HeaderValue};
+    use blog_romira_dev_cms::PublishedArticleVisibility;
+    use leptos_axum::ResponseOptions;
+
+    let app_state = expect_context::<AppState>();
+    let response = expect_context::<ResponseOptions>();
+
+    set_private_article_page_cache_control(&response);
+
+    let client = leptos_axum::extract::<HeaderMap>()
+        .await
+        .map(|headers| app_state.unlock_attempt_limiter().client_ip(&headers))
+        .unwrap_or_else(|_| "unknown".to_string());
+    if !app_state
+        .unlock_attempt_limiter()
+        .try_attempt(&client, &slug)
+        .await
+    {
+        response.set_status(StatusCode::TOO_MANY_REQUESTS);
+        return Err(ServerFnError::from(GetArticleError::TooManyAttempts));
+    }
+
+    let password_hash = match app_state
+        .published_article_service
+        .fetch_password_hash(&slug)
+        .await
+    {
+        Ok(Some(password_hash)) => password_hash,
+        Ok(None) => return Ok(false),
+        Err(err) => {
+            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            tracing::error!(error = err.to_string(), "Failed to get password hash");
+            return Err(ServerFnError::from(GetArticleError::DatabaseError(
+                "Failed to get password hash".to_string(),
+            )));
+        }
+    };
+    // Argon2の検証は重いため非同期のワーカーを塞がないよう別スレッドで実行する
+    let verified = {
+        let password_hash = password_hash.clone();
+        tokio::task::spawn_blocking(move || {
+            PublishedArticleVisibility::verify_password(&password, &password_hash)
+        })
+        .await
+    };
+    match verified {
+        Ok(true) => {}
+        Ok(false) => return Ok(false),
+        Err(err) => {
+            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            tracing::error!(error = err.to_string(), "Failed to verify password");
+            return Err(ServerFnError::from(GetArticleError::Unknown(
+                "Failed to verify password".to_string(),
+            )));
+        }
+    }
+
+    let set_cookie = app_state.article_access_service().build_set_cookie_header(
+        &slug,
+        &password_hash,
+        chrono::Utc::now().timestamp(),
+    );
+    if let Ok(value) = HeaderValue::from_str(&set_cookie) {
+        response.insert_header(SET_COOKIE, value);
+    }
+    Ok(true)
+}
+
 #[instrument]
 #[server(input = GetUrl, endpoint = "get_preview_article_handler")]
 pub(crate) async fn get_preview_article_handler(
@@ -190,7 +391,13 @@ pub(crate) async fn get_preview_article_handler(
 
     let article = draft_article_service.fetch_by_id(uuid).await;
     match article {
-        Ok(Some(article)) => Ok(Some(ArticlePageDto::from(article))),
+        Ok(Some(article)) => {
+            let render_context = app_state
+                .article_render_service
+                .build_context(&article.article.body, None)
+                .await;
+            Ok(Some(ArticlePageDto::from_draft(article, &render_context)))
+        }
         Ok(None) => {
             response.set_status(StatusCode::NOT_FOUND);
             Ok(None)
@@ -1,21 +1,52 @@
+#[cfg(feature = "ssr")]
+mod article_links;
 mod delete_article;
+mod external_sources;
+mod featured_items;
 mod get_admin_articles;
 mod get_article_for_edit;
 mod get_categories;
 pub mod images;
+mod lint_article;
+mod preview_og_image;
+mod prose_check_settings;
 mod publish_article;
+mod qiita_import;
+#[cfg(feature = "ssr")]
+mod related_articles;
 mod save_draft;
 mod save_published;
+mod syndications;
 
 pub use delete_article::{DeleteArticleInput, delete_article_handler};
+pub use external_sources::{ExternalSourceStatusData, get_external_source_statuses_handler};
+pub use featured_items::{
+    FeaturedItemData, SaveFeaturedItemsInput, get_featured_items_handler,
+    save_featured_items_handler,
+};
 pub use get_admin_articles::{AdminArticleListItem, get_admin_articles_handler};
-pub use get_article_for_edit::{ArticleEditData, get_article_for_edit_handler};
+pub use get_article_for_edit::{
+    ArticleEditData, ArticleSeoData, ArticleVisibilityData, get_article_for_edit_handler,
+};
 pub use get_categories::get_categories_handler;
 pub use images::{
     DeleteImageInput, GenerateUploadUrlInput, GenerateUploadUrlResponse, ImageDto,
     RegisterImageInput, delete_image_handler, generate_upload_url_handler, get_images_handler,
     register_image_handler,
 };
+pub use lint_article::{LintArticleInput, lint_article_handler};
+pub use preview_og_image::{PreviewOgImageInput, preview_og_image_handler};
+pub use prose_check_settings::{
+    get_prose_check_settings_handler, save_prose_check_settings_handler,
+};
 pub use publish_article::{PublishArticleInput, publish_article_handler};
+pub use qiita_import::{
+    ImportQiitaItemsInput, ImportedDraftData, QiitaImportCandidateData,
+    get_qiita_import_candidates_handler, import_qiita_items_handler,
+};
 pub use save_draft::{SaveDraftInput, save_draft_handler};
 pub use save_published::{SavePublishedInput, save_published_handler};
+pub use syndications::{
+    RetrySyndicationInput, SyndicationStatusData, get_syndications_handler,
+    retry_syndication_handler,
+};
@@ -0,0 +1,44 @@
+use crate::server::contexts::AppState;
+use uuid::Uuid;
+
+/// 公開記事の本文中のリンク先を保存し、表示が変わる記事のCDNキャッシュタグを返す
+///
+/// リンク先の記事はバックリンクが、この記事を参照している記事はウィキリンクの表示が変わりうる。
+/// 保存に失敗しても記事の保存・公開自体は成功扱いにする（ベストエフォート）。
+pub(super) async fn save_article_links(
+    state: &AppState,
+    article_id: Uuid,
+    slug: &str,
+    body: &str,
+) -> Vec<String> {
+    let target_slugs: Vec<String> = state
+        .content_lint_service()
+        .referenced_article_slugs(body)
+        .into_iter()
+        .filter(|target| target != slug)
+        .collect();
+
+    let mut affected_slugs = match state
+        .article_link_service()
+        .save_links(article_id, &target_slugs)
+        .await
+    {
+        Ok(slugs) => slugs,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to save article links");
+            Vec::new()
+        }
+    };
+
+    match state.article_link_service().fetch_backlinks(slug).await {
+        Ok(backlinks) => affected_slugs.extend(backlinks.into_iter().map(|article| article.slug)),
+        Err(e) => tracing::warn!(error = %e, "Failed to fetch backlinks"),
+    }
+
+    affected_slugs.sort();
+    affected_slugs.dedup();
+    affected_slugs
+        .into_iter()
+        .map(|slug| format!("article:{slug}"))
+        .collect()
+}
@@ -29,16 +29,20 @@ pub async fn delete_article_handler(input: DeleteArticleInput) -> Result<(), Ser
             .await
             .map_err(|e| cms_error_to_response(&response, e))?;
     } else {
-        // slug取得（パージサービスが設定されている場合のみ）
-        let article = if state.cloudflare_purge_service().is_some() {
-            state
+        // slugと、この記事を関連記事に含む記事の取得（パージサービスが設定されている場合のみ。
+        // 関連記事は削除と同時に消えるため、削除前に取得する）
+        let (article, referring_tags) = if state.cloudflare_purge_service().is_some() {
+            let article = state
                 .published_article_service()
                 .fetch_by_id_for_admin(uuid)
                 .await
                 .ok()
-                .flatten()
+                .flatten();
+            let referring_tags =
+                super::related_articles::fetch_referring_article_tags(&state, uuid).await;
+            (article, referring_tags)
         } else {
-            None
+            (None, Vec::new())
         };
 
         state
@@ -47,12 +51,23 @@ pub async fn delete_article_handler(input: DeleteArticleInput) -> Result<(), Ser
             .await
             .map_err(|e| cms_error_to_response(&response, e))?;
 
+        // 関連記事を再計算（削除した記事が抜けた一覧を埋め直す）
+        let related_tags = super::related_articles::refresh_related_articles(&state, uuid).await;
+
         // CDNキャッシュパージ（ベストエフォート、未設定ならスキップ）
         if let Some(purge_service) = state.cloudflare_purge_service() {
-            let mut tags = vec!["top-page".to_string(), "sitemap".to_string()];
+            let mut tags = vec![
+                "top-page".to_string(),
+                "sitemap".to_string(),
+                "feed".to_string(),
+            ];
             if let Some(ref article) = article {
                 tags.push(format!("article:{}", article.article.slug));
             }
+            tags.extend(referring_tags);
+            tags.extend(related_tags);
+            tags.sort();
+            tags.dedup();
             if let Err(e) = purge_service.purge_tags(&tags).await {
                 tracing::warn!(error = %e, "Failed to purge Cloudflare cache after delete");
             }
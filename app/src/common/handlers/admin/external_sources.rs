@@ -0,0 +1,38 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::GetUrl;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// 外部記事ソースの最終更新の状況
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExternalSourceStatusData {
+    /// ソース名
+    pub name: String,
+    /// 最後に取得を試みた日時（JST、表示用）
+    pub last_attempt_at: Option<String>,
+    /// 最後に取得に成功した日時（JST、表示用）
+    pub last_success_at: Option<String>,
+    /// 直近の取得が失敗した場合のエラー
+    pub last_error: Option<String>,
+    /// キャッシュしている記事数
+    pub article_count: usize,
+    /// 続けて失敗したため取得を止めている場合、再開する日時（JST、表示用）
+    pub paused_until: Option<String>,
+}
+
+/// 外部記事ソースごとの最終更新の状況を登録順に取得
+#[instrument]
+#[server(input = GetUrl, endpoint = "admin/get_external_source_statuses")]
+pub async fn get_external_source_statuses_handler()
+-> Result<Vec<ExternalSourceStatusData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+
+    let state = expect_context::<AppState>();
+
+    Ok(state
+        .external_article_cache()
+        .statuses()
+        .into_iter()
+        .map(ExternalSourceStatusData::from)
+        .collect())
+}
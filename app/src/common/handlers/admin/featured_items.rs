@@ -0,0 +1,76 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{GetUrl, Json};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// トップページの「おすすめ」の1項目
+///
+/// ローカル記事（`article_id`）か外部記事のURL（`external_url`）のどちらか一方を指定する。
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeaturedItemData {
+    /// 公開記事ID（UUID文字列）
+    pub article_id: Option<String>,
+    /// Qiita / WordPress の記事URL
+    pub external_url: Option<String>,
+    /// この日（JST）まで表示する（`YYYY-MM-DD`）。未指定なら無期限
+    pub expires_on: Option<String>,
+}
+
+/// おすすめの保存用入力（並び順が表示順になる）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SaveFeaturedItemsInput {
+    pub items: Vec<FeaturedItemData>,
+}
+
+/// おすすめを表示順に取得（期限切れの項目を含む）
+#[instrument]
+#[server(input = GetUrl, endpoint = "admin/get_featured_items")]
+pub async fn get_featured_items_handler() -> Result<Vec<FeaturedItemData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+
+    let state = expect_context::<AppState>();
+    let items = state
+        .featured_item_service()
+        .fetch_all()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(items.into_iter().map(FeaturedItemData::from).collect())
+}
+
+/// おすすめを保存（すべての項目を入力の順で置き換える）
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/save_featured_items")]
+pub async fn save_featured_items_handler(
+    input: SaveFeaturedItemsInput,
+) -> Result<(), ServerFnError> {
+    use crate::server::contexts::AppState;
+    use crate::server::http::response::cms_error_to_response;
+    use blog_romira_dev_cms::FeaturedItemEntry;
+    use leptos_axum::ResponseOptions;
+
+    let response = expect_context::<ResponseOptions>();
+    let state = expect_context::<AppState>();
+
+    let entries = input
+        .items
+        .into_iter()
+        .map(FeaturedItemEntry::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| cms_error_to_response(&response, e))?;
+
+    state
+        .featured_item_service()
+        .save(&entries)
+        .await
+        .map_err(|e| cms_error_to_response(&response, e))?;
+
+    // CDNキャッシュパージ（ベストエフォート、未設定ならスキップ）
+    if let Some(purge_service) = state.cloudflare_purge_service()
+        && let Err(e) = purge_service.purge_tags(&["top-page".to_string()]).await
+    {
+        tracing::warn!(error = %e, "Failed to purge Cloudflare cache after saving featured items");
+    }
+
+    Ok(())
+}
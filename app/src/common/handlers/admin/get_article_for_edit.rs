@@ -3,6 +3,59 @@ use leptos::server_fn::codec::GetUrl;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
+use super::SyndicationStatusData;
+
+/// 記事ごとのSEO設定（未設定の項目は `None`）
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleSeoData {
+    pub canonical_url: Option<String>,
+    pub robots: Option<String>,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image_url: Option<String>,
+    pub twitter_card: Option<String>,
+}
+
+/// 公開記事の公開範囲
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArticleVisibilityData {
+    /// 一覧に表示する
+    #[default]
+    Public,
+    /// URLを知っている人のみ閲覧できる
+    Unlisted,
+    /// パスワードを入力した人のみ閲覧できる
+    Protected,
+}
+
+impl ArticleVisibilityData {
+    pub const ALL: [Self; 3] = [Self::Public, Self::Unlisted, Self::Protected];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unlisted => "unlisted",
+            Self::Protected => "protected",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Public => "公開",
+            Self::Unlisted => "限定公開（URLを知っている人のみ）",
+            Self::Protected => "パスワード保護",
+        }
+    }
+
+    /// `as_str` の値から変換（不明な値は `None`）
+    pub fn from_value(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|visibility| visibility.as_str() == value)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ArticleEditData {
     pub id: String,
@@ -13,6 +66,20 @@ pub struct ArticleEditData {
     pub cover_image_url: Option<String>,
     pub is_draft: bool,
     pub categories: Vec<String>,
+    #[serde(default)]
+    pub seo: ArticleSeoData,
+    /// 公開範囲（下書きは常に `Public`）
+    #[serde(default)]
+    pub visibility: ArticleVisibilityData,
+    /// パスワードが設定済みか
+    #[serde(default)]
+    pub has_password: bool,
+    /// 外部サービスから取り込んだ記事の取り込み元URL
+    #[serde(default)]
+    pub origin_url: Option<String>,
+    /// 転載先ごとの反映状況（下書きは常に空）
+    #[serde(default)]
+    pub syndications: Vec<SyndicationStatusData>,
 }
 
 #[instrument]
@@ -44,6 +111,11 @@ pub async fn get_article_for_edit_handler(
             cover_image_url: draft.article.cover_image_url,
             is_draft: true,
             categories: draft.categories.into_iter().map(|c| c.name).collect(),
+            seo: draft.article.seo.0.into(),
+            visibility: ArticleVisibilityData::Public,
+            has_password: false,
+            origin_url: draft.article.origin_url,
+            syndications: Vec::new(),
         }));
     }
 
@@ -53,6 +125,15 @@ pub async fn get_article_for_edit_handler(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?
     {
+        let has_password = published_service
+            .has_password(uuid)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let syndications = state
+            .syndication_service()
+            .fetch_statuses(uuid)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
         return Ok(Some(ArticleEditData {
             id: published.article.id.to_string(),
             title: published.article.title,
@@ -62,6 +143,14 @@ pub async fn get_article_for_edit_handler(
             cover_image_url: published.article.cover_image_url,
             is_draft: false,
             categories: published.categories.into_iter().map(|c| c.name).collect(),
+            seo: published.article.seo.0.into(),
+            visibility: published.article.visibility.into(),
+            has_password,
+            origin_url: published.article.origin_url,
+            syndications: syndications
+                .into_iter()
+                .map(SyndicationStatusData::from)
+                .collect(),
         }));
     }
 
@@ -0,0 +1,58 @@
+use crate::common::content_lint::LintIssue;
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// 記事の構造チェック用入力
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LintArticleInput {
+    pub body: String,
+    pub description: Option<String>,
+}
+
+/// 記事の構造チェック（エディタの問題一覧表示用）
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/lint_article")]
+pub async fn lint_article_handler(
+    input: LintArticleInput,
+) -> Result<Vec<LintIssue>, ServerFnError> {
+    use crate::server::contexts::AppState;
+    use crate::server::http::response::cms_error_to_response;
+    use leptos_axum::ResponseOptions;
+
+    let response = expect_context::<ResponseOptions>();
+    let state = expect_context::<AppState>();
+
+    state
+        .content_lint_service()
+        .lint(&input.body, input.description.as_deref())
+        .await
+        .map_err(|e| cms_error_to_response(&response, e))
+}
+
+/// 公開前の構造チェック（エラーがあれば公開を拒否する）
+#[cfg(feature = "ssr")]
+pub(super) async fn ensure_publishable(
+    state: &crate::server::contexts::AppState,
+    body: &str,
+    description: Option<&str>,
+) -> Result<(), blog_romira_dev_cms::CmsError> {
+    use crate::common::content_lint::LintSeverity;
+    use blog_romira_dev_cms::CmsError;
+
+    let issues = state.content_lint_service().lint(body, description).await?;
+    let errors: Vec<&str> = issues
+        .iter()
+        .filter(|issue| issue.severity == LintSeverity::Error)
+        .map(|issue| issue.message.as_str())
+        .collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(CmsError::ValidationError(format!(
+        "公開できない問題があります: {}",
+        errors.join("、")
+    )))
+}
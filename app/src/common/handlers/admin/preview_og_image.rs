@@ -0,0 +1,39 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// OGP画像プレビュー用入力
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreviewOgImageInput {
+    pub title: String,
+    pub category_names: Vec<String>,
+}
+
+/// 編集中のタイトル・カテゴリで自動生成のOGP画像を作成（PNGのdata URLを返す）
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/preview_og_image")]
+pub async fn preview_og_image_handler(input: PreviewOgImageInput) -> Result<String, ServerFnError> {
+    use crate::server::contexts::AppState;
+    use crate::server::services::og_image::OgCardContent;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use leptos_axum::ResponseOptions;
+    use reqwest::StatusCode;
+
+    let response = expect_context::<ResponseOptions>();
+    let state = expect_context::<AppState>();
+
+    let content = OgCardContent {
+        title: input.title,
+        categories: input.category_names,
+    };
+    match state.og_image_service().render_preview(&content).await {
+        Ok(png) => Ok(format!("data:image/png;base64,{}", STANDARD.encode(png))),
+        Err(err) => {
+            tracing::error!(error = err.to_string(), "Failed to render OG image preview");
+            response.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+            Err(ServerFnError::new("OGP画像の生成に失敗しました"))
+        }
+    }
+}
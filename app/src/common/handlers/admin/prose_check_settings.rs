@@ -0,0 +1,60 @@
+use crate::common::prose_lint::ProseCheckSettings;
+use leptos::prelude::*;
+use leptos::server_fn::codec::{GetUrl, Json};
+use tracing::instrument;
+
+/// 文章チェックの設定を取得（未保存のルールは有効として返す）
+#[instrument]
+#[server(input = GetUrl, endpoint = "admin/get_prose_check_settings")]
+pub async fn get_prose_check_settings_handler() -> Result<ProseCheckSettings, ServerFnError> {
+    use crate::common::prose_lint::ProseRule;
+    use crate::server::contexts::AppState;
+
+    let state = expect_context::<AppState>();
+    let saved = state
+        .prose_check_rule_service()
+        .fetch_all()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let mut settings = ProseCheckSettings::default();
+    for saved_rule in saved {
+        let Some(rule) = ProseRule::from_key(&saved_rule.rule) else {
+            continue;
+        };
+        if let Some(setting) = settings.rules.iter_mut().find(|s| s.rule == rule) {
+            setting.enabled = saved_rule.enabled;
+        }
+    }
+    Ok(settings)
+}
+
+/// 文章チェックの設定を保存
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/save_prose_check_settings")]
+pub async fn save_prose_check_settings_handler(
+    input: ProseCheckSettings,
+) -> Result<(), ServerFnError> {
+    use crate::server::contexts::AppState;
+    use crate::server::http::response::cms_error_to_response;
+    use blog_romira_dev_cms::ProseCheckRule;
+    use leptos_axum::ResponseOptions;
+
+    let response = expect_context::<ResponseOptions>();
+    let state = expect_context::<AppState>();
+
+    let rules: Vec<ProseCheckRule> = input
+        .rules
+        .iter()
+        .map(|setting| ProseCheckRule {
+            rule: setting.rule.key().to_string(),
+            enabled: setting.enabled,
+        })
+        .collect();
+
+    state
+        .prose_check_rule_service()
+        .save(&rules)
+        .await
+        .map_err(|e| cms_error_to_response(&response, e))
+}
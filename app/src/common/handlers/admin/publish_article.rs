@@ -7,6 +7,9 @@ use tracing::instrument;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PublishArticleInput {
     pub id: String,
+    /// 構造チェックのエラーを無視して公開する
+    #[serde(default)]
+    pub ignore_lint_errors: bool,
 }
 
 #[instrument(skip(input))]
@@ -14,6 +17,7 @@ pub struct PublishArticleInput {
 pub async fn publish_article_handler(input: PublishArticleInput) -> Result<String, ServerFnError> {
     use crate::server::contexts::AppState;
     use crate::server::http::response::cms_error_to_response;
+    use blog_romira_dev_cms::CmsError;
     use leptos_axum::ResponseOptions;
     use uuid::Uuid;
 
@@ -21,15 +25,62 @@ pub async fn publish_article_handler(input: PublishArticleInput) -> Result<Strin
     let state = expect_context::<AppState>();
     let uuid = Uuid::parse_str(&input.id).map_err(|e| ServerFnError::new(e.to_string()))?;
 
+    // 下書きが取得できない場合は構造チェックを飛ばして公開しないよう、ここでエラーにする
+    let draft = state
+        .draft_article_service()
+        .fetch_by_id(uuid)
+        .await
+        .and_then(|draft| draft.ok_or(CmsError::NotFound))
+        .map_err(|e| cms_error_to_response(&response, e))?;
+
+    // 構造チェック（エラーがあれば明示的に無視しない限り公開しない）
+    if !input.ignore_lint_errors {
+        super::lint_article::ensure_publishable(
+            &state,
+            &draft.article.body,
+            draft.article.description.as_deref(),
+        )
+        .await
+        .map_err(|e| cms_error_to_response(&response, e))?;
+    }
+
+    // リンクカードのOGPを事前取得（公開後の初回描画で外部取得を待たないようにする）
+    state
+        .link_card_service()
+        .prefetch(&draft.article.body)
+        .await;
+
     let published_id = state
         .draft_article_service()
         .publish(uuid)
         .await
         .map_err(|e| cms_error_to_response(&response, e))?;
 
+    // 記事間リンクを保存（リンク先・参照元の記事はキャッシュをパージする）
+    let link_tags = super::article_links::save_article_links(
+        &state,
+        published_id,
+        &draft.article.slug,
+        &draft.article.body,
+    )
+    .await;
+
+    // 関連記事を再計算（一覧が変わった記事はキャッシュをパージする）
+    let related_tags =
+        super::related_articles::refresh_related_articles(&state, published_id).await;
+
+    // 転載先に反映（失敗は反映状況に記録する）
+    super::syndications::syndicate_article(&state, published_id).await;
+
     // CDNキャッシュパージ（ベストエフォート、未設定ならスキップ）
     if let Some(purge_service) = state.cloudflare_purge_service() {
-        let tags = vec!["top-page".to_string(), "sitemap".to_string()];
+        let mut tags = vec![
+            "top-page".to_string(),
+            "sitemap".to_string(),
+            "feed".to_string(),
+        ];
+        tags.extend(link_tags);
+        tags.extend(related_tags);
         if let Err(e) = purge_service.purge_tags(&tags).await {
             tracing::warn!(error = %e, "Failed to purge Cloudflare cache after publish");
         }
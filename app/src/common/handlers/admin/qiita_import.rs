@@ -0,0 +1,71 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{GetUrl, Json};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// 取り込みの候補となるQiitaの記事
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QiitaImportCandidateData {
+    /// Qiitaの記事ID
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub tags: Vec<String>,
+    /// 投稿日（JST、表示用）
+    pub created_at: String,
+    /// 取り込み済みか
+    pub imported: bool,
+}
+
+/// Qiita記事の取り込み用入力
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ImportQiitaItemsInput {
+    /// 取り込むQiitaの記事ID
+    pub ids: Vec<String>,
+}
+
+/// 取り込んで作成した下書き
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportedDraftData {
+    /// 下書き記事ID（UUID文字列）
+    pub id: String,
+    pub title: String,
+}
+
+/// Qiitaの記事一覧を取り込み済みかどうかと合わせて取得
+#[instrument]
+#[server(input = GetUrl, endpoint = "admin/get_qiita_import_candidates")]
+pub async fn get_qiita_import_candidates_handler()
+-> Result<Vec<QiitaImportCandidateData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+
+    let state = expect_context::<AppState>();
+    let candidates = state
+        .qiita_import_service()
+        .fetch_candidates()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(candidates
+        .into_iter()
+        .map(QiitaImportCandidateData::from)
+        .collect())
+}
+
+/// 指定したQiitaの記事を下書きとして取り込む（取り込み済みの記事はスキップする）
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/import_qiita_items")]
+pub async fn import_qiita_items_handler(
+    input: ImportQiitaItemsInput,
+) -> Result<Vec<ImportedDraftData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+
+    let state = expect_context::<AppState>();
+    let drafts = state
+        .qiita_import_service()
+        .import(&input.ids)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(drafts.into_iter().map(ImportedDraftData::from).collect())
+}
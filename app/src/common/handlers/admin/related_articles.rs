@@ -0,0 +1,41 @@
+use crate::server::contexts::AppState;
+use uuid::Uuid;
+
+/// 関連記事を再計算し、関連記事の表示が変わる記事のCDNキャッシュタグを返す
+///
+/// 再計算に失敗しても記事の保存・公開自体は成功扱いにする（ベストエフォート）。
+pub(super) async fn refresh_related_articles(state: &AppState, article_id: Uuid) -> Vec<String> {
+    match state.related_article_service().refresh(article_id).await {
+        Ok(slugs) => slugs
+            .into_iter()
+            .map(|slug| format!("article:{slug}"))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to refresh related articles");
+            Vec::new()
+        }
+    }
+}
+
+/// 指定した記事を関連記事に含む記事のCDNキャッシュタグを返す
+///
+/// 削除や非公開化で関連記事から外れる前に呼ぶ。取得に失敗しても処理は続ける（ベストエフォート）。
+pub(super) async fn fetch_referring_article_tags(
+    state: &AppState,
+    article_id: Uuid,
+) -> Vec<String> {
+    match state
+        .related_article_service()
+        .fetch_referring_slugs(article_id)
+        .await
+    {
+        Ok(slugs) => slugs
+            .into_iter()
+            .map(|slug| format!("article:{slug}"))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to fetch articles referring as related");
+            Vec::new()
+        }
+    }
+}
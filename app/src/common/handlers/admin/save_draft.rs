@@ -3,6 +3,8 @@ use leptos::server_fn::codec::Json;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
+use super::ArticleSeoData;
+
 /// 下書き保存用入力（idは必須）
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SaveDraftInput {
@@ -14,6 +16,8 @@ pub struct SaveDraftInput {
     pub cover_image_url: Option<String>,
     #[serde(default)]
     pub category_names: Vec<String>,
+    #[serde(default)]
+    pub seo: ArticleSeoData,
 }
 
 /// 下書き記事の保存（Upsert: 存在しなければ作成、存在すれば更新）
@@ -21,7 +25,7 @@ pub struct SaveDraftInput {
 #[server(input = Json, endpoint = "admin/save_draft")]
 pub async fn save_draft_handler(input: SaveDraftInput) -> Result<String, ServerFnError> {
     use crate::server::contexts::AppState;
-    use blog_romira_dev_cms::ArticleContent;
+    use blog_romira_dev_cms::{ArticleContent, ArticleSeo};
     use uuid::Uuid;
 
     let state = expect_context::<AppState>();
@@ -29,12 +33,15 @@ pub async fn save_draft_handler(input: SaveDraftInput) -> Result<String, ServerF
 
     let uuid = Uuid::parse_str(&input.id).map_err(|e| ServerFnError::new(e.to_string()))?;
 
+    // 下書きではSEO設定も検証しない（公開時に検証する）
+    let seo = ArticleSeo::from(input.seo);
     let content = ArticleContent {
         title: &input.title,
         slug: &input.slug,
         body: &input.body,
         description: input.description.as_deref(),
         cover_image_url: input.cover_image_url.as_deref(),
+        seo: &seo,
     };
 
     service
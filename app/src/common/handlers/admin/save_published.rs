@@ -3,6 +3,8 @@ use leptos::server_fn::codec::Json;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
+use super::{ArticleSeoData, ArticleVisibilityData};
+
 /// 公開記事保存用入力（バリデーション厳格）
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SavePublishedInput {
@@ -14,6 +16,16 @@ pub struct SavePublishedInput {
     pub cover_image_url: Option<String>,
     #[serde(default)]
     pub category_names: Vec<String>,
+    #[serde(default)]
+    pub seo: ArticleSeoData,
+    #[serde(default)]
+    pub visibility: ArticleVisibilityData,
+    /// パスワード保護する場合の新しいパスワード（空なら設定済みのパスワードを引き継ぐ）
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 構造チェックのエラーを無視して保存する
+    #[serde(default)]
+    pub ignore_lint_errors: bool,
 }
 
 /// 公開記事の保存（更新のみ）
@@ -22,7 +34,10 @@ pub struct SavePublishedInput {
 pub async fn save_published_handler(input: SavePublishedInput) -> Result<String, ServerFnError> {
     use crate::server::contexts::AppState;
     use crate::server::http::response::cms_error_to_response;
-    use blog_romira_dev_cms::{PublishedArticleSlug, PublishedArticleTitle};
+    use blog_romira_dev_cms::{
+        ArticleVisibility, PublishedArticleSeo, PublishedArticleSlug, PublishedArticleTitle,
+        PublishedArticleVisibility,
+    };
     use leptos_axum::ResponseOptions;
     use uuid::Uuid;
 
@@ -32,10 +47,21 @@ pub async fn save_published_handler(input: SavePublishedInput) -> Result<String,
         PublishedArticleTitle::new(input.title).map_err(|e| cms_error_to_response(&response, e))?;
     let slug =
         PublishedArticleSlug::new(input.slug).map_err(|e| cms_error_to_response(&response, e))?;
+    let seo = PublishedArticleSeo::new(input.seo.into())
+        .map_err(|e| cms_error_to_response(&response, e))?;
+    let visibility = PublishedArticleVisibility::new(input.visibility.into(), input.password)
+        .map_err(|e| cms_error_to_response(&response, e))?;
 
     let state = expect_context::<AppState>();
     let uuid = Uuid::parse_str(&input.id).map_err(|e| ServerFnError::new(e.to_string()))?;
 
+    // 構造チェック（エラーがあれば明示的に無視しない限り保存しない）
+    if !input.ignore_lint_errors {
+        super::lint_article::ensure_publishable(&state, &input.body, input.description.as_deref())
+            .await
+            .map_err(|e| cms_error_to_response(&response, e))?;
+    }
+
     // 旧slug取得（パージサービスが設定されている場合のみ）
     let old_article = if state.cloudflare_purge_service().is_some() {
         state
@@ -48,6 +74,18 @@ pub async fn save_published_handler(input: SavePublishedInput) -> Result<String,
         None
     };
 
+    // 一般公開でなくなる場合、この記事を関連記事に含む記事を取得（再計算で関連記事から外れる前に取得する）
+    let referring_tags = match old_article {
+        Some(ref old)
+            if old.article.visibility == ArticleVisibility::Public
+                && visibility.visibility() != ArticleVisibility::Public =>
+        {
+            super::related_articles::fetch_referring_article_tags(&state, uuid).await
+        }
+        _ => Vec::new(),
+    };
+
+    // 内容と公開範囲をまとめて更新（スラッグの重複やパスワード未設定の保護はどちらも保存しない）
     state
         .published_article_service()
         .update(
@@ -57,6 +95,8 @@ pub async fn save_published_handler(input: SavePublishedInput) -> Result<String,
             &input.body,
             input.description.as_deref(),
             input.cover_image_url.as_deref(),
+            &seo,
+            &visibility,
         )
         .await
         .map_err(|e| cms_error_to_response(&response, e))?;
@@ -67,11 +107,25 @@ pub async fn save_published_handler(input: SavePublishedInput) -> Result<String,
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
+    // リンクカードのOGPを事前取得（パージ後の再描画で外部取得を待たないようにする）
+    state.link_card_service().prefetch(&input.body).await;
+
+    // 記事間リンクを保存（リンク先・参照元の記事はキャッシュをパージする）
+    let link_tags =
+        super::article_links::save_article_links(&state, uuid, slug.as_str(), &input.body).await;
+
+    // 関連記事を再計算（一覧が変わった記事・この記事を関連記事に含む記事はキャッシュをパージする）
+    let related_tags = super::related_articles::refresh_related_articles(&state, uuid).await;
+
+    // 転載先に反映（失敗は反映状況に記録する）
+    super::syndications::syndicate_article(&state, uuid).await;
+
     // CDNキャッシュパージ（ベストエフォート、未設定ならスキップ）
     if let Some(purge_service) = state.cloudflare_purge_service() {
         let mut tags = vec![
             "top-page".to_string(),
             "sitemap".to_string(),
+            "feed".to_string(),
             format!("article:{}", slug.as_str()),
         ];
         if let Some(ref old) = old_article
@@ -79,6 +133,11 @@ pub async fn save_published_handler(input: SavePublishedInput) -> Result<String,
         {
             tags.push(format!("article:{}", old.article.slug));
         }
+        tags.extend(link_tags);
+        tags.extend(referring_tags);
+        tags.extend(related_tags);
+        tags.sort();
+        tags.dedup();
         if let Err(e) = purge_service.purge_tags(&tags).await {
             tracing::warn!(error = %e, "Failed to purge Cloudflare cache after update");
         }
@@ -0,0 +1,90 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{GetUrl, Json};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// 公開記事の転載先への反映状況
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyndicationStatusData {
+    /// 転載先（例: `qiita`）
+    pub target: String,
+    /// 転載先の記事のURL（まだ投稿できていなければ `None`）
+    pub remote_url: Option<String>,
+    /// 直近の反映が失敗した場合のエラー
+    pub last_error: Option<String>,
+    /// 最後に反映に成功した日時（JST、表示用）
+    pub synced_at: Option<String>,
+}
+
+/// 転載の再試行用入力
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RetrySyndicationInput {
+    /// 公開記事ID（UUID文字列）
+    pub article_id: String,
+}
+
+/// 公開記事の転載先ごとの反映状況を取得
+#[instrument]
+#[server(input = GetUrl, endpoint = "admin/get_syndications")]
+pub async fn get_syndications_handler(
+    article_id: String,
+) -> Result<Vec<SyndicationStatusData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+    use uuid::Uuid;
+
+    let state = expect_context::<AppState>();
+    let uuid = Uuid::parse_str(&article_id).map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    fetch_statuses(&state, uuid).await
+}
+
+/// 公開記事を転載先に反映し直し、反映状況を返す
+#[instrument(skip(input))]
+#[server(input = Json, endpoint = "admin/retry_syndication")]
+pub async fn retry_syndication_handler(
+    input: RetrySyndicationInput,
+) -> Result<Vec<SyndicationStatusData>, ServerFnError> {
+    use crate::server::contexts::AppState;
+    use uuid::Uuid;
+
+    let state = expect_context::<AppState>();
+    let uuid = Uuid::parse_str(&input.article_id).map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    state
+        .syndication_service()
+        .syndicate(uuid)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    fetch_statuses(&state, uuid).await
+}
+
+#[cfg(feature = "ssr")]
+async fn fetch_statuses(
+    state: &crate::server::contexts::AppState,
+    article_id: uuid::Uuid,
+) -> Result<Vec<SyndicationStatusData>, ServerFnError> {
+    let syndications = state
+        .syndication_service()
+        .fetch_statuses(article_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(syndications
+        .into_iter()
+        .map(SyndicationStatusData::from)
+        .collect())
+}
+
+/// 公開記事を転載先に反映する（保存・公開の後に呼ぶ）
+///
+/// 反映の失敗は反映状況に記録するため、記事の保存・公開自体は成功扱いにする（ベストエフォート）。
+#[cfg(feature = "ssr")]
+pub(super) async fn syndicate_article(
+    state: &crate::server::contexts::AppState,
+    article_id: uuid::Uuid,
+) {
+    if let Err(e) = state.syndication_service().syndicate(article_id).await {
+        tracing::warn!(error = %e, "Failed to syndicate article");
+    }
+}
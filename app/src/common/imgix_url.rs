@@ -8,11 +8,59 @@ pub fn extract_base_url(url: &str) -> &str {
     url.split('?').next().unwrap_or(url)
 }
 
-/// ベースURLと幅リストからsrcset文字列を生成する
-pub fn generate_srcset(base_url: &str, widths: &[u32]) -> String {
+/// 幅を指定したimgixの画像URLを生成する
+///
+/// 既定の `auto=format&q=75` に、元のURLに書かれたパラメータ（`fit` / `crop` / `q` など）を上書きで引き継ぐ。
+/// `w` は指定した幅に置き換え、`w` と `h` が両方書かれていれば `h` は縦横比を保つように拡縮する。
+pub fn imgix_width_url(url: &str, width: u32) -> String {
+    let base_url = extract_base_url(url);
+    let author_params: Vec<(&str, &str)> = url
+        .split_once('?')
+        .map(|(_, query)| query.split('#').next().unwrap_or(query))
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .collect();
+    let author_param = |key: &str| {
+        author_params
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    };
+    let author_width = author_param("w").and_then(|value| value.parse::<f64>().ok());
+
+    let mut params = vec![("w".to_string(), width.to_string())];
+    for (key, default) in [("auto", "format"), ("q", "75")] {
+        let value = author_param(key).unwrap_or(default);
+        params.push((key.to_string(), value.to_string()));
+    }
+    for (key, value) in &author_params {
+        if params.iter().any(|(name, _)| name == key) {
+            continue;
+        }
+        let value = match (*key, author_width, value.parse::<f64>()) {
+            ("h", Some(author_width), Ok(height)) if author_width > 0.0 => {
+                ((height * f64::from(width) / author_width).round() as u32).to_string()
+            }
+            _ => value.to_string(),
+        };
+        params.push((key.to_string(), value));
+    }
+
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{base_url}?{query}")
+}
+
+/// imgixの画像URLと幅リストからsrcset文字列を生成する（URLのパラメータは `imgix_width_url` と同様に引き継ぐ）
+pub fn generate_srcset(url: &str, widths: &[u32]) -> String {
     widths
         .iter()
-        .map(|w| format!("{base_url}?w={w}&auto=format&q=75 {w}w"))
+        .map(|w| format!("{} {w}w", imgix_width_url(url, *w)))
         .collect::<Vec<_>>()
         .join(", ")
 }
@@ -64,4 +112,23 @@ mod tests {
         let base = "https://blog-romira.imgix.net/dev/photo.jpg";
         assert_eq!(generate_srcset(base, &[]), "");
     }
+
+    #[test]
+    fn 元のurlのパラメータを幅ごとのurlに引き継ぐ() {
+        let url = "https://blog-romira.imgix.net/dev/photo.jpg?fit=crop&q=60";
+        assert_eq!(
+            generate_srcset(url, &[400, 800]),
+            "https://blog-romira.imgix.net/dev/photo.jpg?w=400&auto=format&q=60&fit=crop 400w, \
+             https://blog-romira.imgix.net/dev/photo.jpg?w=800&auto=format&q=60&fit=crop 800w"
+        );
+    }
+
+    #[test]
+    fn 元のurlの幅は置き換えて高さは縦横比を保つ() {
+        let url = "https://blog-romira.imgix.net/dev/photo.jpg?w=1000&h=500&fit=crop&auto=compress";
+        assert_eq!(
+            imgix_width_url(url, 400),
+            "https://blog-romira.imgix.net/dev/photo.jpg?w=400&auto=compress&q=75&h=200&fit=crop"
+        );
+    }
 }
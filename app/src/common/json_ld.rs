@@ -0,0 +1,312 @@
+//! schema.org の構造化データ（JSON-LD）
+//!
+//! 各型は `@type` に型名を持つJSONとしてシリアライズされる。
+//! `<script type="application/ld+json">` に埋め込むときは [`to_script_json`] を使う。
+
+use crate::constants::{
+    ORIGIN, ROMIRA_AVATAR_URL, ROMIRA_GITHUB_URL, WEB_APP_DESCRIPTION, WEB_APP_TITLE,
+};
+use serde::Serialize;
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+const IN_LANGUAGE: &str = "ja";
+/// 記事のOGP画像のサイズ（カバー画像の変換・自動生成ともに同じ）
+const OG_IMAGE_WIDTH: u32 = 1200;
+const OG_IMAGE_HEIGHT: u32 = 630;
+
+/// ルート要素（`@context` を付ける）
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct JsonLd<T: Serialize> {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(flatten)]
+    item: T,
+}
+
+impl<T: Serialize> JsonLd<T> {
+    pub(crate) fn new(item: T) -> Self {
+        Self {
+            context: SCHEMA_CONTEXT,
+            item,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub(crate) struct WebSite {
+    pub(crate) name: String,
+    pub(crate) url: String,
+    pub(crate) description: String,
+    pub(crate) author: Person,
+    pub(crate) in_language: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub(crate) struct BlogPosting {
+    pub(crate) headline: String,
+    pub(crate) description: String,
+    pub(crate) image: ImageObject,
+    pub(crate) date_published: String,
+    pub(crate) date_modified: String,
+    pub(crate) author: Person,
+    pub(crate) publisher: Person,
+    pub(crate) main_entity_of_page: WebPage,
+    pub(crate) keywords: Vec<String>,
+    pub(crate) in_language: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub(crate) struct Person {
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) image: Option<ImageObject>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub(crate) struct ImageObject {
+    pub(crate) url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type")]
+pub(crate) struct WebPage {
+    #[serde(rename = "@id")]
+    pub(crate) id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub(crate) struct BreadcrumbList {
+    pub(crate) item_list_element: Vec<ListItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type")]
+pub(crate) struct ListItem {
+    /// 1始まりの順番
+    pub(crate) position: usize,
+    pub(crate) name: String,
+    pub(crate) item: String,
+}
+
+/// 著者（Romira）
+pub(crate) fn author() -> Person {
+    Person {
+        name: "Romira".to_string(),
+        url: Some(ROMIRA_GITHUB_URL.to_string()),
+        image: Some(ImageObject {
+            url: ROMIRA_AVATAR_URL.to_string(),
+            width: None,
+            height: None,
+        }),
+    }
+}
+
+/// サイト全体の `WebSite`
+pub(crate) fn web_site() -> WebSite {
+    WebSite {
+        name: WEB_APP_TITLE.to_string(),
+        url: ORIGIN.to_string(),
+        description: WEB_APP_DESCRIPTION.to_string(),
+        author: author(),
+        in_language: IN_LANGUAGE,
+    }
+}
+
+/// [`blog_posting`] の入力
+pub(crate) struct BlogPostingParams {
+    pub(crate) url: String,
+    pub(crate) headline: String,
+    pub(crate) description: String,
+    pub(crate) og_image_url: String,
+    pub(crate) date_published: String,
+    pub(crate) date_modified: String,
+    pub(crate) keywords: Vec<String>,
+}
+
+/// 記事ページの `BlogPosting`
+pub(crate) fn blog_posting(params: BlogPostingParams) -> BlogPosting {
+    BlogPosting {
+        headline: params.headline,
+        description: params.description,
+        image: ImageObject {
+            url: params.og_image_url,
+            width: Some(OG_IMAGE_WIDTH),
+            height: Some(OG_IMAGE_HEIGHT),
+        },
+        date_published: params.date_published,
+        date_modified: params.date_modified,
+        author: author(),
+        publisher: Person {
+            name: "Romira".to_string(),
+            url: Some(ORIGIN.to_string()),
+            image: None,
+        },
+        main_entity_of_page: WebPage { id: params.url },
+        keywords: params.keywords,
+        in_language: IN_LANGUAGE,
+    }
+}
+
+/// トップページを先頭にしたパンくずリスト（`items` は名前とURLの組）
+pub(crate) fn breadcrumb_list(items: Vec<(String, String)>) -> BreadcrumbList {
+    let home = (WEB_APP_TITLE.to_string(), format!("{}/", ORIGIN));
+    BreadcrumbList {
+        item_list_element: std::iter::once(home)
+            .chain(items)
+            .enumerate()
+            .map(|(index, (name, item))| ListItem {
+                position: index + 1,
+                name,
+                item,
+            })
+            .collect(),
+    }
+}
+
+/// `<script>` 内にそのまま埋め込めるJSONへシリアライズする
+///
+/// `</script>` や `<!--` で要素が閉じられないよう `<` `>` `&` をUnicodeエスケープし、
+/// JavaScriptの文字列として改行扱いになる U+2028 / U+2029 もエスケープする。
+/// いずれもJSONの文字列中にしか現れないため、エスケープ後も同じ値として解釈される。
+pub(crate) fn to_script_json<T: Serialize>(value: &JsonLd<T>) -> String {
+    let json = serde_json::to_string(value).expect("JSON-LD のシリアライズに失敗");
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn sample_posting(headline: &str) -> BlogPosting {
+        blog_posting(BlogPostingParams {
+            url: format!("{}/articles/sample", ORIGIN),
+            headline: headline.to_string(),
+            description: "説明\n2行目 \\ バックスラッシュ".to_string(),
+            og_image_url: format!("{}/og/sample.png", ORIGIN),
+            date_published: "2025-01-02T03:04:05+09:00".to_string(),
+            date_modified: "2025-01-03T03:04:05+09:00".to_string(),
+            keywords: vec!["Rust".to_string(), "\"引用\"".to_string()],
+        })
+    }
+
+    #[test]
+    fn 引用符とscript終了タグを含むタイトルを安全にシリアライズできること() {
+        let headline = r#""引用" と </script><script>alert(1)</script> & <!-- x -->"#;
+        let script = to_script_json(&JsonLd::new(sample_posting(headline)));
+
+        assert!(!script.contains('<'), "{script}");
+        assert!(!script.contains('>'), "{script}");
+        assert!(!script.contains('&'), "{script}");
+        assert!(!script.contains('\n'), "{script}");
+
+        // エスケープしても同じ値として読める
+        let value: Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(value["headline"], headline);
+        assert_eq!(value["description"], "説明\n2行目 \\ バックスラッシュ");
+        assert_eq!(value["keywords"], json!(["Rust", "\"引用\""]));
+    }
+
+    #[test]
+    fn 行区切り文字をエスケープすること() {
+        let script = to_script_json(&JsonLd::new(sample_posting("a\u{2028}b\u{2029}c")));
+
+        assert!(script.contains(r"a\u2028b\u2029c"), "{script}");
+        let value: Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(value["headline"], "a\u{2028}b\u{2029}c");
+    }
+
+    #[test]
+    fn blogpostingが型付きのネストした構造になること() {
+        let script = to_script_json(&JsonLd::new(sample_posting("タイトル")));
+        let value: Value = serde_json::from_str(&script).unwrap();
+
+        assert_eq!(value["@context"], "https://schema.org");
+        assert_eq!(value["@type"], "BlogPosting");
+        assert_eq!(value["datePublished"], "2025-01-02T03:04:05+09:00");
+        assert_eq!(value["dateModified"], "2025-01-03T03:04:05+09:00");
+        assert_eq!(value["inLanguage"], "ja");
+        assert_eq!(
+            value["image"],
+            json!({
+                "@type": "ImageObject",
+                "url": "https://blog.romira.dev/og/sample.png",
+                "width": 1200,
+                "height": 630,
+            })
+        );
+        assert_eq!(value["author"]["@type"], "Person");
+        assert_eq!(value["author"]["url"], ROMIRA_GITHUB_URL);
+        assert_eq!(value["author"]["image"]["@type"], "ImageObject");
+        assert_eq!(
+            value["mainEntityOfPage"],
+            json!({
+                "@type": "WebPage",
+                "@id": "https://blog.romira.dev/articles/sample",
+            })
+        );
+    }
+
+    #[test]
+    fn websiteにサイト情報と著者が含まれること() {
+        let value: Value = serde_json::from_str(&to_script_json(&JsonLd::new(web_site()))).unwrap();
+
+        assert_eq!(value["@type"], "WebSite");
+        assert_eq!(value["name"], WEB_APP_TITLE);
+        assert_eq!(value["url"], ORIGIN);
+        assert_eq!(value["author"]["name"], "Romira");
+        assert!(value["author"].get("image").is_some());
+    }
+
+    #[test]
+    fn パンくずリストはトップページから1始まりで並ぶこと() {
+        let list = breadcrumb_list(vec![(
+            "Rust </script>".to_string(),
+            format!("{}/categories/rust", ORIGIN),
+        )]);
+        let script = to_script_json(&JsonLd::new(list));
+        let value: Value = serde_json::from_str(&script).unwrap();
+
+        assert_eq!(value["@type"], "BreadcrumbList");
+        assert_eq!(
+            value["itemListElement"],
+            json!([
+                {
+                    "@type": "ListItem",
+                    "position": 1,
+                    "name": WEB_APP_TITLE,
+                    "item": "https://blog.romira.dev/",
+                },
+                {
+                    "@type": "ListItem",
+                    "position": 2,
+                    "name": "Rust </script>",
+                    "item": "https://blog.romira.dev/categories/rust",
+                },
+            ])
+        );
+    }
+}
@@ -1,77 +1,540 @@
-use comrak::{Options, markdown_to_html};
+use crate::common::embed_provider::{EmbedProviders, url_host};
+use crate::common::imgix_url::{extract_base_url, generate_srcset, imgix_width_url, is_imgix_url};
+use crate::constants::{IMAGE_SIZES, IMAGE_WIDTHS};
+use comrak::nodes::{AlertType, AstNode, NodeHtmlBlock, NodeValue};
+use comrak::{Arena, Options, format_html, parse_document};
+use std::collections::HashMap;
 
-/// MarkdownをHTMLに変換
-pub fn convert_markdown_to_html(markdown: &str) -> String {
+/// Markdownのパース・レンダリングオプション
+pub(crate) fn markdown_options() -> Options<'static> {
     let mut options = Options::default();
     options.extension.strikethrough = true;
     options.extension.table = true;
     options.extension.autolink = true;
     options.extension.tasklist = true;
+    options.extension.alerts = true;
+    options.extension.wikilinks_title_after_pipe = true;
     options.extension.header_ids = None;
     options.render.r#unsafe = true;
+    options
+}
+
+/// リンクカードの表示に使うOGPメタデータ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkCard {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub site_name: Option<String>,
+    /// oEmbedで取得した埋め込みHTML（あればカードの代わりに表示）
+    pub embed_html: Option<String>,
+}
+
+/// 本文画像のサイズ（ピクセル）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Markdown変換時に参照する、DB等から事前に取得したデータ
+#[derive(Debug, Clone, Default)]
+pub struct RenderContext {
+    /// 単独行URLごとのリンクカード
+    pub link_cards: HashMap<String, LinkCard>,
+    /// imgix画像のベースURLごとのサイズ
+    pub image_sizes: HashMap<String, ImageSize>,
+    /// ウィキリンク先のslugごとの記事タイトル
+    ///
+    /// `None` の場合はリンク先を解決せず、すべて通常のリンクとして出力する（エディタのプレビュー用）。
+    pub wiki_links: Option<HashMap<String, String>>,
+    /// サニタイズで埋め込みを許可するプロバイダー
+    pub embed_providers: EmbedProviders,
+}
+
+/// MarkdownをHTMLに変換
+///
+/// 単独行のURLはメタデータなし（URLとホスト名のみ）のリンクカードになる。
+pub fn convert_markdown_to_html(markdown: &str) -> String {
+    convert_markdown_to_html_with_context(markdown, &RenderContext::default())
+}
+
+/// MarkdownをHTMLに変換（リンクカードや画像サイズは `context` を参照する）
+pub fn convert_markdown_to_html_with_context(markdown: &str, context: &RenderContext) -> String {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    localize_alert_titles(root);
+    replace_standalone_links_with_cards(root, &context.link_cards);
+    rewrite_images(root, &context.image_sizes);
+    resolve_wiki_links(root, context.wiki_links.as_ref());
+
+    let mut html = String::new();
+    format_html(root, &options, &mut html).expect("Failed to render markdown");
+    html
+}
+
+/// 本文中でリンクカードになるURLを出現順に重複なく抽出
+pub fn extract_link_card_urls(markdown: &str) -> Vec<String> {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut urls = Vec::new();
+    for node in root.descendants() {
+        if let Some(url) = standalone_link_url(node)
+            && !urls.contains(&url)
+        {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// 本文中のimgix画像のベースURLを出現順に重複なく抽出
+pub fn extract_imgix_image_urls(markdown: &str) -> Vec<String> {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut urls = Vec::new();
+    for node in root.descendants() {
+        if let NodeValue::Image(ref link) = node.data().value
+            && is_imgix_url(&link.url)
+        {
+            let base_url = extract_base_url(&link.url).to_string();
+            if !urls.contains(&base_url) {
+                urls.push(base_url);
+            }
+        }
+    }
+    urls
+}
+
+/// 本文中のウィキリンク先のslugを出現順に重複なく抽出
+pub fn extract_wiki_link_slugs(markdown: &str) -> Vec<String> {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut slugs = Vec::new();
+    for node in root.descendants() {
+        if let NodeValue::WikiLink(ref link) = node.data().value
+            && let Some(slug) = wiki_link_slug(&link.url)
+            && !slugs.iter().any(|s| s == slug)
+        {
+            slugs.push(slug.to_string());
+        }
+    }
+    slugs
+}
+
+/// ウィキリンク（`[[slug]]` / `[[slug|ラベル]]`）のリンク先からslugを取り出す
+pub(crate) fn wiki_link_slug(url: &str) -> Option<&str> {
+    let slug = url.trim();
+    (!slug.is_empty()).then_some(slug)
+}
+
+/// ウィキリンクを記事ページへのリンクに置き換える
+///
+/// ラベル省略時は記事タイトルを表示し、リンク先が公開されていない場合はリンクにしない。
+fn resolve_wiki_links<'a>(root: &'a AstNode<'a>, titles: Option<&HashMap<String, String>>) {
+    let wiki_links: Vec<_> = root
+        .descendants()
+        .filter(|node| matches!(node.data().value, NodeValue::WikiLink(_)))
+        .collect();
+
+    for node in wiki_links {
+        let url = match node.data().value {
+            NodeValue::WikiLink(ref link) => link.url.clone(),
+            _ => continue,
+        };
+        let label = collect_plain_text(node);
+        for child in node.children().collect::<Vec<_>>() {
+            child.detach();
+        }
+
+        let html = match wiki_link_slug(&url) {
+            None => escape_html(&label),
+            Some(slug) => {
+                // ラベル省略時（`[[slug]]`）は子ノードのテキストがslugそのものになる
+                let has_label = label != url;
+                match titles.map(|titles| titles.get(slug)) {
+                    Some(None) => format!(
+                        r#"<span class="wiki-link-unresolved">{}</span>"#,
+                        escape_html(&label)
+                    ),
+                    resolved => {
+                        let text = match resolved.flatten() {
+                            Some(title) if !has_label => title,
+                            _ => &label,
+                        };
+                        format!(
+                            r#"<a class="wiki-link" href="/articles/{}">{}</a>"#,
+                            escape_html(slug),
+                            escape_html(text)
+                        )
+                    }
+                }
+            }
+        };
+        node.data_mut().value = NodeValue::HtmlInline(html);
+    }
+}
+
+/// アラート種別ごとの既定タイトル
+fn alert_default_title(alert_type: AlertType) -> &'static str {
+    match alert_type {
+        AlertType::Note => "補足",
+        AlertType::Tip => "ヒント",
+        AlertType::Important => "重要",
+        AlertType::Warning => "注意",
+        AlertType::Caution => "警告",
+    }
+}
+
+/// `> [!NOTE]` 等のアラートでタイトル未指定のものに日本語の既定タイトルを設定
+fn localize_alert_titles<'a>(root: &'a AstNode<'a>) {
+    for node in root.descendants() {
+        if let NodeValue::Alert(ref mut alert) = node.data_mut().value
+            && alert.title.is_none()
+        {
+            alert.title = Some(alert_default_title(alert.alert_type).to_string());
+        }
+    }
+}
+
+/// トップレベルの段落がURLの自動リンク1つだけの場合、そのURLを返す
+fn standalone_link_url<'a>(node: &'a AstNode<'a>) -> Option<String> {
+    if !matches!(node.data().value, NodeValue::Paragraph)
+        || !matches!(node.parent()?.data().value, NodeValue::Document)
+    {
+        return None;
+    }
+
+    let link = node.first_child()?;
+    if link.next_sibling().is_some() {
+        return None;
+    }
+    let url = match link.data().value {
+        NodeValue::Link(ref link) => link.url.clone(),
+        _ => return None,
+    };
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return None;
+    }
+
+    // リンクテキストがURLそのものでなければ通常のリンクとして扱う
+    let mut text = String::new();
+    for child in link.children() {
+        match child.data().value {
+            NodeValue::Text(ref t) => text.push_str(t),
+            _ => return None,
+        }
+    }
+    (text == url).then_some(url)
+}
+
+/// 単独行のURLの段落をリンクカードのHTMLブロックに置き換え
+fn replace_standalone_links_with_cards<'a>(
+    root: &'a AstNode<'a>,
+    link_cards: &HashMap<String, LinkCard>,
+) {
+    let targets: Vec<_> = root
+        .descendants()
+        .filter_map(|node| standalone_link_url(node).map(|url| (node, url)))
+        .collect();
+
+    for (node, url) in targets {
+        let card = link_cards.get(&url).cloned().unwrap_or_default();
+        for child in node.children().collect::<Vec<_>>() {
+            child.detach();
+        }
+        node.data_mut().value = NodeValue::HtmlBlock(NodeHtmlBlock {
+            block_type: 6,
+            literal: render_link_card(&url, &card),
+        });
+    }
+}
+
+/// ノード配下のテキストを連結（画像のalt用）
+fn collect_plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+    for child in node.descendants().skip(1) {
+        match child.data().value {
+            NodeValue::Text(ref t) => text.push_str(t),
+            NodeValue::Code(ref code) => text.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Markdownの画像をレスポンシブな `<img>` に置き換え
+///
+/// imgix画像は `srcset` / `sizes` と `image_sizes` にあるサイズを付与し、
+/// タイトル付きで段落に単独の画像は `<figure>` / `<figcaption>` にする。
+fn rewrite_images<'a>(root: &'a AstNode<'a>, image_sizes: &HashMap<String, ImageSize>) {
+    let images: Vec<_> = root
+        .descendants()
+        .filter(|node| matches!(node.data().value, NodeValue::Image(_)))
+        .collect();
+
+    for node in images {
+        let (url, title) = match node.data().value {
+            NodeValue::Image(ref link) => (link.url.clone(), link.title.clone()),
+            _ => continue,
+        };
+        let alt = collect_plain_text(node);
+        let img = render_image(&url, &alt, &title, image_sizes);
+
+        for child in node.children().collect::<Vec<_>>() {
+            child.detach();
+        }
 
-    markdown_to_html(markdown, &options)
+        let figure_parent = node.parent().filter(|parent| {
+            !title.is_empty()
+                && matches!(parent.data().value, NodeValue::Paragraph)
+                && node.previous_sibling().is_none()
+                && node.next_sibling().is_none()
+        });
+        match figure_parent {
+            Some(parent) => {
+                node.detach();
+                parent.data_mut().value = NodeValue::HtmlBlock(NodeHtmlBlock {
+                    block_type: 6,
+                    literal: format!(
+                        "<figure>{}<figcaption>{}</figcaption></figure>\n",
+                        img,
+                        escape_html(&title)
+                    ),
+                });
+            }
+            None => node.data_mut().value = NodeValue::HtmlInline(img),
+        }
+    }
+}
+
+/// 画像の `<img>` タグを生成
+fn render_image(
+    url: &str,
+    alt: &str,
+    title: &str,
+    image_sizes: &HashMap<String, ImageSize>,
+) -> String {
+    let mut attributes = Vec::new();
+    if is_imgix_url(url) {
+        let base_url = extract_base_url(url);
+        attributes.push(format!(
+            r#"src="{}""#,
+            escape_html(&imgix_width_url(url, IMAGE_WIDTHS[1]))
+        ));
+        attributes.push(format!(
+            r#"srcset="{}""#,
+            escape_html(&generate_srcset(url, &IMAGE_WIDTHS))
+        ));
+        attributes.push(format!(r#"sizes="{IMAGE_SIZES}""#));
+        if let Some(size) = image_sizes.get(base_url) {
+            attributes.push(format!(
+                r#"width="{}" height="{}""#,
+                size.width, size.height
+            ));
+        }
+    } else {
+        attributes.push(format!(r#"src="{}""#, escape_html(url)));
+    }
+    attributes.push(format!(r#"alt="{}""#, escape_html(alt)));
+    if !title.is_empty() {
+        attributes.push(format!(r#"title="{}""#, escape_html(title)));
+    }
+    attributes.push(r#"loading="lazy" decoding="async""#.to_string());
+
+    format!("<img {}>", attributes.join(" "))
+}
+
+/// HTMLの特殊文字をエスケープ
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// リンクカードのHTMLを生成（埋め込みHTMLがあれば埋め込みとして出力）
+fn render_link_card(url: &str, card: &LinkCard) -> String {
+    if let Some(embed_html) = card.embed_html.as_deref() {
+        return format!("<div class=\"embed\">{}</div>\n", embed_html.trim());
+    }
+
+    let host = url_host(url).unwrap_or(url);
+    let title = card.title.as_deref().unwrap_or(url);
+    let site_name = card.site_name.as_deref().unwrap_or(host);
+
+    let mut html = format!(
+        r#"<div class="link-card"><a class="link-card-link" href="{}"><span class="link-card-body"><span class="link-card-title">{}</span>"#,
+        escape_html(url),
+        escape_html(title),
+    );
+    if let Some(description) = card.description.as_deref() {
+        html.push_str(&format!(
+            r#"<span class="link-card-description">{}</span>"#,
+            escape_html(description)
+        ));
+    }
+    html.push_str(r#"<span class="link-card-site">"#);
+    if let Some(favicon_url) = card.favicon_url.as_deref() {
+        html.push_str(&format!(
+            r#"<img class="link-card-favicon" src="{}" alt="" width="16" height="16" loading="lazy">"#,
+            escape_html(favicon_url)
+        ));
+    }
+    html.push_str(&format!("{}</span></span>", escape_html(site_name)));
+    if let Some(image_url) = card.image_url.as_deref() {
+        html.push_str(&format!(
+            r#"<img class="link-card-image" src="{}" alt="" loading="lazy">"#,
+            escape_html(image_url)
+        ));
+    }
+    html.push_str("</a></div>\n");
+    html
 }
 
-/// iframe の src として許可するホスト
+/// サニタイズ後も保持するアラートのクラス
 #[cfg(feature = "ssr")]
-const ALLOWED_IFRAME_HOSTS: &[&str] = &[
-    "www.youtube.com",
-    "www.youtube-nocookie.com",
-    "platform.twitter.com",
-    "codepen.io",
+const ALERT_CLASSES: &[&str] = &[
+    "markdown-alert",
+    "markdown-alert-note",
+    "markdown-alert-tip",
+    "markdown-alert-important",
+    "markdown-alert-warning",
+    "markdown-alert-caution",
+];
+
+/// サニタイズ後も保持するリンクカードのクラス
+#[cfg(feature = "ssr")]
+const LINK_CARD_SPAN_CLASSES: &[&str] = &[
+    "link-card-body",
+    "link-card-title",
+    "link-card-description",
+    "link-card-site",
 ];
 
 /// HTMLをサニタイズして公開用に安全な形に整える
 ///
 /// `<script>` / `on*` 属性 / `javascript:` URL を除去し、
-/// `<img>` のレスポンシブ属性 (srcset / sizes / loading) と
-/// 信頼ドメインからの `<iframe>` 埋め込みを許可する。
+/// `<img>` のレスポンシブ属性 (srcset / sizes / loading / decoding)、
+/// `embed_providers` で許可された `<iframe>` と埋め込みのクラス、アラート・リンクカード・ウィキリンクのクラスを許可する。
 #[cfg(feature = "ssr")]
-pub fn sanitize_html(html: &str) -> String {
+pub fn sanitize_html(html: &str, embed_providers: &EmbedProviders) -> String {
     use std::borrow::Cow;
     use std::collections::HashSet;
 
     let url_schemes: HashSet<&str> = ["http", "https", "mailto"].into_iter().collect();
+    let iframe_providers = embed_providers.clone();
 
-    ammonia::Builder::default()
+    let mut builder = ammonia::Builder::default();
+    builder
         .add_tags(["iframe"])
-        .add_tag_attributes("img", ["srcset", "sizes", "loading"])
-        .add_tag_attributes(
-            "iframe",
-            [
-                "src",
-                "width",
-                "height",
-                "title",
-                "allow",
-                "allowfullscreen",
-                "loading",
-                "referrerpolicy",
-                "sandbox",
-                "frameborder",
-            ],
-        )
+        .add_tag_attributes("img", ["srcset", "sizes", "loading", "decoding"])
+        .add_tag_attributes("iframe", embed_providers.allowed_iframe_attributes())
+        .add_allowed_classes("div", ALERT_CLASSES)
+        .add_allowed_classes("p", ["markdown-alert-title"])
+        .add_allowed_classes("div", ["link-card", "embed"])
+        .add_allowed_classes("a", ["link-card-link", "wiki-link"])
+        .add_allowed_classes("span", LINK_CARD_SPAN_CLASSES)
+        .add_allowed_classes("span", ["wiki-link-unresolved"])
+        .add_allowed_classes("img", ["link-card-favicon", "link-card-image"])
         .url_schemes(url_schemes)
-        .attribute_filter(|element, attribute, value| {
+        .attribute_filter(move |element, attribute, value| {
             if element == "iframe" && attribute == "src" {
-                let host = url::Url::parse(value)
-                    .ok()
-                    .and_then(|u| u.host_str().map(str::to_owned));
-                match host {
-                    Some(h) if ALLOWED_IFRAME_HOSTS.contains(&h.as_str()) => {
-                        Some(Cow::Borrowed(value))
-                    }
-                    _ => None,
-                }
+                let allowed = url::Url::parse(value).is_ok_and(|u| {
+                    u.scheme() == "https"
+                        && u.host_str()
+                            .is_some_and(|host| iframe_providers.is_allowed_iframe_host(host))
+                });
+                allowed.then_some(Cow::Borrowed(value))
             } else {
                 Some(Cow::Borrowed(value))
             }
-        })
-        .clean(html)
+        });
+    for (tag, class) in embed_providers.allowed_classes() {
+        builder.add_allowed_classes(tag, [class]);
+    }
+    builder
+        .clean(&restrict_iframe_attributes(html, embed_providers))
         .to_string()
 }
 
+/// iframeから、src のホストのプロバイダーが許可していない属性を除く
+///
+/// ammoniaの属性フィルターは同じ要素の src を参照できないため、サニタイズの前にDOMにして絞り込む
+/// （結果は必ずammoniaでサニタイズする）。
+#[cfg(feature = "ssr")]
+fn restrict_iframe_attributes(html: &str, embed_providers: &EmbedProviders) -> String {
+    use html5ever::serialize::{SerializeOpts, TraversalScope, serialize};
+    use html5ever::tendril::TendrilSink;
+    use html5ever::{ParseOpts, QualName, local_name, namespace_url, ns, parse_fragment};
+    use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
+
+    fn restrict(node: &Handle, embed_providers: &EmbedProviders) {
+        if let NodeData::Element { name, attrs, .. } = &node.data
+            && name.local == local_name!("iframe")
+        {
+            let mut attrs = attrs.borrow_mut();
+            let src = attrs
+                .iter()
+                .find(|attr| attr.name.local == local_name!("src"))
+                .map(|attr| attr.value.to_string());
+            let host = src.as_deref().and_then(url_host);
+            attrs
+                .retain(|attr| embed_providers.is_allowed_iframe_attribute(host, &attr.name.local));
+        }
+        for child in node.children.borrow().iter() {
+            restrict(child, embed_providers);
+        }
+    }
+
+    let dom = parse_fragment(
+        RcDom::default(),
+        ParseOpts::default(),
+        QualName::new(None, ns!(html), local_name!("body")),
+        vec![],
+    )
+    .one(html);
+    let Some(root) = dom.document.children.borrow().first().cloned() else {
+        return html.to_string();
+    };
+    restrict(&root, embed_providers);
+
+    let mut restricted = Vec::new();
+    let options = SerializeOpts {
+        traversal_scope: TraversalScope::ChildrenOnly(None),
+        ..Default::default()
+    };
+    match serialize(&mut restricted, &SerializableHandle::from(root), options) {
+        Ok(()) => String::from_utf8_lossy(&restricted).into_owned(),
+        Err(err) => {
+            // 絞り込めなくてもammoniaで許可した属性の範囲には収まる
+            tracing::warn!(
+                error = err.to_string(),
+                "Failed to restrict iframe attributes"
+            );
+            html.to_string()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,11 +563,456 @@ mod tests {
         );
     }
 
+    #[test]
+    fn アラート記法が既定の日本語タイトル付きで変換されること() {
+        let cases = [
+            ("NOTE", "markdown-alert-note", "補足"),
+            ("TIP", "markdown-alert-tip", "ヒント"),
+            ("WARNING", "markdown-alert-warning", "注意"),
+            ("CAUTION", "markdown-alert-caution", "警告"),
+        ];
+        for (marker, class, title) in cases {
+            let input = format!("> [!{marker}]\n> 本文です");
+            let result = convert_markdown_to_html(&input);
+            assert!(
+                result.contains(&format!(r#"<div class="markdown-alert {class}">"#)),
+                "alert class missing for {marker}: {result}"
+            );
+            assert!(
+                result.contains(&format!(r#"<p class="markdown-alert-title">{title}</p>"#)),
+                "default title missing for {marker}: {result}"
+            );
+            assert!(result.contains("<p>本文です</p>"));
+        }
+    }
+
+    #[test]
+    fn アラート記法でカスタムタイトルが使われること() {
+        let result = convert_markdown_to_html("> [!TIP] 便利な小技\n> 本文です");
+        assert!(
+            result.contains(r#"<p class="markdown-alert-title">便利な小技</p>"#),
+            "custom title missing: {result}"
+        );
+    }
+
+    fn example_context() -> RenderContext {
+        let link_cards = HashMap::from([(
+            "https://example.com/post".to_string(),
+            LinkCard {
+                title: Some("記事タイトル <b>".to_string()),
+                description: Some("説明文".to_string()),
+                image_url: Some("https://example.com/og.png".to_string()),
+                favicon_url: Some("https://example.com/favicon.ico".to_string()),
+                site_name: Some("Example".to_string()),
+                embed_html: None,
+            },
+        )]);
+        RenderContext {
+            link_cards,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn 単独行のurlがメタデータ付きのリンクカードになること() {
+        let result = convert_markdown_to_html_with_context(
+            "前文\n\nhttps://example.com/post\n\n後文",
+            &example_context(),
+        );
+        assert!(
+            result.contains(r#"<a class="link-card-link" href="https://example.com/post">"#),
+            "card link missing: {result}"
+        );
+        assert!(
+            result.contains(r#"<span class="link-card-title">記事タイトル &lt;b&gt;</span>"#),
+            "escaped title missing: {result}"
+        );
+        assert!(result.contains("説明文"), "description missing: {result}");
+        assert!(
+            result.contains(r#"src="https://example.com/og.png""#),
+            "og image missing: {result}"
+        );
+        assert!(
+            !result.contains("<p>https://"),
+            "autolink remains: {result}"
+        );
+    }
+
+    #[test]
+    fn メタデータ未取得のurlはホスト名のみのリンクカードになること() {
+        let result = convert_markdown_to_html("https://example.com/unknown");
+        assert!(
+            result.contains(r#"<span class="link-card-title">https://example.com/unknown</span>"#),
+            "fallback title missing: {result}"
+        );
+        assert!(
+            result.contains(r#"<span class="link-card-site">example.com</span>"#),
+            "host missing: {result}"
+        );
+        assert!(
+            !result.contains("link-card-image"),
+            "image present: {result}"
+        );
+    }
+
+    #[test]
+    fn 文中のurlや名前付きリンクはリンクカードにならないこと() {
+        let markdown = "参考: https://example.com/post\n\n[リンク](https://example.com/post)\n\n- https://example.com/post";
+        let result = convert_markdown_to_html_with_context(markdown, &example_context());
+        assert!(!result.contains("link-card"), "unexpected card: {result}");
+        assert!(extract_link_card_urls(markdown).is_empty());
+    }
+
+    #[test]
+    fn extract_link_card_urlsで単独行のurlが重複なく抽出されること() {
+        let markdown =
+            "https://a.example.com/\n\n本文\n\nhttps://b.example.com/\n\nhttps://a.example.com/";
+        assert_eq!(
+            extract_link_card_urls(markdown),
+            vec![
+                "https://a.example.com/".to_string(),
+                "https://b.example.com/".to_string()
+            ]
+        );
+    }
+
+    fn wiki_link_context() -> RenderContext {
+        RenderContext {
+            wiki_links: Some(HashMap::from([(
+                "prev".to_string(),
+                "前回の <記事>".to_string(),
+            )])),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ウィキリンクが記事タイトル付きのリンクになること() {
+        let result = convert_markdown_to_html_with_context(
+            "[[prev]]と[[prev|こちら]]",
+            &wiki_link_context(),
+        );
+        assert!(
+            result
+                .contains(r#"<a class="wiki-link" href="/articles/prev">前回の &lt;記事&gt;</a>"#),
+            "title link missing: {result}"
+        );
+        assert!(
+            result.contains(r#"<a class="wiki-link" href="/articles/prev">こちら</a>"#),
+            "label link missing: {result}"
+        );
+    }
+
+    #[test]
+    fn 解決できないウィキリンクはリンクにならないこと() {
+        let result =
+            convert_markdown_to_html_with_context("[[missing|未公開]]", &wiki_link_context());
+        assert!(
+            result.contains(r#"<span class="wiki-link-unresolved">未公開</span>"#),
+            "unresolved span missing: {result}"
+        );
+        assert!(!result.contains("href"), "unexpected link: {result}");
+    }
+
+    #[test]
+    fn 解決情報がなければウィキリンクはそのままリンクになること() {
+        let result = convert_markdown_to_html("[[missing]]");
+        assert!(
+            result.contains(r#"<a class="wiki-link" href="/articles/missing">missing</a>"#),
+            "plain link missing: {result}"
+        );
+    }
+
+    #[test]
+    fn extract_wiki_link_slugsでウィキリンク先が重複なく抽出されること() {
+        let markdown = "[[foo]] [[bar|ラベル]]\n\n[[foo|再掲]] [リンク](/articles/baz)";
+        assert_eq!(
+            extract_wiki_link_slugs(markdown),
+            vec!["foo".to_string(), "bar".to_string()]
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでウィキリンクのクラスが保持されること() {
+        let html =
+            convert_markdown_to_html_with_context("[[prev]] [[missing]]", &wiki_link_context());
+        let result = sanitize_html(&html, &EmbedProviders::default());
+        assert!(
+            result.contains(r#"class="wiki-link""#),
+            "class stripped: {result}"
+        );
+        assert!(
+            result.contains(r#"class="wiki-link-unresolved""#),
+            "class stripped: {result}"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでリンクカードが保持されること() {
+        let html =
+            convert_markdown_to_html_with_context("https://example.com/post", &example_context());
+        let result = sanitize_html(&html, &EmbedProviders::default());
+        for class in [
+            "link-card",
+            "link-card-link",
+            "link-card-title",
+            "link-card-description",
+            "link-card-site",
+            "link-card-favicon",
+            "link-card-image",
+        ] {
+            assert!(
+                result.contains(&format!(r#"class="{class}""#)),
+                "{class} stripped: {result}"
+            );
+        }
+        assert!(
+            result.contains(r#"href="https://example.com/post""#),
+            "href stripped: {result}"
+        );
+    }
+
+    #[test]
+    fn 埋め込みhtmlがあるurlはリンクカードの代わりに埋め込みになること() {
+        let link_cards = HashMap::from([(
+            "https://www.youtube.com/watch?v=abc".to_string(),
+            LinkCard {
+                title: Some("動画".to_string()),
+                embed_html: Some(
+                    r#"<iframe src="https://www.youtube.com/embed/abc"></iframe>"#.to_string(),
+                ),
+                ..Default::default()
+            },
+        )]);
+        let result = convert_markdown_to_html_with_context(
+            "https://www.youtube.com/watch?v=abc",
+            &RenderContext {
+                link_cards,
+                ..Default::default()
+            },
+        );
+        assert!(
+            result.contains(
+                r#"<div class="embed"><iframe src="https://www.youtube.com/embed/abc"></iframe></div>"#
+            ),
+            "embed missing: {result}"
+        );
+        assert!(!result.contains("link-card"), "card rendered: {result}");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlで埋め込みのscriptが除去されiframeが保持されること() {
+        let input = r#"<div class="embed"><blockquote class="twitter-tweet"><p>本文</p></blockquote><script src="https://platform.twitter.com/widgets.js"></script><iframe src="https://codepen.io/a/embed/b" scrolling="no"></iframe></div>"#;
+        let result = sanitize_html(input, &EmbedProviders::default());
+        assert!(result.contains(r#"<div class="embed">"#), "{result}");
+        assert!(!result.contains("<script"), "script remains: {result}");
+        assert!(
+            result.contains(r#"<iframe src="https://codepen.io/a/embed/b" scrolling="no">"#),
+            "iframe stripped: {result}"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでiframeの属性がsrcのホストのプロバイダーごとに絞り込まれること() {
+        let input = r#"<iframe src="https://www.youtube.com/embed/abc?a=1&amp;b=&quot;2&quot;" scrolling="no" allowfullscreen></iframe><iframe src="https://codepen.io/a/embed/b" scrolling="no" allowtransparency="true"></iframe><iframe scrolling="no"></iframe>"#;
+        let result = sanitize_html(input, &EmbedProviders::default());
+        assert_eq!(
+            result,
+            r#"<iframe src="https://www.youtube.com/embed/abc?a=1&amp;b=&quot;2&quot;" allowfullscreen=""></iframe><iframe src="https://codepen.io/a/embed/b" scrolling="no" allowtransparency="true"></iframe><iframe></iframe>"#
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでaltやtitleの中のiframeの文字列から属性が復活しないこと() {
+        let html = convert_markdown_to_html(
+            r#"![<iframe x](https://a/b.png) [t](https://e "<iframe src=x onerror=alert(1)>")"#,
+        );
+        let result = sanitize_html(&html, &EmbedProviders::default());
+        // onerror は title の値の文字列としてのみ残り、属性にはならない
+        assert_eq!(
+            result,
+            "<p><img src=\"https://a/b.png\" alt=\"<iframe x\" loading=\"lazy\" decoding=\"async\"> <a href=\"https://e\" title=\"<iframe src=x onerror=alert(1)>\" rel=\"noopener noreferrer\">t</a></p>\n"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlで設定したプロバイダーのiframeだけ許可されること() {
+        let providers = EmbedProviders::from_config(
+            r#"[{"name": "Vimeo", "url_hosts": ["vimeo.com"], "oembed_endpoint": "https://vimeo.com/api/oembed.json", "iframe_hosts": ["player.vimeo.com"]}]"#,
+        );
+        let input = r#"<iframe src="https://player.vimeo.com/video/1"></iframe><iframe src="https://www.youtube.com/embed/abc"></iframe>"#;
+        let result = sanitize_html(input, &providers);
+        assert_eq!(
+            result,
+            r#"<iframe src="https://player.vimeo.com/video/1"></iframe><iframe></iframe>"#
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでxのポストのクラスが保持されること() {
+        let input = r#"<blockquote class="twitter-tweet other"><p>本文</p></blockquote>"#;
+        let result = sanitize_html(input, &EmbedProviders::default());
+        assert_eq!(
+            result,
+            r#"<blockquote class="twitter-tweet"><p>本文</p></blockquote>"#
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでhttpのiframeのsrcが除去されること() {
+        let input = r#"<iframe src="http://www.youtube.com/embed/abc"></iframe>"#;
+        let result = sanitize_html(input, &EmbedProviders::default());
+        assert!(!result.contains("youtube"), "http src remains: {result}");
+    }
+
+    #[test]
+    fn imgix画像にsrcsetとdbのサイズが付与されること() {
+        let context = RenderContext {
+            image_sizes: HashMap::from([(
+                "https://blog-romira.imgix.net/dev/images/a/photo.jpg".to_string(),
+                ImageSize {
+                    width: 1600,
+                    height: 900,
+                },
+            )]),
+            ..Default::default()
+        };
+        let result = convert_markdown_to_html_with_context(
+            "本文 ![写真](https://blog-romira.imgix.net/dev/images/a/photo.jpg?w=100) 続き",
+            &context,
+        );
+        assert!(
+            result.contains(
+                r#"src="https://blog-romira.imgix.net/dev/images/a/photo.jpg?w=800&amp;auto=format&amp;q=75""#
+            ),
+            "src missing: {result}"
+        );
+        assert!(
+            result.contains("photo.jpg?w=400&amp;auto=format&amp;q=75 400w"),
+            "srcset missing: {result}"
+        );
+        assert!(
+            result.contains(r#"width="1600" height="900""#),
+            "size missing: {result}"
+        );
+        assert!(result.contains(r#"alt="写真""#), "alt missing: {result}");
+        assert!(
+            result.contains(r#"loading="lazy" decoding="async""#),
+            "lazy missing: {result}"
+        );
+        assert!(!result.contains("<figure>"), "unexpected figure: {result}");
+    }
+
+    #[test]
+    fn imgix画像のパラメータがsrcsetの各幅に引き継がれること() {
+        let result =
+            convert_markdown_to_html("![写真](https://blog-romira.imgix.net/a.jpg?fit=crop&q=60)");
+        assert!(
+            result.contains(
+                r#"src="https://blog-romira.imgix.net/a.jpg?w=800&amp;auto=format&amp;q=60&amp;fit=crop""#
+            ),
+            "src missing params: {result}"
+        );
+        for width in IMAGE_WIDTHS {
+            assert!(
+                result.contains(&format!(
+                    "a.jpg?w={width}&amp;auto=format&amp;q=60&amp;fit=crop {width}w"
+                )),
+                "srcset missing params for {width}: {result}"
+            );
+        }
+        assert!(!result.contains("q=75"), "default quality kept: {result}");
+    }
+
+    #[test]
+    fn imgix以外の画像は遅延読み込み属性のみ付与されること() {
+        let result = convert_markdown_to_html("![外部](https://example.com/a.png)");
+        assert!(
+            result.contains(
+                r#"<img src="https://example.com/a.png" alt="外部" loading="lazy" decoding="async">"#
+            ),
+            "unexpected img: {result}"
+        );
+        assert!(!result.contains("srcset"), "srcset present: {result}");
+    }
+
+    #[test]
+    fn タイトル付きの単独画像がfigureとfigcaptionになること() {
+        let result =
+            convert_markdown_to_html(r#"![構成図](https://example.com/a.png "全体の構成 <v2>")"#);
+        assert!(
+            result.starts_with("<figure><img src=\"https://example.com/a.png\""),
+            "figure missing: {result}"
+        );
+        assert!(
+            result.contains("<figcaption>全体の構成 &lt;v2&gt;</figcaption></figure>"),
+            "figcaption missing: {result}"
+        );
+        assert!(!result.contains("<p>"), "paragraph remains: {result}");
+    }
+
+    #[test]
+    fn extract_imgix_image_urlsでimgix画像のベースurlのみ抽出されること() {
+        let markdown = "![a](https://x.imgix.net/a.jpg?w=1)\n\n![b](https://example.com/b.jpg)\n\n![c](https://x.imgix.net/a.jpg)";
+        assert_eq!(
+            extract_imgix_image_urls(markdown),
+            vec!["https://x.imgix.net/a.jpg".to_string()]
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでfigureとdecodingが保持されること() {
+        let html = convert_markdown_to_html(r#"![図](https://example.com/a.png "説明")"#);
+        let result = sanitize_html(&html, &EmbedProviders::default());
+        assert!(result.contains("<figure>"), "figure stripped: {result}");
+        assert!(
+            result.contains("<figcaption>説明</figcaption>"),
+            "figcaption stripped: {result}"
+        );
+        assert!(
+            result.contains(r#"decoding="async""#),
+            "decoding stripped: {result}"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlでアラートのクラスが保持されること() {
+        let html = convert_markdown_to_html("> [!WARNING]\n> 本文です");
+        let result = sanitize_html(&html, &EmbedProviders::default());
+        assert!(
+            result.contains(r#"class="markdown-alert markdown-alert-warning""#),
+            "alert classes stripped: {result}"
+        );
+        assert!(
+            result.contains(r#"<p class="markdown-alert-title">注意</p>"#),
+            "alert title stripped: {result}"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn sanitize_htmlで許可外のクラスが除去されること() {
+        let input = r#"<div class="markdown-alert evil-class"><p class="other">x</p></div>"#;
+        let result = sanitize_html(input, &EmbedProviders::default());
+        assert!(!result.contains("evil-class"), "class remains: {result}");
+        assert!(!result.contains("other"), "class remains: {result}");
+    }
+
     #[cfg(feature = "ssr")]
     #[test]
     fn sanitize_htmlでscriptタグが除去されること() {
         let input = r#"<p>hello</p><script>alert(1)</script>"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(
             !result.contains("<script"),
             "script tag remains: {}",
@@ -117,7 +1025,7 @@ mod tests {
     #[test]
     fn sanitize_htmlでイベント属性が除去されること() {
         let input = r#"<img src="https://example.com/x.jpg" onerror="alert(1)" alt="x">"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(!result.contains("onerror"), "onerror remains: {}", result);
         assert!(result.contains(r#"src="https://example.com/x.jpg""#));
     }
@@ -126,7 +1034,7 @@ mod tests {
     #[test]
     fn sanitize_htmlでjavascriptスキームが除去されること() {
         let input = r#"<a href="javascript:alert(1)">click</a>"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(
             !result.contains("javascript:"),
             "javascript scheme remains: {}",
@@ -138,7 +1046,7 @@ mod tests {
     #[test]
     fn sanitize_htmlでimgのレスポンシブ属性が保持されること() {
         let input = r#"<img src="https://blog-romira.imgix.net/x.jpg?w=800" srcset="https://blog-romira.imgix.net/x.jpg?w=400 400w, https://blog-romira.imgix.net/x.jpg?w=800 800w" sizes="(max-width: 800px) 800px" loading="lazy" alt="x">"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(result.contains("srcset="), "srcset stripped: {}", result);
         assert!(result.contains("sizes="), "sizes stripped: {}", result);
         assert!(
@@ -152,7 +1060,7 @@ mod tests {
     #[test]
     fn sanitize_htmlで信頼ドメインのiframeが保持されること() {
         let input = r#"<iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ" width="560" height="315" allowfullscreen></iframe>"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(result.contains("<iframe"), "iframe stripped: {}", result);
         assert!(
             result.contains(r#"src="https://www.youtube.com/embed/dQw4w9WgXcQ""#),
@@ -166,7 +1074,7 @@ mod tests {
     fn sanitize_htmlで未知ドメインのiframeのsrcが除去されること() {
         let input =
             r#"<iframe src="https://evil.example.com/x" width="560" height="315"></iframe>"#;
-        let result = sanitize_html(input);
+        let result = sanitize_html(input, &EmbedProviders::default());
         assert!(
             !result.contains("evil.example.com"),
             "untrusted host remains: {}",
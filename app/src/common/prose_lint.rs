@@ -0,0 +1,670 @@
+use crate::common::markdown::markdown_options;
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::{Arena, parse_document};
+use serde::{Deserialize, Serialize};
+
+/// 一文の長さの目安（文字数）
+pub const MAX_SENTENCE_LENGTH: usize = 100;
+
+/// 文章チェックのルール
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProseRule {
+    /// 「です・ます」調と「だ・である」調の混在
+    StyleMixing,
+    /// 全角英数字
+    FullWidthAlphanumeric,
+    /// 長すぎる文
+    LongSentence,
+    /// ら抜き言葉
+    RaNuki,
+    /// 同じ語の連続（「はは」「the the」など）
+    RepeatedWord,
+}
+
+impl ProseRule {
+    /// 全ルール（設定画面の表示順）
+    pub const ALL: [ProseRule; 5] = [
+        ProseRule::StyleMixing,
+        ProseRule::FullWidthAlphanumeric,
+        ProseRule::LongSentence,
+        ProseRule::RaNuki,
+        ProseRule::RepeatedWord,
+    ];
+
+    /// 設定の保存に使うキー
+    pub fn key(self) -> &'static str {
+        match self {
+            ProseRule::StyleMixing => "style_mixing",
+            ProseRule::FullWidthAlphanumeric => "full_width_alphanumeric",
+            ProseRule::LongSentence => "long_sentence",
+            ProseRule::RaNuki => "ra_nuki",
+            ProseRule::RepeatedWord => "repeated_word",
+        }
+    }
+
+    /// キーからルールを取得
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.key() == key)
+    }
+
+    /// 表示名
+    pub fn label(self) -> &'static str {
+        match self {
+            ProseRule::StyleMixing => "文体の混在",
+            ProseRule::FullWidthAlphanumeric => "全角英数字",
+            ProseRule::LongSentence => "長すぎる文",
+            ProseRule::RaNuki => "ら抜き言葉",
+            ProseRule::RepeatedWord => "同じ語の連続",
+        }
+    }
+}
+
+/// ルールごとの有効/無効
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProseRuleSetting {
+    pub rule: ProseRule,
+    pub enabled: bool,
+}
+
+/// 文章チェックの設定（管理設定に保存）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProseCheckSettings {
+    pub rules: Vec<ProseRuleSetting>,
+}
+
+impl Default for ProseCheckSettings {
+    /// 全ルール有効
+    fn default() -> Self {
+        Self {
+            rules: ProseRule::ALL
+                .into_iter()
+                .map(|rule| ProseRuleSetting {
+                    rule,
+                    enabled: true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ProseCheckSettings {
+    /// ルールが有効か（設定に無いルールは有効）
+    pub fn is_enabled(&self, rule: ProseRule) -> bool {
+        self.rules
+            .iter()
+            .find(|setting| setting.rule == rule)
+            .is_none_or(|setting| setting.enabled)
+    }
+}
+
+/// 文章チェックで検出した問題
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProseIssue {
+    pub rule: ProseRule,
+    pub message: String,
+    /// 本文Markdown中の開始位置（バイト）
+    pub start: usize,
+    /// 本文Markdown中の終了位置（バイト、排他的）
+    pub end: usize,
+    /// 開始位置の行番号（1始まり）
+    pub line: usize,
+}
+
+/// 段落などのテキスト（コードやHTMLを除く）と本文中の位置の対応
+#[derive(Default)]
+struct TextRun {
+    text: String,
+    /// (text中の開始位置, 本文中の開始位置, 長さ)
+    segments: Vec<(usize, Option<usize>, usize)>,
+    /// 文体判定の対象か（見出しや表のセルは対象外）
+    is_paragraph: bool,
+}
+
+impl TextRun {
+    fn push(&mut self, literal: &str, source_start: Option<usize>) {
+        self.segments
+            .push((self.text.len(), source_start, literal.len()));
+        self.text.push_str(literal);
+    }
+
+    /// text中の範囲を本文中の範囲に変換（位置が特定できないテキストはNone）
+    fn source_range(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let map = |offset: usize, is_end: bool| {
+            self.segments
+                .iter()
+                .find(|(text_start, _, len)| {
+                    if is_end {
+                        *text_start < offset && offset <= text_start + len
+                    } else {
+                        *text_start <= offset && offset < text_start + len
+                    }
+                })
+                .and_then(|(text_start, source_start, _)| {
+                    source_start.map(|source_start| source_start + offset - text_start)
+                })
+        };
+        Some((map(start, false)?, map(end, true)?))
+    }
+}
+
+/// 行番号と列番号（1始まり、バイト単位）を本文中の位置に変換
+fn source_offset(line_starts: &[usize], line: usize, column: usize) -> Option<usize> {
+    let line_start = line_starts.get(line.checked_sub(1)?)?;
+    Some(line_start + column.checked_sub(1)?)
+}
+
+/// インライン要素を順に辿ってテキストを集める（コード・HTML・画像の代替テキストは除く）
+fn collect_text<'a>(
+    node: &'a AstNode<'a>,
+    markdown: &str,
+    cursor: &mut usize,
+    block_end: usize,
+    run: &mut TextRun,
+) {
+    for child in node.children() {
+        match &child.data().value {
+            NodeValue::Text(literal) => {
+                // 位置はブロック内でリテラルを前方検索して求める（エスケープ等で列がずれるため）
+                let source_start = markdown
+                    .get(*cursor..block_end)
+                    .and_then(|rest| rest.find(literal.as_ref()))
+                    .map(|pos| *cursor + pos);
+                if let Some(source_start) = source_start {
+                    *cursor = source_start + literal.len();
+                }
+                run.push(literal, source_start);
+            }
+            NodeValue::Code(_)
+            | NodeValue::HtmlInline(_)
+            | NodeValue::Image(_)
+            | NodeValue::SoftBreak
+            | NodeValue::LineBreak => {}
+            _ => collect_text(child, markdown, cursor, block_end, run),
+        }
+    }
+}
+
+/// Markdownから検査対象のテキストを段落・見出し・表のセル単位で抽出
+fn extract_text_runs(markdown: &str) -> Vec<TextRun> {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(markdown.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    let mut runs = Vec::new();
+    for node in root.descendants() {
+        let is_paragraph = match node.data().value {
+            NodeValue::Paragraph => true,
+            NodeValue::Heading(_) | NodeValue::TableCell => false,
+            _ => continue,
+        };
+        let sourcepos = node.data().sourcepos;
+        let Some(block_start) =
+            source_offset(&line_starts, sourcepos.start.line, sourcepos.start.column)
+        else {
+            continue;
+        };
+        let block_end = source_offset(&line_starts, sourcepos.end.line, sourcepos.end.column + 1)
+            .map_or(markdown.len(), |end| end.min(markdown.len()));
+
+        let mut run = TextRun {
+            is_paragraph,
+            ..Default::default()
+        };
+        let mut cursor = block_start;
+        collect_text(node, markdown, &mut cursor, block_end, &mut run);
+        if !run.text.is_empty() {
+            runs.push(run);
+        }
+    }
+    runs
+}
+
+/// 文末として扱う文字
+const SENTENCE_TERMINATORS: &[char] = &['。', '！', '？', '!', '?'];
+/// 文末記号の後に続いても同じ文に含める閉じ括弧
+const CLOSING_BRACKETS: &[char] = &['」', '』', '）', ')', '】'];
+
+/// テキストを文に分割（text中のバイト範囲）
+fn split_sentences(text: &str) -> Vec<(usize, usize)> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        if !SENTENCE_TERMINATORS.contains(&ch) {
+            continue;
+        }
+        let mut end = i + ch.len_utf8();
+        while let Some(&(j, next)) = chars.peek() {
+            if SENTENCE_TERMINATORS.contains(&next) || CLOSING_BRACKETS.contains(&next) {
+                end = j + next.len_utf8();
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        sentences.push((start, end));
+        start = end;
+    }
+    if start < text.len() {
+        sentences.push((start, text.len()));
+    }
+
+    // 前後の空白を除いた範囲にする
+    sentences
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let sentence = &text[start..end];
+            let trimmed_start = start + (sentence.len() - sentence.trim_start().len());
+            let trimmed_end = start + sentence.trim_end().len();
+            (trimmed_start < trimmed_end).then_some((trimmed_start, trimmed_end))
+        })
+        .collect()
+}
+
+/// 文体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProseStyle {
+    DesuMasu,
+    DaDearu,
+}
+
+const DESU_MASU_ENDINGS: &[&str] = &[
+    "です",
+    "ます",
+    "でした",
+    "ました",
+    "ません",
+    "でしょう",
+    "ましょう",
+    "ください",
+];
+const DA_DEARU_ENDINGS: &[&str] = &["である", "であった", "ではない", "だった", "だろう", "だ"];
+
+/// 文末から文体を判定
+fn sentence_style(sentence: &str) -> Option<ProseStyle> {
+    let body = sentence.trim_end_matches(|c: char| {
+        SENTENCE_TERMINATORS.contains(&c) || CLOSING_BRACKETS.contains(&c) || c.is_whitespace()
+    });
+    let body = body.strip_suffix('か').unwrap_or(body);
+    if DESU_MASU_ENDINGS
+        .iter()
+        .any(|ending| body.ends_with(ending))
+    {
+        Some(ProseStyle::DesuMasu)
+    } else if DA_DEARU_ENDINGS.iter().any(|ending| body.ends_with(ending)) {
+        Some(ProseStyle::DaDearu)
+    } else {
+        None
+    }
+}
+
+/// ら抜きになりやすい一段動詞（とカ変）の語幹
+const RA_NUKI_STEMS: &[&str] = &[
+    "見",
+    "来",
+    "着",
+    "寝",
+    "出",
+    "居",
+    "似",
+    "食べ",
+    "起き",
+    "生き",
+    "降り",
+    "借り",
+    "信じ",
+    "感じ",
+    "考え",
+    "覚え",
+    "教え",
+    "答え",
+    "変え",
+    "決め",
+    "止め",
+    "辞め",
+    "始め",
+    "集め",
+    "調べ",
+    "比べ",
+    "並べ",
+    "受け",
+    "開け",
+    "続け",
+    "助け",
+    "避け",
+    "付け",
+    "見つけ",
+    "投げ",
+    "逃げ",
+    "上げ",
+    "下げ",
+    "建て",
+    "捨て",
+    "育て",
+    "立て",
+    "伝え",
+    "与え",
+    "耐え",
+    "迎え",
+    "数え",
+];
+/// 「語幹＋れ」の後に続くとら抜きになる活用語尾
+const RA_NUKI_SUFFIXES: &[&str] = &["る", "ない", "なかっ", "ます", "ません", "た", "て", "ず"];
+
+/// 連続すると誤りの可能性が高い助詞
+const DOUBLED_PARTICLES: &[char] = &['は', 'が', 'を', 'に', 'で', 'の'];
+
+fn is_hiragana(ch: char) -> bool {
+    ('\u{3041}'..='\u{309F}').contains(&ch)
+}
+
+fn is_full_width_alphanumeric(ch: char) -> bool {
+    matches!(ch, '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ')
+}
+
+/// 1つのテキスト範囲の問題（text中のバイト範囲）
+struct RunIssue {
+    rule: ProseRule,
+    message: String,
+    start: usize,
+    end: usize,
+}
+
+fn check_full_width_alphanumeric(text: &str, issues: &mut Vec<RunIssue>) {
+    let mut run_start = None;
+    for (i, ch) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (is_full_width_alphanumeric(ch), run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                issues.push(RunIssue {
+                    rule: ProseRule::FullWidthAlphanumeric,
+                    message: format!("全角英数字「{}」は半角で書いてください", &text[start..i]),
+                    start,
+                    end: i,
+                });
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_long_sentences(text: &str, sentences: &[(usize, usize)], issues: &mut Vec<RunIssue>) {
+    for &(start, end) in sentences {
+        let length = text[start..end].chars().count();
+        if length > MAX_SENTENCE_LENGTH {
+            issues.push(RunIssue {
+                rule: ProseRule::LongSentence,
+                message: format!(
+                    "一文が長すぎます（{length}文字。目安は{MAX_SENTENCE_LENGTH}文字以内）"
+                ),
+                start,
+                end,
+            });
+        }
+    }
+}
+
+fn check_ra_nuki(text: &str, issues: &mut Vec<RunIssue>) {
+    for stem in RA_NUKI_STEMS {
+        let pattern = format!("{stem}れ");
+        for (start, _) in text.match_indices(&pattern) {
+            let end = start + pattern.len();
+            let rest = &text[end..];
+            let Some(suffix) = RA_NUKI_SUFFIXES.iter().find(|s| rest.starts_with(*s)) else {
+                continue;
+            };
+            issues.push(RunIssue {
+                rule: ProseRule::RaNuki,
+                message: format!(
+                    "ら抜き言葉の可能性があります（「{stem}れ{suffix}」→「{stem}られ{suffix}」）"
+                ),
+                start,
+                end,
+            });
+        }
+    }
+}
+
+fn check_repeated_words(text: &str, issues: &mut Vec<RunIssue>) {
+    // 助詞の重複（「記事のの」など）。前後もひらがなの場合は別の語の一部とみなす
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    for window in chars.windows(2) {
+        let [(start, first), (second_start, second)] = window else {
+            continue;
+        };
+        if first != second || !DOUBLED_PARTICLES.contains(first) {
+            continue;
+        }
+        let before = text[..*start].chars().next_back();
+        let end = second_start + second.len_utf8();
+        let after = text[end..].chars().next();
+        if before.is_none_or(is_hiragana) || after == Some(*first) {
+            continue;
+        }
+        issues.push(RunIssue {
+            rule: ProseRule::RepeatedWord,
+            message: format!("同じ語が続いています（「{first}{second}」）"),
+            start: *start,
+            end,
+        });
+    }
+
+    // 英単語の重複（「the the」など）
+    let mut previous: Option<(usize, &str)> = None;
+    let mut offset = 0;
+    for token in text.split(|c: char| c.is_ascii_whitespace()) {
+        let start = offset;
+        offset += token.len() + 1;
+        if token.is_empty() {
+            continue;
+        }
+        if token.chars().all(|c| c.is_ascii_alphabetic())
+            && let Some((previous_start, previous_token)) = previous
+            && previous_token.eq_ignore_ascii_case(token)
+        {
+            issues.push(RunIssue {
+                rule: ProseRule::RepeatedWord,
+                message: format!("同じ語が続いています（「{previous_token} {token}」）"),
+                start: previous_start,
+                end: start + token.len(),
+            });
+        }
+        previous = Some((start, token));
+    }
+}
+
+/// 記事本文の文章をチェック（コードとHTMLは対象外）
+pub fn check_prose(markdown: &str, settings: &ProseCheckSettings) -> Vec<ProseIssue> {
+    let runs = extract_text_runs(markdown);
+    let mut run_issues: Vec<(usize, RunIssue)> = Vec::new();
+
+    // 文体は本文全体で多い方を基準とし、少ない方の文を指摘する
+    let mut styled_sentences = Vec::new();
+
+    for (index, run) in runs.iter().enumerate() {
+        let sentences = split_sentences(&run.text);
+        let mut issues = Vec::new();
+
+        if settings.is_enabled(ProseRule::FullWidthAlphanumeric) {
+            check_full_width_alphanumeric(&run.text, &mut issues);
+        }
+        if settings.is_enabled(ProseRule::LongSentence) {
+            check_long_sentences(&run.text, &sentences, &mut issues);
+        }
+        if settings.is_enabled(ProseRule::RaNuki) {
+            check_ra_nuki(&run.text, &mut issues);
+        }
+        if settings.is_enabled(ProseRule::RepeatedWord) {
+            check_repeated_words(&run.text, &mut issues);
+        }
+        if run.is_paragraph {
+            for (start, end) in sentences {
+                if let Some(style) = sentence_style(&run.text[start..end]) {
+                    styled_sentences.push((index, start, end, style));
+                }
+            }
+        }
+
+        run_issues.extend(issues.into_iter().map(|issue| (index, issue)));
+    }
+
+    if settings.is_enabled(ProseRule::StyleMixing) {
+        let desu_masu = styled_sentences
+            .iter()
+            .filter(|(_, _, _, style)| *style == ProseStyle::DesuMasu)
+            .count();
+        let (majority, majority_label, minority_label) = if desu_masu * 2 >= styled_sentences.len()
+        {
+            (ProseStyle::DesuMasu, "です・ます", "だ・である")
+        } else {
+            (ProseStyle::DaDearu, "だ・である", "です・ます")
+        };
+        for (index, start, end, style) in styled_sentences {
+            if style != majority {
+                run_issues.push((
+                    index,
+                    RunIssue {
+                        rule: ProseRule::StyleMixing,
+                        message: format!(
+                            "「{majority_label}」調の本文に「{minority_label}」調の文が混在しています"
+                        ),
+                        start,
+                        end,
+                    },
+                ));
+            }
+        }
+    }
+
+    let mut issues: Vec<ProseIssue> = run_issues
+        .into_iter()
+        .filter_map(|(index, issue)| {
+            let (start, end) = runs[index].source_range(issue.start, issue.end)?;
+            Some(ProseIssue {
+                rule: issue.rule,
+                message: issue.message,
+                start,
+                end,
+                line: markdown[..start].matches('\n').count() + 1,
+            })
+        })
+        .collect();
+    issues.sort_by_key(|issue| (issue.start, issue.end));
+    issues
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(markdown: &str) -> Vec<ProseIssue> {
+        check_prose(markdown, &ProseCheckSettings::default())
+    }
+
+    fn rules(issues: &[ProseIssue]) -> Vec<ProseRule> {
+        issues.iter().map(|issue| issue.rule).collect()
+    }
+
+    #[test]
+    fn 問題のない文章では何も検出されないこと() {
+        let markdown = "## はじめに\n\nこれは記事です。Rustで書きました。\n\n```rust\nlet ｘ = 1; // 見れる\n```\n";
+        assert_eq!(check(markdown), vec![]);
+    }
+
+    #[test]
+    fn 文体の混在で少ない方の文が指摘されること() {
+        let markdown = "これは記事です。\n\nテストを書きました。\n\nこれは例外である。";
+        let issues = check(markdown);
+
+        assert_eq!(rules(&issues), vec![ProseRule::StyleMixing]);
+        assert_eq!(
+            &markdown[issues[0].start..issues[0].end],
+            "これは例外である。"
+        );
+        assert_eq!(issues[0].line, 5);
+    }
+
+    #[test]
+    fn 全角英数字が指摘されること() {
+        let markdown = "バージョン**２**で`ＡＢＣ`と\nＲｕｓｔを使う";
+        let issues = check(markdown);
+
+        assert_eq!(
+            rules(&issues),
+            vec![
+                ProseRule::FullWidthAlphanumeric,
+                ProseRule::FullWidthAlphanumeric
+            ]
+        );
+        assert_eq!(&markdown[issues[0].start..issues[0].end], "２");
+        assert_eq!(&markdown[issues[1].start..issues[1].end], "Ｒｕｓｔ");
+        assert_eq!(issues[1].line, 2);
+    }
+
+    #[test]
+    fn 長すぎる文が指摘されること() {
+        let long = "あ".repeat(MAX_SENTENCE_LENGTH + 1);
+        let markdown = format!("短い文です。{long}。");
+        let issues = check(&markdown);
+
+        assert_eq!(rules(&issues), vec![ProseRule::LongSentence]);
+        assert_eq!(
+            &markdown[issues[0].start..issues[0].end],
+            format!("{long}。")
+        );
+    }
+
+    #[test]
+    fn ら抜き言葉が指摘されること() {
+        let markdown = "ここから見れる。明日は来れない。見れば分かる。見られる。";
+        let issues = check(markdown);
+
+        assert_eq!(rules(&issues), vec![ProseRule::RaNuki, ProseRule::RaNuki]);
+        assert_eq!(&markdown[issues[0].start..issues[0].end], "見れ");
+        assert!(issues[0].message.contains("見られる"));
+        assert_eq!(&markdown[issues[1].start..issues[1].end], "来れ");
+    }
+
+    #[test]
+    fn 同じ語の連続が指摘されること() {
+        let markdown = "記事のの内容と the the end。ははは。言葉をののしる。";
+        let issues = check(markdown);
+
+        assert_eq!(
+            rules(&issues),
+            vec![ProseRule::RepeatedWord, ProseRule::RepeatedWord]
+        );
+        assert_eq!(&markdown[issues[0].start..issues[0].end], "のの");
+        assert_eq!(&markdown[issues[1].start..issues[1].end], "the the");
+    }
+
+    #[test]
+    fn 無効にしたルールは検出されないこと() {
+        let settings = ProseCheckSettings {
+            rules: vec![ProseRuleSetting {
+                rule: ProseRule::FullWidthAlphanumeric,
+                enabled: false,
+            }],
+        };
+        let issues = check_prose("ＡＢＣと見れる", &settings);
+
+        assert_eq!(rules(&issues), vec![ProseRule::RaNuki]);
+    }
+
+    #[test]
+    fn ルールのキーから相互に変換できること() {
+        for rule in ProseRule::ALL {
+            assert_eq!(ProseRule::from_key(rule.key()), Some(rule));
+        }
+        assert_eq!(ProseRule::from_key("unknown"), None);
+    }
+}
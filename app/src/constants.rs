@@ -17,13 +17,23 @@ pub(crate) const HOUR: i32 = 3600;
 pub(crate) const JST_TZ: i32 = 9;
 pub(crate) const DATE_DISPLAY_FORMAT: &str = "%Y年%m月%d日";
 pub(crate) const DATE_ISO_FORMAT: &str = "%Y-%m-%d";
+pub(crate) const DATE_TIME_DISPLAY_FORMAT: &str = "%Y年%m月%d日 %H:%M";
+/// Twitter Cardの種類（記事のSEO設定で上書きしていない場合）
+pub(crate) const DEFAULT_TWITTER_CARD: &str = "summary_large_image";
 pub(crate) const ROMIRA_GITHUB_URL: &str = "https://github.com/Romira915";
+/// 著者アイコン（imgix）
+pub(crate) const ROMIRA_AVATAR_URL: &str =
+    "https://blog-romira.imgix.net/4874cb12-6e50-4aa3-a1f5-541de4ae184c/icon.JPG";
 pub(crate) const ROMIRA_X_URL: &str = "https://x.com/Romira915";
 pub(crate) const QIITA_BASE_URL: &str = "https://qiita.com";
+pub(crate) const ZENN_BASE_URL: &str = "https://zenn.dev";
 
 /// 本文画像用のレスポンシブサイズ (srcset用)
 pub const IMAGE_WIDTHS: [u32; 3] = [400, 800, 1200];
 
+/// 本文画像の `sizes` 属性
+pub const IMAGE_SIZES: &str = "(max-width: 800px) 100vw, 800px";
+
 /// カバー画像用のレスポンシブサイズ (srcset用)
 pub const COVER_IMAGE_WIDTHS: [u32; 3] = [800, 1200, 1920];
 
@@ -24,14 +24,143 @@ pub(crate) enum QiitaArticleServiceError {
     FailedReqwestSend(#[from] reqwest::Error),
     #[error("Failed to api response status code: {0}")]
     UnexpectedStatusCode(reqwest::StatusCode),
+    #[error("Rate limited until {0:?}")]
+    RateLimited(Option<chrono::DateTime<chrono::Utc>>),
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Error, Debug)]
+pub(crate) enum QiitaImportError {
+    #[error(transparent)]
+    Qiita(#[from] QiitaArticleServiceError),
+    #[error(transparent)]
+    Cms(#[from] blog_romira_dev_cms::CmsError),
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Error, Debug)]
+pub(crate) enum SyndicationError {
+    #[error(transparent)]
+    Qiita(#[from] QiitaArticleServiceError),
+    #[error(transparent)]
+    Cms(#[from] blog_romira_dev_cms::CmsError),
+    #[error("At least one category is required to post to Qiita")]
+    NoTags,
+    #[error("The article is no longer public but the Qiita item could not be made private: {0}")]
+    NotMadePrivate(QiitaArticleServiceError),
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum ZennArticleServiceError {
+    #[error(transparent)]
+    FailedReqwestSend(#[from] reqwest::Error),
+    #[error("Failed to api response status code: {0}")]
+    UnexpectedStatusCode(reqwest::StatusCode),
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum FeedArticleServiceError {
+    #[error(transparent)]
+    FailedReqwestSend(#[from] reqwest::Error),
+    #[error("Failed to api response status code: {0}")]
+    UnexpectedStatusCode(reqwest::StatusCode),
+    #[error("Failed to parse feed: {0}")]
+    InvalidFeed(String),
+}
+
+/// 外部記事ソース共通のエラー
+#[derive(Error, Debug)]
+pub(crate) enum ExternalArticleSourceError {
+    #[error(transparent)]
+    FailedReqwestSend(#[from] reqwest::Error),
+    #[error("Failed to api response status code: {0}")]
+    UnexpectedStatusCode(reqwest::StatusCode),
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    #[error("Timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    #[error("Rate limited until {0:?}")]
+    RateLimited(Option<chrono::DateTime<chrono::Utc>>),
+}
+
+impl From<WordPressArticleServiceError> for ExternalArticleSourceError {
+    fn from(value: WordPressArticleServiceError) -> Self {
+        match value {
+            WordPressArticleServiceError::FailedReqwestSend(err) => Self::FailedReqwestSend(err),
+            WordPressArticleServiceError::UnexpectedStatusCode(status) => {
+                Self::UnexpectedStatusCode(status)
+            }
+        }
+    }
+}
+
+impl From<QiitaArticleServiceError> for ExternalArticleSourceError {
+    fn from(value: QiitaArticleServiceError) -> Self {
+        match value {
+            QiitaArticleServiceError::FailedReqwestSend(err) => Self::FailedReqwestSend(err),
+            QiitaArticleServiceError::UnexpectedStatusCode(status) => {
+                Self::UnexpectedStatusCode(status)
+            }
+            QiitaArticleServiceError::RateLimited(until) => Self::RateLimited(until),
+        }
+    }
+}
+
+impl From<ZennArticleServiceError> for ExternalArticleSourceError {
+    fn from(value: ZennArticleServiceError) -> Self {
+        match value {
+            ZennArticleServiceError::FailedReqwestSend(err) => Self::FailedReqwestSend(err),
+            ZennArticleServiceError::UnexpectedStatusCode(status) => {
+                Self::UnexpectedStatusCode(status)
+            }
+        }
+    }
+}
+
+impl From<FeedArticleServiceError> for ExternalArticleSourceError {
+    fn from(value: FeedArticleServiceError) -> Self {
+        match value {
+            FeedArticleServiceError::FailedReqwestSend(err) => Self::FailedReqwestSend(err),
+            FeedArticleServiceError::UnexpectedStatusCode(status) => {
+                Self::UnexpectedStatusCode(status)
+            }
+            FeedArticleServiceError::InvalidFeed(message) => Self::InvalidResponse(message),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum OgpFetchError {
+    #[error(transparent)]
+    FailedReqwestSend(#[from] reqwest::Error),
+    #[error("Failed to api response status code: {0}")]
+    UnexpectedStatusCode(reqwest::StatusCode),
+    #[error("Unsupported content type: {0}")]
+    UnsupportedContentType(String),
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum OgImageError {
+    #[error("Failed to parse OG image SVG: {0}")]
+    InvalidSvg(String),
+    #[error("Failed to encode OG image: {0}")]
+    Encode(String),
+    #[error("OG image render task failed: {0}")]
+    Task(String),
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum OEmbedFetchError {
+    #[error(transparent)]
+    FailedReqwestSend(#[from] reqwest::Error),
+    #[error("Failed to api response status code: {0}")]
+    UnexpectedStatusCode(reqwest::StatusCode),
+    #[error("oEmbed response has no html")]
+    MissingHtml,
 }
 
 #[derive(Error, Debug, Serialize, Deserialize)]
 pub enum GetArticlesError {
-    #[error("Failed to get articles from WordPressArticleService: {0}")]
-    WordPressArticleService(String),
-    #[error("Failed to get articles from QiitaArticleService: {0}")]
-    QiitaArticleService(String),
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
@@ -64,6 +193,8 @@ pub enum GetArticleError {
     NewtArticleServiceGetArticle(String),
     #[error("Failed to get article from DB: {0}")]
     DatabaseError(String),
+    #[error("Too many password attempts")]
+    TooManyAttempts,
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
@@ -71,6 +202,10 @@ pub enum GetArticleError {
 impl FromStr for GetArticleError {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // クライアントでパスワードの試行回数の超過を見分けられるようにする
+        if s == Self::TooManyAttempts.to_string() {
+            return Ok(Self::TooManyAttempts);
+        }
         Ok(Self::Unknown(s.to_string()))
     }
 }
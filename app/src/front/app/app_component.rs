@@ -1,5 +1,8 @@
-use crate::front::pages::admin_page::{ArticleEditorPage, ArticleListPage, ImagesPage};
+use crate::front::pages::admin_page::{
+    ArticleEditorPage, ArticleListPage, ImagesPage, ImportPage, SettingsPage,
+};
 use crate::front::pages::article_page::ArticlePage;
+use crate::front::pages::category_page::CategoryPage;
 use crate::front::pages::not_found_page::NotFoundPage;
 use crate::front::pages::preview_article_page::PreviewArticlePage;
 use crate::front::pages::top_page::TopPage;
@@ -23,6 +26,11 @@ pub fn App() -> impl IntoView {
                 <Routes fallback=|| view! { <NotFoundPage /> }>
                     <Route path=StaticSegment("") view=TopPage ssr=SsrMode::Async />
                     <Route path=path!("/articles/:id") view=ArticlePage ssr=SsrMode::Async />
+                    <Route
+                        path=path!("/categories/:slug")
+                        view=CategoryPage
+                        ssr=SsrMode::Async
+                    />
                     <Route path=path!("/preview/:id") view=PreviewArticlePage ssr=SsrMode::Async />
                     // Admin routes
                     <Route path=path!("/admin") view=ArticleListPage ssr=SsrMode::Async />
@@ -33,6 +41,8 @@ pub fn App() -> impl IntoView {
                         ssr=SsrMode::Async
                     />
                     <Route path=path!("/admin/images") view=ImagesPage ssr=SsrMode::Async />
+                    <Route path=path!("/admin/import") view=ImportPage ssr=SsrMode::Async />
+                    <Route path=path!("/admin/settings") view=SettingsPage ssr=SsrMode::Async />
                 </Routes>
             </main>
         </Router>
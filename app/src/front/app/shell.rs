@@ -3,6 +3,8 @@ use leptos::prelude::*;
 use leptos::{IntoView, view};
 use leptos_meta::{HashedStylesheet, MetaTags, Stylesheet};
 
+use crate::constants::WEB_APP_TITLE;
+
 use super::ASSETS_ROOT;
 use super::App;
 
@@ -36,6 +38,24 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                     
                     gtag('config', 'G-4P5K3SBG1K');"}
                 </script>
+                <link
+                    rel="alternate"
+                    type="application/rss+xml"
+                    title=WEB_APP_TITLE
+                    href="/feed.xml"
+                />
+                <link
+                    rel="alternate"
+                    type="application/atom+xml"
+                    title=WEB_APP_TITLE
+                    href="/atom.xml"
+                />
+                <link
+                    rel="alternate"
+                    type="application/feed+json"
+                    title=WEB_APP_TITLE
+                    href="/feed.json"
+                />
                 <link rel="stylesheet" href=format!("{}/google.min.css", ASSETS_ROOT) />
                 <script
                     src="https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.11.1/highlight.min.js"
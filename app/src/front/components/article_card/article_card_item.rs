@@ -9,6 +9,48 @@ pub(crate) fn ArticleCard(article: HomePageArticleDto) -> impl IntoView {
         ArticleSource::Local => ("", ""),
         _ => ("_blank", "noopener noreferrer"),
     };
+    let source_badge = article.article_source.label().map(|label| {
+        let source_class = match article.article_source {
+            ArticleSource::WordPress => article_card_style::article_source_word_press,
+            ArticleSource::Qiita => article_card_style::article_source_qiita,
+            ArticleSource::Zenn => article_card_style::article_source_zenn,
+            ArticleSource::Feed(_) => article_card_style::article_source_feed,
+            ArticleSource::Local => "",
+        };
+        view! {
+            <span class=format!(
+                "{} {}",
+                article_card_style::article_source,
+                source_class,
+            )>{label}</span>
+        }
+    });
+    let emoji = article.emoji.clone().map(|emoji| {
+        view! {
+            <span class=article_card_style::article_emoji aria-hidden="true">
+                {emoji}
+            </span>
+        }
+    });
+    // カードのリンクの中にリンクは置けないため、他の掲載先はリンクの外に並べる
+    let also_on = (!article.also_on.is_empty()).then(|| {
+        view! {
+            <p class=article_card_style::article_also_on>
+                "他の掲載先:"
+                {article
+                    .also_on
+                    .iter()
+                    .map(|link| {
+                        view! {
+                            <a href=link.url.clone() target="_blank" rel="noopener noreferrer">
+                                {link.article_source.label().unwrap_or_default().to_string()}
+                            </a>
+                        }
+                    })
+                    .collect_view()}
+            </p>
+        }
+    });
 
     view! {
         <article class=article_card_style::article_card>
@@ -28,7 +70,9 @@ pub(crate) fn ArticleCard(article: HomePageArticleDto) -> impl IntoView {
                         class=article_card_style::article_thumbnail
                     />
                     <figcaption class=article_card_style::article_info>
-                        <h2 class=article_card_style::article_title>{article.title.get()}</h2>
+                        <h2 class=article_card_style::article_title>
+                            {emoji}{article.title.get()}
+                        </h2>
                         <ul class=article_card_style::article_category_list>
                             {article
                                 .category
@@ -43,11 +87,13 @@ pub(crate) fn ArticleCard(article: HomePageArticleDto) -> impl IntoView {
                                 .collect_view()}
                         </ul>
                         <p class=article_card_style::article_published_at>
-                            {article.first_published_at.get()}
+                            {source_badge}{article.first_published_at.get()}
                         </p>
                     </figcaption>
                 </figure>
             </a>
+            {also_on}
         </article>
     }
+    .into_any()
 }
@@ -57,6 +57,29 @@ pub(crate) fn ArticleDetail(article: ArticleDetailDto) -> impl IntoView {
                 )
                 inner_html=article.body
             ></section>
+            {(!article.backlinks.is_empty())
+                .then(|| {
+                    view! {
+                <nav class=article_detail_style::article_backlinks>
+                    <h2 class=article_detail_style::article_backlinks_title>
+                        "この記事を参照している記事"
+                    </h2>
+                    <ul class=article_detail_style::article_backlinks_list>
+                        {article
+                            .backlinks
+                            .into_iter()
+                            .map(|backlink| {
+                                view! {
+                                    <li>
+                                        <a href=backlink.src>{backlink.title}</a>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+                    </ul>
+                </nav>
+                    }
+                })}
         </article>
     }
 }
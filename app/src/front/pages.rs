@@ -1,5 +1,6 @@
 pub(crate) mod admin_page;
 pub(crate) mod article_page;
+pub(crate) mod category_page;
 pub(crate) mod not_found_page;
 pub(crate) mod preview_article_page;
 pub(crate) mod top_page;
@@ -1,12 +1,16 @@
 pub mod article_editor;
 pub mod article_list;
 pub mod images_page;
+pub mod import_page;
 pub mod layout;
+pub mod settings_page;
 
 pub use article_editor::ArticleEditorPage;
 pub use article_list::ArticleListPage;
 pub use images_page::ImagesPage;
+pub use import_page::ImportPage;
 pub use layout::AdminLayout;
+pub use settings_page::SettingsPage;
 
 // Re-export auth functions from common
 pub use crate::common::handlers::auth::{get_auth_user, is_oauth_configured};
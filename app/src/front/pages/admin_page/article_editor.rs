@@ -5,7 +5,10 @@ pub use view::ArticleEditorPage;
 
 // Re-export from common handlers
 pub use crate::common::handlers::admin::{
-    ArticleEditData, DeleteArticleInput, PublishArticleInput, SaveDraftInput, SavePublishedInput,
-    delete_article_handler, get_article_for_edit_handler, get_categories_handler,
-    publish_article_handler, save_draft_handler, save_published_handler,
+    ArticleEditData, ArticleSeoData, ArticleVisibilityData, DeleteArticleInput, LintArticleInput,
+    PreviewOgImageInput, PublishArticleInput, RetrySyndicationInput, SaveDraftInput,
+    SavePublishedInput, SyndicationStatusData, delete_article_handler,
+    get_article_for_edit_handler, get_categories_handler, get_prose_check_settings_handler,
+    get_syndications_handler, lint_article_handler, preview_og_image_handler,
+    publish_article_handler, retry_syndication_handler, save_draft_handler, save_published_handler,
 };
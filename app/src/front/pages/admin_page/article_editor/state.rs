@@ -1,9 +1,16 @@
 use leptos::prelude::*;
 
 use super::{
-    ArticleEditData, DeleteArticleInput, PublishArticleInput, SaveDraftInput, SavePublishedInput,
-    delete_article_handler, publish_article_handler, save_draft_handler, save_published_handler,
+    ArticleEditData, ArticleSeoData, ArticleVisibilityData, DeleteArticleInput, LintArticleInput,
+    PublishArticleInput, RetrySyndicationInput, SaveDraftInput, SavePublishedInput,
+    SyndicationStatusData, delete_article_handler, get_syndications_handler, lint_article_handler,
+    publish_article_handler, retry_syndication_handler, save_draft_handler, save_published_handler,
 };
+use crate::common::content_lint::{LintIssue, has_lint_errors};
+use crate::common::prose_lint::ProseCheckSettings;
+
+/// 構造チェックのエラーで公開を中止した際のメッセージ
+const LINT_ERROR_MESSAGE: &str = "公開できない問題があります。問題一覧を確認してください";
 
 #[derive(Clone, Copy, PartialEq, Default)]
 pub enum ViewMode {
@@ -22,6 +29,20 @@ pub struct ArticleFormState {
     pub description: RwSignal<String>,
     pub cover_image_url: RwSignal<Option<String>>,
     pub categories: RwSignal<Vec<String>>,
+    /// SEO設定（未設定の項目は記事の内容から決める）
+    pub seo: RwSignal<ArticleSeoData>,
+    /// 公開範囲（公開記事のみ変更できる）
+    pub visibility: RwSignal<ArticleVisibilityData>,
+    /// パスワードが設定済みか
+    pub has_password: RwSignal<bool>,
+    /// パスワード保護する場合の新しいパスワード（空なら変更しない）
+    pub password: RwSignal<String>,
+    /// 外部サービスから取り込んだ記事の取り込み元URL
+    pub origin_url: RwSignal<Option<String>>,
+    /// 転載先ごとの反映状況（公開記事のみ）
+    pub syndications: RwSignal<Vec<SyndicationStatusData>>,
+    /// 転載を再試行中か
+    pub syndicating: RwSignal<bool>,
     pub is_draft: RwSignal<bool>,
     pub view_mode: RwSignal<ViewMode>,
     pub is_fullscreen: RwSignal<bool>,
@@ -29,6 +50,12 @@ pub struct ArticleFormState {
     pub publishing: RwSignal<bool>,
     pub deleting: RwSignal<bool>,
     pub message: RwSignal<Option<(bool, String)>>,
+    /// 構造チェックで検出した問題一覧
+    pub lint_issues: RwSignal<Vec<LintIssue>>,
+    /// 構造チェックのエラーを無視して公開するか
+    pub ignore_lint_errors: RwSignal<bool>,
+    /// 文章チェックの設定（管理設定から読み込む）
+    pub prose_check_settings: RwSignal<ProseCheckSettings>,
 }
 
 impl Default for ArticleFormState {
@@ -40,6 +67,13 @@ impl Default for ArticleFormState {
             description: RwSignal::new(String::new()),
             cover_image_url: RwSignal::new(None),
             categories: RwSignal::new(vec![]),
+            seo: RwSignal::new(ArticleSeoData::default()),
+            visibility: RwSignal::new(ArticleVisibilityData::default()),
+            has_password: RwSignal::new(false),
+            password: RwSignal::new(String::new()),
+            origin_url: RwSignal::new(None),
+            syndications: RwSignal::new(vec![]),
+            syndicating: RwSignal::new(false),
             is_draft: RwSignal::new(true), // 新規作成時は下書き
             view_mode: RwSignal::new(ViewMode::default()),
             is_fullscreen: RwSignal::new(false),
@@ -47,6 +81,9 @@ impl Default for ArticleFormState {
             publishing: RwSignal::new(false),
             deleting: RwSignal::new(false),
             message: RwSignal::new(None),
+            lint_issues: RwSignal::new(vec![]),
+            ignore_lint_errors: RwSignal::new(false),
+            prose_check_settings: RwSignal::new(ProseCheckSettings::default()),
         }
     }
 }
@@ -61,6 +98,12 @@ impl ArticleFormState {
             .set(article.description.clone().unwrap_or_default());
         self.cover_image_url.set(article.cover_image_url.clone());
         self.categories.set(article.categories.clone());
+        self.seo.set(article.seo.clone());
+        self.visibility.set(article.visibility);
+        self.has_password.set(article.has_password);
+        self.password.set(String::new());
+        self.origin_url.set(article.origin_url.clone());
+        self.syndications.set(article.syndications.clone());
         self.is_draft.set(article.is_draft);
     }
 
@@ -84,6 +127,7 @@ impl ArticleFormState {
             },
             cover_image_url: self.cover_image_url.get(),
             category_names: self.categories.get(),
+            seo: self.seo.get(),
         }
     }
 
@@ -102,9 +146,45 @@ impl ArticleFormState {
             },
             cover_image_url: self.cover_image_url.get(),
             category_names: self.categories.get(),
+            seo: self.seo.get(),
+            visibility: self.visibility.get(),
+            password: Some(self.password.get()).filter(|password| !password.is_empty()),
+            ignore_lint_errors: self.ignore_lint_errors.get(),
+        }
+    }
+
+    /// 構造チェックを実行して問題一覧を更新
+    ///
+    /// エラーがあり、無視する指定もない場合は公開できないのでfalseを返す。
+    /// チェック自体に失敗した場合はサーバー側の判定に任せてtrueを返す。
+    async fn check_lint(&self) -> bool {
+        let description = self.description.get();
+        let input = LintArticleInput {
+            body: self.body.get(),
+            description: if description.is_empty() {
+                None
+            } else {
+                Some(description)
+            },
+        };
+        match lint_article_handler(input).await {
+            Ok(issues) => {
+                let publishable = self.ignore_lint_errors.get() || !has_lint_errors(&issues);
+                self.lint_issues.set(issues);
+                publishable
+            }
+            Err(_) => true,
         }
     }
 
+    /// 構造チェックアクションを生成
+    pub fn create_lint_action(&self) -> Action<(), ()> {
+        let form = *self;
+        Action::new(move |_: &()| async move {
+            form.check_lint().await;
+        })
+    }
+
     /// 保存アクションを生成（下書き/公開で適切なエンドポイントを呼び分け）
     pub fn create_save_action<F>(&self, get_article_id: F) -> Action<(), ()>
     where
@@ -125,13 +205,21 @@ impl ArticleFormState {
                 form.saving.set(true);
                 form.message.set(None);
 
+                // 構造チェック（公開記事はエラーがあれば保存しない）
+                if !form.check_lint().await && !is_draft {
+                    form.saving.set(false);
+                    form.message
+                        .set(Some((false, LINT_ERROR_MESSAGE.to_string())));
+                    return;
+                }
+
                 let result = if is_draft {
                     // 下書きの保存（Upsert）
-                    let input = form.as_draft_input(id);
+                    let input = form.as_draft_input(id.clone());
                     save_draft_handler(input).await
                 } else {
                     // 公開記事の保存
-                    let input = form.as_published_input(id);
+                    let input = form.as_published_input(id.clone());
                     save_published_handler(input).await
                 };
 
@@ -139,6 +227,20 @@ impl ArticleFormState {
 
                 match result {
                     Ok(_) => {
+                        if !is_draft {
+                            // 保存したパスワードは入力欄に残さない
+                            let password_saved = !form.password.get_untracked().is_empty();
+                            let protected =
+                                form.visibility.get_untracked() == ArticleVisibilityData::Protected;
+                            form.has_password.set(
+                                protected && (password_saved || form.has_password.get_untracked()),
+                            );
+                            form.password.set(String::new());
+                            // 保存時に転載先へ反映した結果を表示する
+                            if let Ok(syndications) = get_syndications_handler(id).await {
+                                form.syndications.set(syndications);
+                            }
+                        }
                         form.message.set(Some((true, "保存しました".to_string())));
                     }
                     Err(e) => {
@@ -176,8 +278,20 @@ impl ArticleFormState {
                     return;
                 }
 
+                // 構造チェック（エラーがあれば公開しない）
+                if !form.check_lint().await {
+                    form.publishing.set(false);
+                    form.message
+                        .set(Some((false, LINT_ERROR_MESSAGE.to_string())));
+                    return;
+                }
+
                 // 次に公開
-                let result = publish_article_handler(PublishArticleInput { id }).await;
+                let result = publish_article_handler(PublishArticleInput {
+                    id,
+                    ignore_lint_errors: form.ignore_lint_errors.get(),
+                })
+                .await;
 
                 form.publishing.set(false);
 
@@ -196,6 +310,34 @@ impl ArticleFormState {
         })
     }
 
+    /// 転載の再試行アクションを生成（公開記事を転載先に反映し直す）
+    pub fn create_retry_syndication_action<F>(&self, get_article_id: F) -> Action<(), ()>
+    where
+        F: Fn() -> Option<String> + Copy + Send + Sync + 'static,
+    {
+        let form = *self;
+        Action::new(move |_: &()| {
+            let id = get_article_id();
+            async move {
+                let Some(article_id) = id else {
+                    return;
+                };
+
+                form.syndicating.set(true);
+                let result = retry_syndication_handler(RetrySyndicationInput { article_id }).await;
+                form.syndicating.set(false);
+
+                match result {
+                    Ok(syndications) => form.syndications.set(syndications),
+                    Err(e) => {
+                        form.message
+                            .set(Some((false, format!("転載エラー: {}", e))));
+                    }
+                }
+            }
+        })
+    }
+
     /// 削除アクションを生成
     pub fn create_delete_action<F>(&self, get_article_id: F) -> Action<(), ()>
     where
@@ -2,6 +2,7 @@ mod article_editor_page;
 mod article_form;
 mod editor_header;
 mod editor_workspace;
+mod lint_panel;
 mod markdown_preview;
 
 pub use article_editor_page::ArticleEditorPage;
@@ -9,6 +10,7 @@ pub use article_editor_page::ArticleEditorPage;
 use article_form::ArticleForm;
 use editor_header::EditorHeader;
 use editor_workspace::EditorWorkspace;
+use lint_panel::LintPanel;
 use markdown_preview::MarkdownPreview;
 
 use stylance::import_style;
@@ -1,12 +1,13 @@
 use leptos::prelude::*;
 use leptos_router::hooks::use_params_map;
 
-use super::super::get_article_for_edit_handler;
 use super::super::state::ArticleFormState;
+use super::super::{get_article_for_edit_handler, get_prose_check_settings_handler};
 use super::style;
-use super::{ArticleForm, EditorHeader, EditorWorkspace};
+use super::{ArticleForm, EditorHeader, EditorWorkspace, LintPanel};
 use crate::common::imgix_url::{extract_base_url, generate_srcset};
-use crate::constants::IMAGE_WIDTHS;
+use crate::common::prose_lint::check_prose;
+use crate::constants::{IMAGE_SIZES, IMAGE_WIDTHS};
 use crate::front::components::ImagePickerModal;
 use crate::front::pages::admin_page::AdminLayout;
 
@@ -48,10 +49,24 @@ pub fn ArticleEditorPage() -> impl IntoView {
         }
     });
 
+    // 文章チェックの設定を読み込み（取得できなければ全ルール有効のまま）
+    let prose_settings_resource = Resource::new(|| (), |_| get_prose_check_settings_handler());
+    Effect::new(move || {
+        if let Some(Ok(settings)) = prose_settings_resource.get() {
+            form.prose_check_settings.set(settings);
+        }
+    });
+    let prose_issues = Memo::new(move |_| {
+        form.prose_check_settings
+            .with(|settings| form.body.with(|body| check_prose(body, settings)))
+    });
+
     // Actions
     let save_article = form.create_save_action(article_id);
     let publish_article = form.create_publish_action(article_id);
     let delete_article = form.create_delete_action(article_id);
+    let retry_syndication = form.create_retry_syndication_action(article_id);
+    let lint_article = form.create_lint_action();
 
     // Scroll sync refs
     let editor_ref: NodeRef<leptos::html::Textarea> = NodeRef::new();
@@ -83,14 +98,13 @@ pub fn ArticleEditorPage() -> impl IntoView {
                     let image_url =
                         format!("{}?w={}&auto=format&q=75", image.imgix_url, IMAGE_WIDTHS[1]);
                     let srcset = generate_srcset(extract_base_url(&image.imgix_url), &IMAGE_WIDTHS);
-                    let sizes = "(max-width: 800px) 100vw, 800px";
                     let (w, h) = match (image.width, image.height) {
                         (Some(w), Some(h)) => (w, h),
                         _ => (IMAGE_WIDTHS[1] as i32, 0),
                     };
                     let markdown_image = format!(
                         r#"<img src="{}" srcset="{}" sizes="{}" width="{}" height="{}" loading="lazy" alt="{}">"#,
-                        image_url, srcset, sizes, w, h, image.filename,
+                        image_url, srcset, IMAGE_SIZES, w, h, image.filename,
                     );
                     let new_body = format!(
                         "{}{}{}",
@@ -133,6 +147,7 @@ pub fn ArticleEditorPage() -> impl IntoView {
                                             save_action=save_article
                                             publish_action=publish_article
                                             delete_action=delete_article
+                                            retry_syndication_action=retry_syndication
                                         />
                                         <ArticleForm form show_cover_picker />
                                     </Show>
@@ -141,7 +156,16 @@ pub fn ArticleEditorPage() -> impl IntoView {
                                         show_insert_picker
                                         editor_ref
                                         preview_ref
+                                        prose_issues
                                     />
+                                    <Show when=move || !form.is_fullscreen.get()>
+                                        <LintPanel
+                                            form
+                                            lint_action=lint_article
+                                            editor_ref
+                                            prose_issues
+                                        />
+                                    </Show>
                                 }
                             })
                     }}
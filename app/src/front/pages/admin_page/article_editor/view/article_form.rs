@@ -1,6 +1,9 @@
 use leptos::prelude::*;
 
-use super::super::get_categories_handler;
+use super::super::{
+    ArticleSeoData, ArticleVisibilityData, PreviewOgImageInput, get_categories_handler,
+    preview_og_image_handler,
+};
 use super::style;
 use crate::constants::IMAGE_WIDTHS;
 use crate::front::pages::admin_page::article_editor::state::ArticleFormState;
@@ -48,6 +51,24 @@ pub fn ArticleForm(form: ArticleFormState, show_cover_picker: RwSignal<bool>) ->
         }
     };
 
+    // カバー画像がない場合に使われる自動生成のOGP画像のプレビュー（data URL）
+    let og_preview = RwSignal::new(None::<Result<String, String>>);
+    let og_preview_loading = RwSignal::new(false);
+    let on_preview_og_image = move |_| {
+        let input = PreviewOgImageInput {
+            title: form.title.get_untracked(),
+            category_names: form.categories.get_untracked(),
+        };
+        og_preview_loading.set(true);
+        leptos::task::spawn_local(async move {
+            let result = preview_og_image_handler(input)
+                .await
+                .map_err(|e| e.to_string());
+            og_preview.set(Some(result));
+            og_preview_loading.set(false);
+        });
+    };
+
     let remove_tag = move |index: usize| {
         let mut current = form.categories.get();
         if index < current.len() {
@@ -199,9 +220,199 @@ pub fn ArticleForm(form: ArticleFormState, show_cover_picker: RwSignal<bool>) ->
                                 "解除"
                             </button>
                         </Show>
+                        <Show when=move || { form.cover_image_url.get().is_none() }>
+                            <button
+                                type="button"
+                                class=style::cover_remove_button
+                                disabled=move || og_preview_loading.get()
+                                on:click=on_preview_og_image
+                            >
+                                "自動生成のOGP画像をプレビュー"
+                            </button>
+                        </Show>
                     </div>
+                    <Show when=move || { form.cover_image_url.get().is_none() }>
+                        {move || {
+                            og_preview
+                                .get()
+                                .map(|result| match result {
+                                    Ok(data_url) => {
+                                        view! {
+                                            <div class=style::cover_image_preview>
+                                                <img src=data_url alt="自動生成のOGP画像" />
+                                            </div>
+                                        }
+                                            .into_any()
+                                    }
+                                    Err(message) => {
+                                        view! { <p class=style::og_preview_error>{message}</p> }
+                                            .into_any()
+                                    }
+                                })
+                        }}
+                    </Show>
                 </div>
             </div>
+
+            // 公開範囲は公開済みの記事のみ変更できる（公開時は常に公開）
+            <Show when=move || !form.is_draft.get()>
+                <div class=style::form_row>
+                    <label class=style::label>"公開範囲"</label>
+                    <select
+                        class=style::input
+                        prop:value=move || form.visibility.get().as_str()
+                        on:change=move |ev| {
+                            if let Some(visibility) = ArticleVisibilityData::from_value(
+                                &event_target_value(&ev),
+                            ) {
+                                form.visibility.set(visibility);
+                            }
+                        }
+                    >
+                        {ArticleVisibilityData::ALL
+                            .into_iter()
+                            .map(|visibility| {
+                                view! {
+                                    <option value=visibility.as_str()>{visibility.label()}</option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </div>
+                <Show when=move || form.visibility.get() == ArticleVisibilityData::Protected>
+                    <div class=style::form_row>
+                        <label class=style::label>"パスワード"</label>
+                        <input
+                            type="password"
+                            class=style::input
+                            autocomplete="new-password"
+                            placeholder=move || {
+                                if form.has_password.get() {
+                                    "変更しない場合は空欄"
+                                } else {
+                                    "閲覧用のパスワード（4文字以上）"
+                                }
+                            }
+                            prop:value=move || form.password.get()
+                            on:input=move |ev| form.password.set(event_target_value(&ev))
+                        />
+                    </div>
+                </Show>
+            </Show>
+
+            <details class=style::seo_section>
+                <summary class=style::seo_summary>"SEO"</summary>
+                <p class=style::seo_hint>"空欄の項目はタイトル・説明・カバー画像から決まります"</p>
+                {move || {
+                    form.origin_url
+                        .get()
+                        .map(|origin_url| {
+                            let href = origin_url.clone();
+                            let canonical_url = origin_url.clone();
+                            view! {
+                                <div class=style::form_row>
+                                    <label class=style::label>"取り込み元"</label>
+                                    <div class=style::origin_url_row>
+                                        <a href=href target="_blank" rel="noopener noreferrer">
+                                            {origin_url}
+                                        </a>
+                                        <button
+                                            type="button"
+                                            class=style::cover_remove_button
+                                            on:click=move |_| {
+                                                let url = canonical_url.clone();
+                                                form.seo.update(|seo| seo.canonical_url = Some(url));
+                                            }
+                                        >
+                                            "canonical URLに設定"
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        })
+                }}
+                <SeoTextField
+                    form=form
+                    label="canonical URL"
+                    placeholder="転載元の記事のURLなど"
+                    get=|seo| seo.canonical_url.clone()
+                    set=|seo, value| seo.canonical_url = value
+                />
+                <SeoTextField
+                    form=form
+                    label="robots"
+                    placeholder="noindex, nofollow"
+                    get=|seo| seo.robots.clone()
+                    set=|seo, value| seo.robots = value
+                />
+                <SeoTextField
+                    form=form
+                    label="OGPタイトル"
+                    placeholder="記事のタイトル"
+                    get=|seo| seo.og_title.clone()
+                    set=|seo, value| seo.og_title = value
+                />
+                <SeoTextField
+                    form=form
+                    label="OGP説明"
+                    placeholder="記事の説明"
+                    get=|seo| seo.og_description.clone()
+                    set=|seo, value| seo.og_description = value
+                />
+                <SeoTextField
+                    form=form
+                    label="OGP画像URL"
+                    placeholder="カバー画像（なければ自動生成）"
+                    get=|seo| seo.og_image_url.clone()
+                    set=|seo, value| seo.og_image_url = value
+                />
+                <div class=style::form_row>
+                    <label class=style::label>"Twitter Card"</label>
+                    <select
+                        class=style::input
+                        prop:value=move || {
+                            form.seo.with(|seo| seo.twitter_card.clone().unwrap_or_default())
+                        }
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            form.seo
+                                .update(|seo| {
+                                    seo.twitter_card = (!value.is_empty()).then_some(value);
+                                });
+                        }
+                    >
+                        <option value="">"既定（summary_large_image）"</option>
+                        <option value="summary">"summary"</option>
+                        <option value="summary_large_image">"summary_large_image"</option>
+                    </select>
+                </div>
+            </details>
+        </div>
+    }
+}
+
+/// SEO設定の1項目を編集するテキスト入力（空欄は未設定として扱う）
+#[component]
+fn SeoTextField(
+    form: ArticleFormState,
+    label: &'static str,
+    placeholder: &'static str,
+    get: fn(&ArticleSeoData) -> Option<String>,
+    set: fn(&mut ArticleSeoData, Option<String>),
+) -> impl IntoView {
+    view! {
+        <div class=style::form_row>
+            <label class=style::label>{label}</label>
+            <input
+                type="text"
+                class=style::input
+                placeholder=placeholder
+                prop:value=move || form.seo.with(|seo| get(seo).unwrap_or_default())
+                on:input=move |ev| {
+                    let value = event_target_value(&ev);
+                    form.seo.update(|seo| set(seo, (!value.is_empty()).then_some(value)));
+                }
+            />
         </div>
     }
 }
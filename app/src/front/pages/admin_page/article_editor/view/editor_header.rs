@@ -1,8 +1,47 @@
 use leptos::prelude::*;
 
 use super::style;
+use crate::front::pages::admin_page::article_editor::SyndicationStatusData;
 use crate::front::pages::admin_page::article_editor::state::ArticleFormState;
 
+/// 転載先の表示名
+fn syndication_target_label(target: &str) -> &str {
+    match target {
+        "qiita" => "Qiita",
+        other => other,
+    }
+}
+
+/// 転載先1件の反映状況
+fn syndication_status_item(status: SyndicationStatusData) -> impl IntoView {
+    let label = syndication_target_label(&status.target).to_string();
+    let state = match (&status.last_error, &status.synced_at) {
+        (Some(error), _) => view! {
+            <span class=style::syndication_error>{format!("反映に失敗しました: {error}")}</span>
+        }
+        .into_any(),
+        (None, Some(synced_at)) => {
+            view! { <span>{format!("{synced_at} に反映済み")}</span> }.into_any()
+        }
+        (None, None) => view! { <span>"未反映"</span> }.into_any(),
+    };
+    view! {
+        <li>
+            <strong>{label}</strong>
+            {state}
+            {status
+                .remote_url
+                .map(|url| {
+                    view! {
+                        <a href=url target="_blank" rel="noopener noreferrer">
+                            "転載先の記事"
+                        </a>
+                    }
+                })}
+        </li>
+    }
+}
+
 #[component]
 pub fn EditorHeader(
     form: ArticleFormState,
@@ -10,6 +49,7 @@ pub fn EditorHeader(
     save_action: Action<(), ()>,
     publish_action: Action<(), ()>,
     delete_action: Action<(), ()>,
+    retry_syndication_action: Action<(), ()>,
 ) -> impl IntoView {
     view! {
         <header class=style::header>
@@ -76,6 +116,29 @@ pub fn EditorHeader(
             </div>
         </header>
 
+        <Show when=move || !form.is_draft.get() && form.syndications.with(|s| !s.is_empty())>
+            <div class=style::syndication_status>
+                <ul>
+                    {move || {
+                        form.syndications
+                            .get()
+                            .into_iter()
+                            .map(syndication_status_item)
+                            .collect_view()
+                    }}
+                </ul>
+                <button
+                    class=style::syndication_retry_button
+                    disabled=move || form.is_busy() || form.syndicating.get()
+                    on:click=move |_| {
+                        let _ = retry_syndication_action.dispatch(());
+                    }
+                >
+                    {move || { if form.syndicating.get() { "再試行中..." } else { "転載を再試行" } }}
+                </button>
+            </div>
+        </Show>
+
         {move || {
             form.message
                 .get()
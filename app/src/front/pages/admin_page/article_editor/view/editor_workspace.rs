@@ -2,16 +2,50 @@ use leptos::prelude::*;
 
 use super::MarkdownPreview;
 use super::style;
+use crate::common::prose_lint::ProseIssue;
 use crate::front::hooks::use_scroll_sync;
 use crate::front::pages::admin_page::article_editor::state::{ArticleFormState, ViewMode};
 
+/// 本文を文章チェックの指摘箇所とそれ以外に分割（重なる指摘は先のものを優先）
+fn highlight_segments(body: &str, issues: &[ProseIssue]) -> Vec<(String, Option<String>)> {
+    let mut segments = Vec::new();
+    let mut position = 0;
+    for issue in issues {
+        if issue.start < position {
+            continue;
+        }
+        let (Some(before), Some(marked)) = (
+            body.get(position..issue.start),
+            body.get(issue.start..issue.end),
+        ) else {
+            continue;
+        };
+        segments.push((before.to_string(), None));
+        segments.push((marked.to_string(), Some(issue.message.clone())));
+        position = issue.end;
+    }
+    // 末尾の改行の高さをtextareaと揃えるため空白を足す
+    segments.push((format!("{} ", &body[position..]), None));
+    segments
+}
+
 #[component]
 pub fn EditorWorkspace(
     form: ArticleFormState,
     show_insert_picker: RwSignal<bool>,
     editor_ref: NodeRef<leptos::html::Textarea>,
     preview_ref: NodeRef<leptos::html::Div>,
+    prose_issues: Memo<Vec<ProseIssue>>,
 ) -> impl IntoView {
+    let backdrop_ref: NodeRef<leptos::html::Div> = NodeRef::new();
+
+    // 指摘箇所のハイライトをtextareaのスクロールに追従させる
+    let sync_backdrop_scroll = move || {
+        if let (Some(editor), Some(backdrop)) = (editor_ref.get(), backdrop_ref.get()) {
+            backdrop.set_scroll_top(editor.scroll_top());
+        }
+    };
+
     view! {
         <div
             class=move || { if form.is_fullscreen.get() { style::fullscreen } else { "" } }
@@ -86,14 +120,44 @@ pub fn EditorWorkspace(
             >
                 <Show when=move || form.view_mode.get() != ViewMode::Preview>
                     <div class=style::editor_pane>
-                        <textarea
-                            class=style::textarea
-                            node_ref=editor_ref
-                            prop:value=move || form.body.get()
-                            on:input=move |ev| form.body.set(event_target_value(&ev))
-                            on:scroll=move |_| use_scroll_sync(editor_ref, preview_ref)
-                            placeholder="Markdownで記事を書く..."
-                        />
+                        <div class=style::editor_surface>
+                            <div class=style::editor_backdrop node_ref=backdrop_ref aria-hidden="true">
+                                {move || {
+                                    let segments = form.body
+                                        .with(|body| {
+                                            prose_issues.with(|issues| highlight_segments(body, issues))
+                                        });
+                                    segments
+                                        .into_iter()
+                                        .map(|(text, message)| match message {
+                                            Some(message) => {
+                                                view! {
+                                                    <mark class=style::prose_mark title=message>
+                                                        {text}
+                                                    </mark>
+                                                }
+                                                    .into_any()
+                                            }
+                                            None => text.into_any(),
+                                        })
+                                        .collect_view()
+                                }}
+                            </div>
+                            <textarea
+                                class=style::textarea
+                                node_ref=editor_ref
+                                prop:value=move || form.body.get()
+                                on:input=move |ev| {
+                                    form.body.set(event_target_value(&ev));
+                                    sync_backdrop_scroll();
+                                }
+                                on:scroll=move |_| {
+                                    use_scroll_sync(editor_ref, preview_ref);
+                                    sync_backdrop_scroll();
+                                }
+                                placeholder="Markdownで記事を書く..."
+                            />
+                        </div>
                     </div>
                 </Show>
                 <Show when=move || form.view_mode.get() != ViewMode::Editor>
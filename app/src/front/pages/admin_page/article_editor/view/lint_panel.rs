@@ -0,0 +1,181 @@
+use leptos::prelude::*;
+
+use super::style;
+use crate::common::content_lint::LintSeverity;
+use crate::common::prose_lint::ProseIssue;
+use crate::front::pages::admin_page::article_editor::state::{ArticleFormState, ViewMode};
+
+/// 本文の指定行（1始まり）のバイト範囲を返す
+fn line_range(body: &str, line: usize) -> (usize, usize) {
+    let mut start = 0;
+    for (index, text) in body.split('\n').enumerate() {
+        if index + 1 == line {
+            return (start, start + text.len());
+        }
+        start += text.len() + 1;
+    }
+    (body.len(), body.len())
+}
+
+/// エディタで本文の指定範囲（バイト）を選択してスクロール
+fn select_in_editor(
+    form: ArticleFormState,
+    editor_ref: NodeRef<leptos::html::Textarea>,
+    start: usize,
+    end: usize,
+) {
+    // プレビューのみの表示ではエディタが無いので分割表示に切り替える
+    if form.view_mode.get_untracked() == ViewMode::Preview {
+        form.view_mode.set(ViewMode::Split);
+    }
+
+    #[cfg(feature = "hydrate")]
+    leptos::task::spawn_local(async move {
+        let Some(textarea) = editor_ref.get_untracked() else {
+            return;
+        };
+        let el: &web_sys::HtmlTextAreaElement = textarea.as_ref();
+        let body = form.body.get_untracked();
+        let (Some(before), Some(selected)) = (body.get(..start), body.get(start..end)) else {
+            return;
+        };
+        // textareaの選択位置はUTF-16コード単位
+        let start_utf16 = before.encode_utf16().count();
+        let end_utf16 = start_utf16 + selected.encode_utf16().count();
+
+        let _ = el.focus();
+        let _ = el.set_selection_range(start_utf16 as u32, end_utf16 as u32);
+
+        // 行の高さは一定とみなし、対象行が上から1/3付近に来るようにスクロール
+        let line = before.matches('\n').count();
+        let total_lines = body.split('\n').count().max(1);
+        let line_height = el.scroll_height() as f64 / total_lines as f64;
+        let top = line_height * line as f64 - el.client_height() as f64 / 3.0;
+        el.set_scroll_top(top.max(0.0) as i32);
+    });
+
+    #[cfg(not(feature = "hydrate"))]
+    let _ = (editor_ref, start, end);
+}
+
+/// 構造チェックと文章チェックの問題一覧（クリックで該当箇所へ移動）
+#[component]
+pub fn LintPanel(
+    form: ArticleFormState,
+    lint_action: Action<(), ()>,
+    editor_ref: NodeRef<leptos::html::Textarea>,
+    prose_issues: Memo<Vec<ProseIssue>>,
+) -> impl IntoView {
+    let checking = lint_action.pending();
+
+    view! {
+        <section class=style::lint_panel>
+            <div class=style::lint_header>
+                <h2>
+                    {move || format!("構造チェック（{}件）", form.lint_issues.read().len())}
+                </h2>
+                <label class=style::checkbox_label>
+                    <input
+                        type="checkbox"
+                        prop:checked=move || form.ignore_lint_errors.get()
+                        on:change=move |ev| form.ignore_lint_errors.set(event_target_checked(&ev))
+                    />
+                    "エラーを無視して公開する"
+                </label>
+                <button
+                    type="button"
+                    class=style::lint_button
+                    disabled=move || checking.get()
+                    on:click=move |_| {
+                        lint_action.dispatch(());
+                    }
+                >
+                    {move || if checking.get() { "チェック中..." } else { "チェック" }}
+                </button>
+            </div>
+            <Show
+                when=move || !form.lint_issues.read().is_empty()
+                fallback=|| view! { <p class=style::lint_empty>"問題は見つかっていません"</p> }
+            >
+                <ul class=style::lint_list>
+                    {move || {
+                        form.lint_issues
+                            .get()
+                            .into_iter()
+                            .map(|issue| {
+                                let (badge_class, badge) = match issue.severity {
+                                    LintSeverity::Error => (style::lint_badge_error, "エラー"),
+                                    LintSeverity::Warning => (style::lint_badge_warning, "警告"),
+                                };
+                                let location = issue
+                                    .line
+                                    .map_or_else(|| "説明文".to_string(), |line| format!("{line}行目"));
+                                view! {
+                                    <li>
+                                        <button
+                                            type="button"
+                                            class=style::lint_item
+                                            disabled=issue.line.is_none()
+                                            on:click=move |_| {
+                                                if let Some(line) = issue.line {
+                                                    let (start, end) = form
+                                                        .body
+                                                        .with_untracked(|body| line_range(body, line));
+                                                    select_in_editor(form, editor_ref, start, end);
+                                                }
+                                            }
+                                        >
+                                            <span class=badge_class>{badge}</span>
+                                            <span class=style::lint_location>{location}</span>
+                                            <span>{issue.message}</span>
+                                        </button>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </ul>
+            </Show>
+            <div class=style::lint_header>
+                <h2>{move || format!("文章チェック（{}件）", prose_issues.read().len())}</h2>
+                <a href="/admin/settings" target="_blank" class=style::lint_settings_link>
+                    "ルール設定"
+                </a>
+            </div>
+            <Show
+                when=move || !prose_issues.read().is_empty()
+                fallback=|| view! { <p class=style::lint_empty>"問題は見つかっていません"</p> }
+            >
+                <ul class=style::lint_list>
+                    {move || {
+                        prose_issues
+                            .get()
+                            .into_iter()
+                            .map(|issue| {
+                                view! {
+                                    <li>
+                                        <button
+                                            type="button"
+                                            class=style::lint_item
+                                            on:click=move |_| {
+                                                select_in_editor(form, editor_ref, issue.start, issue.end)
+                                            }
+                                        >
+                                            <span class=style::lint_badge_warning>
+                                                {issue.rule.label()}
+                                            </span>
+                                            <span class=style::lint_location>
+                                                {format!("{}行目", issue.line)}
+                                            </span>
+                                            <span>{issue.message.clone()}</span>
+                                        </button>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </ul>
+            </Show>
+        </section>
+    }
+}
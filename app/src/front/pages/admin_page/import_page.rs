@@ -0,0 +1,147 @@
+use leptos::prelude::*;
+use std::collections::HashSet;
+use stylance::import_style;
+
+use super::AdminLayout;
+use crate::common::handlers::admin::{
+    ImportQiitaItemsInput, QiitaImportCandidateData, get_qiita_import_candidates_handler,
+    import_qiita_items_handler,
+};
+
+import_style!(style, "import_page.module.scss");
+
+/// Qiitaの記事を選んで下書きとして取り込むページ
+#[component]
+pub fn ImportPage() -> impl IntoView {
+    let candidates_resource = Resource::new(|| (), |_| get_qiita_import_candidates_handler());
+    let selected_ids = RwSignal::new(HashSet::<String>::new());
+    let message = RwSignal::new(None::<(bool, String)>);
+
+    let import_action = Action::new(move |_: &()| {
+        let input = ImportQiitaItemsInput {
+            ids: selected_ids.get().into_iter().collect(),
+        };
+        async move {
+            message.set(None);
+            match import_qiita_items_handler(input).await {
+                Ok(drafts) => {
+                    message.set(Some((
+                        true,
+                        format!("{}件の記事を下書きとして取り込みました", drafts.len()),
+                    )));
+                    selected_ids.set(HashSet::new());
+                    candidates_resource.refetch();
+                }
+                Err(e) => message.set(Some((false, format!("取り込みエラー: {}", e)))),
+            }
+        }
+    });
+    let importing = import_action.pending();
+
+    let candidate_row = move |candidate: QiitaImportCandidateData| {
+        let id = candidate.id.clone();
+        let checked_id = candidate.id.clone();
+        view! {
+            <tr class=if candidate.imported { style::imported_row } else { "" }>
+                <td>
+                    <input
+                        type="checkbox"
+                        disabled=candidate.imported
+                        prop:checked=move || selected_ids.with(|ids| ids.contains(&checked_id))
+                        on:change=move |ev| {
+                            let checked = event_target_checked(&ev);
+                            let id = id.clone();
+                            selected_ids
+                                .update(|ids| {
+                                    if checked {
+                                        ids.insert(id);
+                                    } else {
+                                        ids.remove(&id);
+                                    }
+                                });
+                        }
+                    />
+                </td>
+                <td>
+                    <a href=candidate.url target="_blank" rel="noopener noreferrer">
+                        {candidate.title}
+                    </a>
+                    <div class=style::tags>{candidate.tags.join(", ")}</div>
+                </td>
+                <td>{candidate.created_at}</td>
+                <td>{if candidate.imported { "取り込み済み" } else { "—" }}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <AdminLayout>
+            <div class=style::container>
+                <header class=style::header>
+                    <h1>"Qiitaから取り込み"</h1>
+                </header>
+                {move || {
+                    message
+                        .get()
+                        .map(|(success, msg)| {
+                            view! {
+                                <div class=if success {
+                                    style::message_success
+                                } else {
+                                    style::message_error
+                                }>{msg}</div>
+                            }
+                        })
+                }}
+                <section class=style::section>
+                    <p class=style::section_description>
+                        "選んだ記事を下書きとして作成します。タグはカテゴリに、Qiita独自の記法（:::note、コードブロックのファイル名）はこのブログの記法に変換し、取り込み元のURLを記録します。"
+                    </p>
+                    <Suspense fallback=|| view! { <p>"読み込み中..."</p> }>
+                        {move || {
+                            candidates_resource
+                                .get()
+                                .map(|result| match result {
+                                    Ok(candidates) if candidates.is_empty() => {
+                                        view! { <p>"Qiitaの記事はありません"</p> }.into_any()
+                                    }
+                                    Ok(candidates) => {
+                                        view! {
+                                            <table class=style::candidate_table>
+                                                <thead>
+                                                    <tr>
+                                                        <th></th>
+                                                        <th>"タイトル"</th>
+                                                        <th>"投稿日"</th>
+                                                        <th>"状態"</th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {candidates.into_iter().map(candidate_row).collect_view()}
+                                                </tbody>
+                                            </table>
+                                        }
+                                            .into_any()
+                                    }
+                                    Err(e) => {
+                                        view! { <p>{format!("読み込みエラー: {}", e)}</p> }
+                                            .into_any()
+                                    }
+                                })
+                        }}
+                    </Suspense>
+                    <button
+                        type="button"
+                        class=style::import_button
+                        disabled=move || importing.get() || selected_ids.with(|ids| ids.is_empty())
+                        on:click=move |_| {
+                            import_action.dispatch(());
+                        }
+                    >
+                        {move || if importing.get() { "取り込み中..." } else { "選んだ記事を取り込む" }}
+                    </button>
+                </section>
+            </div>
+        </AdminLayout>
+    }
+}
@@ -36,6 +36,16 @@ pub fn AdminLayout(children: Children) -> impl IntoView {
                             "画像管理"
                         </A>
                     </li>
+                    <li>
+                        <A href="/admin/import" attr:class=style::nav_link>
+                            "Qiitaから取り込み"
+                        </A>
+                    </li>
+                    <li>
+                        <A href="/admin/settings" attr:class=style::nav_link>
+                            "設定"
+                        </A>
+                    </li>
                 </ul>
                 <div class=style::auth_section>
                     <Suspense fallback=|| ()>
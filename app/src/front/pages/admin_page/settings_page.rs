@@ -0,0 +1,389 @@
+use leptos::prelude::*;
+use stylance::import_style;
+
+use super::AdminLayout;
+use crate::common::handlers::admin::{
+    ExternalSourceStatusData, FeaturedItemData, SaveFeaturedItemsInput, get_admin_articles_handler,
+    get_external_source_statuses_handler, get_featured_items_handler,
+    get_prose_check_settings_handler, save_featured_items_handler,
+    save_prose_check_settings_handler,
+};
+use crate::common::prose_lint::{MAX_SENTENCE_LENGTH, ProseCheckSettings, ProseRule};
+
+import_style!(style, "settings_page.module.scss");
+
+/// ルールの説明
+fn rule_description(rule: ProseRule) -> String {
+    match rule {
+        ProseRule::StyleMixing => {
+            "「です・ます」調と「だ・である」調が混在している文を指摘します".to_string()
+        }
+        ProseRule::FullWidthAlphanumeric => {
+            "全角の英数字（ＡＢＣ、１２３）を指摘します".to_string()
+        }
+        ProseRule::LongSentence => {
+            format!("{MAX_SENTENCE_LENGTH}文字を超える文を指摘します")
+        }
+        ProseRule::RaNuki => "「見れる」「来れる」などのら抜き言葉を指摘します".to_string(),
+        ProseRule::RepeatedWord => "「のの」「the the」など同じ語の連続を指摘します".to_string(),
+    }
+}
+
+/// 外部記事ソースの最終更新の状況（取得はバックグラウンドのタスクが行う）
+#[component]
+fn ExternalSourceStatusSection() -> impl IntoView {
+    let statuses_resource = Resource::new(|| (), |_| get_external_source_statuses_handler());
+
+    let status_row = |status: ExternalSourceStatusData| {
+        let failed = status.last_error.is_some();
+        view! {
+            <tr>
+                <td>{status.name}</td>
+                <td>{status.last_success_at.unwrap_or_else(|| "—".to_string())}</td>
+                <td>{status.last_attempt_at.unwrap_or_else(|| "—".to_string())}</td>
+                <td>{status.article_count}</td>
+                <td class=if failed { style::status_error } else { "" }>
+                    {status.last_error.unwrap_or_else(|| "—".to_string())}
+                    {status.paused_until.map(|until| format!("（{until}まで取得を停止中）"))}
+                </td>
+            </tr>
+        }
+    };
+
+    view! {
+        <section class=style::section>
+            <h2>"外部記事ソース"</h2>
+            <p class=style::section_description>
+                "トップページに並べる外部記事は一定間隔で取得し直します。取得に失敗したソースは前回の記事を表示し、続けて失敗したソースはしばらく取得を止めます。"
+            </p>
+            <Suspense fallback=|| view! { <p>"読み込み中..."</p> }>
+                {move || {
+                    statuses_resource
+                        .get()
+                        .map(|result| match result {
+                            Ok(statuses) if statuses.is_empty() => {
+                                view! { <p>"有効な外部記事ソースはありません"</p> }.into_any()
+                            }
+                            Ok(statuses) => {
+                                view! {
+                                    <table class=style::status_table>
+                                        <thead>
+                                            <tr>
+                                                <th>"ソース"</th>
+                                                <th>"最終取得"</th>
+                                                <th>"最終試行"</th>
+                                                <th>"記事数"</th>
+                                                <th>"エラー"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {statuses.into_iter().map(status_row).collect_view()}
+                                        </tbody>
+                                    </table>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p>{format!("読み込みエラー: {}", e)}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
+        </section>
+    }
+}
+
+#[component]
+pub fn SettingsPage() -> impl IntoView {
+    let settings_resource = Resource::new(|| (), |_| get_prose_check_settings_handler());
+    let settings = RwSignal::new(ProseCheckSettings::default());
+    let message = RwSignal::new(None::<(bool, String)>);
+
+    Effect::new(move || {
+        if let Some(Ok(loaded)) = settings_resource.get() {
+            settings.set(loaded);
+        }
+    });
+
+    let save_action = Action::new(move |_: &()| {
+        let input = settings.get();
+        async move {
+            message.set(None);
+            match save_prose_check_settings_handler(input).await {
+                Ok(()) => message.set(Some((true, "保存しました".to_string()))),
+                Err(e) => message.set(Some((false, format!("保存エラー: {}", e)))),
+            }
+        }
+    });
+    let saving = save_action.pending();
+
+    let featured_resource = Resource::new(|| (), |_| get_featured_items_handler());
+    let articles_resource = Resource::new(|| (), |_| get_admin_articles_handler());
+    let featured_items = RwSignal::new(Vec::<FeaturedItemData>::new());
+
+    Effect::new(move || {
+        if let Some(Ok(loaded)) = featured_resource.get() {
+            featured_items.set(loaded);
+        }
+    });
+
+    let save_featured_action = Action::new(move |_: &()| {
+        let input = SaveFeaturedItemsInput {
+            items: featured_items.get(),
+        };
+        async move {
+            message.set(None);
+            match save_featured_items_handler(input).await {
+                Ok(()) => message.set(Some((true, "おすすめを保存しました".to_string()))),
+                Err(e) => message.set(Some((false, format!("保存エラー: {}", e)))),
+            }
+        }
+    });
+    let saving_featured = save_featured_action.pending();
+
+    // 公開記事のみ（ID, タイトル）
+    let published_articles = move || {
+        articles_resource
+            .get()
+            .and_then(|r| r.ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|a| !a.is_draft)
+            .map(|a| (a.id, a.title))
+            .collect::<Vec<_>>()
+    };
+    let update_item = move |index: usize, f: Box<dyn FnOnce(&mut FeaturedItemData)>| {
+        featured_items.update(|items| {
+            if let Some(item) = items.get_mut(index) {
+                f(item);
+            }
+        });
+    };
+
+    view! {
+        <AdminLayout>
+            <div class=style::container>
+                <header class=style::header>
+                    <h1>"設定"</h1>
+                </header>
+                {move || {
+                    message
+                        .get()
+                        .map(|(success, msg)| {
+                            view! {
+                                <div class=if success {
+                                    style::message_success
+                                } else {
+                                    style::message_error
+                                }>{msg}</div>
+                            }
+                        })
+                }}
+                <section class=style::section>
+                    <h2>"文章チェック"</h2>
+                    <p class=style::section_description>
+                        "記事エディタで本文を書いている間に指摘するルールを選びます。"
+                    </p>
+                    <Suspense fallback=move || {
+                        view! { <p>"読み込み中..."</p> }
+                    }>
+                        {move || {
+                            settings_resource
+                                .get()
+                                .map(|_| {
+                                    ProseRule::ALL
+                                        .into_iter()
+                                        .map(|rule| {
+                                            view! {
+                                                <label class=style::rule_row>
+                                                    <input
+                                                        type="checkbox"
+                                                        prop:checked=move || {
+                                                            settings.with(|s| s.is_enabled(rule))
+                                                        }
+                                                        on:change=move |ev| {
+                                                            let enabled = event_target_checked(&ev);
+                                                            settings
+                                                                .update(|s| {
+                                                                    if let Some(setting) = s
+                                                                        .rules
+                                                                        .iter_mut()
+                                                                        .find(|setting| setting.rule == rule)
+                                                                    {
+                                                                        setting.enabled = enabled;
+                                                                    }
+                                                                });
+                                                        }
+                                                    />
+                                                    <span class=style::rule_label>{rule.label()}</span>
+                                                    <span class=style::rule_description>
+                                                        {rule_description(rule)}
+                                                    </span>
+                                                </label>
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                        }}
+                    </Suspense>
+                    <button
+                        type="button"
+                        class=style::save_button
+                        disabled=move || saving.get()
+                        on:click=move |_| {
+                            save_action.dispatch(());
+                        }
+                    >
+                        {move || if saving.get() { "保存中..." } else { "保存" }}
+                    </button>
+                </section>
+                <section class=style::section>
+                    <h2>"おすすめ"</h2>
+                    <p class=style::section_description>
+                        "トップページの記事一覧の上に、上から順に表示します。外部記事はQiita・WordPressの記事URLを指定します。"
+                    </p>
+                    <Suspense fallback=move || {
+                        view! { <p>"読み込み中..."</p> }
+                    }>
+                        {move || {
+                            featured_resource
+                                .get()
+                                .map(|_| {
+                                    let articles = published_articles();
+                                    let len = featured_items.with(|items| items.len());
+                                    featured_items
+                                        .get()
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, item)| {
+                                            let is_external = item.article_id.is_none();
+                                            let selected_id = item.article_id.clone().unwrap_or_default();
+                                            view! {
+                                                <div class=style::featured_row>
+                                                    <select
+                                                        class=style::featured_input
+                                                        on:change=move |ev| {
+                                                            let value = event_target_value(&ev);
+                                                            update_item(
+                                                                index,
+                                                                Box::new(move |item| {
+                                                                    if value.is_empty() {
+                                                                        item.article_id = None;
+                                                                    } else {
+                                                                        item.article_id = Some(value);
+                                                                        item.external_url = None;
+                                                                    }
+                                                                }),
+                                                            );
+                                                        }
+                                                    >
+                                                        <option value="" selected=is_external>
+                                                            "外部記事（URL）"
+                                                        </option>
+                                                        {articles
+                                                            .iter()
+                                                            .map(|(id, title)| {
+                                                                view! {
+                                                                    <option value=id.clone() selected=*id == selected_id>
+                                                                        {title.clone()}
+                                                                    </option>
+                                                                }
+                                                            })
+                                                            .collect_view()}
+                                                    </select>
+                                                    <Show when=move || is_external>
+                                                        <input
+                                                            type="url"
+                                                            class=style::featured_input
+                                                            placeholder="https://qiita.com/..."
+                                                            prop:value=item.external_url.clone().unwrap_or_default()
+                                                            on:change=move |ev| {
+                                                                let value = event_target_value(&ev);
+                                                                update_item(
+                                                                    index,
+                                                                    Box::new(move |item| item.external_url = Some(value)),
+                                                                );
+                                                            }
+                                                        />
+                                                    </Show>
+                                                    <label class=style::featured_expires>
+                                                        "表示期限"
+                                                        <input
+                                                            type="date"
+                                                            prop:value=item.expires_on.clone().unwrap_or_default()
+                                                            on:change=move |ev| {
+                                                                let value = event_target_value(&ev);
+                                                                update_item(
+                                                                    index,
+                                                                    Box::new(move |item| {
+                                                                        item.expires_on = Some(value)
+                                                                            .filter(|v| !v.is_empty());
+                                                                    }),
+                                                                );
+                                                            }
+                                                        />
+                                                    </label>
+                                                    <div class=style::featured_actions>
+                                                        <button
+                                                            type="button"
+                                                            class=style::row_button
+                                                            disabled=index == 0
+                                                            on:click=move |_| {
+                                                                featured_items.update(|items| items.swap(index - 1, index));
+                                                            }
+                                                        >
+                                                            "↑"
+                                                        </button>
+                                                        <button
+                                                            type="button"
+                                                            class=style::row_button
+                                                            disabled=index + 1 == len
+                                                            on:click=move |_| {
+                                                                featured_items.update(|items| items.swap(index, index + 1));
+                                                            }
+                                                        >
+                                                            "↓"
+                                                        </button>
+                                                        <button
+                                                            type="button"
+                                                            class=style::row_button
+                                                            on:click=move |_| {
+                                                                featured_items
+                                                                    .update(|items| {
+                                                                        items.remove(index);
+                                                                    });
+                                                            }
+                                                        >
+                                                            "削除"
+                                                        </button>
+                                                    </div>
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                        }}
+                    </Suspense>
+                    <button
+                        type="button"
+                        class=style::add_button
+                        on:click=move |_| {
+                            featured_items.update(|items| items.push(FeaturedItemData::default()));
+                        }
+                    >
+                        "追加"
+                    </button>
+                    <button
+                        type="button"
+                        class=style::save_button
+                        disabled=move || saving_featured.get()
+                        on:click=move |_| {
+                            save_featured_action.dispatch(());
+                        }
+                    >
+                        {move || if saving_featured.get() { "保存中..." } else { "保存" }}
+                    </button>
+                </section>
+                <ExternalSourceStatusSection />
+            </div>
+        </AdminLayout>
+    }
+}
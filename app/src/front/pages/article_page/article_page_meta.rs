@@ -1,5 +1,6 @@
 use crate::common::dto::ArticleMetaDto;
-use crate::constants::{ORIGIN, ROMIRA_GITHUB_URL, WEB_APP_TITLE};
+use crate::common::json_ld::{self, BlogPostingParams, JsonLd};
+use crate::constants::{ORIGIN, WEB_APP_TITLE};
 use leptos::prelude::*;
 use leptos_meta::{Link, Meta, Script, Title};
 
@@ -11,24 +12,22 @@ pub(crate) fn ArticlePageMeta(meta: ArticleMetaDto) -> impl IntoView {
         .map(|k| k.get_untracked())
         .collect::<Vec<String>>()
         .join(", ");
-    let canonical_url = format!("{}/articles/{}", ORIGIN, meta.slug.get_untracked());
-    let keywords_json = meta
-        .keywords
-        .iter()
-        .map(|k| format!(r#""{}""#, k.get_untracked().replace('"', r#"\""#)))
-        .collect::<Vec<String>>()
-        .join(",");
-    let jsonld = format!(
-        r#"{{"@context":"https://schema.org","@type":"BlogPosting","headline":"{}","description":"{}","image":"{}","datePublished":"{}","dateModified":"{}","author":{{"@type":"Person","name":"Romira","url":"{}"}},"publisher":{{"@type":"Person","name":"Romira"}},"mainEntityOfPage":{{"@type":"WebPage","@id":"{}"}},"keywords":[{}],"inLanguage":"ja"}}"#,
-        meta.title.get_untracked().replace('"', r#"\""#),
-        meta.description.get_untracked().replace('"', r#"\""#),
-        meta.og_image_url.get_untracked(),
-        meta.first_published_at.get_untracked(),
-        meta.published_at.get_untracked(),
-        ROMIRA_GITHUB_URL,
-        canonical_url,
-        keywords_json
-    );
+    let page_url = format!("{}/articles/{}", ORIGIN, meta.slug.get_untracked());
+    let canonical_url = meta.canonical_url.get_untracked();
+    let jsonld = json_ld::to_script_json(&JsonLd::new(json_ld::blog_posting(BlogPostingParams {
+        url: page_url.clone(),
+        headline: meta.title.get_untracked(),
+        description: meta.description.get_untracked(),
+        og_image_url: meta.og_image_url.get_untracked(),
+        date_published: meta.first_published_at.get_untracked(),
+        date_modified: meta.published_at.get_untracked(),
+        keywords: meta.keywords.iter().map(|k| k.get_untracked()).collect(),
+    })));
+    let breadcrumb_jsonld =
+        json_ld::to_script_json(&JsonLd::new(json_ld::breadcrumb_list(vec![(
+            meta.title.get_untracked(),
+            page_url,
+        )])));
     let article_tags = meta
         .keywords
         .iter()
@@ -37,17 +36,23 @@ pub(crate) fn ArticlePageMeta(meta: ArticleMetaDto) -> impl IntoView {
             view! { <Meta property="article:tag" content=tag /> }
         })
         .collect_view();
+    let robots = meta
+        .robots
+        .get_untracked()
+        .map(|robots| view! { <Meta name="robots" content=robots /> });
     view! {
         <Title text=meta.title.get() />
         <Link rel="canonical" href=canonical_url.clone() />
+        {robots}
         <Script type_="application/ld+json">{jsonld}</Script>
+        <Script type_="application/ld+json">{breadcrumb_jsonld}</Script>
         <Meta name="description" content=meta.description.get_untracked() />
         <Meta name="keywords" content=keywords />
         <Meta name="date" content=meta.published_at.get_untracked() />
         <Meta name="creation_date" content=meta.first_published_at.get_untracked() />
         <Meta property="og:site_name" content=WEB_APP_TITLE />
-        <Meta property="og:title" content=meta.title.get_untracked() />
-        <Meta property="og:description" content=meta.description.get_untracked() />
+        <Meta property="og:title" content=meta.og_title.get_untracked() />
+        <Meta property="og:description" content=meta.og_description.get_untracked() />
         <Meta property="og:image" content=meta.og_image_url.get_untracked() />
         <Meta property="og:type" content="article" />
         <Meta property="og:locale" content="ja_JP" />
@@ -56,11 +61,11 @@ pub(crate) fn ArticlePageMeta(meta: ArticleMetaDto) -> impl IntoView {
         <Meta property="article:modified_time" content=meta.published_at.get_untracked() />
         <Meta property="article:author" content=ORIGIN />
         {article_tags}
-        <Meta name="twitter:card" content="summary_large_image" />
+        <Meta name="twitter:card" content=meta.twitter_card.get_untracked() />
         <Meta name="twitter:site" content="@Romira915" />
         <Meta name="twitter:creator" content="@Romira915" />
-        <Meta name="twitter:title" content=meta.title.get_untracked() />
-        <Meta name="twitter:description" content=meta.description.get_untracked() />
+        <Meta name="twitter:title" content=meta.og_title.get_untracked() />
+        <Meta name="twitter:description" content=meta.og_description.get_untracked() />
         <Meta name="twitter:image" content=meta.og_image_url.get_untracked() />
     }
 }
@@ -1,14 +1,17 @@
-use crate::common::dto::ArticleResponse;
-use crate::common::handlers::get_article_handler;
+use crate::common::dto::{ArticleResponse, HomePageArticleDto};
+use crate::common::handlers::{UnlockArticleHandler, get_article_handler};
 use crate::common::response::set_article_page_cache_control;
+use crate::error::GetArticleError;
+use crate::front::components::article_card::ArticleCardList;
 use crate::front::components::article_detail::ArticleDetail;
 use crate::front::components::header::Header;
 use crate::front::components::not_found::NotFound;
 use leptos::prelude::*;
+use leptos_meta::{Meta, Title};
 use leptos_router::NavigateOptions;
 use leptos_router::hooks::{use_navigate, use_params_map};
 
-use super::ArticlePageMeta;
+use super::{ArticlePageMeta, article_page_style};
 
 #[component]
 pub(crate) fn ArticlePage() -> impl IntoView {
@@ -32,12 +35,22 @@ pub(crate) fn ArticlePage() -> impl IntoView {
                                 view! {
                                     <ArticlePageMeta meta=article.article_meta_dto.clone() />
                                     <ArticleDetail article=article.article_detail_dto.clone() />
+                                    <RelatedArticles articles=article.related_articles.clone() />
                                     <script>{"hljs.highlightAll();"}</script>
                                     // newt embed
                                     <script async src="//cdn.iframe.ly/embed.js"></script>
                                 }
                                     .into_any()
                             }
+                            Ok(ArticleResponse::Protected(slug)) => {
+                                view! {
+                                    <ProtectedArticle
+                                        slug=slug.clone()
+                                        on_unlock=Callback::new(move |_| article.refetch())
+                                    />
+                                }
+                                    .into_any()
+                            }
                             Ok(ArticleResponse::Redirect(url)) => {
                                 view! { <ClientRedirect url=url.clone() /> }.into_any()
                             }
@@ -50,6 +63,89 @@ pub(crate) fn ArticlePage() -> impl IntoView {
     }
 }
 
+/// 本文の下に表示する関連記事（なければ何も表示しない）
+#[component]
+fn RelatedArticles(articles: Vec<HomePageArticleDto>) -> impl IntoView {
+    (!articles.is_empty()).then(|| {
+        view! {
+            <section class=article_page_style::related_articles>
+                <h2 class=article_page_style::related_articles_title>"関連記事"</h2>
+                <ArticleCardList articles=articles />
+            </section>
+        }
+    })
+}
+
+/// パスワード保護された記事のパスワード入力フォーム
+///
+/// パスワードが一致すると閲覧許可Cookieが発行されるため、`on_unlock` で記事を再取得する。
+/// JavaScriptが無効でも通常のフォーム送信として動作する。
+#[component]
+fn ProtectedArticle(slug: String, on_unlock: Callback<()>) -> impl IntoView {
+    let unlock_action = ServerAction::<UnlockArticleHandler>::new();
+    let pending = unlock_action.pending();
+
+    Effect::new(move |_| {
+        if unlock_action
+            .value()
+            .with(|value| matches!(value, Some(Ok(true))))
+        {
+            on_unlock.run(());
+        }
+    });
+
+    let message = move || {
+        unlock_action.value().with(|value| match value {
+            Some(Ok(false)) => Some("パスワードが違います"),
+            Some(Err(err))
+                if matches!(
+                    std::error::Error::source(err)
+                        .and_then(|source| source.downcast_ref::<GetArticleError>()),
+                    Some(GetArticleError::TooManyAttempts)
+                ) =>
+            {
+                Some("試行回数の上限に達しました。しばらく時間をおいて再度お試しください")
+            }
+            Some(Err(_)) => Some("エラーが発生しました。時間をおいて再度お試しください"),
+            _ => None,
+        })
+    };
+
+    view! {
+        <Title text="パスワードで保護された記事" />
+        <Meta name="robots" content="noindex" />
+        <section class=article_page_style::protected_article>
+            <h1 class=article_page_style::protected_article_title>
+                "この記事はパスワードで保護されています"
+            </h1>
+            <p>"閲覧するにはパスワードを入力してください。"</p>
+            <ActionForm action=unlock_action attr:class=article_page_style::protected_article_form>
+                <input type="hidden" name="slug" value=slug />
+                <input
+                    type="password"
+                    name="password"
+                    required
+                    autocomplete="current-password"
+                    aria-label="パスワード"
+                />
+                <button type="submit" disabled=pending>
+                    "表示する"
+                </button>
+            </ActionForm>
+            {move || {
+                message()
+                    .map(|message| {
+                        view! {
+                            <p class=article_page_style::protected_article_error role="alert">
+                                {message}
+                            </p>
+                        }
+                    })
+            }}
+        </section>
+    }
+}
+
 /// クライアントサイドリダイレクト（replace: trueで履歴を置き換え）
 #[allow(clippy::unused_unit)]
 #[component]
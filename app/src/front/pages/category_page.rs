@@ -0,0 +1,7 @@
+mod category_page_meta;
+mod category_page_view;
+
+pub(crate) use category_page_meta::CategoryPageMeta;
+pub(crate) use category_page_view::CategoryPage;
+
+stylance::import_style!(pub(super) category_page_style, "category_page.module.scss");
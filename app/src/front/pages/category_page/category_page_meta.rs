@@ -0,0 +1,40 @@
+use crate::common::json_ld::{self, JsonLd};
+use crate::constants::{ORIGIN, WEB_APP_TITLE, WEB_TOP_PAGE_OG_IMAGE_URL};
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Script, Title};
+
+#[component]
+pub(crate) fn CategoryPageMeta(name: String, slug: String) -> impl IntoView {
+    let title = format!("{} - {}", name, WEB_APP_TITLE);
+    let description = format!("「{}」カテゴリの記事一覧", name);
+    let canonical_url = format!("{}/categories/{}", ORIGIN, slug);
+    let jsonld = json_ld::to_script_json(&JsonLd::new(json_ld::breadcrumb_list(vec![(
+        name,
+        canonical_url.clone(),
+    )])));
+    view! {
+        <Title text=title.clone() />
+        <Link rel="canonical" href=canonical_url.clone() />
+        <Script type_="application/ld+json">{jsonld}</Script>
+        <Link
+            rel="alternate"
+            type_="application/rss+xml"
+            title=title.clone()
+            href=format!("/categories/{}/feed.xml", slug)
+        />
+        <Meta name="description" content=description.clone() />
+        <Meta property="og:title" content=title.clone() />
+        <Meta property="og:description" content=description.clone() />
+        <Meta property="og:type" content="website" />
+        <Meta property="og:url" content=canonical_url />
+        <Meta property="og:site_name" content=WEB_APP_TITLE />
+        <Meta property="og:locale" content="ja_JP" />
+        <Meta property="og:image" content=WEB_TOP_PAGE_OG_IMAGE_URL />
+        <Meta name="twitter:card" content="summary_large_image" />
+        <Meta name="twitter:site" content="@Romira915" />
+        <Meta name="twitter:creator" content="@Romira915" />
+        <Meta name="twitter:title" content=title />
+        <Meta name="twitter:description" content=description />
+        <Meta name="twitter:image" content=WEB_TOP_PAGE_OG_IMAGE_URL />
+    }
+}
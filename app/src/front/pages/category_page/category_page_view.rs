@@ -0,0 +1,50 @@
+use crate::common::handlers::get_category_articles_handler;
+use crate::front::components::article_card::ArticleCardList;
+use crate::front::components::header::Header;
+use crate::front::components::not_found::NotFound;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use super::{CategoryPageMeta, category_page_style};
+
+#[component]
+pub(crate) fn CategoryPage() -> impl IntoView {
+    let params = use_params_map();
+    let slug = move || params.read().get("slug").unwrap_or_default();
+
+    let category = Resource::new(slug, move |slug| async move {
+        get_category_articles_handler(slug).await
+    });
+
+    view! {
+        <Header is_h1=false />
+        <Suspense fallback=|| {
+            "Loading..."
+        }>
+            {move || {
+                category
+                    .map(|response| {
+                        match response {
+                            Ok(Some(category)) => {
+                                view! {
+                                    <CategoryPageMeta
+                                        name=category.name.clone()
+                                        slug=category.slug.clone()
+                                    />
+                                    <section class=category_page_style::category_page>
+                                        <h1 class=category_page_style::category_title>
+                                            {category.name.clone()}
+                                        </h1>
+                                        <ArticleCardList articles=category.articles.clone() />
+                                    </section>
+                                }
+                                    .into_any()
+                            }
+                            Ok(None) => view! { <NotFound /> }.into_any(),
+                            Err(e) => view! { <p>{format!("Error: {}", e)}</p> }.into_any(),
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
@@ -6,4 +6,8 @@ pub(crate) use preview_article_page_view::PreviewArticlePage;
 
 use stylance::import_style;
 
-import_style!(pub(crate) article_page_style, "article_page.module.scss");
+import_style!(
+    #[allow(dead_code)]
+    pub(crate) article_page_style,
+    "article_page.module.scss"
+);
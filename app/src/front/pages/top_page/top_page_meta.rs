@@ -1,15 +1,11 @@
-use crate::constants::{
-    ORIGIN, ROMIRA_GITHUB_URL, WEB_APP_DESCRIPTION, WEB_APP_TITLE, WEB_TOP_PAGE_OG_IMAGE_URL,
-};
+use crate::common::json_ld::{self, JsonLd};
+use crate::constants::{ORIGIN, WEB_APP_DESCRIPTION, WEB_APP_TITLE, WEB_TOP_PAGE_OG_IMAGE_URL};
 use leptos::prelude::*;
 use leptos_meta::{Link, Meta, Script, Title};
 
 #[component]
 pub(crate) fn TopPageMeta() -> impl IntoView {
-    let jsonld = format!(
-        r#"{{"@context":"https://schema.org","@type":"WebSite","name":"{}","url":"{}","description":"{}","author":{{"@type":"Person","name":"Romira","url":"{}"}},"inLanguage":"ja"}}"#,
-        WEB_APP_TITLE, ORIGIN, WEB_APP_DESCRIPTION, ROMIRA_GITHUB_URL
-    );
+    let jsonld = json_ld::to_script_json(&JsonLd::new(json_ld::web_site()));
     view! {
         <Title text=WEB_APP_TITLE />
         <Link rel="canonical" href=ORIGIN />
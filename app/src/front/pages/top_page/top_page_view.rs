@@ -31,8 +31,44 @@ pub(crate) fn TopPage() -> impl IntoView {
                     articles
                         .map(|articles| {
                             match articles {
-                                Ok(articles) => {
-                                    view! { <ArticleCardList articles=articles.clone() /> }
+                                Ok(top_page_articles) => {
+                                    // 取得できなかったソースも前回の記事は表示しているため、控えめに知らせるだけにする
+                                    let degraded_notice = (!top_page_articles
+                                        .degraded_sources
+                                        .is_empty())
+                                        .then(|| {
+                                            view! {
+                                                <p
+                                                    class=top_page_style::degraded_notice
+                                                    title=top_page_articles.degraded_sources.join(", ")
+                                                >
+                                                    "一部の外部記事を取得できませんでした"
+                                                </p>
+                                            }
+                                        });
+                                    // おすすめはサーバー側で先頭に並べてある
+                                    let (featured, timeline): (Vec<_>, Vec<_>) = top_page_articles
+                                        .articles
+                                        .iter()
+                                        .cloned()
+                                        .partition(|article| article.featured);
+                                    let featured_band = (!featured.is_empty())
+                                        .then(|| {
+                                            view! {
+                                                <section class=top_page_style::featured_band>
+                                                    <h2 class=top_page_style::featured_heading>
+                                                        "おすすめ"
+                                                    </h2>
+                                                    <ArticleCardList articles=featured />
+                                                </section>
+                                            }
+                                                .into_any()
+                                        });
+                                    view! {
+                                        {degraded_notice}
+                                        {featured_band}
+                                        <ArticleCardList articles=timeline />
+                                    }
                                         .into_any()
                                 }
                                 Err(e) => view! { <p>{format!("Error: {e:?}")}</p> }.into_any(),
@@ -1,10 +1,14 @@
 pub mod admin_routes;
+pub(crate) mod article_dedup;
 pub mod auth;
 pub mod config;
 pub mod contexts;
 pub mod dbsc_routes;
+pub(crate) mod featured;
+pub(crate) mod feed;
 pub(crate) mod http;
 pub(crate) mod models;
 pub mod seo_routes;
 pub(crate) mod services;
+pub(crate) mod sitemap;
 pub(crate) mod utils;
@@ -0,0 +1,418 @@
+//! トップページの記事一覧で、ソースをまたいで重複する記事を1件にまとめる
+
+use std::collections::{HashMap, HashSet};
+
+use blog_romira_dev_cms::{ArticleSyndication, PublishedArticleWithCategories};
+use leptos::prelude::{GetUntracked, WithUntracked};
+
+use crate::common::dto::{AlsoOnDto, ArticleSource, HomePageArticleDto};
+
+/// タイトルの類似度がこの値以上なら同じ記事とみなす
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.8;
+/// 正規化したタイトルがこの文字数未満なら類似度で判定しない（短いタイトルは偶然一致しやすいため）
+const MIN_TITLE_CHARS: usize = 6;
+
+/// URLの比較用にスキーム・`www.`・フラグメント・末尾のスラッシュを除去して小文字にする
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split_once('#').map_or(url, |(url, _)| url);
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    url.trim_end_matches('/').to_lowercase()
+}
+
+/// タイトルの比較用に英数字（日本語を含む）だけを残して小文字にする（全角数字は半角にする）
+fn normalize_title(title: &str) -> Vec<char> {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            _ => c,
+        })
+        .collect()
+}
+
+/// タイトル中の数字の並び（連載の番号やバージョンなど）
+fn digit_runs(title: &[char]) -> Vec<String> {
+    title
+        .split(|c| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .map(|run| run.iter().collect())
+        .collect()
+}
+
+/// 文字のバイグラムによるDice係数
+fn title_similarity(a: &[char], b: &[char]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let mut bigrams: HashMap<(char, char), usize> = HashMap::new();
+    for pair in a.windows(2) {
+        *bigrams.entry((pair[0], pair[1])).or_default() += 1;
+    }
+    let mut common = 0;
+    for pair in b.windows(2) {
+        if let Some(count) = bigrams.get_mut(&(pair[0], pair[1]))
+            && *count > 0
+        {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    (2 * common) as f64 / (a.len() + b.len() - 2) as f64
+}
+
+/// 重複したときに残すソースの優先順（小さいほど優先）
+fn source_priority(source: &ArticleSource) -> u8 {
+    match source {
+        ArticleSource::Local => 0,
+        ArticleSource::Qiita => 1,
+        ArticleSource::Zenn => 2,
+        ArticleSource::WordPress => 3,
+        ArticleSource::Feed(_) => 4,
+    }
+}
+
+/// ローカル記事ごとに、同じ記事を指す外部のURL（正規化済み）を集める
+///
+/// 取り込み元のURL、SEO設定の正規URL、転載先の記事のURLを対象にする。
+/// キーはトップページの記事一覧と同じルート相対パス（`/articles/{slug}`）。
+pub(crate) fn local_article_links(
+    articles: &[PublishedArticleWithCategories],
+    syndications: &[ArticleSyndication],
+) -> HashMap<String, HashSet<String>> {
+    articles
+        .iter()
+        .map(|value| {
+            let article = &value.article;
+            let urls = article
+                .origin_url
+                .iter()
+                .chain(article.seo.canonical_url.iter())
+                .chain(
+                    syndications
+                        .iter()
+                        .filter(|syndication| syndication.article_id == article.id)
+                        .filter_map(|syndication| syndication.remote_url.as_ref()),
+                )
+                .map(|url| normalize_url(url))
+                .collect();
+            (format!("/articles/{}", article.slug), urls)
+        })
+        .collect()
+}
+
+/// `candidate` が残す記事 `kept` と同じ記事か
+fn is_same_article(
+    kept: &HomePageArticleDto,
+    candidate: &HomePageArticleDto,
+    links: &HashMap<String, HashSet<String>>,
+) -> bool {
+    let linked = kept.src.with_untracked(|src| {
+        links.get(src).is_some_and(|urls| {
+            candidate
+                .src
+                .with_untracked(|candidate_src| urls.contains(&normalize_url(candidate_src)))
+        })
+    });
+    if linked {
+        return true;
+    }
+
+    let kept_title = kept.title.with_untracked(|title| normalize_title(title));
+    let candidate_title = candidate
+        .title
+        .with_untracked(|title| normalize_title(title));
+    // 数字だけが異なるタイトル（「その1」と「その2」など）は連載の別の記事とみなす
+    kept_title.len() >= MIN_TITLE_CHARS
+        && candidate_title.len() >= MIN_TITLE_CHARS
+        && digit_runs(&kept_title) == digit_runs(&candidate_title)
+        && title_similarity(&kept_title, &candidate_title) >= TITLE_SIMILARITY_THRESHOLD
+}
+
+/// ソースをまたいで重複する記事を優先するソースの1件にまとめる
+///
+/// ローカル記事を最優先に、ソースの優先順で残す記事を決める。ローカル記事のリンク（`links`）に
+/// 含まれるURLの記事か、正規化したタイトルが似ていて数字の並びが一致する記事を同じ記事とみなし、残す記事の
+/// `also_on` に掲載先として追加する。同じソースの記事どうしはまとめない。
+/// 残った記事は元の順のまま返す。
+pub(crate) fn merge_duplicate_articles(
+    articles: Vec<HomePageArticleDto>,
+    links: &HashMap<String, HashSet<String>>,
+) -> Vec<HomePageArticleDto> {
+    let mut order: Vec<usize> = (0..articles.len()).collect();
+    order.sort_by_key(|&index| (source_priority(&articles[index].article_source), index));
+
+    let mut kept: Vec<usize> = Vec::new();
+    let mut also_on: Vec<Vec<AlsoOnDto>> = vec![Vec::new(); articles.len()];
+    for index in order {
+        let candidate = &articles[index];
+        if candidate.article_source != ArticleSource::Local
+            && let Some(&kept_index) = kept.iter().find(|&&kept_index| {
+                let kept_article = &articles[kept_index];
+                kept_article.article_source != candidate.article_source
+                    && !also_on[kept_index]
+                        .iter()
+                        .any(|link| link.article_source == candidate.article_source)
+                    && is_same_article(kept_article, candidate, links)
+            })
+        {
+            also_on[kept_index].push(AlsoOnDto {
+                article_source: candidate.article_source.clone(),
+                url: candidate.src.get_untracked(),
+            });
+            continue;
+        }
+        kept.push(index);
+    }
+
+    let kept: HashSet<usize> = kept.into_iter().collect();
+    articles
+        .into_iter()
+        .zip(also_on)
+        .enumerate()
+        .filter(|(index, _)| kept.contains(index))
+        .map(|(_, (mut article, also_on))| {
+            article.also_on = also_on;
+            article
+        })
+        .collect()
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::prelude::*;
+
+    fn article(title: &str, src: &str, article_source: ArticleSource) -> HomePageArticleDto {
+        HomePageArticleDto {
+            title: RwSignal::new(title.to_string()),
+            thumbnail_url: RwSignal::new(String::new()),
+            src: RwSignal::new(src.to_string()),
+            category: Vec::new(),
+            first_published_at: RwSignal::new(String::new()),
+            article_source,
+            featured: false,
+            emoji: None,
+            also_on: Vec::new(),
+        }
+    }
+
+    fn summary(articles: &[HomePageArticleDto]) -> Vec<(String, Vec<String>)> {
+        articles
+            .iter()
+            .map(|a| {
+                (
+                    a.src.get_untracked(),
+                    a.also_on.iter().map(|link| link.url.clone()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn links(entries: &[(&str, &[&str])]) -> HashMap<String, HashSet<String>> {
+        entries
+            .iter()
+            .map(|(src, urls)| {
+                (
+                    src.to_string(),
+                    urls.iter().map(|url| normalize_url(url)).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_比較用にスキームやフラグメントを除去して小文字にすること() {
+        assert_eq!(
+            normalize_url("https://www.Qiita.com/Romira/items/abc/#comments"),
+            "qiita.com/romira/items/abc"
+        );
+        assert_eq!(normalize_url("http://zenn.dev/a/"), "zenn.dev/a");
+    }
+
+    // RwSignalを使うためリアクティブなOwnerの中で実行する
+    #[test]
+    fn test_リンクが記録された外部記事をローカル記事にまとめること() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article(
+                    "Qiitaでのタイトル",
+                    "https://qiita.com/Romira/items/abc",
+                    ArticleSource::Qiita,
+                ),
+                article(
+                    "ローカルのタイトル",
+                    "/articles/local",
+                    ArticleSource::Local,
+                ),
+                article(
+                    "別の記事です",
+                    "https://zenn.dev/romira/x",
+                    ArticleSource::Zenn,
+                ),
+            ];
+            let links = links(&[("/articles/local", &["https://qiita.com/romira/items/abc/"])]);
+
+            let result = merge_duplicate_articles(articles, &links);
+
+            assert_eq!(
+                summary(&result),
+                vec![
+                    (
+                        "/articles/local".to_string(),
+                        vec!["https://qiita.com/Romira/items/abc".to_string()]
+                    ),
+                    ("https://zenn.dev/romira/x".to_string(), vec![]),
+                ]
+            );
+            assert_eq!(result[0].also_on[0].article_source, ArticleSource::Qiita);
+        });
+    }
+
+    #[test]
+    fn test_タイトルが似ている記事を優先するソースの記事にまとめること() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article(
+                    "Rustで始めるWebAssembly入門",
+                    "https://zenn.dev/romira/wasm",
+                    ArticleSource::Zenn,
+                ),
+                article(
+                    "Rustで始める WebAssembly 入門!",
+                    "https://qiita.com/Romira/items/wasm",
+                    ArticleSource::Qiita,
+                ),
+                article(
+                    "LeptosでSSRする方法",
+                    "https://qiita.com/Romira/items/leptos",
+                    ArticleSource::Qiita,
+                ),
+            ];
+
+            let result = merge_duplicate_articles(articles, &HashMap::new());
+
+            assert_eq!(
+                summary(&result),
+                vec![
+                    (
+                        "https://qiita.com/Romira/items/wasm".to_string(),
+                        vec!["https://zenn.dev/romira/wasm".to_string()]
+                    ),
+                    ("https://qiita.com/Romira/items/leptos".to_string(), vec![]),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_同じソースの記事や短いタイトルの記事はまとめないこと() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article("Rust入門 その1", "/articles/a", ArticleSource::Local),
+                article("Rust入門 その1", "/articles/b", ArticleSource::Local),
+                article(
+                    "memo",
+                    "https://qiita.com/Romira/items/a",
+                    ArticleSource::Qiita,
+                ),
+                article("Memo", "https://zenn.dev/romira/a", ArticleSource::Zenn),
+            ];
+
+            let result = merge_duplicate_articles(articles, &HashMap::new());
+
+            assert_eq!(result.len(), 4);
+            assert!(result.iter().all(|a| a.also_on.is_empty()));
+        });
+    }
+
+    #[test]
+    fn test_番号だけが異なる連載の記事はソースが異なってもまとめないこと() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article(
+                    "Rust入門 その1",
+                    "https://zenn.dev/romira/rust-1",
+                    ArticleSource::Zenn,
+                ),
+                article(
+                    "Rust入門 その2",
+                    "https://qiita.com/Romira/items/rust-2",
+                    ArticleSource::Qiita,
+                ),
+                article(
+                    "Rust入門 その１",
+                    "https://qiita.com/Romira/items/rust-1",
+                    ArticleSource::Qiita,
+                ),
+            ];
+
+            let result = merge_duplicate_articles(articles, &HashMap::new());
+
+            assert_eq!(
+                summary(&result),
+                vec![
+                    ("https://qiita.com/Romira/items/rust-2".to_string(), vec![]),
+                    (
+                        "https://qiita.com/Romira/items/rust-1".to_string(),
+                        vec!["https://zenn.dev/romira/rust-1".to_string()]
+                    ),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_ひとつの記事にソースごとに1件ずつまとめること() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article(
+                    "LeptosでSSRする方法",
+                    "/articles/leptos",
+                    ArticleSource::Local,
+                ),
+                article(
+                    "LeptosでSSRする方法",
+                    "https://zenn.dev/romira/leptos",
+                    ArticleSource::Zenn,
+                ),
+                article(
+                    "LeptosでSSRする方法",
+                    "https://qiita.com/Romira/items/leptos",
+                    ArticleSource::Qiita,
+                ),
+                article(
+                    "LeptosでSSRする方法",
+                    "https://qiita.com/Romira/items/leptos2",
+                    ArticleSource::Qiita,
+                ),
+            ];
+
+            let result = merge_duplicate_articles(articles, &HashMap::new());
+
+            assert_eq!(
+                summary(&result),
+                vec![
+                    (
+                        "/articles/leptos".to_string(),
+                        vec![
+                            "https://qiita.com/Romira/items/leptos".to_string(),
+                            "https://zenn.dev/romira/leptos".to_string(),
+                        ]
+                    ),
+                    ("https://qiita.com/Romira/items/leptos2".to_string(), vec![]),
+                ]
+            );
+        });
+    }
+}
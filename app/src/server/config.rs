@@ -1,3 +1,6 @@
+use crate::constants::{
+    PRTIMES_WORD_PRESS_AUTHOR_ID, PRTIMES_WORD_PRESS_BASE_URL, QIITA_BASE_URL, ZENN_BASE_URL,
+};
 use serde::Deserialize;
 use std::sync::LazyLock;
 
@@ -9,6 +12,52 @@ pub struct ServerConfig {
     pub newt_cdn_api_token: String,
     pub newt_api_token: String,
     pub qiita_api_token: String,
+    // External article sources on the top page (comma-separated: wordpress, qiita, zenn, feed)
+    #[serde(default = "default_external_article_sources")]
+    pub external_article_sources: Vec<String>,
+    #[serde(default = "default_word_press_base_url")]
+    pub word_press_base_url: String,
+    #[serde(default = "default_word_press_author_id")]
+    pub word_press_author_id: u64,
+    #[serde(default = "default_qiita_base_url")]
+    pub qiita_base_url: String,
+    // Qiita user whose items are listed (optional — authenticated user's items if not set)
+    #[serde(default)]
+    pub qiita_user_name: String,
+    // Upper limit of pages (100 items each) fetched from Qiita
+    #[serde(default = "default_qiita_max_pages")]
+    pub qiita_max_pages: u32,
+    #[serde(default = "default_zenn_base_url")]
+    pub zenn_base_url: String,
+    // Zenn user whose articles are listed (required when the zenn source is enabled)
+    #[serde(default)]
+    pub zenn_user_name: String,
+    // RSS/Atom feeds as a JSON array (used when the feed source is enabled)
+    #[serde(default)]
+    pub feed_sources: String,
+    // Interval of refreshing external articles in the background
+    #[serde(default = "default_external_article_refresh_interval_secs")]
+    pub external_article_refresh_interval_secs: u64,
+    // Time limit of fetching each external source
+    #[serde(default = "default_external_article_source_timeout_secs")]
+    pub external_article_source_timeout_secs: u64,
+    // Pause a source for the cool-down period after this many consecutive failures
+    #[serde(default = "default_external_article_failure_threshold")]
+    pub external_article_failure_threshold: u32,
+    #[serde(default = "default_external_article_cool_down_secs")]
+    pub external_article_cool_down_secs: u64,
+    // Share cached external articles through Valkey (optional — in-memory only if not set)
+    #[serde(default)]
+    pub external_article_cache_valkey: bool,
+    // Services that published articles are cross-posted to (comma-separated: qiita — none if not set)
+    #[serde(default)]
+    pub syndication_targets: Vec<String>,
+    // oEmbed providers of standalone URLs in articles as a JSON array (optional — YouTube, X, CodePen and Speaker Deck if not set)
+    #[serde(default)]
+    pub embed_providers: String,
+    // Use CF-Connecting-IP as the client IP of unlock attempts (only when Cloudflare is in front of nginx)
+    #[serde(default)]
+    pub trust_cf_connecting_ip: bool,
     pub database_url: String,
     // OAuth (required for admin authentication)
     pub google_client_id: String,
@@ -24,16 +73,61 @@ pub struct ServerConfig {
     pub valkey_url: String,
     // Admin access control (comma-separated list of allowed email addresses)
     pub admin_emails: String,
+    // Secret key for signing access cookies of password-protected articles
+    pub article_access_secret: String,
     // Cloudflare CDN cache purge (optional — skip purge if not set)
     #[serde(default)]
     pub cloudflare_zone_id: String,
     #[serde(default)]
     pub cloudflare_api_token: String,
+    // Font file for generated OG images (optional — system fonts only if not set)
+    #[serde(default)]
+    pub og_image_font_path: String,
     // OpenTelemetry OTLP endpoint (optional — uses New Relic default if not set)
     #[serde(default)]
     pub otlp_endpoint: String,
 }
 
+fn default_external_article_sources() -> Vec<String> {
+    vec!["wordpress".to_string(), "qiita".to_string()]
+}
+
+fn default_word_press_base_url() -> String {
+    PRTIMES_WORD_PRESS_BASE_URL.to_string()
+}
+
+fn default_word_press_author_id() -> u64 {
+    PRTIMES_WORD_PRESS_AUTHOR_ID
+}
+
+fn default_qiita_base_url() -> String {
+    QIITA_BASE_URL.to_string()
+}
+
+fn default_qiita_max_pages() -> u32 {
+    10
+}
+
+fn default_zenn_base_url() -> String {
+    ZENN_BASE_URL.to_string()
+}
+
+fn default_external_article_refresh_interval_secs() -> u64 {
+    600
+}
+
+fn default_external_article_source_timeout_secs() -> u64 {
+    10
+}
+
+fn default_external_article_failure_threshold() -> u32 {
+    3
+}
+
+fn default_external_article_cool_down_secs() -> u64 {
+    1800
+}
+
 fn default_valkey_url() -> String {
     "redis://localhost:6379/0".to_string()
 }
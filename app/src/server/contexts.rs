@@ -1,20 +1,33 @@
-use crate::constants::{
-    NEWT_BASE_URL, NEWT_CDN_BASE_URL, PRTIMES_WORD_PRESS_BASE_URL, QIITA_BASE_URL,
-};
+use crate::common::embed_provider::EmbedProviders;
+use crate::constants::{NEWT_BASE_URL, NEWT_CDN_BASE_URL};
 use crate::server::config::SERVER_CONFIG;
+use crate::server::featured::spawn_featured_expiry_task;
+use crate::server::services::article_access::ArticleAccessService;
+use crate::server::services::article_render::ArticleRenderService;
 use crate::server::services::cloudflare::CloudflarePurgeService;
+use crate::server::services::content_lint::ContentLintService;
 use crate::server::services::dbsc::DbscService;
+use crate::server::services::external_article_cache::{
+    ExternalArticleCache, RefreshPolicy, ValkeyArticleStore,
+};
+use crate::server::services::external_source::ExternalArticleSources;
 use crate::server::services::gcs::GcsStorageService;
 use crate::server::services::imgix::ImgixService;
+use crate::server::services::link_card::LinkCardService;
 use crate::server::services::newt::NewtArticleService;
+use crate::server::services::og_image::{OgImageService, default_avatar_url};
 use crate::server::services::qiita::QiitaArticleService;
+use crate::server::services::qiita_import::QiitaImportService;
 use crate::server::services::signing::GcsSigningService;
-use crate::server::services::word_press::WordPressArticleService;
+use crate::server::services::syndication::{QIITA_SYNDICATION_TARGET, SyndicationService};
+use crate::server::services::unlock_attempt::UnlockAttemptLimiter;
 use axum::extract::FromRef;
 use blog_romira_dev_cms::{
-    AdminArticleService, CategoryService, DraftArticleService, ImageService,
-    PublishedArticleService,
+    AdminArticleService, ArticleLinkService, ArticleSyndicationService, CategoryService,
+    DraftArticleService, FeaturedItemService, ImageService, OgImageCacheService, OgpCacheService,
+    ProseCheckRuleService, PublishedArticleService, RelatedArticleService,
 };
+use fred::prelude::Pool;
 use leptos::prelude::*;
 use sqlx::PgPool;
 use tracing::instrument;
@@ -24,23 +37,35 @@ pub struct AppState {
     pub(crate) leptos_options: LeptosOptions,
     pub(crate) db_pool: PgPool,
     pub(crate) newt_article_service: NewtArticleService,
-    pub(crate) word_press_article_service: WordPressArticleService,
-    pub(crate) qiita_article_service: QiitaArticleService,
+    pub(crate) external_article_cache: ExternalArticleCache,
     pub(crate) published_article_service: PublishedArticleService,
     pub(crate) draft_article_service: DraftArticleService,
     pub(crate) admin_article_service: AdminArticleService,
     pub(crate) image_service: ImageService,
     pub(crate) category_service: CategoryService,
+    pub(crate) prose_check_rule_service: ProseCheckRuleService,
+    pub(crate) article_link_service: ArticleLinkService,
+    pub(crate) related_article_service: RelatedArticleService,
+    pub(crate) featured_item_service: FeaturedItemService,
     pub(crate) signing_service: GcsSigningService,
     pub(crate) gcs_storage_service: GcsStorageService,
     pub(crate) imgix_service: ImgixService,
     pub(crate) cloudflare_purge_service: Option<CloudflarePurgeService>,
+    pub(crate) link_card_service: LinkCardService,
+    pub(crate) article_render_service: ArticleRenderService,
+    pub(crate) content_lint_service: ContentLintService,
+    pub(crate) og_image_service: OgImageService,
     pub(crate) dbsc_service: DbscService,
+    pub(crate) article_access_service: ArticleAccessService,
+    pub(crate) unlock_attempt_limiter: UnlockAttemptLimiter,
+    pub(crate) qiita_import_service: QiitaImportService,
+    pub(crate) syndication_service: SyndicationService,
 }
 
 impl AppState {
-    #[instrument(skip(db_pool))]
-    pub fn new(leptos_options: LeptosOptions, db_pool: PgPool) -> Self {
+    /// `valkey_pool` はセッションストアと共有する接続済みのValkeyのプール
+    #[instrument(skip(db_pool, valkey_pool))]
+    pub fn new(leptos_options: LeptosOptions, db_pool: PgPool, valkey_pool: Pool) -> Self {
         let client = reqwest::Client::new();
 
         // 署名サービスの初期化
@@ -56,6 +81,103 @@ impl AppState {
         // imgixサービスの初期化
         let imgix_service = ImgixService::new(SERVER_CONFIG.imgix_domain.clone());
 
+        // Cloudflareキャッシュパージサービスの初期化（未設定なら無効）
+        let cloudflare_purge_service = if !SERVER_CONFIG.cloudflare_zone_id.is_empty()
+            && !SERVER_CONFIG.cloudflare_api_token.is_empty()
+        {
+            Some(CloudflarePurgeService::new(
+                client.clone(),
+                &SERVER_CONFIG.cloudflare_zone_id,
+                &SERVER_CONFIG.cloudflare_api_token,
+            ))
+        } else {
+            None
+        };
+
+        // リンクカードサービスの初期化
+        let link_card_service = LinkCardService::new(
+            client.clone(),
+            OgpCacheService::new(db_pool.clone()),
+            cloudflare_purge_service.clone(),
+            EmbedProviders::from_config(&SERVER_CONFIG.embed_providers),
+        );
+
+        let image_service =
+            ImageService::new(db_pool.clone(), SERVER_CONFIG.gcs_path_prefix.clone());
+
+        let published_article_service = PublishedArticleService::new(db_pool.clone());
+
+        // 記事本文の変換データ取得サービスの初期化
+        let article_render_service = ArticleRenderService::new(
+            link_card_service.clone(),
+            image_service.clone(),
+            published_article_service.clone(),
+        );
+
+        // 記事の構造チェックサービスの初期化
+        let content_lint_service = ContentLintService::new(
+            published_article_service.clone(),
+            SERVER_CONFIG.imgix_domain.clone(),
+            SERVER_CONFIG.app_url.clone(),
+        );
+
+        // 外部記事キャッシュの初期化（Valkeyは設定した場合のみ）
+        let external_article_store = SERVER_CONFIG
+            .external_article_cache_valkey
+            .then(|| ValkeyArticleStore::new(valkey_pool.clone()));
+        // パスワード試行回数はインスタンス間で共有する
+        let unlock_attempt_limiter =
+            UnlockAttemptLimiter::new(Some(valkey_pool), SERVER_CONFIG.trust_cf_connecting_ip);
+        let external_article_cache = ExternalArticleCache::new(
+            ExternalArticleSources::from_config(client.clone(), &SERVER_CONFIG),
+            external_article_store,
+            cloudflare_purge_service.clone(),
+            RefreshPolicy::from_config(&SERVER_CONFIG),
+        );
+
+        // OGP画像生成サービスの初期化（フォント未指定ならシステムフォントのみ）
+        let og_image_service = OgImageService::new(
+            client.clone(),
+            OgImageCacheService::new(db_pool.clone()),
+            (!SERVER_CONFIG.og_image_font_path.is_empty())
+                .then(|| SERVER_CONFIG.og_image_font_path.clone()),
+            default_avatar_url(),
+        );
+
+        let draft_article_service = DraftArticleService::new(db_pool.clone());
+        let admin_article_service = AdminArticleService::new(db_pool.clone());
+        let category_service = CategoryService::new(db_pool.clone());
+
+        // Qiita記事の取り込み・転載で使うQiita APIクライアント（外部記事の一覧とは別に取得する）
+        let qiita_article_service = QiitaArticleService::new(
+            client.clone(),
+            &SERVER_CONFIG.qiita_base_url,
+            &SERVER_CONFIG.qiita_api_token,
+            (!SERVER_CONFIG.qiita_user_name.is_empty())
+                .then(|| SERVER_CONFIG.qiita_user_name.clone()),
+            SERVER_CONFIG.qiita_max_pages,
+        );
+
+        // Qiita記事の取り込みサービスの初期化
+        let qiita_import_service = QiitaImportService::new(
+            qiita_article_service.clone(),
+            draft_article_service.clone(),
+            category_service.clone(),
+            admin_article_service.clone(),
+        );
+
+        // 転載サービスの初期化（転載先に設定したサービスのみ）
+        let syndication_service = SyndicationService::new(
+            SERVER_CONFIG
+                .syndication_targets
+                .iter()
+                .any(|target| target.trim().eq_ignore_ascii_case(QIITA_SYNDICATION_TARGET))
+                .then_some(qiita_article_service),
+            published_article_service.clone(),
+            ArticleSyndicationService::new(db_pool.clone()),
+            &SERVER_CONFIG.app_url,
+        );
+
         Self {
             leptos_options,
             db_pool: db_pool.clone(),
@@ -64,34 +186,29 @@ impl AppState {
                 NEWT_CDN_BASE_URL,
                 NEWT_BASE_URL,
             ),
-            word_press_article_service: WordPressArticleService::new(
-                client.clone(),
-                PRTIMES_WORD_PRESS_BASE_URL,
-            ),
-            qiita_article_service: QiitaArticleService::new(client.clone(), QIITA_BASE_URL),
-            published_article_service: PublishedArticleService::new(db_pool.clone()),
-            draft_article_service: DraftArticleService::new(db_pool.clone()),
-            admin_article_service: AdminArticleService::new(db_pool.clone()),
-            image_service: ImageService::new(
-                db_pool.clone(),
-                SERVER_CONFIG.gcs_path_prefix.clone(),
-            ),
-            category_service: CategoryService::new(db_pool),
+            external_article_cache,
+            published_article_service,
+            draft_article_service,
+            admin_article_service,
+            image_service,
+            prose_check_rule_service: ProseCheckRuleService::new(db_pool.clone()),
+            featured_item_service: FeaturedItemService::new(db_pool.clone()),
+            article_link_service: ArticleLinkService::new(db_pool.clone()),
+            related_article_service: RelatedArticleService::new(db_pool),
+            category_service,
             signing_service,
             gcs_storage_service,
             imgix_service,
-            cloudflare_purge_service: if !SERVER_CONFIG.cloudflare_zone_id.is_empty()
-                && !SERVER_CONFIG.cloudflare_api_token.is_empty()
-            {
-                Some(CloudflarePurgeService::new(
-                    client,
-                    &SERVER_CONFIG.cloudflare_zone_id,
-                    &SERVER_CONFIG.cloudflare_api_token,
-                ))
-            } else {
-                None
-            },
+            cloudflare_purge_service,
+            link_card_service,
+            article_render_service,
+            content_lint_service,
+            og_image_service,
             dbsc_service: DbscService::new(SERVER_CONFIG.app_url.clone()),
+            article_access_service: ArticleAccessService::new(&SERVER_CONFIG.article_access_secret),
+            unlock_attempt_limiter,
+            qiita_import_service,
+            syndication_service,
         }
     }
 
@@ -131,6 +248,22 @@ impl AppState {
         &self.category_service
     }
 
+    pub fn prose_check_rule_service(&self) -> &ProseCheckRuleService {
+        &self.prose_check_rule_service
+    }
+
+    pub fn article_link_service(&self) -> &ArticleLinkService {
+        &self.article_link_service
+    }
+
+    pub fn related_article_service(&self) -> &RelatedArticleService {
+        &self.related_article_service
+    }
+
+    pub fn featured_item_service(&self) -> &FeaturedItemService {
+        &self.featured_item_service
+    }
+
     pub fn imgix_service(&self) -> &ImgixService {
         &self.imgix_service
     }
@@ -139,14 +272,97 @@ impl AppState {
         self.cloudflare_purge_service.as_ref()
     }
 
+    pub(crate) fn link_card_service(&self) -> &LinkCardService {
+        &self.link_card_service
+    }
+
+    pub(crate) fn content_lint_service(&self) -> &ContentLintService {
+        &self.content_lint_service
+    }
+
+    pub(crate) fn og_image_service(&self) -> &OgImageService {
+        &self.og_image_service
+    }
+
     pub(crate) fn dbsc_service(&self) -> &DbscService {
         &self.dbsc_service
     }
 
+    pub(crate) fn article_access_service(&self) -> &ArticleAccessService {
+        &self.article_access_service
+    }
+
+    pub(crate) fn unlock_attempt_limiter(&self) -> &UnlockAttemptLimiter {
+        &self.unlock_attempt_limiter
+    }
+
+    pub(crate) fn external_article_cache(&self) -> &ExternalArticleCache {
+        &self.external_article_cache
+    }
+
+    pub(crate) fn qiita_import_service(&self) -> &QiitaImportService {
+        &self.qiita_import_service
+    }
+
+    pub(crate) fn syndication_service(&self) -> &SyndicationService {
+        &self.syndication_service
+    }
+
+    /// バックグラウンドのタスク（外部記事の定期取得、おすすめの表示期限の反映）を起動する
+    pub fn spawn_background_tasks(&self) {
+        self.external_article_cache.spawn_refresh_task();
+        spawn_featured_expiry_task(
+            self.featured_item_service.clone(),
+            self.cloudflare_purge_service.clone(),
+        );
+    }
+
     /// テスト用のインスタンスを作成（署名サービスはスタブ）
     #[cfg(any(test, feature = "test-utils"))]
     pub fn new_for_test(leptos_options: LeptosOptions, db_pool: PgPool) -> Self {
         let client = reqwest::Client::new();
+        let image_service = ImageService::new(db_pool.clone(), "test".to_string());
+        let link_card_service = LinkCardService::new(
+            client.clone(),
+            OgpCacheService::new(db_pool.clone()),
+            None,
+            EmbedProviders::default(),
+        );
+        let published_article_service = PublishedArticleService::new(db_pool.clone());
+        let content_lint_service = ContentLintService::new(
+            published_article_service.clone(),
+            "test.imgix.net".to_string(),
+            "http://localhost:3000".to_string(),
+        );
+        let article_render_service = ArticleRenderService::new(
+            link_card_service.clone(),
+            image_service.clone(),
+            published_article_service.clone(),
+        );
+        // テストでは著者アイコンを外部から取得しない
+        let og_image_service = OgImageService::new(
+            client.clone(),
+            OgImageCacheService::new(db_pool.clone()),
+            None,
+            None,
+        );
+        let draft_article_service = DraftArticleService::new(db_pool.clone());
+        let admin_article_service = AdminArticleService::new(db_pool.clone());
+        let category_service = CategoryService::new(db_pool.clone());
+        // テストでは `with_qiita_base_url` で取得先を差し替える
+        let qiita_import_service = QiitaImportService::new(
+            QiitaArticleService::new(client.clone(), "http://localhost", "test", None, 10),
+            draft_article_service.clone(),
+            category_service.clone(),
+            admin_article_service.clone(),
+        );
+        // テストでは `with_qiita_base_url` で転載を有効にする
+        let syndication_service = SyndicationService::new(
+            None,
+            published_article_service.clone(),
+            ArticleSyndicationService::new(db_pool.clone()),
+            "http://localhost:3000",
+        );
 
         Self {
             leptos_options,
@@ -156,21 +372,56 @@ impl AppState {
                 NEWT_CDN_BASE_URL,
                 NEWT_BASE_URL,
             ),
-            word_press_article_service: WordPressArticleService::new(
-                client.clone(),
-                PRTIMES_WORD_PRESS_BASE_URL,
+            // テストでは外部記事を取得しない
+            external_article_cache: ExternalArticleCache::new(
+                ExternalArticleSources::default(),
+                None,
+                None,
+                RefreshPolicy::default(),
             ),
-            qiita_article_service: QiitaArticleService::new(client.clone(), QIITA_BASE_URL),
-            published_article_service: PublishedArticleService::new(db_pool.clone()),
-            draft_article_service: DraftArticleService::new(db_pool.clone()),
-            admin_article_service: AdminArticleService::new(db_pool.clone()),
-            image_service: ImageService::new(db_pool.clone(), "test".to_string()),
-            category_service: CategoryService::new(db_pool),
+            published_article_service,
+            draft_article_service,
+            admin_article_service,
+            image_service,
+            prose_check_rule_service: ProseCheckRuleService::new(db_pool.clone()),
+            featured_item_service: FeaturedItemService::new(db_pool.clone()),
+            article_link_service: ArticleLinkService::new(db_pool.clone()),
+            related_article_service: RelatedArticleService::new(db_pool),
+            category_service,
             signing_service: GcsSigningService::new_stub("test-bucket".to_string()),
             gcs_storage_service: GcsStorageService::new_stub(),
             imgix_service: ImgixService::new("test.imgix.net".to_string()),
             cloudflare_purge_service: None,
+            link_card_service,
+            article_render_service,
+            content_lint_service,
+            og_image_service,
             dbsc_service: DbscService::new("http://localhost:3000".to_string()),
+            article_access_service: ArticleAccessService::new("test-article-access-secret"),
+            // テストではValkeyを使わずメモリ上で数える
+            unlock_attempt_limiter: UnlockAttemptLimiter::new(None, false),
+            qiita_import_service,
+            syndication_service,
         }
     }
+
+    /// テスト用: Qiita記事の取り込み・転載で使うQiita APIのベースURLを差し替える（Qiitaへの転載も有効にする）
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn with_qiita_base_url(mut self, qiita_base_url: &str) -> Self {
+        let qiita_article_service =
+            QiitaArticleService::new(reqwest::Client::new(), qiita_base_url, "test", None, 10);
+        self.syndication_service = SyndicationService::new(
+            Some(qiita_article_service.clone()),
+            self.published_article_service.clone(),
+            ArticleSyndicationService::new(self.db_pool.clone()),
+            "http://localhost:3000",
+        );
+        self.qiita_import_service = QiitaImportService::new(
+            qiita_article_service,
+            self.draft_article_service.clone(),
+            self.category_service.clone(),
+            self.admin_article_service.clone(),
+        );
+        self
+    }
 }
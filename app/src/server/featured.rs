@@ -0,0 +1,198 @@
+//! トップページの「おすすめ」の並べ替えと表示期限の反映
+
+use blog_romira_dev_cms::{FeaturedItem, FeaturedItemService};
+use leptos::prelude::WithUntracked;
+use std::time::Duration;
+
+use crate::common::dto::HomePageArticleDto;
+use crate::server::services::cloudflare::CloudflarePurgeService;
+
+/// おすすめの表示期限を確認する間隔
+const FEATURED_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// URLの比較用に末尾のスラッシュを除去
+fn normalize_url(url: &str) -> &str {
+    url.trim_end_matches('/')
+}
+
+/// おすすめの項目が指す記事のURL（ローカル記事はルート相対パス）
+fn featured_item_src(item: &FeaturedItem) -> Option<String> {
+    match (&item.article_slug, &item.external_url) {
+        (Some(slug), _) => Some(format!("/articles/{}", slug)),
+        (None, Some(url)) => Some(normalize_url(url).to_string()),
+        (None, None) => None,
+    }
+}
+
+/// おすすめの記事を `featured` にして指定の順で先頭に移動する
+///
+/// 他の掲載先にまとめた外部記事は、まとめた先の記事をおすすめにする。
+/// 記事一覧に含まれない項目（非公開になった記事や取得できなかった外部記事）は無視する。
+/// 残りの記事は元の順のまま後ろに続く。
+pub(crate) fn pin_featured_articles(
+    mut articles: Vec<HomePageArticleDto>,
+    featured_items: &[FeaturedItem],
+) -> Vec<HomePageArticleDto> {
+    let mut pinned = Vec::new();
+    for src in featured_items.iter().filter_map(featured_item_src) {
+        if let Some(index) = articles.iter().position(|article| {
+            article.src.with_untracked(|s| normalize_url(s) == src)
+                || article
+                    .also_on
+                    .iter()
+                    .any(|link| normalize_url(&link.url) == src)
+        }) {
+            let mut article = articles.remove(index);
+            article.featured = true;
+            pinned.push(article);
+        }
+    }
+    pinned.extend(articles);
+    pinned
+}
+
+/// おすすめの表示期限が来たらトップページのCDNキャッシュをパージするタスクを起動する
+///
+/// トップページはCDNに長くキャッシュされるため、保存時のパージだけでは期限切れの項目が残り続ける。
+/// パージに失敗した場合は次の確認で再度パージする。パージサービスが未設定なら起動しない。
+pub(crate) fn spawn_featured_expiry_task(
+    featured_item_service: FeaturedItemService,
+    cloudflare_purge_service: Option<CloudflarePurgeService>,
+) {
+    let Some(purge_service) = cloudflare_purge_service else {
+        return;
+    };
+    tokio::spawn(async move {
+        let mut since = chrono::Utc::now().naive_utc();
+        let mut interval = tokio::time::interval(FEATURED_EXPIRY_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let now = chrono::Utc::now().naive_utc();
+            match featured_item_service.has_expired_between(since, now).await {
+                Ok(false) => since = now,
+                Ok(true) => match purge_service.purge_tags(&["top-page".to_string()]).await {
+                    Ok(()) => since = now,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to purge Cloudflare cache after featured items expired");
+                    }
+                },
+                Err(err) => {
+                    tracing::warn!(
+                        error = err.to_string(),
+                        "Failed to check expired featured items"
+                    );
+                }
+            }
+        }
+    });
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::dto::{AlsoOnDto, ArticleSource};
+    use leptos::prelude::*;
+    use uuid::Uuid;
+
+    fn article(src: &str, article_source: ArticleSource) -> HomePageArticleDto {
+        HomePageArticleDto {
+            title: RwSignal::new(src.to_string()),
+            thumbnail_url: RwSignal::new(String::new()),
+            src: RwSignal::new(src.to_string()),
+            category: Vec::new(),
+            first_published_at: RwSignal::new(String::new()),
+            article_source,
+            featured: false,
+            emoji: None,
+            also_on: Vec::new(),
+        }
+    }
+
+    fn featured_item(slug: Option<&str>, external_url: Option<&str>) -> FeaturedItem {
+        FeaturedItem {
+            id: Uuid::nil(),
+            article_id: slug.map(|_| Uuid::nil()),
+            article_slug: slug.map(str::to_string),
+            external_url: external_url.map(str::to_string),
+            position: 1,
+            expires_at: None,
+        }
+    }
+
+    fn srcs(articles: &[HomePageArticleDto]) -> Vec<(String, bool)> {
+        articles
+            .iter()
+            .map(|a| (a.src.get_untracked(), a.featured))
+            .collect()
+    }
+
+    // RwSignalを使うためリアクティブなOwnerの中で実行する
+    #[test]
+    fn おすすめの記事が指定の順で先頭に移動すること() {
+        Owner::new().with(|| {
+            let articles = vec![
+                article("https://qiita.com/Romira/items/a", ArticleSource::Qiita),
+                article("/articles/local", ArticleSource::Local),
+                article("https://prtimes.jp/b/", ArticleSource::WordPress),
+            ];
+            let featured = vec![
+                featured_item(None, Some("https://prtimes.jp/b")),
+                featured_item(Some("local"), None),
+            ];
+
+            let result = pin_featured_articles(articles, &featured);
+
+            assert_eq!(
+                srcs(&result),
+                vec![
+                    ("https://prtimes.jp/b/".to_string(), true),
+                    ("/articles/local".to_string(), true),
+                    ("https://qiita.com/Romira/items/a".to_string(), false),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn 他の掲載先にまとめた外部記事のおすすめはまとめた先の記事を移動すること() {
+        Owner::new().with(|| {
+            let mut local = article("/articles/local", ArticleSource::Local);
+            local.also_on = vec![AlsoOnDto {
+                article_source: ArticleSource::Qiita,
+                url: "https://qiita.com/Romira/items/a".to_string(),
+            }];
+            let articles = vec![article("/articles/other", ArticleSource::Local), local];
+            let featured = vec![featured_item(
+                None,
+                Some("https://qiita.com/Romira/items/a/"),
+            )];
+
+            let result = pin_featured_articles(articles, &featured);
+
+            assert_eq!(
+                srcs(&result),
+                vec![
+                    ("/articles/local".to_string(), true),
+                    ("/articles/other".to_string(), false),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn 記事一覧にないおすすめは無視されること() {
+        Owner::new().with(|| {
+            let articles = vec![article("/articles/local", ArticleSource::Local)];
+            let featured = vec![
+                featured_item(Some("unlisted"), None),
+                featured_item(None, Some("https://qiita.com/Romira/items/deleted")),
+            ];
+
+            let result = pin_featured_articles(articles, &featured);
+
+            assert_eq!(srcs(&result), vec![("/articles/local".to_string(), false)]);
+        });
+    }
+}
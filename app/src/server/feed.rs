@@ -0,0 +1,406 @@
+//! RSS 2.0 / Atom / JSON Feed の生成
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::constants::ORIGIN;
+use crate::server::utils::xml::escape_xml;
+
+/// フィードに含める記事の最大件数
+pub(crate) const FEED_ENTRY_LIMIT: usize = 20;
+
+/// フィードのメタデータ
+#[derive(Debug, Clone)]
+pub(crate) struct FeedMeta {
+    pub(crate) title: String,
+    pub(crate) description: String,
+    /// フィードの対象ページのURL
+    pub(crate) home_page_url: String,
+    /// 各形式のフィードURLの拡張子前までのパス（例: `/categories/rust/`）
+    pub(crate) feed_path_prefix: String,
+}
+
+/// フィードの1記事
+#[derive(Debug, Clone)]
+pub(crate) struct FeedEntry {
+    pub(crate) url: String,
+    pub(crate) title: String,
+    pub(crate) summary: Option<String>,
+    /// サニタイズ済みの本文HTML（URLは絶対URL）
+    pub(crate) content_html: String,
+    pub(crate) categories: Vec<String>,
+    pub(crate) cover_image_url: Option<String>,
+    /// 公開日時（UTC）
+    pub(crate) published_at: NaiveDateTime,
+    /// 更新日時（UTC）
+    pub(crate) updated_at: NaiveDateTime,
+}
+
+impl FeedEntry {
+    /// フィード上の更新日時（予約投稿では公開日時が更新日時より後になる）
+    fn effective_updated_at(&self) -> NaiveDateTime {
+        self.updated_at.max(self.published_at)
+    }
+}
+
+/// フィード全体の更新日時（記事がなければ `None`）
+fn feed_updated_at(entries: &[FeedEntry]) -> Option<NaiveDateTime> {
+    entries.iter().map(FeedEntry::effective_updated_at).max()
+}
+
+/// 本文HTML中のルート相対URL（`href="/..."` / `src="/..."`）を絶対URLにする
+pub(crate) fn absolutize_urls(html: &str) -> String {
+    html.replace("href=\"/", &format!("href=\"{ORIGIN}/"))
+        .replace("src=\"/", &format!("src=\"{ORIGIN}/"))
+}
+
+/// 画像URLの拡張子からMIMEタイプを推定（不明ならJPEG）
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("svg") => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+fn to_rfc2822(datetime: NaiveDateTime) -> String {
+    datetime.and_utc().to_rfc2822()
+}
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    datetime
+        .and_utc()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// RSS 2.0 を生成
+pub(crate) fn render_rss(meta: &FeedMeta, entries: &[FeedEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+         xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n\
+         \x20 <channel>\n",
+    );
+    xml.push_str(&format!(
+        "    <title>{}</title>\n\
+         \x20   <link>{}</link>\n\
+         \x20   <description>{}</description>\n\
+         \x20   <language>ja</language>\n\
+         \x20   <atom:link href=\"{}{}feed.xml\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&meta.title),
+        escape_xml(&meta.home_page_url),
+        escape_xml(&meta.description),
+        ORIGIN,
+        escape_xml(&meta.feed_path_prefix),
+    ));
+    if let Some(updated_at) = feed_updated_at(entries) {
+        xml.push_str(&format!(
+            "    <lastBuildDate>{}</lastBuildDate>\n",
+            to_rfc2822(updated_at)
+        ));
+    }
+
+    for entry in entries {
+        xml.push_str(&format!(
+            "    <item>\n\
+             \x20     <title>{}</title>\n\
+             \x20     <link>{url}</link>\n\
+             \x20     <guid isPermaLink=\"true\">{url}</guid>\n\
+             \x20     <pubDate>{}</pubDate>\n",
+            escape_xml(&entry.title),
+            to_rfc2822(entry.published_at),
+            url = escape_xml(&entry.url),
+        ));
+        if let Some(summary) = entry.summary.as_deref() {
+            xml.push_str(&format!(
+                "      <description>{}</description>\n",
+                escape_xml(summary)
+            ));
+        }
+        xml.push_str(&format!(
+            "      <content:encoded>{}</content:encoded>\n",
+            escape_xml(&entry.content_html)
+        ));
+        for category in &entry.categories {
+            xml.push_str(&format!(
+                "      <category>{}</category>\n",
+                escape_xml(category)
+            ));
+        }
+        if let Some(cover_image_url) = entry.cover_image_url.as_deref() {
+            // 画像サイズは不明なため length は 0 とする
+            xml.push_str(&format!(
+                "      <enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+                escape_xml(cover_image_url),
+                image_mime_type(cover_image_url)
+            ));
+        }
+        xml.push_str("    </item>\n");
+    }
+
+    xml.push_str("  </channel>\n</rss>\n");
+    xml
+}
+
+/// Atom を生成
+pub(crate) fn render_atom(meta: &FeedMeta, entries: &[FeedEntry]) -> String {
+    // 記事がない場合でも updated は必須のため、Unix epoch を使う
+    let updated_at = feed_updated_at(entries).unwrap_or_default();
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"ja\">\n",
+    );
+    xml.push_str(&format!(
+        "  <id>{origin}{prefix}atom.xml</id>\n\
+         \x20 <title>{}</title>\n\
+         \x20 <subtitle>{}</subtitle>\n\
+         \x20 <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n\
+         \x20 <link rel=\"self\" type=\"application/atom+xml\" href=\"{origin}{prefix}atom.xml\"/>\n\
+         \x20 <updated>{}</updated>\n\
+         \x20 <author><name>Romira</name><uri>{origin}</uri></author>\n",
+        escape_xml(&meta.title),
+        escape_xml(&meta.description),
+        escape_xml(&meta.home_page_url),
+        to_rfc3339(updated_at),
+        origin = ORIGIN,
+        prefix = escape_xml(&meta.feed_path_prefix),
+    ));
+
+    for entry in entries {
+        xml.push_str(&format!(
+            "  <entry>\n\
+             \x20   <id>{url}</id>\n\
+             \x20   <title>{}</title>\n\
+             \x20   <link rel=\"alternate\" type=\"text/html\" href=\"{url}\"/>\n\
+             \x20   <published>{}</published>\n\
+             \x20   <updated>{}</updated>\n",
+            escape_xml(&entry.title),
+            to_rfc3339(entry.published_at),
+            to_rfc3339(entry.effective_updated_at()),
+            url = escape_xml(&entry.url),
+        ));
+        if let Some(summary) = entry.summary.as_deref() {
+            xml.push_str(&format!("    <summary>{}</summary>\n", escape_xml(summary)));
+        }
+        xml.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_xml(&entry.content_html)
+        ));
+        for category in &entry.categories {
+            xml.push_str(&format!(
+                "    <category term=\"{}\"/>\n",
+                escape_xml(category)
+            ));
+        }
+        if let Some(cover_image_url) = entry.cover_image_url.as_deref() {
+            xml.push_str(&format!(
+                "    <link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>\n",
+                image_mime_type(cover_image_url),
+                escape_xml(cover_image_url)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// JSON Feed 1.1 のトップレベル
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    description: &'a str,
+    language: &'static str,
+    authors: [JsonFeedAuthor; 1],
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor {
+    name: &'static str,
+    url: &'static str,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    content_html: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    date_published: String,
+    date_modified: String,
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<JsonFeedAttachment<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAttachment<'a> {
+    url: &'a str,
+    mime_type: &'static str,
+}
+
+/// JSON Feed 1.1 を生成
+pub(crate) fn render_json_feed(meta: &FeedMeta, entries: &[FeedEntry]) -> String {
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &meta.title,
+        home_page_url: &meta.home_page_url,
+        feed_url: format!("{}{}feed.json", ORIGIN, meta.feed_path_prefix),
+        description: &meta.description,
+        language: "ja",
+        authors: [JsonFeedAuthor {
+            name: "Romira",
+            url: ORIGIN,
+        }],
+        items: entries
+            .iter()
+            .map(|entry| JsonFeedItem {
+                id: &entry.url,
+                url: &entry.url,
+                title: &entry.title,
+                content_html: &entry.content_html,
+                summary: entry.summary.as_deref(),
+                image: entry.cover_image_url.as_deref(),
+                date_published: to_rfc3339(entry.published_at),
+                date_modified: to_rfc3339(entry.effective_updated_at()),
+                tags: &entry.categories,
+                attachments: entry
+                    .cover_image_url
+                    .as_deref()
+                    .map(|url| JsonFeedAttachment {
+                        url,
+                        mime_type: image_mime_type(url),
+                    })
+                    .into_iter()
+                    .collect(),
+            })
+            .collect(),
+    };
+    serde_json::to_string(&feed).expect("Failed to serialize JSON Feed")
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn example_meta() -> FeedMeta {
+        FeedMeta {
+            title: "Blog & Notes".to_string(),
+            description: "説明".to_string(),
+            home_page_url: ORIGIN.to_string(),
+            feed_path_prefix: "/".to_string(),
+        }
+    }
+
+    fn example_entry() -> FeedEntry {
+        FeedEntry {
+            url: format!("{ORIGIN}/articles/hello"),
+            title: "Hello <World>".to_string(),
+            summary: Some("概要".to_string()),
+            content_html: "<p>本文 &amp; <a href=\"https://example.com\">リンク</a></p>"
+                .to_string(),
+            categories: vec!["Rust".to_string()],
+            cover_image_url: Some("https://blog-romira.imgix.net/a/cover.png".to_string()),
+            published_at: parse_datetime("2025-01-01 00:00:00"),
+            updated_at: parse_datetime("2025-01-02 03:04:05"),
+        }
+    }
+
+    #[test]
+    fn rssに本文カテゴリカバー画像と日時が含まれること() {
+        let xml = render_rss(&example_meta(), &[example_entry()]);
+
+        assert!(xml.contains("<title>Blog &amp; Notes</title>"), "{xml}");
+        assert!(xml.contains("<title>Hello &lt;World&gt;</title>"), "{xml}");
+        assert!(
+            xml.contains("<content:encoded>&lt;p&gt;本文 &amp;amp; &lt;a href=&quot;https://example.com&quot;&gt;"),
+            "{xml}"
+        );
+        assert!(xml.contains("<category>Rust</category>"), "{xml}");
+        assert!(
+            xml.contains(r#"<enclosure url="https://blog-romira.imgix.net/a/cover.png" length="0" type="image/png"/>"#),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<pubDate>Wed, 1 Jan 2025 00:00:00 +0000</pubDate>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<lastBuildDate>Thu, 2 Jan 2025 03:04:05 +0000</lastBuildDate>"),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn atomのupdatedは予約投稿では公開日時になること() {
+        let mut entry = example_entry();
+        entry.published_at = parse_datetime("2025-02-01 00:00:00");
+        let xml = render_atom(&example_meta(), &[entry]);
+
+        assert!(
+            xml.contains("<published>2025-02-01T00:00:00Z</published>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("  <updated>2025-02-01T00:00:00Z</updated>\n  <author>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("    <updated>2025-02-01T00:00:00Z</updated>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains(r#"<link rel="enclosure" type="image/png" href="https://blog-romira.imgix.net/a/cover.png"/>"#),
+            "{xml}"
+        );
+        assert!(xml.contains(r#"<category term="Rust"/>"#), "{xml}");
+    }
+
+    #[test]
+    fn json_feedが仕様どおりの構造になること() {
+        let json = render_json_feed(&example_meta(), &[example_entry()]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(value["feed_url"], format!("{ORIGIN}/feed.json"));
+        let item = &value["items"][0];
+        assert_eq!(item["id"], format!("{ORIGIN}/articles/hello"));
+        assert_eq!(item["date_published"], "2025-01-01T00:00:00Z");
+        assert_eq!(item["date_modified"], "2025-01-02T03:04:05Z");
+        assert_eq!(item["tags"], serde_json::json!(["Rust"]));
+        assert_eq!(item["attachments"][0]["mime_type"], "image/png");
+    }
+
+    #[test]
+    fn absolutize_urlsでルート相対urlが絶対urlになること() {
+        let html = r#"<a href="/articles/foo">a</a><img src="/img.png"><a href="https://example.com/">b</a>"#;
+        assert_eq!(
+            absolutize_urls(html),
+            format!(
+                r#"<a href="{ORIGIN}/articles/foo">a</a><img src="{ORIGIN}/img.png"><a href="https://example.com/">b</a>"#
+            )
+        );
+    }
+}
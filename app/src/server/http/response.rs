@@ -59,6 +59,25 @@ pub(crate) fn set_article_page_cache_control(response: &ResponseOptions, slug: &
     }
 }
 
+/// 公開範囲が `public` でない記事ページ用のヘッダー設定
+///
+/// CDNやブラウザの共有キャッシュに載せず、検索エンジンにもインデックスさせない。
+#[instrument(skip(response))]
+pub(crate) fn set_private_article_page_cache_control(response: &ResponseOptions) {
+    response.insert_header(
+        CACHE_CONTROL,
+        HeaderValue::from_static("private, no-store, max-age=0"),
+    );
+    response.insert_header(
+        CDN_CACHE_CONTROL,
+        HeaderValue::from_static("private, no-store, max-age=0"),
+    );
+    response.insert_header(
+        HeaderName::from_static("x-robots-tag"),
+        HeaderValue::from_static("noindex"),
+    );
+}
+
 #[instrument(skip(response))]
 pub(crate) fn set_preview_article_page_cache_control(response: &ResponseOptions) {
     response.insert_header(
@@ -1,6 +1,14 @@
+pub(crate) mod article_seo;
+pub(crate) mod article_syndication;
+pub(crate) mod article_visibility;
+pub(crate) mod external_article;
+pub(crate) mod external_source_status;
+pub(crate) mod featured_item;
 pub(crate) mod local_article;
 pub(crate) mod newt_article;
 pub(crate) mod newt_author;
 pub(crate) mod qiita_article;
+pub(crate) mod qiita_import;
 pub(crate) mod word_press_article;
 pub(crate) mod word_press_category;
+pub(crate) mod zenn_article;
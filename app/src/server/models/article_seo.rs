@@ -0,0 +1,30 @@
+//! 記事のSEO設定と管理画面の入出力との変換
+
+use crate::common::handlers::admin::ArticleSeoData;
+use blog_romira_dev_cms::ArticleSeo;
+
+impl From<ArticleSeo> for ArticleSeoData {
+    fn from(value: ArticleSeo) -> Self {
+        Self {
+            canonical_url: value.canonical_url,
+            robots: value.robots,
+            og_title: value.og_title,
+            og_description: value.og_description,
+            og_image_url: value.og_image_url,
+            twitter_card: value.twitter_card,
+        }
+    }
+}
+
+impl From<ArticleSeoData> for ArticleSeo {
+    fn from(value: ArticleSeoData) -> Self {
+        Self {
+            canonical_url: value.canonical_url,
+            robots: value.robots,
+            og_title: value.og_title,
+            og_description: value.og_description,
+            og_image_url: value.og_image_url,
+            twitter_card: value.twitter_card,
+        }
+    }
+}
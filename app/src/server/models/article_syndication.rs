@@ -0,0 +1,23 @@
+//! 公開記事の転載状況と管理画面の出力との変換
+
+use crate::common::handlers::admin::SyndicationStatusData;
+use crate::constants::{DATE_TIME_DISPLAY_FORMAT, HOUR, JST_TZ};
+use blog_romira_dev_cms::ArticleSyndication;
+use chrono::FixedOffset;
+
+impl From<ArticleSyndication> for SyndicationStatusData {
+    fn from(syndication: ArticleSyndication) -> Self {
+        Self {
+            target: syndication.target,
+            remote_url: syndication.remote_url,
+            last_error: syndication.last_error,
+            synced_at: syndication.synced_at.map(|synced_at| {
+                synced_at
+                    .and_utc()
+                    .with_timezone(&FixedOffset::east_opt(JST_TZ * HOUR).unwrap())
+                    .format(DATE_TIME_DISPLAY_FORMAT)
+                    .to_string()
+            }),
+        }
+    }
+}
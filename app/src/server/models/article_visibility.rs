@@ -0,0 +1,24 @@
+//! 公開記事の公開範囲と管理画面の入出力との変換
+
+use crate::common::handlers::admin::ArticleVisibilityData;
+use blog_romira_dev_cms::ArticleVisibility;
+
+impl From<ArticleVisibility> for ArticleVisibilityData {
+    fn from(value: ArticleVisibility) -> Self {
+        match value {
+            ArticleVisibility::Public => Self::Public,
+            ArticleVisibility::Unlisted => Self::Unlisted,
+            ArticleVisibility::Protected => Self::Protected,
+        }
+    }
+}
+
+impl From<ArticleVisibilityData> for ArticleVisibility {
+    fn from(value: ArticleVisibilityData) -> Self {
+        match value {
+            ArticleVisibilityData::Public => Self::Public,
+            ArticleVisibilityData::Unlisted => Self::Unlisted,
+            ArticleVisibilityData::Protected => Self::Protected,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::common::dto::{ArticleSource, HomePageArticleDto};
+use crate::constants::{DATE_DISPLAY_FORMAT, HOUR, JST_TZ};
+use chrono::{DateTime, FixedOffset, Utc};
+use leptos::prelude::RwSignal;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// 外部記事ソースから取得した記事（ソースによらない共通形式）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ExternalArticle {
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) thumbnail_url: String,
+    pub(crate) categories: Vec<String>,
+    pub(crate) published_at: DateTime<Utc>,
+    pub(crate) source: ArticleSource,
+    pub(crate) emoji: Option<String>,
+}
+
+impl From<ExternalArticle> for HomePageArticleDto {
+    #[instrument]
+    fn from(value: ExternalArticle) -> Self {
+        Self {
+            title: RwSignal::new(value.title),
+            thumbnail_url: RwSignal::new(value.thumbnail_url),
+            src: RwSignal::new(value.url),
+            category: value.categories.into_iter().map(RwSignal::new).collect(),
+            first_published_at: RwSignal::new(
+                value
+                    .published_at
+                    .with_timezone(&FixedOffset::east_opt(JST_TZ * HOUR).unwrap())
+                    .format(DATE_DISPLAY_FORMAT)
+                    .to_string(),
+            ),
+            article_source: value.source,
+            featured: false,
+            emoji: value.emoji,
+            also_on: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! 外部記事ソースの更新状況と管理画面の出力との変換
+
+use crate::common::handlers::admin::ExternalSourceStatusData;
+use crate::constants::{DATE_TIME_DISPLAY_FORMAT, HOUR, JST_TZ};
+use crate::server::services::external_article_cache::SourceRefreshStatus;
+use chrono::{DateTime, FixedOffset, Utc};
+
+fn to_jst_display(at: DateTime<Utc>) -> String {
+    at.with_timezone(&FixedOffset::east_opt(JST_TZ * HOUR).unwrap())
+        .format(DATE_TIME_DISPLAY_FORMAT)
+        .to_string()
+}
+
+impl From<(String, SourceRefreshStatus)> for ExternalSourceStatusData {
+    fn from((name, status): (String, SourceRefreshStatus)) -> Self {
+        Self {
+            name,
+            last_attempt_at: status.last_attempt_at.map(to_jst_display),
+            last_success_at: status.last_success_at.map(to_jst_display),
+            last_error: status.last_error,
+            article_count: status.article_count,
+            paused_until: status.paused_until.map(to_jst_display),
+        }
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn 日時をjstの表示用文字列にすること() {
+        let status = SourceRefreshStatus {
+            last_attempt_at: Some(Utc.with_ymd_and_hms(2026, 10, 1, 15, 30, 0).unwrap()),
+            last_success_at: None,
+            last_error: Some("Failed to api response status code: 500".to_string()),
+            article_count: 3,
+            consecutive_failures: 3,
+            paused_until: Some(Utc.with_ymd_and_hms(2026, 10, 1, 16, 0, 0).unwrap()),
+        };
+
+        let data = ExternalSourceStatusData::from(("Qiita".to_string(), status));
+
+        assert_eq!(
+            data,
+            ExternalSourceStatusData {
+                name: "Qiita".to_string(),
+                last_attempt_at: Some("2026年10月02日 00:30".to_string()),
+                last_success_at: None,
+                last_error: Some("Failed to api response status code: 500".to_string()),
+                article_count: 3,
+                paused_until: Some("2026年10月02日 01:00".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! おすすめの項目と管理画面の入出力との変換
+
+use crate::common::handlers::admin::FeaturedItemData;
+use crate::constants::{HOUR, JST_TZ};
+use blog_romira_dev_cms::{CmsError, FeaturedItem, FeaturedItemEntry};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use uuid::Uuid;
+
+/// 表示期限の日付の形式
+const EXPIRES_ON_FORMAT: &str = "%Y-%m-%d";
+
+/// 表示期限の日付（JST）の翌日0時（JST）をUTCで返す
+fn expires_at_from_date(date: NaiveDate) -> NaiveDateTime {
+    (date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap()
+        - Duration::seconds((JST_TZ * HOUR) as i64)
+}
+
+/// UTCの表示期限を、最後に表示する日（JST）にする
+fn date_from_expires_at(expires_at: NaiveDateTime) -> NaiveDate {
+    (expires_at + Duration::seconds((JST_TZ * HOUR) as i64) - Duration::seconds(1)).date()
+}
+
+impl From<FeaturedItem> for FeaturedItemData {
+    fn from(value: FeaturedItem) -> Self {
+        Self {
+            article_id: value.article_id.map(|id| id.to_string()),
+            external_url: value.external_url,
+            expires_on: value.expires_at.map(|at| {
+                date_from_expires_at(at)
+                    .format(EXPIRES_ON_FORMAT)
+                    .to_string()
+            }),
+        }
+    }
+}
+
+impl TryFrom<FeaturedItemData> for FeaturedItemEntry {
+    type Error = CmsError;
+
+    fn try_from(value: FeaturedItemData) -> Result<Self, Self::Error> {
+        let article_id = value
+            .article_id
+            .filter(|id| !id.is_empty())
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|_| CmsError::ValidationError("おすすめの記事IDが不正です".to_string()))?;
+        let expires_at = value
+            .expires_on
+            .filter(|date| !date.is_empty())
+            .map(|date| NaiveDate::parse_from_str(&date, EXPIRES_ON_FORMAT))
+            .transpose()
+            .map_err(|_| {
+                CmsError::ValidationError("おすすめの表示期限が不正な日付です".to_string())
+            })?
+            .map(expires_at_from_date);
+        FeaturedItemEntry::new(article_id, value.external_url, expires_at)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn 表示期限は指定日の翌日0時jstになること() {
+        let data = FeaturedItemData {
+            article_id: None,
+            external_url: Some("https://qiita.com/Romira/items/abc".to_string()),
+            expires_on: Some("2026-11-01".to_string()),
+        };
+
+        let entry = FeaturedItemEntry::try_from(data).unwrap();
+
+        assert_eq!(
+            entry.expires_at(),
+            Some(
+                NaiveDate::from_ymd_opt(2026, 11, 1)
+                    .unwrap()
+                    .and_hms_opt(15, 0, 0)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            date_from_expires_at(entry.expires_at().unwrap()),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn 不正な記事idや日付はバリデーションエラーになること() {
+        let invalid_id = FeaturedItemData {
+            article_id: Some("not-a-uuid".to_string()),
+            ..Default::default()
+        };
+        let invalid_date = FeaturedItemData {
+            external_url: Some("https://example.com".to_string()),
+            expires_on: Some("2026/11/01".to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            FeaturedItemEntry::try_from(invalid_id),
+            Err(CmsError::ValidationError(_))
+        ));
+        assert!(matches!(
+            FeaturedItemEntry::try_from(invalid_date),
+            Err(CmsError::ValidationError(_))
+        ));
+    }
+}
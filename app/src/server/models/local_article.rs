@@ -1,17 +1,23 @@
 //! cmsクレートのモデルからDTOへの変換
 
 use crate::common::dto::{
-    ArticleDetailDto, ArticleMetaDto, ArticlePageDto, ArticleSource, HomePageArticleDto,
+    ArticleDetailDto, ArticleLinkDto, ArticleMetaDto, ArticlePageDto, ArticleSource,
+    HomePageArticleDto,
 };
 use crate::common::imgix_url::{extract_base_url, generate_srcset, is_imgix_url};
-use crate::common::markdown::{convert_markdown_to_html, sanitize_html};
+use crate::common::markdown::{
+    RenderContext, convert_markdown_to_html_with_context, sanitize_html,
+};
 use crate::constants::{
-    COVER_IMAGE_WIDTHS, DATE_DISPLAY_FORMAT, DATE_ISO_FORMAT, HOUR, JST_TZ, THUMBNAIL_NO_IMAGE_URL,
+    COVER_IMAGE_WIDTHS, DATE_DISPLAY_FORMAT, DATE_ISO_FORMAT, DEFAULT_TWITTER_CARD, HOUR, JST_TZ,
+    ORIGIN, THUMBNAIL_NO_IMAGE_URL,
 };
 use crate::server::utils::url::{
     to_optimize_cover_image_url, to_optimize_og_image_url, to_optimize_thumbnail_url,
 };
-use blog_romira_dev_cms::{DraftArticleWithCategories, PublishedArticleWithCategories};
+use blog_romira_dev_cms::{
+    ArticleSeo, DraftArticleWithCategories, LinkedArticle, PublishedArticleWithCategories,
+};
 use chrono::{FixedOffset, NaiveDateTime, TimeZone, Utc};
 use leptos::prelude::RwSignal;
 use tracing::instrument;
@@ -46,14 +52,98 @@ impl From<PublishedArticleWithCategories> for HomePageArticleDto {
                     .to_string(),
             ),
             article_source: ArticleSource::Local,
+            featured: false,
+            emoji: None,
+            also_on: Vec::new(),
         }
     }
 }
 
-impl From<PublishedArticleWithCategories> for ArticlePageDto {
-    #[instrument(skip(value))]
-    fn from(value: PublishedArticleWithCategories) -> Self {
+impl From<LinkedArticle> for ArticleLinkDto {
+    fn from(value: LinkedArticle) -> Self {
+        Self {
+            title: value.title,
+            src: format!("/articles/{}", value.slug),
+        }
+    }
+}
+
+/// SEO設定で上書きできるメタ情報（未設定の項目は記事の内容から決める）
+struct SeoMeta {
+    canonical_url: RwSignal<String>,
+    robots: RwSignal<Option<String>>,
+    og_title: RwSignal<String>,
+    og_description: RwSignal<String>,
+    twitter_card: RwSignal<String>,
+}
+
+impl SeoMeta {
+    fn new(seo: ArticleSeo, slug: &str, title: &str, description: &str) -> Self {
+        Self {
+            canonical_url: RwSignal::new(
+                seo.canonical_url
+                    .unwrap_or_else(|| format!("{}/articles/{}", ORIGIN, slug)),
+            ),
+            robots: RwSignal::new(seo.robots),
+            og_title: RwSignal::new(seo.og_title.unwrap_or_else(|| title.to_string())),
+            og_description: RwSignal::new(
+                seo.og_description
+                    .unwrap_or_else(|| description.to_string()),
+            ),
+            twitter_card: RwSignal::new(
+                seo.twitter_card
+                    .unwrap_or_else(|| DEFAULT_TWITTER_CARD.to_string()),
+            ),
+        }
+    }
+}
+
+/// robotsメタタグの値に `noindex` を加える（`index` は取り除く）
+fn with_noindex(robots: Option<&str>) -> String {
+    let directives = robots
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| {
+            !directive.is_empty()
+                && !directive.eq_ignore_ascii_case("index")
+                && !directive.eq_ignore_ascii_case("noindex")
+        });
+    std::iter::once("noindex")
+        .chain(directives)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 本文のMarkdownを公開用のHTMLに変換
+pub(crate) fn render_body(markdown: &str, render_context: &RenderContext) -> String {
+    sanitize_html(
+        &convert_markdown_to_html_with_context(markdown, render_context),
+        &render_context.embed_providers,
+    )
+}
+
+impl ArticlePageDto {
+    /// 公開記事からDTOを生成（本文は `render_context` を参照して変換する）
+    #[instrument(skip(value, render_context, backlinks, related_articles))]
+    pub(crate) fn from_published(
+        value: PublishedArticleWithCategories,
+        render_context: &RenderContext,
+        backlinks: Vec<LinkedArticle>,
+        related_articles: Vec<PublishedArticleWithCategories>,
+    ) -> Self {
         let article = value.article;
+        let mut seo = article.seo.0;
+        // 一覧に載せない記事は検索エンジンにもインデックスさせない
+        if !article.visibility.is_public() {
+            seo.robots = Some(with_noindex(seo.robots.as_deref()));
+        }
+        let seo_meta = SeoMeta::new(
+            seo.clone(),
+            &article.slug,
+            &article.title,
+            article.description.as_deref().unwrap_or_default(),
+        );
         let title = RwSignal::new(article.title);
         let cover_image_raw = article
             .cover_image_url
@@ -65,9 +155,7 @@ impl From<PublishedArticleWithCategories> for ArticlePageDto {
         } else {
             String::new()
         });
-        let body = RwSignal::new(sanitize_html(&convert_markdown_to_html(
-            article.body.as_str(),
-        )));
+        let body = RwSignal::new(render_body(&article.body, render_context));
         let category: Vec<RwSignal<String>> = value
             .categories
             .iter()
@@ -83,15 +171,21 @@ impl From<PublishedArticleWithCategories> for ArticlePageDto {
             RwSignal::new(published_at_jst.format(DATE_ISO_FORMAT).to_string());
         let first_published_at_rfc3339 = RwSignal::new(published_at_jst.to_rfc3339());
 
+        // SEO設定の画像 → カバー画像 → 記事ごとに自動生成したOGP画像の順に使う
+        let og_image_url = RwSignal::new(match seo.og_image_url {
+            Some(url) => url,
+            None => match article
+                .cover_image_url
+                .as_deref()
+                .filter(|url| !url.is_empty())
+            {
+                Some(url) => to_optimize_og_image_url(url),
+                None => format!("{}/og/{}.png", ORIGIN, article.slug),
+            },
+        });
         let id = RwSignal::new(article.id.to_string());
         let slug = RwSignal::new(article.slug);
         let description = RwSignal::new(article.description.unwrap_or_default());
-        let og_image_url = RwSignal::new(to_optimize_og_image_url(
-            article
-                .cover_image_url
-                .as_deref()
-                .unwrap_or(THUMBNAIL_NO_IMAGE_URL),
-        ));
 
         Self {
             article_detail_dto: ArticleDetailDto {
@@ -102,6 +196,7 @@ impl From<PublishedArticleWithCategories> for ArticlePageDto {
                 category: category.clone(),
                 first_published_at,
                 first_published_at_iso,
+                backlinks: backlinks.into_iter().map(ArticleLinkDto::from).collect(),
             },
             article_meta_dto: ArticleMetaDto {
                 id,
@@ -112,15 +207,33 @@ impl From<PublishedArticleWithCategories> for ArticlePageDto {
                 og_image_url,
                 published_at: updated_at_rfc3339,
                 first_published_at: first_published_at_rfc3339,
+                canonical_url: seo_meta.canonical_url,
+                robots: seo_meta.robots,
+                og_title: seo_meta.og_title,
+                og_description: seo_meta.og_description,
+                twitter_card: seo_meta.twitter_card,
             },
+            related_articles: related_articles
+                .into_iter()
+                .map(HomePageArticleDto::from)
+                .collect(),
         }
     }
-}
 
-impl From<DraftArticleWithCategories> for ArticlePageDto {
-    #[instrument(skip(value))]
-    fn from(value: DraftArticleWithCategories) -> Self {
+    /// 下書き記事からプレビュー用のDTOを生成
+    #[instrument(skip(value, render_context))]
+    pub(crate) fn from_draft(
+        value: DraftArticleWithCategories,
+        render_context: &RenderContext,
+    ) -> Self {
         let article = value.article;
+        let seo = article.seo.0;
+        let seo_meta = SeoMeta::new(
+            seo.clone(),
+            &article.slug,
+            &article.title,
+            article.description.as_deref().unwrap_or_default(),
+        );
         let title = RwSignal::new(article.title);
         let cover_image_raw = article
             .cover_image_url
@@ -132,9 +245,7 @@ impl From<DraftArticleWithCategories> for ArticlePageDto {
         } else {
             String::new()
         });
-        let body = RwSignal::new(sanitize_html(&convert_markdown_to_html(
-            article.body.as_str(),
-        )));
+        let body = RwSignal::new(render_body(&article.body, render_context));
         let category: Vec<RwSignal<String>> = value
             .categories
             .iter()
@@ -153,12 +264,14 @@ impl From<DraftArticleWithCategories> for ArticlePageDto {
         let id = RwSignal::new(article.id.to_string());
         let slug = RwSignal::new(article.slug);
         let description = RwSignal::new(article.description.unwrap_or_default());
-        let og_image_url = RwSignal::new(to_optimize_og_image_url(
-            article
-                .cover_image_url
-                .as_deref()
-                .unwrap_or(THUMBNAIL_NO_IMAGE_URL),
-        ));
+        let og_image_url = RwSignal::new(seo.og_image_url.unwrap_or_else(|| {
+            to_optimize_og_image_url(
+                article
+                    .cover_image_url
+                    .as_deref()
+                    .unwrap_or(THUMBNAIL_NO_IMAGE_URL),
+            )
+        }));
 
         Self {
             article_detail_dto: ArticleDetailDto {
@@ -169,6 +282,7 @@ impl From<DraftArticleWithCategories> for ArticlePageDto {
                 category: category.clone(),
                 first_published_at,
                 first_published_at_iso,
+                backlinks: Vec::new(),
             },
             article_meta_dto: ArticleMetaDto {
                 id,
@@ -179,7 +293,13 @@ impl From<DraftArticleWithCategories> for ArticlePageDto {
                 og_image_url,
                 published_at: updated_at_rfc3339,
                 first_published_at: first_published_at_rfc3339,
+                canonical_url: seo_meta.canonical_url,
+                robots: seo_meta.robots,
+                og_title: seo_meta.og_title,
+                og_description: seo_meta.og_description,
+                twitter_card: seo_meta.twitter_card,
             },
+            related_articles: Vec::new(),
         }
     }
 }
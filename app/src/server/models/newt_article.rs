@@ -1,7 +1,8 @@
 use crate::common::dto::{ArticleDetailDto, ArticleMetaDto, ArticlePageDto};
 use crate::common::imgix_url::{extract_base_url, generate_srcset, is_imgix_url};
 use crate::constants::{
-    COVER_IMAGE_WIDTHS, DATE_DISPLAY_FORMAT, DATE_ISO_FORMAT, HOUR, JST_TZ, THUMBNAIL_NO_IMAGE_URL,
+    COVER_IMAGE_WIDTHS, DATE_DISPLAY_FORMAT, DATE_ISO_FORMAT, DEFAULT_TWITTER_CARD, HOUR, JST_TZ,
+    ORIGIN, THUMBNAIL_NO_IMAGE_URL,
 };
 use crate::server::utils::url::{to_optimize_cover_image_url, to_optimize_og_image_url};
 use chrono::{DateTime, FixedOffset, Utc};
@@ -28,6 +29,7 @@ pub(crate) struct NewtArticle {
 impl From<NewtArticle> for ArticlePageDto {
     #[instrument]
     fn from(value: NewtArticle) -> Self {
+        let og_title = RwSignal::new(value.title.clone());
         let title = RwSignal::new(value.title);
         let cover_image_raw = value.cover_image.as_ref().map_or_else(
             || THUMBNAIL_NO_IMAGE_URL,
@@ -76,13 +78,14 @@ impl From<NewtArticle> for ArticlePageDto {
         );
         let first_published_at_rfc3339 = RwSignal::new(first_published_at_date_time.to_rfc3339());
         let id = RwSignal::new(value.id);
+        let canonical_url = RwSignal::new(format!("{}/articles/{}", ORIGIN, value.slug));
         let slug = RwSignal::new(value.slug);
-        let description = RwSignal::new(
-            value
-                .meta
-                .as_ref()
-                .map_or_else(|| "".to_string(), |meta| meta.description.clone()),
-        );
+        let description_raw = value
+            .meta
+            .as_ref()
+            .map_or_else(|| "".to_string(), |meta| meta.description.clone());
+        let og_description = RwSignal::new(description_raw.clone());
+        let description = RwSignal::new(description_raw);
         let og_image_url =
             RwSignal::new(to_optimize_og_image_url(value.meta.as_ref().map_or_else(
                 || THUMBNAIL_NO_IMAGE_URL,
@@ -102,6 +105,7 @@ impl From<NewtArticle> for ArticlePageDto {
                 category: category.clone(),
                 first_published_at,
                 first_published_at_iso,
+                backlinks: Vec::new(),
             },
             article_meta_dto: ArticleMetaDto {
                 id,
@@ -112,7 +116,13 @@ impl From<NewtArticle> for ArticlePageDto {
                 og_image_url,
                 published_at: published_at_rfc3339,
                 first_published_at: first_published_at_rfc3339,
+                canonical_url,
+                robots: RwSignal::new(None),
+                og_title,
+                og_description,
+                twitter_card: RwSignal::new(DEFAULT_TWITTER_CARD.to_string()),
             },
+            related_articles: Vec::new(),
         }
     }
 }
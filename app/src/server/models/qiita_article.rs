@@ -1,7 +1,6 @@
-use crate::common::dto::{ArticleSource, HomePageArticleDto};
-use crate::constants::{DATE_DISPLAY_FORMAT, HOUR, JST_TZ};
-use chrono::{DateTime, FixedOffset, Utc};
-use leptos::prelude::RwSignal;
+use crate::common::dto::ArticleSource;
+use crate::server::models::external_article::ExternalArticle;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
@@ -33,30 +32,37 @@ pub(crate) struct QiitaArticle {
     pub og_image_url: String,
 }
 
-impl From<QiitaArticle> for HomePageArticleDto {
+impl From<QiitaArticle> for ExternalArticle {
     #[instrument]
     fn from(value: QiitaArticle) -> Self {
         Self {
-            title: RwSignal::new(value.title),
-            thumbnail_url: RwSignal::new(value.og_image_url),
-            src: RwSignal::new(value.url),
-            category: value
-                .tags
-                .iter()
-                .map(|tag| RwSignal::new(tag.name.clone()))
-                .collect(),
-            first_published_at: RwSignal::new(
-                value
-                    .created_at
-                    .with_timezone(&FixedOffset::east_opt(JST_TZ * HOUR).unwrap())
-                    .format(DATE_DISPLAY_FORMAT)
-                    .to_string(),
-            ),
-            article_source: ArticleSource::Qiita,
+            title: value.title,
+            url: value.url,
+            thumbnail_url: value.og_image_url,
+            categories: value.tags.into_iter().map(|tag| tag.name).collect(),
+            published_at: value.created_at,
+            source: ArticleSource::Qiita,
+            emoji: None,
         }
     }
 }
 
+/// Qiitaに投稿・更新する記事
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct QiitaItemInput {
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<Tag>,
+    pub private: bool,
+}
+
+/// 投稿・更新したQiitaの記事
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct PostedQiitaItem {
+    pub id: String,
+    pub url: String,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Group {
     pub created_at: String,
@@ -0,0 +1,33 @@
+//! Qiita記事の取り込み結果と管理画面の出力との変換
+
+use crate::common::handlers::admin::{ImportedDraftData, QiitaImportCandidateData};
+use crate::constants::{DATE_DISPLAY_FORMAT, HOUR, JST_TZ};
+use crate::server::services::qiita_import::{ImportedDraft, QiitaImportCandidate};
+use chrono::FixedOffset;
+
+impl From<QiitaImportCandidate> for QiitaImportCandidateData {
+    fn from(candidate: QiitaImportCandidate) -> Self {
+        let article = candidate.article;
+        Self {
+            created_at: article
+                .created_at
+                .with_timezone(&FixedOffset::east_opt(JST_TZ * HOUR).unwrap())
+                .format(DATE_DISPLAY_FORMAT)
+                .to_string(),
+            tags: article.tags.into_iter().map(|tag| tag.name).collect(),
+            id: article.id,
+            title: article.title,
+            url: article.url,
+            imported: candidate.imported,
+        }
+    }
+}
+
+impl From<ImportedDraft> for ImportedDraftData {
+    fn from(draft: ImportedDraft) -> Self {
+        Self {
+            id: draft.id.to_string(),
+            title: draft.title,
+        }
+    }
+}
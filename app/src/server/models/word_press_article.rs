@@ -1,8 +1,7 @@
-use crate::common::dto::{ArticleSource, HomePageArticleDto};
-use crate::constants::{DATE_DISPLAY_FORMAT, HOUR, JST_TZ};
+use crate::common::dto::ArticleSource;
+use crate::server::models::external_article::ExternalArticle;
 use crate::server::models::word_press_category::Category;
-use chrono::{FixedOffset, NaiveDateTime, TimeZone};
-use leptos::prelude::RwSignal;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
@@ -44,26 +43,21 @@ pub(crate) struct WordPressArticle {
     pub(crate) category_names: Vec<Category>,
 }
 
-impl From<WordPressArticle> for HomePageArticleDto {
+impl From<WordPressArticle> for ExternalArticle {
     #[instrument]
     fn from(value: WordPressArticle) -> Self {
         Self {
-            title: RwSignal::new(value.title.rendered),
-            thumbnail_url: RwSignal::new(value.jetpack_featured_media_url),
-            src: RwSignal::new(value.link),
-            category: value
+            title: value.title.rendered,
+            url: value.link,
+            thumbnail_url: value.jetpack_featured_media_url,
+            categories: value
                 .category_names
-                .iter()
-                .map(|category| RwSignal::new(category.name.clone()))
+                .into_iter()
+                .map(|category| category.name)
                 .collect(),
-            first_published_at: RwSignal::new(
-                FixedOffset::east_opt(JST_TZ * HOUR)
-                    .unwrap()
-                    .from_utc_datetime(&value.date)
-                    .format(DATE_DISPLAY_FORMAT)
-                    .to_string(),
-            ),
-            article_source: ArticleSource::WordPress,
+            published_at: value.date.and_utc(),
+            source: ArticleSource::WordPress,
+            emoji: None,
         }
     }
 }
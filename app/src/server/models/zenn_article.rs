@@ -0,0 +1,71 @@
+use crate::common::dto::ArticleSource;
+use crate::server::models::external_article::ExternalArticle;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// `GET /api/articles?username={user}` のレスポンス
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ZennArticleList {
+    pub(crate) articles: Vec<ZennArticle>,
+    /// 次のページ番号（最後のページでは `null`）
+    pub(crate) next_page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ZennArticle {
+    pub(crate) id: u64,
+    pub(crate) title: String,
+    pub(crate) slug: String,
+    pub(crate) emoji: String,
+    pub(crate) published_at: DateTime<FixedOffset>,
+    /// 本文の最終更新日時（更新されていなければ前回取得した詳細を使う）
+    #[serde(default)]
+    pub(crate) body_updated_at: Option<DateTime<FixedOffset>>,
+    /// 記事ページのパス（例: `/romira/articles/{slug}`）
+    pub(crate) path: String,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) url: String,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) topics: Vec<ZennTopic>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub(crate) og_image_url: String,
+}
+
+/// `GET /api/articles/{slug}` のレスポンス（一覧にないトピックとOG画像だけ使う）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ZennArticleDetailResponse {
+    pub(crate) article: ZennArticleDetail,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ZennArticleDetail {
+    #[serde(default)]
+    pub(crate) topics: Vec<ZennTopic>,
+    pub(crate) og_image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ZennTopic {
+    pub(crate) name: String,
+    pub(crate) display_name: String,
+}
+
+impl From<ZennArticle> for ExternalArticle {
+    #[instrument]
+    fn from(value: ZennArticle) -> Self {
+        Self {
+            title: value.title,
+            url: value.url,
+            thumbnail_url: value.og_image_url,
+            categories: value
+                .topics
+                .into_iter()
+                .map(|topic| topic.display_name)
+                .collect(),
+            published_at: value.published_at.with_timezone(&Utc),
+            source: ArticleSource::Zenn,
+            emoji: (!value.emoji.is_empty()).then_some(value.emoji),
+        }
+    }
+}
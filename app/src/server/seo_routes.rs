@@ -1,14 +1,26 @@
 use axum::Router;
-use axum::extract::State;
-use axum::http::header;
-use axum::response::IntoResponse;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use blog_romira_dev_cms::PublishedArticleService;
-use chrono::Utc;
+use blog_romira_dev_cms::{
+    ArticleVisibility, ImageService, PublishedArticleService, PublishedArticleWithCategories,
+};
+use chrono::NaiveDateTime;
+use std::collections::{BTreeMap, HashSet};
 
 use tracing::instrument;
 
-use crate::constants::ORIGIN;
+use crate::common::markdown::extract_imgix_image_urls;
+use crate::constants::{ORIGIN, WEB_APP_DESCRIPTION, WEB_APP_TITLE};
+use crate::server::feed::{
+    FEED_ENTRY_LIMIT, FeedEntry, FeedMeta, absolutize_urls, render_atom, render_json_feed,
+    render_rss,
+};
+use crate::server::models::local_article::render_body;
+use crate::server::services::article_render::{ArticleRenderService, gcs_path_from_imgix_url};
+use crate::server::services::og_image::{OgCardContent, OgImageService};
+use crate::server::sitemap::{SitemapRef, SitemapUrl, render_sitemap_index, render_urlset};
 
 use super::contexts::AppState;
 
@@ -28,51 +40,389 @@ async fn robots_txt() -> impl IntoResponse {
     ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body)
 }
 
-/// sitemap.xml を動的生成するハンドラ
+/// サイトマップのレスポンス（CDNキャッシュは `sitemap` タグでパージする）
+fn sitemap_response(xml: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (
+                header::CACHE_CONTROL,
+                "no-cache, must-revalidate, max-age=10, stale-while-revalidate=1296000",
+            ),
+            (
+                header::CDN_CACHE_CONTROL,
+                "max-age=1296000, stale-while-revalidate=1296000",
+            ),
+            (header::HeaderName::from_static("cache-tag"), "sitemap"),
+        ],
+        xml,
+    )
+        .into_response()
+}
+
+/// サイトマップ用に公開記事を取得（失敗した場合は空のサイトマップではなく500を返す）
+///
+/// SEO設定で `noindex` を指定した記事は含めない。
+async fn fetch_sitemap_articles(
+    published_article_service: &PublishedArticleService,
+) -> Result<Vec<PublishedArticleWithCategories>, Response> {
+    let articles = published_article_service.fetch_all().await.map_err(|err| {
+        tracing::error!(
+            error = err.to_string(),
+            "Failed to fetch articles for sitemap"
+        );
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+    Ok(articles
+        .into_iter()
+        .filter(|article| !article.article.seo.is_noindex())
+        .collect())
+}
+
+/// 記事の最終更新日時（予約投稿では公開日時が更新日時より後になる）
+fn article_lastmod(article: &PublishedArticleWithCategories) -> NaiveDateTime {
+    article.article.updated_at.max(article.article.published_at)
+}
+
+/// 記事のうち最も新しい更新日時
+fn newest_lastmod<'a>(
+    articles: impl IntoIterator<Item = &'a PublishedArticleWithCategories>,
+) -> Option<NaiveDateTime> {
+    articles.into_iter().map(article_lastmod).max()
+}
+
+/// 記事のあるカテゴリをスラッグ順に、そのカテゴリの記事の最新更新日時と合わせて集計
+fn categories_with_lastmod(
+    articles: &[PublishedArticleWithCategories],
+) -> BTreeMap<String, NaiveDateTime> {
+    let mut categories = BTreeMap::new();
+    for article in articles {
+        let lastmod = article_lastmod(article);
+        for category in &article.categories {
+            categories
+                .entry(category.slug.clone())
+                .and_modify(|current: &mut NaiveDateTime| *current = (*current).max(lastmod))
+                .or_insert(lastmod);
+        }
+    }
+    categories
+}
+
+/// サイトマップインデックスを返すハンドラ
+#[instrument(skip_all)]
+async fn sitemap_index(
+    State(published_article_service): State<PublishedArticleService>,
+) -> Response {
+    let articles = match fetch_sitemap_articles(&published_article_service).await {
+        Ok(articles) => articles,
+        Err(response) => return response,
+    };
+    let newest = newest_lastmod(&articles);
+    let categories_newest = categories_with_lastmod(&articles).into_values().max();
+
+    sitemap_response(render_sitemap_index(&[
+        SitemapRef {
+            loc: format!("{}/sitemaps/pages.xml", ORIGIN),
+            lastmod: newest,
+        },
+        SitemapRef {
+            loc: format!("{}/sitemaps/articles.xml", ORIGIN),
+            lastmod: newest,
+        },
+        SitemapRef {
+            loc: format!("{}/sitemaps/categories.xml", ORIGIN),
+            lastmod: categories_newest,
+        },
+    ]))
+}
+
+/// 固定ページのサイトマップを返すハンドラ
 #[instrument(skip_all)]
-async fn sitemap_xml(
+async fn pages_sitemap(
     State(published_article_service): State<PublishedArticleService>,
-) -> impl IntoResponse {
-    let today = Utc::now().format("%Y-%m-%d").to_string();
+) -> Response {
+    let articles = match fetch_sitemap_articles(&published_article_service).await {
+        Ok(articles) => articles,
+        Err(response) => return response,
+    };
 
-    let mut xml = String::from(
-        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
-         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    // トップページは記事一覧なので、最新の記事の更新日時を lastmod とする
+    sitemap_response(render_urlset(&[SitemapUrl {
+        loc: format!("{}/", ORIGIN),
+        lastmod: newest_lastmod(&articles),
+        changefreq: Some("daily"),
+        priority: Some("1.0"),
+        images: Vec::new(),
+    }]))
+}
+
+/// 記事のサイトマップを返すハンドラ
+///
+/// カバー画像と、本文中の画像のうち画像ライブラリに登録されているものを `image:image` に含める。
+#[instrument(skip_all)]
+async fn articles_sitemap(
+    State(published_article_service): State<PublishedArticleService>,
+    State(image_service): State<ImageService>,
+) -> Response {
+    let articles = match fetch_sitemap_articles(&published_article_service).await {
+        Ok(articles) => articles,
+        Err(response) => return response,
+    };
+
+    let body_images: Vec<Vec<String>> = articles
+        .iter()
+        .map(|article| extract_imgix_image_urls(&article.article.body))
+        .collect();
+    let gcs_paths: Vec<String> = body_images
+        .iter()
+        .flatten()
+        .filter_map(|url| gcs_path_from_imgix_url(url).map(str::to_string))
+        .collect();
+    let registered_gcs_paths: HashSet<String> =
+        match image_service.fetch_by_gcs_paths(&gcs_paths).await {
+            Ok(images) => images.into_iter().map(|image| image.gcs_path).collect(),
+            Err(err) => {
+                tracing::error!(
+                    error = err.to_string(),
+                    "Failed to fetch images for sitemap"
+                );
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+    let urls: Vec<SitemapUrl> = articles
+        .iter()
+        .zip(body_images)
+        .map(|(article, body_images)| {
+            let mut images: Vec<String> = article.article.cover_image_url.iter().cloned().collect();
+            for url in body_images {
+                let registered = gcs_path_from_imgix_url(&url)
+                    .is_some_and(|gcs_path| registered_gcs_paths.contains(gcs_path));
+                if registered && !images.contains(&url) {
+                    images.push(url);
+                }
+            }
+            SitemapUrl {
+                loc: format!("{}/articles/{}", ORIGIN, article.article.slug),
+                lastmod: Some(article_lastmod(article)),
+                changefreq: Some("monthly"),
+                priority: Some("0.8"),
+                images,
+            }
+        })
+        .collect();
+
+    sitemap_response(render_urlset(&urls))
+}
+
+/// カテゴリページのサイトマップを返すハンドラ
+#[instrument(skip_all)]
+async fn categories_sitemap(
+    State(published_article_service): State<PublishedArticleService>,
+) -> Response {
+    let articles = match fetch_sitemap_articles(&published_article_service).await {
+        Ok(articles) => articles,
+        Err(response) => return response,
+    };
+
+    let urls: Vec<SitemapUrl> = categories_with_lastmod(&articles)
+        .into_iter()
+        .map(|(slug, lastmod)| SitemapUrl {
+            loc: format!("{}/categories/{}", ORIGIN, slug),
+            lastmod: Some(lastmod),
+            changefreq: Some("weekly"),
+            priority: Some("0.5"),
+            images: Vec::new(),
+        })
+        .collect();
+
+    sitemap_response(render_urlset(&urls))
+}
+
+/// 記事のOGP画像（`/og/{slug}.png`）を返すハンドラ
+///
+/// 記事の更新時に `article:{slug}` でパージされるよう、記事ページと同じキャッシュタグを付ける。
+#[instrument(skip(published_article_service, og_image_service))]
+async fn og_image(
+    Path(file_name): Path<String>,
+    State(published_article_service): State<PublishedArticleService>,
+    State(og_image_service): State<OgImageService>,
+) -> Response {
+    let Some(slug) = file_name.strip_suffix(".png") else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let article = match published_article_service.fetch_by_slug(slug).await {
+        // パスワード保護された記事はタイトルを見せないため生成しない
+        Ok(Some(article)) if article.article.visibility != ArticleVisibility::Protected => article,
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            tracing::error!(
+                error = err.to_string(),
+                "Failed to fetch article for OG image"
+            );
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let content = OgCardContent {
+        title: article.article.title,
+        categories: article
+            .categories
+            .into_iter()
+            .map(|category| category.name)
+            .collect(),
+    };
+    let image = match og_image_service.render(&content).await {
+        Ok(image) => image,
+        Err(err) => {
+            tracing::error!(error = err.to_string(), "Failed to render OG image");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/png"));
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(
+            "no-cache, must-revalidate, max-age=10, stale-while-revalidate=1296000",
+        ),
+    );
+    headers.insert(
+        header::CDN_CACHE_CONTROL,
+        HeaderValue::from_static("max-age=1296000, stale-while-revalidate=1296000"),
     );
+    if let Ok(value) = HeaderValue::from_str(&format!("article:{slug}")) {
+        headers.insert(header::HeaderName::from_static("cache-tag"), value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!("\"{}\"", image.content_hash)) {
+        headers.insert(header::ETAG, value);
+    }
+    (headers, image.png).into_response()
+}
+
+/// フィードの形式
+#[derive(Debug, Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
 
-    // トップページ
-    xml.push_str(&format!(
-        "  <url>\n\
-         \x20   <loc>{}</loc>\n\
-         \x20   <lastmod>{}</lastmod>\n\
-         \x20   <changefreq>daily</changefreq>\n\
-         \x20   <priority>1.0</priority>\n\
-         \x20 </url>\n",
-        ORIGIN, today
-    ));
-
-    // 公開記事一覧
-    if let Ok(articles) = published_article_service.fetch_all().await {
-        for article_with_cats in &articles {
-            let article = &article_with_cats.article;
-            let lastmod = article.updated_at.format("%Y-%m-%d");
-            xml.push_str(&format!(
-                "  <url>\n\
-                 \x20   <loc>{}/articles/{}</loc>\n\
-                 \x20   <lastmod>{}</lastmod>\n\
-                 \x20   <changefreq>monthly</changefreq>\n\
-                 \x20   <priority>0.8</priority>\n\
-                 \x20 </url>\n",
-                ORIGIN, article.slug, lastmod
-            ));
+impl FeedFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
         }
     }
 
-    xml.push_str("</urlset>\n");
+    fn render(self, meta: &FeedMeta, entries: &[FeedEntry]) -> String {
+        match self {
+            Self::Rss => render_rss(meta, entries),
+            Self::Atom => render_atom(meta, entries),
+            Self::Json => render_json_feed(meta, entries),
+        }
+    }
+}
+
+/// 公開記事からフィードの記事を生成（本文はサニタイズ済みHTMLに変換する）
+async fn to_feed_entries(
+    article_render_service: &ArticleRenderService,
+    articles: Vec<PublishedArticleWithCategories>,
+) -> Vec<FeedEntry> {
+    let mut entries = Vec::with_capacity(articles.len());
+    for PublishedArticleWithCategories {
+        article,
+        categories,
+    } in articles
+    {
+        let render_context = article_render_service
+            .build_context(&article.body, None)
+            .await;
+        entries.push(FeedEntry {
+            url: format!("{}/articles/{}", ORIGIN, article.slug),
+            title: article.title,
+            summary: article.description,
+            content_html: absolutize_urls(&render_body(&article.body, &render_context)),
+            categories: categories
+                .into_iter()
+                .map(|category| category.name)
+                .collect(),
+            cover_image_url: article.cover_image_url,
+            published_at: article.published_at,
+            updated_at: article.updated_at,
+        });
+    }
+    entries
+}
+
+/// フィードを生成してレスポンスを返す
+///
+/// `category_slug` を指定した場合はそのカテゴリの記事のみを対象とし、
+/// 該当する記事がなければ404を返す。
+async fn feed_response(
+    published_article_service: &PublishedArticleService,
+    article_render_service: &ArticleRenderService,
+    category_slug: Option<&str>,
+    format: FeedFormat,
+) -> Response {
+    let articles = match published_article_service.fetch_all().await {
+        Ok(articles) => articles,
+        Err(err) => {
+            tracing::error!(error = err.to_string(), "Failed to fetch articles for feed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let (meta, articles) = match category_slug {
+        None => (
+            FeedMeta {
+                title: WEB_APP_TITLE.to_string(),
+                description: WEB_APP_DESCRIPTION.to_string(),
+                home_page_url: ORIGIN.to_string(),
+                feed_path_prefix: "/".to_string(),
+            },
+            articles,
+        ),
+        Some(slug) => {
+            let articles: Vec<_> = articles
+                .into_iter()
+                .filter(|article| {
+                    article
+                        .categories
+                        .iter()
+                        .any(|category| category.slug == slug)
+                })
+                .collect();
+            let Some(category_name) = articles.first().and_then(|article| {
+                article
+                    .categories
+                    .iter()
+                    .find(|category| category.slug == slug)
+                    .map(|category| category.name.clone())
+            }) else {
+                return StatusCode::NOT_FOUND.into_response();
+            };
+            (
+                FeedMeta {
+                    title: format!("{} - {}", category_name, WEB_APP_TITLE),
+                    description: format!("「{}」カテゴリの記事", category_name),
+                    home_page_url: ORIGIN.to_string(),
+                    feed_path_prefix: format!("/categories/{}/", slug),
+                },
+                articles,
+            )
+        }
+    };
+
+    let articles = articles.into_iter().take(FEED_ENTRY_LIMIT).collect();
+    let entries = to_feed_entries(article_render_service, articles).await;
 
     (
         [
-            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::CONTENT_TYPE, format.content_type()),
             (
                 header::CACHE_CONTROL,
                 "no-cache, must-revalidate, max-age=10, stale-while-revalidate=1296000",
@@ -81,16 +431,70 @@ async fn sitemap_xml(
                 header::CDN_CACHE_CONTROL,
                 "max-age=1296000, stale-while-revalidate=1296000",
             ),
-            (header::HeaderName::from_static("cache-tag"), "sitemap"),
+            (header::HeaderName::from_static("cache-tag"), "feed"),
         ],
-        xml,
+        format.render(&meta, &entries),
     )
+        .into_response()
+}
+
+/// サイト全体のフィードを返すハンドラ
+#[instrument(skip_all)]
+async fn site_feed(
+    format: FeedFormat,
+    State(published_article_service): State<PublishedArticleService>,
+    State(article_render_service): State<ArticleRenderService>,
+) -> Response {
+    feed_response(
+        &published_article_service,
+        &article_render_service,
+        None,
+        format,
+    )
+    .await
+}
+
+/// カテゴリ別のフィードを返すハンドラ
+#[instrument(skip(format, published_article_service, article_render_service))]
+async fn category_feed(
+    format: FeedFormat,
+    Path(slug): Path<String>,
+    State(published_article_service): State<PublishedArticleService>,
+    State(article_render_service): State<ArticleRenderService>,
+) -> Response {
+    feed_response(
+        &published_article_service,
+        &article_render_service,
+        Some(&slug),
+        format,
+    )
+    .await
 }
 
 /// SEO関連ルートを作成
 #[instrument]
 pub fn seo_routes() -> Router<AppState> {
-    Router::new()
+    let mut router = Router::new()
         .route("/robots.txt", get(robots_txt))
-        .route("/sitemap.xml", get(sitemap_xml))
+        .route("/sitemap.xml", get(sitemap_index))
+        .route("/sitemaps/pages.xml", get(pages_sitemap))
+        .route("/sitemaps/articles.xml", get(articles_sitemap))
+        .route("/sitemaps/categories.xml", get(categories_sitemap))
+        .route("/og/{file_name}", get(og_image));
+    for (file_name, format) in [
+        ("feed.xml", FeedFormat::Rss),
+        ("atom.xml", FeedFormat::Atom),
+        ("feed.json", FeedFormat::Json),
+    ] {
+        router = router
+            .route(
+                &format!("/{file_name}"),
+                get(move |published, render| site_feed(format, published, render)),
+            )
+            .route(
+                &format!("/categories/{{slug}}/{file_name}"),
+                get(move |path, published, render| category_feed(format, path, published, render)),
+            );
+    }
+    router
 }
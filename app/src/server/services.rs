@@ -1,8 +1,20 @@
+pub(crate) mod article_access;
+pub(crate) mod article_render;
 pub(crate) mod cloudflare;
+pub(crate) mod content_lint;
 pub(crate) mod dbsc;
+pub(crate) mod external_article_cache;
+pub(crate) mod external_source;
+pub(crate) mod feed_source;
 pub(crate) mod gcs;
 pub(crate) mod imgix;
+pub(crate) mod link_card;
 pub(crate) mod newt;
+pub(crate) mod og_image;
 pub(crate) mod qiita;
+pub(crate) mod qiita_import;
 pub(crate) mod signing;
+pub(crate) mod syndication;
+pub(crate) mod unlock_attempt;
 pub(crate) mod word_press;
+pub(crate) mod zenn;
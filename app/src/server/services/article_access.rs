@@ -0,0 +1,188 @@
+use axum::http::HeaderMap;
+use axum::http::header::COOKIE;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// 閲覧許可Cookie名の接頭辞（`article_access_{slug}`）
+const ARTICLE_ACCESS_COOKIE_PREFIX: &str = "article_access_";
+/// 閲覧許可Cookieの有効期間（30日）
+pub(crate) const ARTICLE_ACCESS_COOKIE_MAX_AGE: i64 = 60 * 60 * 24 * 30;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// パスワード保護記事の閲覧許可Cookieを発行・検証するサービス
+///
+/// Cookieの値は `{有効期限のUNIX時刻}.{署名}`。署名はslug・有効期限・パスワードハッシュに対する
+/// HMAC-SHA256で、パスワードを変更すると発行済みのCookieはすべて無効になる。
+#[derive(Clone)]
+pub struct ArticleAccessService {
+    secret: Arc<[u8]>,
+}
+
+impl std::fmt::Debug for ArticleAccessService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArticleAccessService")
+            .finish_non_exhaustive()
+    }
+}
+
+impl ArticleAccessService {
+    #[instrument(skip(secret))]
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: Arc::from(secret.as_bytes()),
+        }
+    }
+
+    fn cookie_name(slug: &str) -> String {
+        format!("{}{}", ARTICLE_ACCESS_COOKIE_PREFIX, slug)
+    }
+
+    fn sign(&self, slug: &str, expires_at: i64, password_hash: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC can take key of any size");
+        mac.update(format!("{}\n{}\n{}", slug, expires_at, password_hash).as_bytes());
+        mac
+    }
+
+    /// 閲覧許可CookieのSet-Cookieヘッダー値を構築
+    #[instrument(skip(self, password_hash))]
+    pub(crate) fn build_set_cookie_header(
+        &self,
+        slug: &str,
+        password_hash: &str,
+        now: i64,
+    ) -> String {
+        let expires_at = now + ARTICLE_ACCESS_COOKIE_MAX_AGE;
+        let signature = URL_SAFE_NO_PAD.encode(
+            self.sign(slug, expires_at, password_hash)
+                .finalize()
+                .into_bytes(),
+        );
+        format!(
+            "{}={}.{}; Secure; HttpOnly; SameSite=Lax; Max-Age={}; Path=/",
+            Self::cookie_name(slug),
+            expires_at,
+            signature,
+            ARTICLE_ACCESS_COOKIE_MAX_AGE
+        )
+    }
+
+    /// リクエストに有効な閲覧許可Cookieが含まれるか
+    #[instrument(skip(self, headers, password_hash))]
+    pub(crate) fn is_unlocked(
+        &self,
+        headers: &HeaderMap,
+        slug: &str,
+        password_hash: &str,
+        now: i64,
+    ) -> bool {
+        let name = Self::cookie_name(slug);
+        headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .filter(|(key, _)| *key == name)
+            .any(|(_, value)| self.verify(value, slug, password_hash, now))
+    }
+
+    fn verify(&self, value: &str, slug: &str, password_hash: &str, now: i64) -> bool {
+        let Some((expires_at, signature)) = value.split_once('.') else {
+            return false;
+        };
+        let Ok(expires_at) = expires_at.parse::<i64>() else {
+            return false;
+        };
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        expires_at > now
+            && self
+                .sign(slug, expires_at, password_hash)
+                .verify_slice(&signature)
+                .is_ok()
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const NOW: i64 = 1_800_000_000;
+
+    fn cookie_headers(set_cookie: &str) -> HeaderMap {
+        let cookie = set_cookie.split(';').next().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("other=1; {}", cookie)).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn 発行したcookieで閲覧が許可されること() {
+        let service = ArticleAccessService::new("secret");
+        let set_cookie = service.build_set_cookie_header("event", "hash", NOW);
+
+        assert!(set_cookie.starts_with("article_access_event="));
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(service.is_unlocked(&cookie_headers(&set_cookie), "event", "hash", NOW));
+    }
+
+    #[test]
+    fn 有効期限切れのcookieは許可されないこと() {
+        let service = ArticleAccessService::new("secret");
+        let set_cookie = service.build_set_cookie_header("event", "hash", NOW);
+        let later = NOW + ARTICLE_ACCESS_COOKIE_MAX_AGE;
+
+        assert!(!service.is_unlocked(&cookie_headers(&set_cookie), "event", "hash", later));
+    }
+
+    #[test]
+    fn パスワード変更後や別のキーで署名したcookieは許可されないこと() {
+        let service = ArticleAccessService::new("secret");
+        let set_cookie = service.build_set_cookie_header("event", "hash", NOW);
+        let headers = cookie_headers(&set_cookie);
+
+        assert!(!service.is_unlocked(&headers, "event", "new-hash", NOW));
+        assert!(!ArticleAccessService::new("other").is_unlocked(&headers, "event", "hash", NOW));
+    }
+
+    #[test]
+    fn 改ざんしたcookieは許可されないこと() {
+        let service = ArticleAccessService::new("secret");
+        let set_cookie = service.build_set_cookie_header("event", "hash", NOW);
+        // 有効期限を延ばしても署名が一致しない
+        let tampered = set_cookie.replacen(
+            &(NOW + ARTICLE_ACCESS_COOKIE_MAX_AGE).to_string(),
+            &(NOW + ARTICLE_ACCESS_COOKIE_MAX_AGE * 10).to_string(),
+            1,
+        );
+
+        assert!(!service.is_unlocked(&cookie_headers(&tampered), "event", "hash", NOW));
+        assert!(!service.is_unlocked(
+            &cookie_headers("article_access_event=garbage"),
+            "event",
+            "hash",
+            NOW
+        ));
+    }
+
+    #[test]
+    fn 別の記事のcookieでは許可されないこと() {
+        let service = ArticleAccessService::new("secret");
+        let set_cookie = service.build_set_cookie_header("event", "hash", NOW);
+        let renamed = set_cookie.replacen("article_access_event", "article_access_other", 1);
+
+        assert!(!service.is_unlocked(&cookie_headers(&renamed), "other", "hash", NOW));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::common::markdown::{
+    ImageSize, RenderContext, extract_imgix_image_urls, extract_wiki_link_slugs,
+};
+use crate::server::services::link_card::LinkCardService;
+use blog_romira_dev_cms::{ImageService, PublishedArticleService};
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// 記事本文のMarkdown変換に必要なデータを集めるサービス
+#[derive(Debug, Clone)]
+pub(crate) struct ArticleRenderService {
+    link_card_service: LinkCardService,
+    image_service: ImageService,
+    published_article_service: PublishedArticleService,
+}
+
+impl ArticleRenderService {
+    pub(crate) fn new(
+        link_card_service: LinkCardService,
+        image_service: ImageService,
+        published_article_service: PublishedArticleService,
+    ) -> Self {
+        Self {
+            link_card_service,
+            image_service,
+            published_article_service,
+        }
+    }
+
+    /// 本文の変換に使うコンテキストを取得
+    ///
+    /// `purge_tag` はリンクカードをバックグラウンド取得した後にパージするCDNキャッシュのタグ。
+    #[instrument(skip(self, markdown))]
+    pub(crate) async fn build_context(
+        &self,
+        markdown: &str,
+        purge_tag: Option<String>,
+    ) -> RenderContext {
+        let link_cards = self.link_card_service.resolve(markdown, purge_tag).await;
+        let image_sizes = self.fetch_image_sizes(markdown).await;
+        let wiki_links = self.fetch_wiki_link_titles(markdown).await;
+
+        RenderContext {
+            link_cards,
+            image_sizes,
+            wiki_links: Some(wiki_links),
+            embed_providers: self.link_card_service.embed_providers().clone(),
+        }
+    }
+
+    /// 本文中のウィキリンク先のうち公開済みの記事のタイトルを取得
+    async fn fetch_wiki_link_titles(&self, markdown: &str) -> HashMap<String, String> {
+        let slugs = extract_wiki_link_slugs(markdown);
+        match self
+            .published_article_service
+            .fetch_linked_articles(&slugs)
+            .await
+        {
+            Ok(articles) => articles
+                .into_iter()
+                .map(|article| (article.slug, article.title))
+                .collect(),
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), "Failed to fetch wiki link targets");
+                HashMap::new()
+            }
+        }
+    }
+
+    /// 本文中のimgix画像のサイズを画像ライブラリから取得
+    async fn fetch_image_sizes(&self, markdown: &str) -> HashMap<String, ImageSize> {
+        let urls_by_gcs_path: HashMap<String, String> = extract_imgix_image_urls(markdown)
+            .into_iter()
+            .filter_map(|url| Some((gcs_path_from_imgix_url(&url)?.to_string(), url)))
+            .collect();
+        let gcs_paths: Vec<String> = urls_by_gcs_path.keys().cloned().collect();
+
+        let images = match self.image_service.fetch_by_gcs_paths(&gcs_paths).await {
+            Ok(images) => images,
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), "Failed to fetch body image sizes");
+                return HashMap::new();
+            }
+        };
+
+        images
+            .into_iter()
+            .filter_map(|image| {
+                let url = urls_by_gcs_path.get(&image.gcs_path)?;
+                let size = ImageSize {
+                    width: image.width?,
+                    height: image.height?,
+                };
+                Some((url.clone(), size))
+            })
+            .collect()
+    }
+}
+
+/// imgixのURLからGCSパスを取り出す（`https://{domain}/{gcs_path}`）
+pub(crate) fn gcs_path_from_imgix_url(url: &str) -> Option<&str> {
+    url.split_once(".imgix.net/")
+        .map(|(_, path)| path)
+        .filter(|path| !path.is_empty())
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcs_path_from_imgix_urlでgcsパスが取り出せること() {
+        assert_eq!(
+            gcs_path_from_imgix_url("https://blog-romira.imgix.net/dev/images/a/photo.jpg"),
+            Some("dev/images/a/photo.jpg")
+        );
+        assert_eq!(
+            gcs_path_from_imgix_url("https://blog-romira.imgix.net/"),
+            None
+        );
+        assert_eq!(gcs_path_from_imgix_url("https://example.com/a.jpg"), None);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::common::content_lint::{
+    LintConfig, LintIssue, extract_internal_article_slugs, lint_article,
+};
+use blog_romira_dev_cms::{CmsError, PublishedArticleService};
+use std::collections::HashSet;
+use tracing::instrument;
+
+/// 記事の構造チェック（内部リンク先の存在確認を含む）サービス
+#[derive(Debug, Clone)]
+pub(crate) struct ContentLintService {
+    published_article_service: PublishedArticleService,
+    imgix_domain: String,
+    site_url: String,
+}
+
+impl ContentLintService {
+    pub(crate) fn new(
+        published_article_service: PublishedArticleService,
+        imgix_domain: String,
+        site_url: String,
+    ) -> Self {
+        Self {
+            published_article_service,
+            imgix_domain,
+            site_url,
+        }
+    }
+
+    /// 本文と説明文をチェックして問題一覧を返す
+    #[instrument(skip(self, body, description))]
+    pub(crate) async fn lint(
+        &self,
+        body: &str,
+        description: Option<&str>,
+    ) -> Result<Vec<LintIssue>, CmsError> {
+        let slugs = self.referenced_article_slugs(body);
+        let existing_slugs: HashSet<String> = self
+            .published_article_service
+            .fetch_existing_slugs(&slugs)
+            .await?
+            .into_iter()
+            .collect();
+        // ウィキリンクは一般公開の記事にしかリンクしないため、一般公開の記事だけを別に取得する
+        let public_slugs: HashSet<String> = self
+            .published_article_service
+            .fetch_linked_articles(&slugs)
+            .await?
+            .into_iter()
+            .map(|article| article.slug)
+            .collect();
+
+        let config = LintConfig {
+            imgix_domain: &self.imgix_domain,
+            site_url: &self.site_url,
+        };
+        Ok(lint_article(
+            body,
+            description,
+            &config,
+            &existing_slugs,
+            &public_slugs,
+        ))
+    }
+
+    /// 本文中の内部リンク（ウィキリンクを含む）先の記事slugを取得
+    pub(crate) fn referenced_article_slugs(&self, body: &str) -> Vec<String> {
+        extract_internal_article_slugs(body, &self.site_url)
+    }
+}
@@ -0,0 +1,590 @@
+//! 外部記事ソースのキャッシュ
+//!
+//! バックグラウンドのタスクが一定間隔で各ソースから取得し直し、
+//! トップページの表示（`get_articles_handler`）はキャッシュだけを読む。
+//! 取得はソースごとに時間を制限し、続けて失敗したソースはしばらく取得を止める（サーキットブレーカー）。
+
+use crate::error::ExternalArticleSourceError;
+use crate::server::config::ServerConfig;
+use crate::server::models::external_article::ExternalArticle;
+use crate::server::services::cloudflare::CloudflarePurgeService;
+use crate::server::services::external_source::{ExternalArticleSource, ExternalArticleSources};
+use chrono::{DateTime, TimeDelta, Utc};
+use fred::prelude::{KeysInterface, Pool};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Valkeyに保存するキーの接頭辞（後ろにソース名が付く）
+const VALKEY_KEY_PREFIX: &str = "external_articles:";
+
+/// 取得し直す間隔・ソースごとの制限時間・サーキットブレーカーの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RefreshPolicy {
+    pub(crate) interval: Duration,
+    /// 1つのソースの取得にかけられる時間（超えると失敗として扱う）
+    pub(crate) source_timeout: Duration,
+    /// 続けて何回失敗したら取得を止めるか
+    pub(crate) failure_threshold: u32,
+    /// 取得を止める時間（過ぎたら1回だけ試し、失敗すればまた止める）
+    pub(crate) cool_down: Duration,
+}
+
+impl RefreshPolicy {
+    pub(crate) fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            interval: Duration::from_secs(config.external_article_refresh_interval_secs),
+            source_timeout: Duration::from_secs(config.external_article_source_timeout_secs),
+            failure_threshold: config.external_article_failure_threshold,
+            cool_down: Duration::from_secs(config.external_article_cool_down_secs),
+        }
+    }
+}
+
+impl Default for RefreshPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(600),
+            source_timeout: Duration::from_secs(10),
+            failure_threshold: 3,
+            cool_down: Duration::from_secs(1800),
+        }
+    }
+}
+
+/// ソースごとの最終更新の状況
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SourceRefreshStatus {
+    pub(crate) last_attempt_at: Option<DateTime<Utc>>,
+    pub(crate) last_success_at: Option<DateTime<Utc>>,
+    /// 直近の取得が失敗した場合のエラー（成功すると消える）
+    pub(crate) last_error: Option<String>,
+    /// キャッシュしている記事数（取得に失敗した場合は前回の記事数）
+    pub(crate) article_count: usize,
+    /// 続けて失敗した回数（成功すると0に戻る）
+    pub(crate) consecutive_failures: u32,
+    /// 取得を止めている場合、再開する日時
+    pub(crate) paused_until: Option<DateTime<Utc>>,
+}
+
+impl SourceRefreshStatus {
+    /// 直近の取得に失敗し、最新の記事を表示できていないか
+    pub(crate) fn is_degraded(&self) -> bool {
+        self.last_error.is_some()
+    }
+
+    fn is_paused(&self, now: DateTime<Utc>) -> bool {
+        self.paused_until.is_some_and(|until| now < until)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CachedSource {
+    articles: Vec<ExternalArticle>,
+    status: SourceRefreshStatus,
+}
+
+/// 取得した記事をインスタンス間・再起動後で共有するためのValkeyの保存先
+#[derive(Debug, Clone)]
+pub(crate) struct ValkeyArticleStore {
+    pool: Pool,
+}
+
+impl ValkeyArticleStore {
+    pub(crate) fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    async fn load(&self, source: &str) -> Result<Option<Vec<ExternalArticle>>, String> {
+        let json: Option<String> = self
+            .pool
+            .get(format!("{VALKEY_KEY_PREFIX}{source}"))
+            .await
+            .map_err(|err| err.to_string())?;
+        json.map(|json| serde_json::from_str(&json).map_err(|err| err.to_string()))
+            .transpose()
+    }
+
+    async fn save(&self, source: &str, articles: &[ExternalArticle]) -> Result<(), String> {
+        let json = serde_json::to_string(articles).map_err(|err| err.to_string())?;
+        self.pool
+            .set::<(), _, _>(
+                format!("{VALKEY_KEY_PREFIX}{source}"),
+                json,
+                None,
+                None,
+                false,
+            )
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExternalArticleCache {
+    sources: ExternalArticleSources,
+    entries: Arc<RwLock<HashMap<String, CachedSource>>>,
+    store: Option<ValkeyArticleStore>,
+    cloudflare_purge_service: Option<CloudflarePurgeService>,
+    policy: RefreshPolicy,
+}
+
+impl ExternalArticleCache {
+    pub(crate) fn new(
+        sources: ExternalArticleSources,
+        store: Option<ValkeyArticleStore>,
+        cloudflare_purge_service: Option<CloudflarePurgeService>,
+        policy: RefreshPolicy,
+    ) -> Self {
+        Self {
+            sources,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            store,
+            cloudflare_purge_service,
+            policy,
+        }
+    }
+
+    /// キャッシュしている記事（ソースの登録順）
+    pub(crate) fn articles(&self) -> Vec<ExternalArticle> {
+        let entries = self.entries.read().unwrap();
+        self.sources
+            .iter()
+            .filter_map(|source| entries.get(source.name()))
+            .flat_map(|entry| entry.articles.iter().cloned())
+            .collect()
+    }
+
+    /// ソースごとの最終更新の状況（ソースの登録順、未取得のソースも含む）
+    ///
+    /// 停止期間が過ぎて次の取得を待っているソースは `paused_until` を返さない。
+    pub(crate) fn statuses(&self) -> Vec<(String, SourceRefreshStatus)> {
+        let now = Utc::now();
+        let entries = self.entries.read().unwrap();
+        self.sources
+            .iter()
+            .map(|source| {
+                let mut status = entries
+                    .get(source.name())
+                    .map(|entry| entry.status.clone())
+                    .unwrap_or_default();
+                if !status.is_paused(now) {
+                    status.paused_until = None;
+                }
+                (source.name().to_string(), status)
+            })
+            .collect()
+    }
+
+    /// 直近の取得に失敗したソースの名前（ソースの登録順）
+    pub(crate) fn degraded_sources(&self) -> Vec<String> {
+        self.statuses()
+            .into_iter()
+            .filter(|(_, status)| status.is_degraded())
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// 起動直後に表示できるよう、Valkeyに保存済みの記事を読み込む
+    async fn load_from_store(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        for source in self.sources.iter() {
+            match store.load(source.name()).await {
+                Ok(Some(articles)) => {
+                    let mut entries = self.entries.write().unwrap();
+                    let entry = entries.entry(source.name().to_string()).or_default();
+                    if entry.articles.is_empty() {
+                        entry.status.article_count = articles.len();
+                        entry.articles = articles;
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(
+                        error = err,
+                        source = source.name(),
+                        "Failed to load external articles from Valkey"
+                    );
+                }
+            }
+        }
+    }
+
+    /// すべてのソースから取得し直し、トップページの表示が変わったかを返す
+    ///
+    /// 取得に失敗したソースは前回の記事を残す。記事が変わった場合と、ソースが劣化した・劣化から
+    /// 回復した場合（「一部の外部記事を取得できませんでした」の表示が変わる）はトップページの
+    /// CDNキャッシュをパージする。
+    pub(crate) async fn refresh_all(&self) -> bool {
+        let mut changed = false;
+        for source in self.sources.iter() {
+            changed |= self.refresh(source.as_ref()).await;
+        }
+
+        if changed
+            && let Some(purge_service) = &self.cloudflare_purge_service
+            && let Err(e) = purge_service.purge_tags(&["top-page".to_string()]).await
+        {
+            tracing::warn!(error = %e, "Failed to purge Cloudflare cache after refreshing external articles");
+        }
+        changed
+    }
+
+    /// 1つのソースを取得し直し、記事か劣化の状態が変わったかを返す
+    ///
+    /// 取得を止めているソースは問い合わせずに前回の記事を残す。
+    async fn refresh(&self, source: &dyn ExternalArticleSource) -> bool {
+        let now = Utc::now();
+        let paused = self
+            .entries
+            .read()
+            .unwrap()
+            .get(source.name())
+            .is_some_and(|entry| entry.status.is_paused(now));
+        if paused {
+            tracing::debug!(source = source.name(), "Skipped paused external source");
+            return false;
+        }
+
+        let result = tokio::time::timeout(self.policy.source_timeout, source.fetch_articles())
+            .await
+            .unwrap_or(Err(ExternalArticleSourceError::Timeout(
+                self.policy.source_timeout,
+            )));
+        match result {
+            Ok(articles) => {
+                if let Some(store) = &self.store
+                    && let Err(err) = store.save(source.name(), &articles).await
+                {
+                    tracing::warn!(
+                        error = err,
+                        source = source.name(),
+                        "Failed to save external articles to Valkey"
+                    );
+                }
+
+                let mut entries = self.entries.write().unwrap();
+                let entry = entries.entry(source.name().to_string()).or_default();
+                let changed = entry.articles != articles || entry.status.is_degraded();
+                entry.status = SourceRefreshStatus {
+                    last_attempt_at: Some(now),
+                    last_success_at: Some(now),
+                    last_error: None,
+                    article_count: articles.len(),
+                    consecutive_failures: 0,
+                    paused_until: None,
+                };
+                entry.articles = articles;
+                changed
+            }
+            Err(err) => {
+                tracing::error!(
+                    error = err.to_string(),
+                    source = source.name(),
+                    "Failed to refresh external articles"
+                );
+                let mut entries = self.entries.write().unwrap();
+                let status = &mut entries.entry(source.name().to_string()).or_default().status;
+                let was_degraded = status.is_degraded();
+                status.last_attempt_at = Some(now);
+                status.last_error = Some(err.to_string());
+                status.consecutive_failures += 1;
+                // 再開後の1回目で失敗した場合もすぐに止め直す
+                if status.consecutive_failures >= self.policy.failure_threshold {
+                    let cool_down =
+                        TimeDelta::from_std(self.policy.cool_down).unwrap_or(TimeDelta::MAX);
+                    status.paused_until = now.checked_add_signed(cool_down);
+                    tracing::warn!(
+                        source = source.name(),
+                        consecutive_failures = status.consecutive_failures,
+                        "Paused external source after repeated failures"
+                    );
+                }
+                !was_degraded
+            }
+        }
+    }
+
+    /// 一定間隔でソースを取得し直すタスクを起動する（初回はすぐに取得する）
+    pub(crate) fn spawn_refresh_task(&self) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            this.load_from_store().await;
+            let mut interval = tokio::time::interval(this.policy.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                this.refresh_all().await;
+            }
+        });
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::dto::ArticleSource;
+    use crate::server::services::external_source::ExternalArticlesFuture;
+    use std::sync::Mutex;
+
+    type StubResult = Result<Vec<ExternalArticle>, reqwest::StatusCode>;
+
+    /// 呼び出すたびに用意した結果を順に返すソース（`delay` だけ待ってから返す）
+    #[derive(Debug)]
+    struct StubSource {
+        name: &'static str,
+        results: Mutex<Vec<StubResult>>,
+        delay: Duration,
+    }
+
+    impl ExternalArticleSource for StubSource {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn fetch_articles(&self) -> ExternalArticlesFuture<'_> {
+            let result = self.results.lock().unwrap().remove(0);
+            let delay = self.delay;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                result.map_err(ExternalArticleSourceError::UnexpectedStatusCode)
+            })
+        }
+    }
+
+    fn article(url: &str) -> ExternalArticle {
+        ExternalArticle {
+            title: url.to_string(),
+            url: url.to_string(),
+            thumbnail_url: String::new(),
+            categories: vec![],
+            published_at: DateTime::UNIX_EPOCH,
+            source: ArticleSource::Qiita,
+            emoji: None,
+        }
+    }
+
+    fn cache(sources: Vec<(&'static str, Vec<StubResult>)>) -> ExternalArticleCache {
+        cache_with(sources, Duration::ZERO, RefreshPolicy::default())
+    }
+
+    fn cache_with(
+        sources: Vec<(&'static str, Vec<StubResult>)>,
+        delay: Duration,
+        policy: RefreshPolicy,
+    ) -> ExternalArticleCache {
+        let sources = sources
+            .into_iter()
+            .map(|(name, results)| -> Arc<dyn ExternalArticleSource> {
+                Arc::new(StubSource {
+                    name,
+                    results: Mutex::new(results),
+                    delay,
+                })
+            })
+            .collect();
+        ExternalArticleCache::new(ExternalArticleSources::new(sources), None, None, policy)
+    }
+
+    fn urls(cache: &ExternalArticleCache) -> Vec<String> {
+        cache.articles().into_iter().map(|a| a.url).collect()
+    }
+
+    #[tokio::test]
+    async fn 取得前は記事も更新状況も空であること() {
+        let cache = cache(vec![("Qiita", vec![])]);
+
+        assert!(cache.articles().is_empty());
+        assert_eq!(
+            cache.statuses(),
+            vec![("Qiita".to_string(), SourceRefreshStatus::default())]
+        );
+    }
+
+    #[tokio::test]
+    async fn 取得した記事をソースの登録順に返すこと() {
+        let cache = cache(vec![
+            ("WordPress", vec![Ok(vec![article("wp")])]),
+            (
+                "Qiita",
+                vec![Ok(vec![article("qiita-1"), article("qiita-2")])],
+            ),
+        ]);
+
+        cache.refresh_all().await;
+
+        assert_eq!(urls(&cache), vec!["wp", "qiita-1", "qiita-2"]);
+        let statuses = cache.statuses();
+        assert_eq!(statuses[1].0, "Qiita");
+        assert_eq!(statuses[1].1.article_count, 2);
+        assert!(statuses[1].1.last_success_at.is_some());
+        assert_eq!(statuses[1].1.last_error, None);
+    }
+
+    #[tokio::test]
+    async fn 取得に失敗したソースは前回の記事を返しエラーを記録すること() {
+        let cache = cache(vec![(
+            "Qiita",
+            vec![
+                Ok(vec![article("qiita-1")]),
+                Err(reqwest::StatusCode::INTERNAL_SERVER_ERROR),
+            ],
+        )]);
+
+        cache.refresh_all().await;
+        let first_success_at = cache.statuses()[0].1.last_success_at;
+        cache.refresh_all().await;
+
+        assert_eq!(urls(&cache), vec!["qiita-1"]);
+        let (_, status) = &cache.statuses()[0];
+        assert_eq!(status.last_success_at, first_success_at);
+        assert!(status.last_attempt_at >= first_success_at);
+        assert!(status.last_error.as_deref().unwrap().contains("500"));
+        assert_eq!(status.article_count, 1);
+    }
+
+    #[tokio::test]
+    async fn 再取得に成功するとエラーが消えること() {
+        let cache = cache(vec![(
+            "Qiita",
+            vec![
+                Err(reqwest::StatusCode::BAD_GATEWAY),
+                Ok(vec![article("qiita-1")]),
+            ],
+        )]);
+
+        cache.refresh_all().await;
+        assert!(cache.articles().is_empty());
+        assert!(cache.statuses()[0].1.last_error.is_some());
+
+        cache.refresh_all().await;
+
+        assert_eq!(urls(&cache), vec!["qiita-1"]);
+        assert_eq!(cache.statuses()[0].1.last_error, None);
+    }
+
+    #[tokio::test]
+    async fn 劣化した時と劣化から回復した時は記事が同じでも表示が変わったとみなすこと() {
+        let cache = cache(vec![(
+            "Qiita",
+            vec![
+                Ok(vec![article("qiita-1")]),
+                Ok(vec![article("qiita-1")]),
+                Err(reqwest::StatusCode::BAD_GATEWAY),
+                Err(reqwest::StatusCode::BAD_GATEWAY),
+                Ok(vec![article("qiita-1")]),
+            ],
+        )]);
+
+        assert!(cache.refresh_all().await);
+        // 記事も状態も変わらなければパージしない
+        assert!(!cache.refresh_all().await);
+        // 劣化した（前回の記事は残る）
+        assert!(cache.refresh_all().await);
+        assert!(!cache.refresh_all().await);
+        // 同じ記事のまま回復した
+        assert!(cache.refresh_all().await);
+        assert!(cache.degraded_sources().is_empty());
+    }
+
+    #[tokio::test]
+    async fn 失敗したソースを劣化したソースとして返すこと() {
+        let cache = cache(vec![
+            (
+                "WordPress",
+                vec![Err(reqwest::StatusCode::INTERNAL_SERVER_ERROR)],
+            ),
+            ("Qiita", vec![Ok(vec![article("qiita-1")])]),
+        ]);
+
+        cache.refresh_all().await;
+
+        assert_eq!(urls(&cache), vec!["qiita-1"]);
+        assert_eq!(cache.degraded_sources(), vec!["WordPress"]);
+    }
+
+    #[tokio::test]
+    async fn 制限時間を超えたソースは失敗として扱うこと() {
+        let policy = RefreshPolicy {
+            source_timeout: Duration::from_millis(10),
+            ..RefreshPolicy::default()
+        };
+        let cache = cache_with(
+            vec![("Qiita", vec![Ok(vec![article("qiita-1")])])],
+            Duration::from_secs(5),
+            policy,
+        );
+
+        cache.refresh_all().await;
+
+        assert!(cache.articles().is_empty());
+        let (_, status) = &cache.statuses()[0];
+        assert!(status.last_error.as_deref().unwrap().contains("Timed out"));
+        assert_eq!(cache.degraded_sources(), vec!["Qiita"]);
+    }
+
+    #[tokio::test]
+    async fn 続けて失敗したソースは一定時間取得しないこと() {
+        let policy = RefreshPolicy {
+            failure_threshold: 2,
+            ..RefreshPolicy::default()
+        };
+        // 3回目が呼ばれると結果が足りずに panic する
+        let cache = cache_with(
+            vec![(
+                "Qiita",
+                vec![
+                    Err(reqwest::StatusCode::BAD_GATEWAY),
+                    Err(reqwest::StatusCode::BAD_GATEWAY),
+                ],
+            )],
+            Duration::ZERO,
+            policy,
+        );
+
+        cache.refresh_all().await;
+        assert_eq!(cache.statuses()[0].1.paused_until, None);
+        cache.refresh_all().await;
+        let attempted_at = cache.statuses()[0].1.last_attempt_at;
+        cache.refresh_all().await;
+
+        let (_, status) = &cache.statuses()[0];
+        assert_eq!(status.consecutive_failures, 2);
+        assert_eq!(status.last_attempt_at, attempted_at);
+        assert!(status.paused_until.unwrap() > attempted_at.unwrap());
+        assert_eq!(cache.degraded_sources(), vec!["Qiita"]);
+    }
+
+    #[tokio::test]
+    async fn 停止期間が過ぎたら取得を再開し成功すると元に戻ること() {
+        let policy = RefreshPolicy {
+            failure_threshold: 1,
+            cool_down: Duration::ZERO,
+            ..RefreshPolicy::default()
+        };
+        let cache = cache_with(
+            vec![(
+                "Qiita",
+                vec![
+                    Err(reqwest::StatusCode::BAD_GATEWAY),
+                    Ok(vec![article("qiita-1")]),
+                ],
+            )],
+            Duration::ZERO,
+            policy,
+        );
+
+        cache.refresh_all().await;
+        assert_eq!(cache.statuses()[0].1.consecutive_failures, 1);
+        cache.refresh_all().await;
+
+        assert_eq!(urls(&cache), vec!["qiita-1"]);
+        let (_, status) = &cache.statuses()[0];
+        assert_eq!(status.consecutive_failures, 0);
+        assert_eq!(status.paused_until, None);
+        assert!(cache.degraded_sources().is_empty());
+    }
+}
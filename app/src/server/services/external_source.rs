@@ -0,0 +1,251 @@
+//! トップページに並べる外部記事ソースの共通インターフェース
+
+use crate::error::ExternalArticleSourceError;
+use crate::server::config::ServerConfig;
+use crate::server::models::external_article::ExternalArticle;
+use crate::server::services::feed_source::{FeedArticleService, FeedSourceConfig};
+use crate::server::services::qiita::QiitaArticleService;
+use crate::server::services::word_press::WordPressArticleService;
+use crate::server::services::zenn::ZennArticleService;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// `dyn ExternalArticleSource` から返す記事取得のFuture
+pub(crate) type ExternalArticlesFuture<'a> = Pin<
+    Box<dyn Future<Output = Result<Vec<ExternalArticle>, ExternalArticleSourceError>> + Send + 'a>,
+>;
+
+/// 外部記事ソース（WordPress / Qiita / Zenn / RSS・Atomフィード）
+///
+/// 取得した記事は共通の [`ExternalArticle`] に正規化して返す。
+pub(crate) trait ExternalArticleSource: Debug + Send + Sync {
+    /// ログに出すソース名
+    fn name(&self) -> &str;
+
+    fn fetch_articles(&self) -> ExternalArticlesFuture<'_>;
+}
+
+/// 設定で有効にできる外部記事ソースの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExternalArticleSourceKind {
+    WordPress,
+    Qiita,
+    Zenn,
+    Feed,
+}
+
+impl FromStr for ExternalArticleSourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "wordpress" => Ok(Self::WordPress),
+            "qiita" => Ok(Self::Qiita),
+            "zenn" => Ok(Self::Zenn),
+            "feed" => Ok(Self::Feed),
+            other => Err(format!("Unknown external article source: {other}")),
+        }
+    }
+}
+
+/// 登録済みの外部記事ソース（設定の順に並ぶ）
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExternalArticleSources {
+    sources: Vec<Arc<dyn ExternalArticleSource>>,
+}
+
+impl ExternalArticleSources {
+    #[cfg(test)]
+    pub(crate) fn new(sources: Vec<Arc<dyn ExternalArticleSource>>) -> Self {
+        Self { sources }
+    }
+
+    /// 設定から有効なソースを組み立てる
+    ///
+    /// 未知のソース名や必須のパラメータ（Zennのユーザー名・フィードの設定）がないソースと、
+    /// 先に登録したソースと名前が重複するソース（大文字・小文字は区別しない）はエラーログを出して無視する。
+    /// ソース名はキャッシュのキーになるため、重複すると記事と更新状況を上書きし合う。
+    pub(crate) fn from_config(client: reqwest::Client, config: &ServerConfig) -> Self {
+        let mut names = HashSet::new();
+        let sources = config
+            .external_article_sources
+            .iter()
+            .filter(|name| !name.trim().is_empty())
+            .filter_map(|name| match name.parse::<ExternalArticleSourceKind>() {
+                Ok(kind) => Some(kind),
+                Err(err) => {
+                    tracing::error!(error = err, "Ignored external article source");
+                    None
+                }
+            })
+            .flat_map(|kind| build_sources(kind, &client, config))
+            .filter(|source| {
+                let unique = names.insert(source.name().trim().to_lowercase());
+                if !unique {
+                    tracing::error!(
+                        source = source.name(),
+                        "Ignored external article source with duplicate name"
+                    );
+                }
+                unique
+            })
+            .collect();
+        Self { sources }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Arc<dyn ExternalArticleSource>> {
+        self.sources.iter()
+    }
+}
+
+/// 種類ごとのソースを組み立てる（フィードは設定した数だけ登録する）
+fn build_sources(
+    kind: ExternalArticleSourceKind,
+    client: &reqwest::Client,
+    config: &ServerConfig,
+) -> Vec<Arc<dyn ExternalArticleSource>> {
+    match kind {
+        ExternalArticleSourceKind::WordPress => vec![Arc::new(WordPressArticleService::new(
+            client.clone(),
+            &config.word_press_base_url,
+            config.word_press_author_id,
+        ))],
+        ExternalArticleSourceKind::Qiita => vec![Arc::new(QiitaArticleService::new(
+            client.clone(),
+            &config.qiita_base_url,
+            &config.qiita_api_token,
+            (!config.qiita_user_name.is_empty()).then(|| config.qiita_user_name.clone()),
+            config.qiita_max_pages,
+        ))],
+        ExternalArticleSourceKind::Zenn if config.zenn_user_name.is_empty() => {
+            tracing::error!("Ignored Zenn article source without ZENN_USER_NAME");
+            vec![]
+        }
+        ExternalArticleSourceKind::Zenn => vec![Arc::new(ZennArticleService::new(
+            client.clone(),
+            &config.zenn_base_url,
+            &config.zenn_user_name,
+        ))],
+        ExternalArticleSourceKind::Feed => {
+            match serde_json::from_str::<Vec<FeedSourceConfig>>(&config.feed_sources) {
+                Ok(feeds) => feeds
+                    .into_iter()
+                    .map(|feed| -> Arc<dyn ExternalArticleSource> {
+                        Arc::new(FeedArticleService::new(client.clone(), feed))
+                    })
+                    .collect(),
+                Err(err) => {
+                    tracing::error!(
+                        error = err.to_string(),
+                        "Ignored feed article sources with invalid FEED_SOURCES"
+                    );
+                    vec![]
+                }
+            }
+        }
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(sources: &[&str]) -> ServerConfig {
+        ServerConfig {
+            external_article_sources: sources.iter().map(|s| s.to_string()).collect(),
+            word_press_base_url: "https://wordpress.example.com".to_string(),
+            qiita_base_url: "https://qiita.example.com".to_string(),
+            zenn_base_url: "https://zenn.example.com".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn names(sources: &ExternalArticleSources) -> Vec<&str> {
+        sources.iter().map(|source| source.name()).collect()
+    }
+
+    #[test]
+    fn 設定の順にソースが登録されること() {
+        let sources = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &config(&["Qiita", "wordpress"]),
+        );
+
+        assert_eq!(names(&sources), vec!["Qiita", "WordPress"]);
+    }
+
+    #[test]
+    fn 未知のソース名と空文字は無視されること() {
+        let sources = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &config(&["", "note", "qiita"]),
+        );
+
+        assert_eq!(names(&sources), vec!["Qiita"]);
+    }
+
+    #[test]
+    fn ユーザー名がないzennは無視されること() {
+        let without_user =
+            ExternalArticleSources::from_config(reqwest::Client::new(), &config(&["zenn"]));
+        let with_user = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &ServerConfig {
+                zenn_user_name: "romira".to_string(),
+                ..config(&["zenn"])
+            },
+        );
+
+        assert!(names(&without_user).is_empty());
+        assert_eq!(names(&with_user), vec!["Zenn"]);
+    }
+
+    #[test]
+    fn フィードは設定した数だけ登録されること() {
+        let sources = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &ServerConfig {
+                feed_sources: r#"[
+                    {"name": "note", "url": "https://note.com/romira/rss", "author": "Romira"},
+                    {"name": "Tech Blog", "url": "https://tech.example.com/feed",
+                     "category_map": {"rust": "Rust"}}
+                ]"#
+                .to_string(),
+                ..config(&["feed"])
+            },
+        );
+        let invalid = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &ServerConfig {
+                feed_sources: "not json".to_string(),
+                ..config(&["feed", "qiita"])
+            },
+        );
+
+        assert_eq!(names(&sources), vec!["note", "Tech Blog"]);
+        assert_eq!(names(&invalid), vec!["Qiita"]);
+    }
+
+    #[test]
+    fn 名前が重複するソースは先に登録したものだけ残ること() {
+        let sources = ExternalArticleSources::from_config(
+            reqwest::Client::new(),
+            &ServerConfig {
+                feed_sources: r#"[
+                    {"name": "note", "url": "https://note.com/romira/rss"},
+                    {"name": "qiita", "url": "https://qiita.example.com/feed"},
+                    {"name": "note", "url": "https://note.com/other/rss"}
+                ]"#
+                .to_string(),
+                ..config(&["qiita", "feed", "Qiita"])
+            },
+        );
+
+        assert_eq!(names(&sources), vec!["Qiita", "note"]);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::common::dto::ArticleSource;
+use crate::constants::THUMBNAIL_NO_IMAGE_URL;
+use crate::error::FeedArticleServiceError;
+use crate::server::models::external_article::ExternalArticle;
+use crate::server::services::external_source::{ExternalArticleSource, ExternalArticlesFuture};
+use crate::server::utils::feed_parser::{FeedEntry, parse_feed};
+use crate::server::utils::html::fetch_og_image_urls;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::instrument;
+
+/// 記事のOGP画像を同時に取得する最大数
+const FEED_OGP_FETCH_CONCURRENCY: usize = 4;
+
+/// RSS / Atom フィードの設定（`FEED_SOURCES` のJSON配列の1要素）
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub(crate) struct FeedSourceConfig {
+    /// 記事カードのバッジに表示するソース名（例: `note`）
+    pub(crate) name: String,
+    pub(crate) url: String,
+    /// 指定した著者の記事だけを表示する
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    /// フィードのカテゴリ名から表示するカテゴリ名への変換（ないものはそのまま表示）
+    #[serde(default)]
+    pub(crate) category_map: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FeedArticleService {
+    client: reqwest::Client,
+    config: Arc<FeedSourceConfig>,
+    /// 前回取得したOG画像のURL（記事のURLごと。取得できなかった記事は含めない）
+    og_image_urls: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl FeedArticleService {
+    #[instrument]
+    pub(crate) fn new(client: reqwest::Client, config: FeedSourceConfig) -> Self {
+        Self {
+            client,
+            config: Arc::new(config),
+            og_image_urls: Arc::default(),
+        }
+    }
+
+    /// フィードを取得して設定の著者で絞り込み、カテゴリを変換する
+    ///
+    /// 日時のない記事は並べられないため除外する。画像のない記事はOGPの画像で補う（前回取得できた記事は前回の画像を使う）。
+    #[instrument]
+    pub(crate) async fn fetch_articles(
+        &self,
+    ) -> Result<Vec<ExternalArticle>, FeedArticleServiceError> {
+        let response = self.client.get(&self.config.url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(FeedArticleServiceError::UnexpectedStatusCode(
+                response.status(),
+            ));
+        }
+
+        let body = response.text().await?;
+        let entries = parse_feed(&body).map_err(FeedArticleServiceError::InvalidFeed)?;
+
+        let entries: Vec<FeedEntry> = entries
+            .into_iter()
+            .filter(|entry| self.is_target_author(entry))
+            .filter(|entry| {
+                if entry.published_at.is_none() {
+                    tracing::warn!(link = entry.link, "Skipped feed entry without date");
+                }
+                entry.published_at.is_some()
+            })
+            .collect();
+        let mut og_image_urls = self.fill_og_image_urls(&entries).await;
+
+        let articles = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let published_at = entry.published_at?;
+                let thumbnail_url = entry
+                    .image_url
+                    .or_else(|| og_image_urls.remove(&entry.link))
+                    .unwrap_or_else(|| THUMBNAIL_NO_IMAGE_URL.to_string());
+                Some(ExternalArticle {
+                    title: entry.title,
+                    url: entry.link,
+                    thumbnail_url,
+                    categories: entry
+                        .categories
+                        .into_iter()
+                        .map(|category| {
+                            self.config
+                                .category_map
+                                .get(&category)
+                                .cloned()
+                                .unwrap_or(category)
+                        })
+                        .collect(),
+                    published_at,
+                    source: ArticleSource::Feed(self.config.name.clone()),
+                    emoji: None,
+                })
+            })
+            .collect();
+
+        Ok(articles)
+    }
+
+    fn is_target_author(&self, entry: &FeedEntry) -> bool {
+        match &self.config.author {
+            Some(author) => entry.author.as_deref().map(str::trim) == Some(author.trim()),
+            None => true,
+        }
+    }
+
+    /// 画像のない記事のOG画像のURL（記事のURLごと）を返す
+    ///
+    /// 前回取得できた記事は前回の画像を使い、それ以外だけ並行して取得する。
+    async fn fill_og_image_urls(&self, entries: &[FeedEntry]) -> HashMap<String, String> {
+        let mut og_image_urls = HashMap::new();
+        let mut links = Vec::new();
+        {
+            let cache = self.og_image_urls.lock().unwrap();
+            for entry in entries.iter().filter(|entry| entry.image_url.is_none()) {
+                match cache.get(&entry.link) {
+                    Some(og_image_url) => {
+                        og_image_urls.insert(entry.link.clone(), og_image_url.clone());
+                    }
+                    None => links.push(entry.link.clone()),
+                }
+            }
+        }
+
+        let fetched = fetch_og_image_urls(&self.client, &links, FEED_OGP_FETCH_CONCURRENCY).await;
+        og_image_urls.extend(
+            links
+                .into_iter()
+                .zip(fetched)
+                .filter_map(|(link, og_image_url)| Some((link, og_image_url?))),
+        );
+
+        *self.og_image_urls.lock().unwrap() = og_image_urls.clone();
+        og_image_urls
+    }
+}
+
+impl ExternalArticleSource for FeedArticleService {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn fetch_articles(&self) -> ExternalArticlesFuture<'_> {
+        Box::pin(async move { Ok(FeedArticleService::fetch_articles(self).await?) })
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    const RSS_FIXTURE: &str = include_str!("../../../tests/fixtures/feeds/rss2.xml");
+
+    fn service(url: String, author: Option<&str>) -> FeedArticleService {
+        FeedArticleService::new(
+            reqwest::Client::new(),
+            FeedSourceConfig {
+                name: "note".to_string(),
+                url,
+                author: author.map(str::to_string),
+                category_map: HashMap::from([("rust".to_string(), "Rust".to_string())]),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_著者で絞り込みカテゴリを変換すること() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/rss")
+            .with_header("content-type", "application/rss+xml")
+            .with_body(RSS_FIXTURE)
+            .create();
+
+        let articles = service(format!("{}/rss", server.url()), Some("Romira"))
+            .fetch_articles()
+            .await
+            .unwrap();
+
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].url, "https://note.com/romira/n/n0001");
+        assert_eq!(
+            articles[0].thumbnail_url,
+            "https://assets.note.com/n0001.png"
+        );
+        assert_eq!(articles[0].categories, vec!["Rust", "個人開発"]);
+        assert_eq!(articles[0].source, ArticleSource::Feed("note".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_日時のない記事は除外されること() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("GET", "/rss").with_body(RSS_FIXTURE).create();
+
+        let articles = service(format!("{}/rss", server.url()), None)
+            .fetch_articles()
+            .await
+            .unwrap();
+
+        let urls: Vec<&str> = articles.iter().map(|a| a.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://note.com/romira/n/n0001",
+                "https://note.com/romira/n/n0002"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_画像のない記事はogp画像で補うこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        server
+            .mock("GET", "/rss")
+            .with_body(format!(
+                r#"<rss version="2.0"><channel>
+                    <item>
+                        <title>OGPあり</title>
+                        <link>{url}/entry/1</link>
+                        <pubDate>Thu, 01 Oct 2026 12:00:00 +0900</pubDate>
+                    </item>
+                    <item>
+                        <title>OGPなし</title>
+                        <link>{url}/entry/2</link>
+                        <pubDate>Thu, 01 Oct 2026 12:00:00 +0900</pubDate>
+                    </item>
+                </channel></rss>"#
+            ))
+            .create();
+        server
+            .mock("GET", "/entry/1")
+            .with_header("content-type", "text/html")
+            .with_body(r#"<html><head><meta property="og:image" content="https://example.com/og.png"></head></html>"#)
+            .create();
+        server.mock("GET", "/entry/2").with_status(404).create();
+
+        let articles = service(format!("{url}/rss"), None)
+            .fetch_articles()
+            .await
+            .unwrap();
+
+        let thumbnails: Vec<&str> = articles.iter().map(|a| a.thumbnail_url.as_str()).collect();
+        assert_eq!(
+            thumbnails,
+            vec!["https://example.com/og.png", THUMBNAIL_NO_IMAGE_URL]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_取得できたogp画像は次回も使い取得できなかった記事だけ取得し直すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        server
+            .mock("GET", "/rss")
+            .with_body(format!(
+                r#"<rss version="2.0"><channel>
+                    <item>
+                        <title>OGPあり</title>
+                        <link>{url}/entry/1</link>
+                        <pubDate>Thu, 01 Oct 2026 12:00:00 +0900</pubDate>
+                    </item>
+                    <item>
+                        <title>OGPなし</title>
+                        <link>{url}/entry/2</link>
+                        <pubDate>Thu, 01 Oct 2026 12:00:00 +0900</pubDate>
+                    </item>
+                </channel></rss>"#
+            ))
+            .expect(2)
+            .create();
+        let first_entry = server
+            .mock("GET", "/entry/1")
+            .with_header("content-type", "text/html")
+            .with_body(r#"<html><head><meta property="og:image" content="https://example.com/og.png"></head></html>"#)
+            .expect(1)
+            .create();
+        let second_entry = server
+            .mock("GET", "/entry/2")
+            .with_status(404)
+            .expect(2)
+            .create();
+
+        let service = service(format!("{url}/rss"), None);
+        service.fetch_articles().await.unwrap();
+        let articles = service.fetch_articles().await.unwrap();
+
+        first_entry.assert();
+        second_entry.assert();
+        assert_eq!(articles[0].thumbnail_url, "https://example.com/og.png");
+    }
+
+    #[tokio::test]
+    async fn test_フィードとして解析できない場合はエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/rss")
+            .with_body("<html></html>")
+            .create();
+
+        let result = service(format!("{}/rss", server.url()), None)
+            .fetch_articles()
+            .await;
+
+        assert!(matches!(
+            result.unwrap_err(),
+            FeedArticleServiceError::InvalidFeed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_200_299以外のステータスコードが返された場合はエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("GET", "/rss").with_status(500).create();
+
+        let result = service(format!("{}/rss", server.url()), None)
+            .fetch_articles()
+            .await;
+
+        assert_eq!(
+            match result.unwrap_err() {
+                FeedArticleServiceError::UnexpectedStatusCode(status) => status,
+                _ => panic!("Unexpected error type"),
+            },
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
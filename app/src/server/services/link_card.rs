@@ -0,0 +1,300 @@
+use crate::common::embed_provider::EmbedProviders;
+use crate::common::markdown::{LinkCard, extract_link_card_urls};
+use crate::server::services::cloudflare::CloudflarePurgeService;
+use crate::server::utils::html::fetch_ogp_metadata;
+use crate::server::utils::oembed::fetch_oembed_html;
+use blog_romira_dev_cms::{OgpCache, OgpCacheService, OgpContent};
+use chrono::{TimeDelta, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::instrument;
+
+/// 取得済みOGPを再取得するまでの期間
+const OGP_CACHE_TTL: TimeDelta = TimeDelta::days(7);
+
+/// 取得に失敗したURLを再試行するまでの期間
+const OGP_FAILED_RETRY_TTL: TimeDelta = TimeDelta::hours(6);
+
+/// 事前取得でOGPを同時に取得する最大数
+const LINK_CARD_PREFETCH_CONCURRENCY: usize = 4;
+
+/// 記事本文のリンクカード用OGPメタデータを扱うサービス
+///
+/// 描画時はDBのキャッシュのみを参照し、外部サイトへの取得はバックグラウンドで行う。
+#[derive(Debug, Clone)]
+pub(crate) struct LinkCardService {
+    client: reqwest::Client,
+    ogp_cache_service: OgpCacheService,
+    cloudflare_purge_service: Option<CloudflarePurgeService>,
+    /// oEmbedで埋め込みに変換するプロバイダー
+    embed_providers: EmbedProviders,
+    /// バックグラウンド取得中のURL（同じURLの多重取得を防ぐ）
+    refreshing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl LinkCardService {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        ogp_cache_service: OgpCacheService,
+        cloudflare_purge_service: Option<CloudflarePurgeService>,
+        embed_providers: EmbedProviders,
+    ) -> Self {
+        Self {
+            client,
+            ogp_cache_service,
+            cloudflare_purge_service,
+            embed_providers,
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// oEmbedで埋め込みに変換するプロバイダー（サニタイズでも同じものを許可する）
+    pub(crate) fn embed_providers(&self) -> &EmbedProviders {
+        &self.embed_providers
+    }
+
+    /// 本文中のリンクカードのメタデータをキャッシュから取得
+    ///
+    /// 未取得・期限切れのURLはバックグラウンドで取得し、保存後に `purge_tag` のCDNキャッシュをパージする。
+    #[instrument(skip(self, markdown))]
+    pub(crate) async fn resolve(
+        &self,
+        markdown: &str,
+        purge_tag: Option<String>,
+    ) -> HashMap<String, LinkCard> {
+        let urls = extract_link_card_urls(markdown);
+        let caches = self.fetch_caches(&urls).await;
+
+        for url in stale_urls(&urls, &caches) {
+            self.spawn_refresh(url, purge_tag.clone());
+        }
+
+        caches
+            .into_iter()
+            .map(|cache| (cache.url.clone(), to_link_card(cache)))
+            .collect()
+    }
+
+    /// 本文中の未取得・期限切れのURLのOGPを取得して保存（公開前の事前取得用）
+    #[instrument(skip(self, markdown))]
+    pub(crate) async fn prefetch(&self, markdown: &str) {
+        let urls = extract_link_card_urls(markdown);
+        let caches = self.fetch_caches(&urls).await;
+
+        let semaphore = Arc::new(Semaphore::new(LINK_CARD_PREFETCH_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for url in stale_urls(&urls, &caches) {
+            let this = self.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.unwrap();
+                this.refresh(&url).await
+            });
+        }
+        tasks.join_all().await;
+    }
+
+    async fn fetch_caches(&self, urls: &[String]) -> Vec<OgpCache> {
+        match self.ogp_cache_service.fetch_by_urls(urls).await {
+            Ok(caches) => caches,
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), "Failed to fetch OGP caches");
+                Vec::new()
+            }
+        }
+    }
+
+    fn spawn_refresh(&self, url: String, purge_tag: Option<String>) {
+        let Some(guard) = RefreshingGuard::acquire(&self.refreshing, &url) else {
+            return;
+        };
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            let fetched = this.refresh(&url).await;
+            drop(guard);
+
+            if fetched
+                && let (Some(purge_service), Some(tag)) =
+                    (&this.cloudflare_purge_service, purge_tag)
+                && let Err(e) = purge_service.purge_tags(&[tag]).await
+            {
+                tracing::warn!(error = %e, "Failed to purge Cloudflare cache after OGP fetch");
+            }
+        });
+    }
+
+    /// OGP（埋め込みプロバイダーのURLはoEmbedも）を取得して保存し、メタデータを取得できたかを返す
+    ///
+    /// 取得に失敗した場合も空のメタデータを保存し、再試行までの間隔を空ける。
+    async fn refresh(&self, url: &str) -> bool {
+        let embed_html = match self.embed_providers.find(url) {
+            Some(provider) => match fetch_oembed_html(&self.client, provider, url).await {
+                Ok(html) => Some(html),
+                Err(err) => {
+                    tracing::warn!(
+                        error = err.to_string(),
+                        url,
+                        provider = provider.name,
+                        "Failed to fetch oEmbed"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let metadata = match fetch_ogp_metadata(&self.client, url).await {
+            Ok(metadata) => Some(metadata),
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), url, "Failed to fetch OGP metadata");
+                None
+            }
+        };
+
+        let mut content = metadata
+            .as_ref()
+            .map(|m| OgpContent {
+                title: m.title.as_deref(),
+                description: m.description.as_deref(),
+                image_url: m.image_url.as_deref(),
+                favicon_url: m.favicon_url.as_deref(),
+                site_name: m.site_name.as_deref(),
+                embed_html: None,
+            })
+            .unwrap_or_default();
+        content.embed_html = embed_html.as_deref();
+
+        if let Err(err) = self.ogp_cache_service.save(url, &content).await {
+            tracing::warn!(error = err.to_string(), url, "Failed to save OGP cache");
+            return false;
+        }
+
+        metadata.is_some() || embed_html.is_some()
+    }
+}
+
+/// バックグラウンド取得中のURLの登録（破棄時に、取得中にパニックした場合も登録を外す）
+struct RefreshingGuard {
+    refreshing: Arc<Mutex<HashSet<String>>>,
+    url: String,
+}
+
+impl RefreshingGuard {
+    /// URLを取得中として登録する（すでに取得中なら `None`）
+    fn acquire(refreshing: &Arc<Mutex<HashSet<String>>>, url: &str) -> Option<Self> {
+        let inserted = refreshing
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(url.to_string());
+        inserted.then(|| Self {
+            refreshing: refreshing.clone(),
+            url: url.to_string(),
+        })
+    }
+}
+
+impl Drop for RefreshingGuard {
+    fn drop(&mut self) {
+        self.refreshing
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.url);
+    }
+}
+
+/// キャッシュがない、または期限切れのURLを抽出
+fn stale_urls(urls: &[String], caches: &[OgpCache]) -> Vec<String> {
+    let now = Utc::now().naive_utc();
+    urls.iter()
+        .filter(|url| {
+            caches
+                .iter()
+                .find(|cache| &cache.url == *url)
+                .is_none_or(|cache| {
+                    let ttl = if cache.title.is_some() || cache.embed_html.is_some() {
+                        OGP_CACHE_TTL
+                    } else {
+                        OGP_FAILED_RETRY_TTL
+                    };
+                    cache.fetched_at + ttl < now
+                })
+        })
+        .cloned()
+        .collect()
+}
+
+fn to_link_card(cache: OgpCache) -> LinkCard {
+    LinkCard {
+        title: cache.title,
+        description: cache.description,
+        image_url: cache.image_url,
+        favicon_url: cache.favicon_url,
+        site_name: cache.site_name,
+        embed_html: cache.embed_html,
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(url: &str, title: Option<&str>, age: TimeDelta) -> OgpCache {
+        OgpCache {
+            url: url.to_string(),
+            title: title.map(str::to_string),
+            description: None,
+            image_url: None,
+            favicon_url: None,
+            site_name: None,
+            embed_html: None,
+            fetched_at: Utc::now().naive_utc() - age,
+        }
+    }
+
+    #[test]
+    fn stale_urlsで未取得と期限切れのurlのみ抽出されること() {
+        let urls = vec![
+            "https://example.com/fresh".to_string(),
+            "https://example.com/expired".to_string(),
+            "https://example.com/failed".to_string(),
+            "https://example.com/missing".to_string(),
+        ];
+        let caches = vec![
+            cache("https://example.com/fresh", Some("A"), TimeDelta::days(1)),
+            cache("https://example.com/expired", Some("B"), TimeDelta::days(8)),
+            cache("https://example.com/failed", None, TimeDelta::hours(7)),
+        ];
+
+        assert_eq!(
+            stale_urls(&urls, &caches),
+            vec![
+                "https://example.com/expired".to_string(),
+                "https://example.com/failed".to_string(),
+                "https://example.com/missing".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn refreshing_guardで取得中にパニックしても登録が外れること() {
+        let refreshing = Arc::new(Mutex::new(HashSet::new()));
+        let url = "https://example.com/";
+
+        let guard = RefreshingGuard::acquire(&refreshing, url).unwrap();
+        assert!(RefreshingGuard::acquire(&refreshing, url).is_none());
+
+        let result = tokio::spawn(async move {
+            let _guard = guard;
+            panic!("refresh panicked");
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert!(refreshing.lock().unwrap().is_empty());
+        assert!(RefreshingGuard::acquire(&refreshing, url).is_some());
+    }
+}
@@ -0,0 +1,485 @@
+use crate::constants::{ROMIRA_AVATAR_URL, WEB_APP_TITLE};
+use crate::error::OgImageError;
+use crate::server::utils::xml::escape_xml;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use blog_romira_dev_cms::OgImageCacheService;
+use resvg::usvg::{self, fontdb};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, OnceLock};
+use tracing::instrument;
+
+/// 画像のデザインを変えたら更新する（内容ハッシュに含め、古いキャッシュを使わないようにする）
+const TEMPLATE_VERSION: &str = "1";
+
+const OG_IMAGE_WIDTH: u32 = 1200;
+const OG_IMAGE_HEIGHT: u32 = 630;
+
+/// 左右の余白
+const PADDING_X: f32 = 80.0;
+
+/// タイトルの最大行数（超えた分は末尾を「…」で省略する）
+const TITLE_MAX_LINES: usize = 3;
+
+/// タイトルの文字サイズの候補（最大行数に収まる最も大きいサイズを使う）
+const TITLE_FONT_SIZES: [f32; 3] = [64.0, 56.0, 48.0];
+
+/// 日本語フォントを優先し、なければ汎用のサンセリフにフォールバックする
+const FONT_FAMILY: &str =
+    "'Noto Sans JP', 'Noto Sans CJK JP', 'Hiragino Sans', 'IPAexGothic', sans-serif";
+
+/// 行頭に置かない文字（前の行の末尾にぶら下げる）
+const NO_LINE_START_CHARS: &str =
+    "、。，．,.)）」』】〕！？!?ー〜…・：；:;ぁぃぅぇぉっゃゅょァィゥェォッャュョ";
+
+/// OGP画像に描画する内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OgCardContent {
+    pub(crate) title: String,
+    pub(crate) categories: Vec<String>,
+}
+
+/// 生成したOGP画像
+#[derive(Debug, Clone)]
+pub(crate) struct OgImage {
+    /// 描画内容から計算したハッシュ（キャッシュキー・ETagに使う）
+    pub(crate) content_hash: String,
+    pub(crate) png: Vec<u8>,
+}
+
+/// 記事のOGP画像（1200x630のPNG）をCPUで生成するサービス
+///
+/// 生成した画像は内容ハッシュをキーにDBへ保存し、内容が変わらない限り再生成しない。
+#[derive(Debug, Clone)]
+pub(crate) struct OgImageService {
+    client: reqwest::Client,
+    og_image_cache_service: OgImageCacheService,
+    /// 追加で読み込むフォントファイル（日本語フォントがシステムにない環境向け）
+    font_path: Option<String>,
+    /// 著者アイコンの取得元（`None` ならアイコンを描画しない）
+    avatar_url: Option<String>,
+    /// 初回の生成時に読み込むフォントデータベース
+    fontdb: Arc<OnceLock<Arc<fontdb::Database>>>,
+    /// 取得済みの著者アイコン（data URL）
+    avatar_data_url: Arc<OnceLock<String>>,
+}
+
+impl OgImageService {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        og_image_cache_service: OgImageCacheService,
+        font_path: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Self {
+        Self {
+            client,
+            og_image_cache_service,
+            font_path,
+            avatar_url,
+            fontdb: Arc::new(OnceLock::new()),
+            avatar_data_url: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// OGP画像を取得（キャッシュになければ生成して保存する）
+    #[instrument(skip(self))]
+    pub(crate) async fn render(&self, content: &OgCardContent) -> Result<OgImage, OgImageError> {
+        let avatar = self.fetch_avatar_data_url().await;
+        let content_hash = content_hash(content, avatar.is_some());
+
+        match self.og_image_cache_service.fetch_png(&content_hash).await {
+            Ok(Some(png)) => return Ok(OgImage { content_hash, png }),
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), "Failed to fetch OG image cache");
+            }
+        }
+
+        let png = self.generate(content, avatar).await?;
+        if let Err(err) = self.og_image_cache_service.save(&content_hash, &png).await {
+            tracing::warn!(error = err.to_string(), "Failed to save OG image cache");
+        }
+        Ok(OgImage { content_hash, png })
+    }
+
+    /// エディタのプレビュー用にOGP画像を生成（編集中の内容なのでキャッシュには保存しない）
+    #[instrument(skip(self))]
+    pub(crate) async fn render_preview(
+        &self,
+        content: &OgCardContent,
+    ) -> Result<Vec<u8>, OgImageError> {
+        let avatar = self.fetch_avatar_data_url().await;
+        self.generate(content, avatar).await
+    }
+
+    /// SVGを組み立ててPNGに変換（CPU負荷が高いのでブロッキング用スレッドで行う）
+    async fn generate(
+        &self,
+        content: &OgCardContent,
+        avatar_data_url: Option<String>,
+    ) -> Result<Vec<u8>, OgImageError> {
+        let svg = build_og_card_svg(content, avatar_data_url.as_deref());
+        let fontdb = self.fontdb();
+        tokio::task::spawn_blocking(move || rasterize(&svg, fontdb))
+            .await
+            .map_err(|err| OgImageError::Task(err.to_string()))?
+    }
+
+    fn fontdb(&self) -> Arc<fontdb::Database> {
+        self.fontdb
+            .get_or_init(|| {
+                let mut db = fontdb::Database::new();
+                db.load_system_fonts();
+                if let Some(path) = self.font_path.as_deref()
+                    && let Err(err) = db.load_font_file(path)
+                {
+                    tracing::warn!(
+                        error = err.to_string(),
+                        path,
+                        "Failed to load OG image font"
+                    );
+                }
+                Arc::new(db)
+            })
+            .clone()
+    }
+
+    /// 著者アイコンを取得してdata URLにする（失敗した場合は次回の生成時に再取得する）
+    async fn fetch_avatar_data_url(&self) -> Option<String> {
+        if let Some(data_url) = self.avatar_data_url.get() {
+            return Some(data_url.clone());
+        }
+        let avatar_url = self.avatar_url.as_deref()?;
+
+        let result = async {
+            let response = self
+                .client
+                .get(avatar_url)
+                .query(&[("w", "160"), ("h", "160"), ("fit", "crop"), ("fm", "png")])
+                .send()
+                .await?
+                .error_for_status()?;
+            response.bytes().await
+        }
+        .await;
+        match result {
+            Ok(bytes) => {
+                let data_url = format!("data:image/png;base64,{}", STANDARD.encode(&bytes));
+                Some(self.avatar_data_url.get_or_init(|| data_url).clone())
+            }
+            Err(err) => {
+                tracing::warn!(error = err.to_string(), "Failed to fetch OG image avatar");
+                None
+            }
+        }
+    }
+}
+
+/// 本番用の著者アイコンURL
+pub(crate) fn default_avatar_url() -> Option<String> {
+    Some(ROMIRA_AVATAR_URL.to_string())
+}
+
+/// 描画内容のハッシュ（SHA-256の16進文字列）
+fn content_hash(content: &OgCardContent, has_avatar: bool) -> String {
+    let mut hasher = Sha256::new();
+    for part in [TEMPLATE_VERSION, &content.title, WEB_APP_TITLE] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    for category in &content.categories {
+        hasher.update(category.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update([u8::from(has_avatar)]);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// 文字の描画幅の目安（文字サイズに対する比率）
+///
+/// フォントが環境によって変わるため、全角は1.0、半角は字形に応じた概算値を使う。
+fn char_width_ratio(c: char) -> f32 {
+    match c {
+        ' ' => 0.3,
+        'i' | 'l' | 'j' | 'I' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 0.3,
+        'm' | 'w' | 'M' | 'W' => 0.85,
+        'A'..='Z' => 0.7,
+        c if c.is_ascii() => 0.58,
+        // 半角カナ
+        '\u{FF61}'..='\u{FF9F}' => 0.5,
+        _ => 1.0,
+    }
+}
+
+fn text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().map(char_width_ratio).sum::<f32>() * font_size
+}
+
+/// 改行位置の単位に分割（英数字の単語は途中で改行しない）
+fn split_wrap_units(text: &str) -> Vec<String> {
+    let mut units: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || (!word.is_empty() && matches!(c, '-' | '_' | '\'')) {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            units.push(std::mem::take(&mut word));
+        }
+        units.push(c.to_string());
+    }
+    if !word.is_empty() {
+        units.push(word);
+    }
+    units
+}
+
+/// タイトルを指定幅で折り返す（行数の上限なし）
+fn wrap_text(text: &str, max_width: f32, font_size: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for unit in split_wrap_units(text) {
+        let fits = text_width(&line, font_size) + text_width(&unit, font_size) <= max_width;
+        let hangs = unit
+            .chars()
+            .next()
+            .is_some_and(|c| NO_LINE_START_CHARS.contains(c));
+        if fits || line.is_empty() || hangs {
+            line.push_str(&unit);
+        } else {
+            lines.push(std::mem::take(&mut line).trim_end().to_string());
+            line = unit.trim_start().to_string();
+        }
+        // 1単語で行幅を超える場合は文字単位で折り返す
+        while text_width(&line, font_size) > max_width && line.chars().count() > 1 {
+            let mut head = String::new();
+            for c in line.chars() {
+                if !head.is_empty()
+                    && !NO_LINE_START_CHARS.contains(c)
+                    && text_width(&head, font_size) + text_width(&c.to_string(), font_size)
+                        > max_width
+                {
+                    break;
+                }
+                head.push(c);
+            }
+            if head.len() == line.len() {
+                // 残りが行頭禁則文字だけならぶら下げる
+                break;
+            }
+            line = line[head.len()..].to_string();
+            lines.push(head);
+        }
+    }
+    if !line.trim().is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// タイトルの文字サイズと行を決める（最小サイズでも収まらなければ最終行を省略する）
+fn layout_title(title: &str, max_width: f32) -> (f32, Vec<String>) {
+    for font_size in TITLE_FONT_SIZES {
+        let lines = wrap_text(title, max_width, font_size);
+        if lines.len() <= TITLE_MAX_LINES {
+            return (font_size, lines);
+        }
+    }
+
+    let font_size = TITLE_FONT_SIZES[TITLE_FONT_SIZES.len() - 1];
+    let mut lines = wrap_text(title, max_width, font_size);
+    lines.truncate(TITLE_MAX_LINES);
+    if let Some(last) = lines.last_mut() {
+        while !last.is_empty() && text_width(&format!("{last}…"), font_size) > max_width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    (font_size, lines)
+}
+
+/// OGP画像のSVGを組み立てる
+fn build_og_card_svg(content: &OgCardContent, avatar_data_url: Option<&str>) -> String {
+    let max_title_width = OG_IMAGE_WIDTH as f32 - PADDING_X * 2.0;
+    let (font_size, lines) = layout_title(&content.title, max_title_width);
+    let line_height = font_size * 1.4;
+    // タイトルは上下の帯の間で縦方向に中央揃えする
+    let title_top = 315.0 - line_height * lines.len() as f32 / 2.0;
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{OG_IMAGE_WIDTH}" height="{OG_IMAGE_HEIGHT}" viewBox="0 0 {OG_IMAGE_WIDTH} {OG_IMAGE_HEIGHT}" font-family="{FONT_FAMILY}">
+<defs>
+<linearGradient id="bg" x1="0" y1="0" x2="1" y2="1">
+<stop offset="0" stop-color="#1a1a2e"/>
+<stop offset="1" stop-color="#16213e"/>
+</linearGradient>
+<clipPath id="avatar"><circle cx="{avatar_cx}" cy="540" r="40"/></clipPath>
+</defs>
+<rect width="100%" height="100%" fill="url(#bg)"/>
+<rect x="0" y="0" width="{OG_IMAGE_WIDTH}" height="12" fill="#e94560"/>
+"##,
+        avatar_cx = PADDING_X + 40.0,
+    );
+
+    if !content.categories.is_empty() {
+        let categories = content
+            .categories
+            .iter()
+            .map(|category| format!("#{category}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        svg.push_str(&format!(
+            "<text x=\"{PADDING_X}\" y=\"110\" font-size=\"32\" fill=\"#e94560\">{}</text>\n",
+            escape_xml(&categories)
+        ));
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        let baseline = title_top + line_height * index as f32 + font_size;
+        svg.push_str(&format!(
+            "<text x=\"{PADDING_X}\" y=\"{baseline}\" font-size=\"{font_size}\" font-weight=\"bold\" fill=\"#ffffff\">{}</text>\n",
+            escape_xml(line)
+        ));
+    }
+
+    let site_name_x = match avatar_data_url {
+        Some(data_url) => {
+            svg.push_str(&format!(
+                "<image x=\"{PADDING_X}\" y=\"500\" width=\"80\" height=\"80\" clip-path=\"url(#avatar)\" href=\"{}\"/>\n",
+                escape_xml(data_url)
+            ));
+            PADDING_X + 104.0
+        }
+        None => PADDING_X,
+    };
+    svg.push_str(&format!(
+        "<text x=\"{site_name_x}\" y=\"552\" font-size=\"34\" fill=\"#c8c8d8\">{}</text>\n",
+        escape_xml(WEB_APP_TITLE)
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// SVGをPNGに変換
+fn rasterize(svg: &str, fontdb: Arc<fontdb::Database>) -> Result<Vec<u8>, OgImageError> {
+    let options = usvg::Options {
+        fontdb,
+        languages: vec!["ja".to_string(), "en".to_string()],
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|err| OgImageError::InvalidSvg(err.to_string()))?;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT)
+        .ok_or_else(|| OgImageError::Encode("Failed to allocate pixmap".to_string()))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|err| OgImageError::Encode(err.to_string()))
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(title: &str) -> OgCardContent {
+        OgCardContent {
+            title: title.to_string(),
+            categories: vec!["Rust".to_string(), "Leptos".to_string()],
+        }
+    }
+
+    #[test]
+    fn 短いタイトルは最大の文字サイズで1行になること() {
+        let (font_size, lines) = layout_title("Rustでブログを作った", 1040.0);
+        assert_eq!(font_size, 64.0);
+        assert_eq!(lines, ["Rustでブログを作った"]);
+    }
+
+    #[test]
+    fn 長い日本語タイトルは行幅で折り返され句読点は行頭に来ないこと() {
+        let title = "あいうえおかきくけこさしすせそたちつてと、なにぬね";
+        let lines = wrap_text(title, 64.0 * 20.0, 64.0);
+        assert_eq!(
+            lines,
+            ["あいうえおかきくけこさしすせそたちつてと、", "なにぬね"]
+        );
+    }
+
+    #[test]
+    fn 英単語の途中では改行しないこと() {
+        let lines = wrap_text("hello wonderful world", 0.58 * 10.0 * 16.0, 10.0);
+        assert!(
+            lines
+                .iter()
+                .all(|line| !line.starts_with(' ') && !line.ends_with(' ')),
+            "{lines:?}"
+        );
+        assert_eq!(lines.concat().replace(' ', ""), "hellowonderfulworld");
+        for line in &lines {
+            for word in line.split(' ') {
+                assert!(["hello", "wonderful", "world"].contains(&word), "{lines:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn 最大行数を超えるタイトルは末尾が省略されること() {
+        let title = "長".repeat(200);
+        let (font_size, lines) = layout_title(&title, 1040.0);
+        assert_eq!(font_size, 48.0);
+        assert_eq!(lines.len(), TITLE_MAX_LINES);
+        assert!(lines[2].ends_with('…'));
+        assert!(
+            lines
+                .iter()
+                .all(|line| text_width(line, font_size) <= 1040.0)
+        );
+    }
+
+    #[test]
+    fn svgのテキストがエスケープされること() {
+        let svg = build_og_card_svg(&content("<script> & \"quote\""), None);
+        assert!(
+            svg.contains("&lt;script&gt; &amp; &quot;quote&quot;"),
+            "{svg}"
+        );
+        assert!(svg.contains("#Rust  #Leptos"), "{svg}");
+        assert!(!svg.contains("<image"), "{svg}");
+    }
+
+    #[test]
+    fn 内容ハッシュはタイトルとカテゴリとアイコンの有無で変わること() {
+        let base = content_hash(&content("タイトル"), true);
+        assert_eq!(base.len(), 64);
+        assert_eq!(base, content_hash(&content("タイトル"), true));
+        assert_ne!(base, content_hash(&content("別のタイトル"), true));
+        assert_ne!(base, content_hash(&content("タイトル"), false));
+        let mut other_categories = content("タイトル");
+        other_categories.categories.pop();
+        assert_ne!(base, content_hash(&other_categories, true));
+    }
+
+    #[test]
+    fn 指定サイズのpngが生成されること() {
+        let png = rasterize(
+            &build_og_card_svg(&content("Rustでブログを作った"), None),
+            Arc::new(fontdb::Database::new()),
+        )
+        .expect("Failed to rasterize");
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDRチャンクの幅と高さ
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 1200);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 630);
+    }
+}
@@ -1,52 +1,869 @@
-use crate::SERVER_CONFIG;
 use crate::error::QiitaArticleServiceError;
-use crate::server::models::qiita_article::QiitaArticleList;
-use crate::server::utils::html::get_og_image_url;
+use crate::server::models::external_article::ExternalArticle;
+use crate::server::models::qiita_article::{
+    PostedQiitaItem, QiitaArticle, QiitaArticleList, QiitaItemInput,
+};
+use crate::server::services::external_source::{ExternalArticleSource, ExternalArticlesFuture};
+use crate::server::utils::html::fetch_og_image_urls;
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, LINK, RETRY_AFTER};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::instrument;
 
+/// 記事のOGP画像を同時に取得する最大数
+const QIITA_OGP_FETCH_CONCURRENCY: usize = 4;
+
+/// 1ページで取得する記事数（Qiita APIの上限）
+const QIITA_PER_PAGE: u32 = 100;
+
+/// レート制限で断られた場合に再試行する最大回数
+const QIITA_MAX_RETRIES: u32 = 3;
+
+/// 再試行まで待つ最大時間（制限の解除がこれより先なら再試行せずに諦める）
+const QIITA_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// 前回の取得結果とレート制限の状況
+#[derive(Debug, Default)]
+struct QiitaSyncState {
+    /// 前回取得した記事（IDごと。OG画像URLを含む）
+    items: HashMap<String, QiitaArticle>,
+    /// レート制限の残りがなくなった場合、制限が解除される日時
+    rate_limited_until: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct QiitaArticleService {
     client: reqwest::Client,
     qiita_base_url: Arc<String>,
+    api_token: Arc<String>,
+    /// 記事を一覧するユーザー（未指定なら認証ユーザー）
+    user_name: Option<Arc<String>>,
+    /// 記事一覧を辿る最大ページ数
+    max_pages: u32,
+    state: Arc<Mutex<QiitaSyncState>>,
 }
 
 impl QiitaArticleService {
-    #[instrument]
-    pub(crate) fn new(client: reqwest::Client, qiita_base_url: impl ToString + Debug) -> Self {
+    #[instrument(skip(api_token))]
+    pub(crate) fn new(
+        client: reqwest::Client,
+        qiita_base_url: impl ToString + Debug,
+        api_token: impl ToString,
+        user_name: Option<String>,
+        max_pages: u32,
+    ) -> Self {
         Self {
             client,
             qiita_base_url: Arc::new(qiita_base_url.to_string()),
+            api_token: Arc::new(api_token.to_string()),
+            user_name: user_name.map(Arc::new),
+            max_pages,
+            state: Arc::new(Mutex::new(QiitaSyncState::default())),
         }
     }
 
+    fn items_url(&self) -> String {
+        let path = match &self.user_name {
+            Some(user_name) => format!("/api/v2/users/{user_name}/items"),
+            None => "/api/v2/authenticated_user/items".to_string(),
+        };
+        format!(
+            "{}{path}?page=1&per_page={QIITA_PER_PAGE}",
+            self.qiita_base_url
+        )
+    }
+
+    /// 記事を全ページ取得し、記事ごとにOG画像を補う
+    ///
+    /// OG画像は前回の取得から更新された記事（と新しい記事）だけ取得し直す。
     #[instrument]
     pub(crate) async fn fetch_articles(
         &self,
     ) -> Result<QiitaArticleList, QiitaArticleServiceError> {
-        let (base_url, api_token) = (&self.qiita_base_url, &SERVER_CONFIG.qiita_api_token);
+        let mut articles = self.fetch_items().await?;
+        self.fill_og_image_urls(&mut articles).await;
+
+        Ok(articles)
+    }
+
+    /// `Link` ヘッダーの `next` を辿って記事を全ページ取得する（OG画像は補わない）
+    ///
+    /// `Total-Count` に達するか `max_pages` ページ取得したら打ち切る。
+    /// 前回の取得結果がある場合、ページの記事がすべて前回取得済みで前回の最終更新日時より新しくなければ、
+    /// 以降のページは前回の取得結果を使って打ち切る（`Total-Count` と件数が合わない場合は削除された記事があるため辿り続ける）。
+    /// `next` がQiitaのベースURLと異なるオリジンの場合はトークンを送らないよう辿らない。
+    #[instrument]
+    pub(crate) async fn fetch_items(&self) -> Result<QiitaArticleList, QiitaArticleServiceError> {
+        let previous = self.state.lock().unwrap().items.clone();
+        let last_synced_at = previous.values().map(|article| article.updated_at).max();
+
+        let mut articles: QiitaArticleList = Vec::new();
+        let mut next_url = Some(self.items_url());
+        let mut pages = 0;
+        while let Some(url) = next_url.take() {
+            if pages >= self.max_pages {
+                tracing::warn!(
+                    max_pages = self.max_pages,
+                    "Stopped fetching Qiita items at the page limit"
+                );
+                break;
+            }
+            pages += 1;
+
+            let response = self.send_with_backoff(|| self.client.get(&url)).await?;
+            let total_count = header_value::<usize>(response.headers(), "total-count");
+            next_url = next_page_url(response.headers()).filter(|next| {
+                let same_origin = is_same_origin(next, &self.qiita_base_url);
+                if !same_origin {
+                    tracing::warn!(next, "Ignored Qiita next page on another origin");
+                }
+                same_origin
+            });
+
+            let page: QiitaArticleList = response.json().await?;
+            let is_empty = page.is_empty();
+            let up_to_date = last_synced_at.is_some_and(|last_synced_at| {
+                page.iter().all(|article| {
+                    article.updated_at <= last_synced_at && previous.contains_key(&article.id)
+                })
+            });
+            articles.extend(page);
+            if is_empty || total_count.is_some_and(|total| articles.len() >= total) {
+                break;
+            }
+            if up_to_date && next_url.is_some() {
+                let fetched: HashSet<&str> =
+                    articles.iter().map(|article| article.id.as_str()).collect();
+                let mut rest: QiitaArticleList = previous
+                    .values()
+                    .filter(|article| !fetched.contains(article.id.as_str()))
+                    .cloned()
+                    .collect();
+                if total_count.is_none_or(|total| articles.len() + rest.len() == total) {
+                    rest.sort_by_key(|article| std::cmp::Reverse(article.created_at));
+                    articles.extend(rest);
+                    break;
+                }
+            }
+        }
+
+        Ok(articles)
+    }
 
+    /// 記事を投稿する
+    #[instrument(skip(self, item), fields(title = %item.title))]
+    pub(crate) async fn create_item(
+        &self,
+        item: &QiitaItemInput,
+    ) -> Result<PostedQiitaItem, QiitaArticleServiceError> {
+        let url = format!("{}/api/v2/items", self.qiita_base_url);
+        let response = self
+            .send_with_backoff(|| self.client.post(&url).json(item))
+            .await?;
+
+        Ok(response.json().await?)
+    }
+
+    /// 投稿済みの記事を更新する
+    #[instrument(skip(self, item), fields(title = %item.title))]
+    pub(crate) async fn update_item(
+        &self,
+        item_id: &str,
+        item: &QiitaItemInput,
+    ) -> Result<PostedQiitaItem, QiitaArticleServiceError> {
+        let url = format!("{}/api/v2/items/{item_id}", self.qiita_base_url);
         let response = self
-            .client
-            .get(format!("{base_url}/api/v2/authenticated_user/items"))
-            .bearer_auth(api_token)
-            .send()
+            .send_with_backoff(|| self.client.patch(&url).json(item))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(QiitaArticleServiceError::UnexpectedStatusCode(
-                response.status(),
-            ));
+        Ok(response.json().await?)
+    }
+
+    /// 前回から変わっていない記事は前回のOG画像を使い、それ以外だけ取得する
+    async fn fill_og_image_urls(&self, articles: &mut QiitaArticleList) {
+        {
+            let state = self.state.lock().unwrap();
+            for article in articles.iter_mut() {
+                if let Some(previous) = state.items.get(&article.id)
+                    && previous.updated_at == article.updated_at
+                {
+                    article.og_image_url = previous.og_image_url.clone();
+                }
+            }
         }
 
-        let mut articles: QiitaArticleList = response.json().await?;
-        for article in articles.iter_mut() {
-            article.og_image_url = get_og_image_url(&self.client, &article.url)
-                .await?
-                .map_or(article.user.profile_image_url.clone(), |url| url);
+        let (indices, urls): (Vec<usize>, Vec<String>) = articles
+            .iter()
+            .enumerate()
+            .filter(|(_, article)| article.og_image_url.is_empty())
+            .map(|(index, article)| (index, article.url.clone()))
+            .unzip();
+        let og_image_urls =
+            fetch_og_image_urls(&self.client, &urls, QIITA_OGP_FETCH_CONCURRENCY).await;
+        for (index, og_image_url) in indices.into_iter().zip(og_image_urls) {
+            let article = &mut articles[index];
+            article.og_image_url =
+                og_image_url.unwrap_or_else(|| article.user.profile_image_url.clone());
         }
 
-        Ok(articles)
+        self.state.lock().unwrap().items = articles
+            .iter()
+            .map(|article| (article.id.clone(), article.clone()))
+            .collect();
+    }
+
+    /// レート制限を守ってリクエストする
+    ///
+    /// 残りがなくなった後は解除まで問い合わせない。断られた場合は `Retry-After`（なければ解除までの時間、
+    /// どちらもなければ指数的に伸ばした時間）だけ待って再試行する。再試行のたびに `request` でリクエストを作り直す。
+    async fn send_with_backoff(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, QiitaArticleServiceError> {
+        let rate_limited_until = self.state.lock().unwrap().rate_limited_until;
+        if let Some(until) = rate_limited_until
+            && Utc::now() < until
+        {
+            return Err(QiitaArticleServiceError::RateLimited(Some(until)));
+        }
+
+        let mut attempt = 0;
+        loop {
+            let response = request()
+                .bearer_auth(self.api_token.as_str())
+                .send()
+                .await?;
+
+            let remaining = header_value::<u64>(response.headers(), "rate-remaining");
+            let reset_at = header_value::<i64>(response.headers(), "rate-reset")
+                .and_then(|reset| DateTime::from_timestamp(reset, 0));
+            self.state.lock().unwrap().rate_limited_until =
+                (remaining == Some(0)).then_some(reset_at).flatten();
+
+            let status = response.status();
+            let rate_limited = status == StatusCode::TOO_MANY_REQUESTS
+                || (status == StatusCode::FORBIDDEN && remaining == Some(0));
+            if !rate_limited {
+                if !status.is_success() {
+                    return Err(QiitaArticleServiceError::UnexpectedStatusCode(status));
+                }
+                return Ok(response);
+            }
+
+            let delay = header_value::<u64>(response.headers(), RETRY_AFTER.as_str())
+                .map(Duration::from_secs)
+                .or_else(|| {
+                    reset_at.map(|reset_at| (reset_at - Utc::now()).to_std().unwrap_or_default())
+                })
+                .unwrap_or_else(|| Duration::from_secs(1 << attempt));
+            if attempt >= QIITA_MAX_RETRIES || delay > QIITA_MAX_BACKOFF {
+                return Err(QiitaArticleServiceError::RateLimited(reset_at));
+            }
+            tracing::warn!(attempt, ?delay, "Qiita API rate limited, retrying");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// ヘッダーの値を数値として読む
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// 2つのURLのオリジン（スキーム・ホスト・ポート）が同じか判定
+fn is_same_origin(url: &str, base_url: &str) -> bool {
+    match (url::Url::parse(url), url::Url::parse(base_url)) {
+        (Ok(url), Ok(base_url)) => url.origin() == base_url.origin(),
+        _ => false,
+    }
+}
+
+/// `Link` ヘッダーから `rel="next"` のURLを取り出す
+fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(LINK)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .find_map(|link| {
+            let (url, params) = link.split_once(';')?;
+            params
+                .split(';')
+                .any(|param| matches!(param.trim(), r#"rel="next""# | "rel=next"))
+                .then(|| {
+                    url.trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                })
+        })
+}
+
+impl ExternalArticleSource for QiitaArticleService {
+    fn name(&self) -> &str {
+        "Qiita"
+    }
+
+    fn fetch_articles(&self) -> ExternalArticlesFuture<'_> {
+        Box::pin(async move {
+            let articles = QiitaArticleService::fetch_articles(self).await?;
+            Ok(articles.into_iter().map(ExternalArticle::from).collect())
+        })
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::models::qiita_article::User;
+    use chrono::TimeZone;
+    use mockito::{Matcher, Server};
+
+    const FIRST_PAGE: &str = "/api/v2/authenticated_user/items?page=1&per_page=100";
+
+    fn item(id: &str, base_url: &str) -> QiitaArticle {
+        QiitaArticle {
+            id: id.to_string(),
+            url: format!("{base_url}/Romira/items/{id}"),
+            updated_at: Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(),
+            user: User {
+                profile_image_url: "https://example.com/avatar.png".to_string(),
+                ..User::default()
+            },
+            ..QiitaArticle::default()
+        }
+    }
+
+    fn items_json(ids: &[&str], base_url: &str) -> String {
+        let items: Vec<QiitaArticle> = ids.iter().map(|id| item(id, base_url)).collect();
+        serde_json::to_string(&items).unwrap()
+    }
+
+    /// `page` ページ目の一覧のモック（`next` があれば `Link` ヘッダーで次のページを示す）
+    fn mock_page(
+        server: &mut Server,
+        page: u32,
+        ids: &[&str],
+        next: Option<u32>,
+        total_count: usize,
+    ) -> mockito::Mock {
+        let url = server.url();
+        let mut mock = server
+            .mock("GET", "/api/v2/authenticated_user/items")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page".into(), page.to_string()),
+                Matcher::UrlEncoded("per_page".into(), "100".into()),
+            ]))
+            .with_header("total-count", &total_count.to_string())
+            .with_body(items_json(ids, &url));
+        if let Some(next) = next {
+            mock = mock.with_header(
+                "link",
+                &format!(
+                    r#"<{url}/api/v2/authenticated_user/items?page=1&per_page=100>; rel="first", <{url}/api/v2/authenticated_user/items?page={next}&per_page=100>; rel="next""#
+                ),
+            );
+        }
+        mock
+    }
+
+    fn service(url: &str, max_pages: u32) -> QiitaArticleService {
+        QiitaArticleService::new(reqwest::Client::new(), url, "token", None, max_pages)
+    }
+
+    fn ids(articles: &[QiitaArticle]) -> Vec<&str> {
+        articles.iter().map(|article| article.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_ユーザー名未指定の場合は認証ユーザーの記事一覧を取得すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let mock = server
+            .mock("GET", FIRST_PAGE)
+            .match_header("authorization", "Bearer token")
+            .with_body(r#"[]"#)
+            .create();
+
+        let service = service(&url, 10);
+
+        let result = service.fetch_articles().await;
+
+        mock.assert();
+        assert_eq!(result.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_ユーザー名を指定した場合はそのユーザーの記事一覧を取得すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let mock = server
+            .mock("GET", "/api/v2/users/Romira/items?page=1&per_page=100")
+            .with_body(r#"[]"#)
+            .create();
+
+        let service = QiitaArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            "token",
+            Some("Romira".to_string()),
+            10,
+        );
+
+        let result = service.fetch_articles().await;
+
+        mock.assert();
+        assert_eq!(result.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_linkヘッダーのnextを辿って全ページを取得すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let pages = [
+            mock_page(&mut server, 1, &["1", "2"], Some(2), 5)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 2, &["3", "4"], Some(3), 5)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 3, &["5"], None, 5)
+                .expect(1)
+                .create(),
+        ];
+
+        let articles = service(&url, 10).fetch_articles().await.unwrap();
+
+        for page in pages {
+            page.assert();
+        }
+        assert_eq!(ids(&articles), vec!["1", "2", "3", "4", "5"]);
+    }
+
+    #[tokio::test]
+    async fn test_最大ページ数で打ち切ること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let pages = [
+            mock_page(&mut server, 1, &["1"], Some(2), 3)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 2, &["2"], Some(3), 3)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 3, &["3"], None, 3)
+                .expect(0)
+                .create(),
+        ];
+
+        let articles = service(&url, 2).fetch_articles().await.unwrap();
+
+        for page in pages {
+            page.assert();
+        }
+        assert_eq!(ids(&articles), vec!["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_total_countに達したら次のページを取得しないこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let pages = [
+            mock_page(&mut server, 1, &["1", "2"], Some(2), 2)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 2, &[], None, 2).expect(0).create(),
+        ];
+
+        let articles = service(&url, 10).fetch_articles().await.unwrap();
+
+        for page in pages {
+            page.assert();
+        }
+        assert_eq!(ids(&articles), vec!["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_前回から更新された記事がないページで打ち切り以降は前回の記事を使うこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let first_page = mock_page(&mut server, 1, &["1", "2"], Some(2), 3)
+            .expect(2)
+            .create();
+        let second_page = mock_page(&mut server, 2, &["3"], None, 3)
+            .expect(1)
+            .create();
+
+        let service = service(&url, 10);
+        service.fetch_articles().await.unwrap();
+        let articles = service.fetch_items().await.unwrap();
+
+        first_page.assert();
+        second_page.assert();
+        assert_eq!(ids(&articles), vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_件数がtotal_countと合わない場合は打ち切らずに次のページを取得すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let first_sync = [
+            mock_page(&mut server, 1, &["1", "2"], Some(2), 4)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 2, &["3", "4"], None, 4)
+                .expect(1)
+                .create(),
+        ];
+        let service = service(&url, 10);
+        service.fetch_articles().await.unwrap();
+        for page in first_sync {
+            page.assert();
+        }
+
+        // 「3」が削除された
+        let second_sync = [
+            mock_page(&mut server, 1, &["1", "2"], Some(2), 3)
+                .expect(1)
+                .create(),
+            mock_page(&mut server, 2, &["4"], None, 3)
+                .expect(1)
+                .create(),
+        ];
+        let articles = service.fetch_items().await.unwrap();
+
+        for page in second_sync {
+            page.assert();
+        }
+        assert_eq!(ids(&articles), vec!["1", "2", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_nextがベースurlと異なるオリジンの場合は辿らないこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let mock = server
+            .mock("GET", FIRST_PAGE)
+            .with_header(
+                "link",
+                r#"<https://evil.example.com/api/v2/authenticated_user/items?page=2&per_page=100>; rel="next""#,
+            )
+            .with_body(items_json(&["1"], &url))
+            .expect(1)
+            .create();
+
+        let articles = service(&url, 10).fetch_items().await.unwrap();
+
+        mock.assert();
+        assert_eq!(ids(&articles), vec!["1"]);
+    }
+
+    #[test]
+    fn test_同じオリジンか判定すること() {
+        assert!(is_same_origin(
+            "https://qiita.com/api/v2/items?page=2",
+            "https://qiita.com"
+        ));
+        assert!(!is_same_origin(
+            "https://qiita.com.evil.example/api/v2/items?page=2",
+            "https://qiita.com"
+        ));
+        assert!(!is_same_origin(
+            "http://qiita.com/api/v2/items?page=2",
+            "https://qiita.com"
+        ));
+        assert!(!is_same_origin("/api/v2/items?page=2", "https://qiita.com"));
+    }
+
+    #[tokio::test]
+    async fn test_前回から更新されていない記事はogp画像を取得し直さないこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let unchanged = item("unchanged", &url);
+        let mut updated = item("updated", &url);
+        let first_page = server
+            .mock("GET", FIRST_PAGE)
+            .with_body(serde_json::to_string(&[&unchanged, &updated]).unwrap())
+            .expect(1)
+            .create();
+        updated.updated_at = Utc.with_ymd_and_hms(2026, 10, 2, 0, 0, 0).unwrap();
+        let added = item("added", &url);
+        let second_page = server
+            .mock("GET", FIRST_PAGE)
+            .with_body(serde_json::to_string(&[&added, &unchanged, &updated]).unwrap())
+            .expect(1)
+            .create();
+        let ogp_mock = |server: &mut Server, id: &str, hits: usize| {
+            server
+                .mock("GET", format!("/Romira/items/{id}").as_str())
+                .with_header("content-type", "text/html")
+                .with_body(format!(
+                    r#"<html><head><meta property="og:image" content="https://example.com/{id}.png"></head></html>"#
+                ))
+                .expect(hits)
+                .create()
+        };
+        let ogp_mocks = [
+            ogp_mock(&mut server, "unchanged", 1),
+            ogp_mock(&mut server, "updated", 2),
+            ogp_mock(&mut server, "added", 1),
+        ];
+
+        let service = service(&url, 10);
+        service.fetch_articles().await.unwrap();
+        let articles = service.fetch_articles().await.unwrap();
+
+        first_page.assert();
+        second_page.assert();
+        for mock in ogp_mocks {
+            mock.assert();
+        }
+        let og_image_urls: Vec<&str> = articles.iter().map(|a| a.og_image_url.as_str()).collect();
+        assert_eq!(
+            og_image_urls,
+            vec![
+                "https://example.com/added.png",
+                "https://example.com/unchanged.png",
+                "https://example.com/updated.png",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_レート制限で断られた場合は待ってから再試行すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let limited = server
+            .mock("GET", FIRST_PAGE)
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create();
+        let succeeded = server
+            .mock("GET", FIRST_PAGE)
+            .with_body(r#"[]"#)
+            .expect(1)
+            .create();
+
+        let result = service(&url, 10).fetch_articles().await;
+
+        limited.assert();
+        succeeded.assert();
+        assert_eq!(result.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_レート制限の解除が先の場合は再試行せずにエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let reset_at = Utc::now().timestamp() + 3600;
+        let mock = server
+            .mock("GET", FIRST_PAGE)
+            .with_status(403)
+            .with_header("rate-remaining", "0")
+            .with_header("rate-reset", &reset_at.to_string())
+            .expect(1)
+            .create();
+
+        let service = service(&url, 10);
+        let first = service.fetch_articles().await;
+        // 解除されるまでは問い合わせない
+        let second = service.fetch_articles().await;
+
+        mock.assert();
+        let reset_at = DateTime::from_timestamp(reset_at, 0);
+        assert!(matches!(
+            first.unwrap_err(),
+            QiitaArticleServiceError::RateLimited(until) if until == reset_at
+        ));
+        assert!(matches!(
+            second.unwrap_err(),
+            QiitaArticleServiceError::RateLimited(until) if until == reset_at
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_残りがなくなった場合は次のページを取得せずにエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let reset_at = Utc::now().timestamp() + 3600;
+        let first_page = mock_page(&mut server, 1, &["1"], Some(2), 2)
+            .with_header("rate-remaining", "0")
+            .with_header("rate-reset", &reset_at.to_string())
+            .expect(1)
+            .create();
+        let second_page = mock_page(&mut server, 2, &["2"], None, 2)
+            .expect(0)
+            .create();
+
+        let result = service(&url, 10).fetch_articles().await;
+
+        first_page.assert();
+        second_page.assert();
+        assert!(matches!(
+            result.unwrap_err(),
+            QiitaArticleServiceError::RateLimited(_)
+        ));
+    }
+
+    #[test]
+    fn test_linkヘッダーからnextのurlを取り出すこと() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            r#"<https://qiita.com/api/v2/items?page=1>; rel="first", <https://qiita.com/api/v2/items?page=3>; rel="next", <https://qiita.com/api/v2/items?page=9>; rel="last""#
+                .parse()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            next_page_url(&headers),
+            Some("https://qiita.com/api/v2/items?page=3".to_string())
+        );
+
+        headers.insert(
+            LINK,
+            r#"<https://qiita.com/api/v2/items?page=1>; rel="first""#
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(next_page_url(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_ogp画像を記事ごとに1回ずつ取得し記事の順に設定すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let items: Vec<QiitaArticle> = (1..=6).map(|i| item(&i.to_string(), &url)).collect();
+        server
+            .mock("GET", FIRST_PAGE)
+            .with_body(serde_json::to_string(&items).unwrap())
+            .create();
+        let ogp_mocks: Vec<_> = (1..=6)
+            .map(|i| {
+                let mock = server.mock("GET", format!("/Romira/items/{i}").as_str());
+                // 3番目の記事はOGPを取得できない
+                let mock = if i == 3 {
+                    mock.with_status(404)
+                } else {
+                    mock.with_header("content-type", "text/html").with_body(format!(
+                        r#"<html><head><meta property="og:image" content="https://example.com/{i}.png"></head></html>"#
+                    ))
+                };
+                mock.expect(1).create()
+            })
+            .collect();
+
+        let service = QiitaArticleService::new(reqwest::Client::new(), &url, "token", None, 10);
+
+        let articles = service.fetch_articles().await.unwrap();
+
+        for mock in ogp_mocks {
+            mock.assert();
+        }
+        let og_image_urls: Vec<&str> = articles.iter().map(|a| a.og_image_url.as_str()).collect();
+        assert_eq!(
+            og_image_urls,
+            vec![
+                "https://example.com/1.png",
+                "https://example.com/2.png",
+                "https://example.com/avatar.png",
+                "https://example.com/4.png",
+                "https://example.com/5.png",
+                "https://example.com/6.png",
+            ]
+        );
+    }
+
+    fn item_input() -> QiitaItemInput {
+        QiitaItemInput {
+            title: "タイトル".to_string(),
+            body: "本文".to_string(),
+            tags: vec![crate::server::models::qiita_article::Tag {
+                name: "Rust".to_string(),
+                versions: vec![],
+            }],
+            private: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_記事を投稿して作成された記事のidとurlを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let mock = server
+            .mock("POST", "/api/v2/items")
+            .match_header("authorization", "Bearer token")
+            .match_body(Matcher::Json(serde_json::json!({
+                "title": "タイトル",
+                "body": "本文",
+                "tags": [{"name": "Rust", "versions": []}],
+                "private": false,
+            })))
+            .with_status(201)
+            .with_body(r#"{"id": "abc123", "url": "https://qiita.com/Romira/items/abc123", "title": "タイトル"}"#)
+            .create();
+
+        let item = service(&url, 10).create_item(&item_input()).await.unwrap();
+
+        mock.assert();
+        assert_eq!(
+            item,
+            PostedQiitaItem {
+                id: "abc123".to_string(),
+                url: "https://qiita.com/Romira/items/abc123".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_投稿済みの記事を更新する場合はpatchでリクエストすること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let limited = server
+            .mock("PATCH", "/api/v2/items/abc123")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create();
+        let updated = server
+            .mock("PATCH", "/api/v2/items/abc123")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"title": "タイトル"}),
+            ))
+            .with_body(r#"{"id": "abc123", "url": "https://qiita.com/Romira/items/abc123"}"#)
+            .expect(1)
+            .create();
+
+        let item = service(&url, 10)
+            .update_item("abc123", &item_input())
+            .await
+            .unwrap();
+
+        // レート制限で断られても作り直したリクエストで再試行する
+        limited.assert();
+        updated.assert();
+        assert_eq!(item.id, "abc123");
     }
 }
@@ -0,0 +1,181 @@
+use crate::error::QiitaImportError;
+use crate::server::models::qiita_article::{QiitaArticle, Tag};
+use crate::server::services::qiita::QiitaArticleService;
+use crate::server::utils::qiita_markdown::convert_qiita_markdown;
+use blog_romira_dev_cms::{
+    AdminArticleService, ArticleContent, ArticleSeo, Category, CategoryService, DraftArticleService,
+};
+use std::collections::HashSet;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 取り込みの候補となるQiitaの記事
+#[derive(Debug, Clone)]
+pub(crate) struct QiitaImportCandidate {
+    pub article: QiitaArticle,
+    /// 取り込み済み（取り込み元URLが記録された記事がある）か
+    pub imported: bool,
+}
+
+/// 取り込んで作成した下書き
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ImportedDraft {
+    pub id: Uuid,
+    pub title: String,
+}
+
+/// Qiitaの記事を下書きとして取り込むサービス
+#[derive(Debug, Clone)]
+pub(crate) struct QiitaImportService {
+    qiita_article_service: QiitaArticleService,
+    draft_article_service: DraftArticleService,
+    category_service: CategoryService,
+    admin_article_service: AdminArticleService,
+}
+
+impl QiitaImportService {
+    pub(crate) fn new(
+        qiita_article_service: QiitaArticleService,
+        draft_article_service: DraftArticleService,
+        category_service: CategoryService,
+        admin_article_service: AdminArticleService,
+    ) -> Self {
+        Self {
+            qiita_article_service,
+            draft_article_service,
+            category_service,
+            admin_article_service,
+        }
+    }
+
+    /// Qiitaの記事一覧を取り込み済みかどうかと合わせて取得
+    #[instrument(skip(self))]
+    pub(crate) async fn fetch_candidates(
+        &self,
+    ) -> Result<Vec<QiitaImportCandidate>, QiitaImportError> {
+        let articles = self.qiita_article_service.fetch_items().await?;
+        let imported_urls = self.imported_urls().await?;
+
+        Ok(articles
+            .into_iter()
+            .map(|article| QiitaImportCandidate {
+                imported: imported_urls.contains(&article.url),
+                article,
+            })
+            .collect())
+    }
+
+    /// 指定したIDのQiitaの記事を下書きとして取り込む
+    ///
+    /// 本文はQiita独自の記法を変換し、タグは同名のカテゴリ（なければ作成）に対応させる。
+    /// 取り込み済みの記事と、一覧に見つからないIDは取り込まない。
+    #[instrument(skip(self))]
+    pub(crate) async fn import(
+        &self,
+        ids: &[String],
+    ) -> Result<Vec<ImportedDraft>, QiitaImportError> {
+        let articles = self.qiita_article_service.fetch_items().await?;
+        let imported_urls = self.imported_urls().await?;
+        let categories = self.category_service.fetch_all().await?;
+
+        let mut drafts = Vec::new();
+        for article in articles
+            .iter()
+            .filter(|article| ids.contains(&article.id) && !imported_urls.contains(&article.url))
+        {
+            drafts.push(self.import_article(article, &categories).await?);
+        }
+        Ok(drafts)
+    }
+
+    async fn import_article(
+        &self,
+        article: &QiitaArticle,
+        categories: &[Category],
+    ) -> Result<ImportedDraft, QiitaImportError> {
+        let id = Uuid::now_v7();
+        let slug = format!("qiita-{}", article.id.to_ascii_lowercase());
+        let body = convert_qiita_markdown(&article.body);
+        let content = ArticleContent {
+            title: &article.title,
+            slug: &slug,
+            body: &body,
+            description: None,
+            cover_image_url: None,
+            seo: &ArticleSeo::default(),
+        };
+        self.draft_article_service.save(id, &content).await?;
+        self.draft_article_service
+            .set_origin_url(id, Some(&article.url))
+            .await?;
+
+        let category_names = category_names_for_tags(&article.tags, categories);
+        self.category_service
+            .save_for_draft(id, &category_names)
+            .await?;
+
+        Ok(ImportedDraft {
+            id,
+            title: article.title.clone(),
+        })
+    }
+
+    async fn imported_urls(&self) -> Result<HashSet<String>, QiitaImportError> {
+        Ok(self
+            .admin_article_service
+            .fetch_origin_urls()
+            .await?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// タグを対応するカテゴリ名にする
+///
+/// 大文字・小文字だけが異なる既存のカテゴリがあればその名前を使う（カテゴリのslugが重複しないように）。
+fn category_names_for_tags(tags: &[Tag], categories: &[Category]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let name = categories
+            .iter()
+            .find(|category| category.name.eq_ignore_ascii_case(tag.name.trim()))
+            .map_or_else(
+                || tag.name.trim().to_string(),
+                |category| category.name.clone(),
+            );
+        if !names
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&name))
+        {
+            names.push(name);
+        }
+    }
+    names
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            name: name.to_string(),
+            versions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_大文字小文字だけが異なる既存のカテゴリ名にそろえること() {
+        let categories = vec![Category {
+            id: Uuid::now_v7(),
+            name: "Rust".to_string(),
+            slug: "rust".to_string(),
+        }];
+
+        let names =
+            category_names_for_tags(&[tag("rust"), tag("Leptos"), tag("RUST")], &categories);
+
+        assert_eq!(names, vec!["Rust".to_string(), "Leptos".to_string()]);
+    }
+}
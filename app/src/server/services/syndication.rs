@@ -0,0 +1,298 @@
+use crate::common::markdown::extract_wiki_link_slugs;
+use crate::error::{QiitaArticleServiceError, SyndicationError};
+use crate::server::models::qiita_article::{PostedQiitaItem, QiitaItemInput, Tag};
+use crate::server::services::qiita::QiitaArticleService;
+use crate::server::utils::qiita_markdown::to_qiita_markdown;
+use blog_romira_dev_cms::{
+    ArticleSyndication, ArticleSyndicationService, ArticleVisibility, Category, CmsError,
+    PublishedArticleService, PublishedArticleWithCategories,
+};
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Qiitaの転載先名（`article_syndications.target`）
+pub(crate) const QIITA_SYNDICATION_TARGET: &str = "qiita";
+
+/// Qiitaの記事に付けられるタグの最大数
+const QIITA_MAX_TAGS: usize = 5;
+
+/// 反映中に記事が更新された場合に反映し直す回数の上限（最初の反映を含む）
+const SYNDICATION_MAX_ROUNDS: usize = 3;
+
+/// 公開記事を外部サービスに転載するサービス
+#[derive(Debug, Clone)]
+pub(crate) struct SyndicationService {
+    /// Qiitaへの転載（転載先に設定されていなければ `None`）
+    qiita_article_service: Option<QiitaArticleService>,
+    published_article_service: PublishedArticleService,
+    article_syndication_service: ArticleSyndicationService,
+    site_url: Arc<String>,
+}
+
+impl SyndicationService {
+    pub(crate) fn new(
+        qiita_article_service: Option<QiitaArticleService>,
+        published_article_service: PublishedArticleService,
+        article_syndication_service: ArticleSyndicationService,
+        site_url: impl ToString,
+    ) -> Self {
+        Self {
+            qiita_article_service,
+            published_article_service,
+            article_syndication_service,
+            site_url: Arc::new(site_url.to_string().trim_end_matches('/').to_string()),
+        }
+    }
+
+    /// 公開記事の転載先ごとの反映状況を取得
+    #[instrument(skip(self))]
+    pub(crate) async fn fetch_statuses(
+        &self,
+        article_id: Uuid,
+    ) -> Result<Vec<ArticleSyndication>, CmsError> {
+        self.article_syndication_service
+            .fetch_for_article(article_id)
+            .await
+    }
+
+    /// 転載先に記事がある反映状況をすべて取得
+    #[instrument(skip(self))]
+    pub(crate) async fn fetch_synced(&self) -> Result<Vec<ArticleSyndication>, CmsError> {
+        self.article_syndication_service.fetch_synced().await
+    }
+
+    /// 公開記事を転載先に反映し、反映状況を記録する
+    ///
+    /// 一覧に表示する公開中の記事だけ反映する（取り込んだ記事は取り込み元があるため反映しない）。
+    /// 一度投稿した記事は以後同じ記事を更新し、公開範囲が `public` でなくなった記事は限定共有にする。
+    /// 同じ記事を同時に投稿しないよう反映中の印を立ててから反映し、他で反映中なら何もしない
+    /// （反映した側が反映後に記事の更新を確かめ、更新されていれば反映し直す）。
+    /// 転載先への反映の失敗は反映状況に記録し、エラーにはしない。
+    #[instrument(skip(self))]
+    pub(crate) async fn syndicate(&self, article_id: Uuid) -> Result<(), CmsError> {
+        let Some(qiita_article_service) = &self.qiita_article_service else {
+            return Ok(());
+        };
+
+        let mut synced_updated_at = None;
+        for _ in 0..SYNDICATION_MAX_ROUNDS {
+            let Some(article) = self
+                .published_article_service
+                .fetch_by_id(article_id)
+                .await?
+            else {
+                return Ok(());
+            };
+            if article.article.origin_url.is_some()
+                || synced_updated_at == Some(article.article.updated_at)
+            {
+                return Ok(());
+            }
+            if !self
+                .syndicate_to_qiita(qiita_article_service, &article)
+                .await?
+            {
+                return Ok(());
+            }
+            synced_updated_at = Some(article.article.updated_at);
+        }
+        Ok(())
+    }
+
+    /// 記事をQiitaに反映して反映状況を記録し、反映を試みたかを返す（他で反映中・反映不要なら `false`）
+    async fn syndicate_to_qiita(
+        &self,
+        qiita_article_service: &QiitaArticleService,
+        article: &PublishedArticleWithCategories,
+    ) -> Result<bool, CmsError> {
+        let article_id = article.article.id;
+        let public = article.article.visibility == ArticleVisibility::Public;
+        // 投稿したことのない記事を限定共有にすることはないため、印を立てずに済ませる
+        if !public
+            && self
+                .article_syndication_service
+                .fetch(article_id, QIITA_SYNDICATION_TARGET)
+                .await?
+                .is_none_or(|syndication| syndication.remote_id.is_none())
+        {
+            return Ok(false);
+        }
+
+        let Some(syndication) = self
+            .article_syndication_service
+            .claim(article_id, QIITA_SYNDICATION_TARGET)
+            .await?
+        else {
+            tracing::info!("Skipped syndicating article already being syndicated to Qiita");
+            return Ok(false);
+        };
+        let result = match (public, syndication.remote_id) {
+            (true, remote_id) => {
+                self.post_to_qiita(qiita_article_service, article, remote_id.as_deref())
+                    .await
+            }
+            (false, Some(remote_id)) => {
+                self.make_private_on_qiita(qiita_article_service, article, &remote_id)
+                    .await
+            }
+            (false, None) => {
+                self.article_syndication_service
+                    .release(article_id, QIITA_SYNDICATION_TARGET)
+                    .await?;
+                return Ok(false);
+            }
+        };
+        match result {
+            Ok(item) => {
+                self.article_syndication_service
+                    .record_success(article_id, QIITA_SYNDICATION_TARGET, &item.id, &item.url)
+                    .await?
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to syndicate article to Qiita");
+                self.article_syndication_service
+                    .record_failure(article_id, QIITA_SYNDICATION_TARGET, &e.to_string())
+                    .await?
+            }
+        }
+        Ok(true)
+    }
+
+    /// Qiitaに投稿する（投稿済みなら更新し、Qiita側で削除されていれば投稿し直す）
+    async fn post_to_qiita(
+        &self,
+        qiita_article_service: &QiitaArticleService,
+        article: &PublishedArticleWithCategories,
+        remote_id: Option<&str>,
+    ) -> Result<PostedQiitaItem, SyndicationError> {
+        let item = self.qiita_item(article, false).await?;
+
+        if let Some(remote_id) = remote_id {
+            match qiita_article_service.update_item(remote_id, &item).await {
+                Err(QiitaArticleServiceError::UnexpectedStatusCode(StatusCode::NOT_FOUND)) => {
+                    tracing::warn!(remote_id, "Qiita item not found, posting it again");
+                }
+                result => return Ok(result?),
+            }
+        }
+        Ok(qiita_article_service.create_item(&item).await?)
+    }
+
+    /// 投稿済みのQiitaの記事を限定共有にする（Qiita側で削除されていても投稿し直さない）
+    async fn make_private_on_qiita(
+        &self,
+        qiita_article_service: &QiitaArticleService,
+        article: &PublishedArticleWithCategories,
+        remote_id: &str,
+    ) -> Result<PostedQiitaItem, SyndicationError> {
+        let item = self.qiita_item(article, true).await?;
+        qiita_article_service
+            .update_item(remote_id, &item)
+            .await
+            .map_err(SyndicationError::NotMadePrivate)
+    }
+
+    /// Qiitaに投稿する記事
+    async fn qiita_item(
+        &self,
+        article: &PublishedArticleWithCategories,
+        private: bool,
+    ) -> Result<QiitaItemInput, SyndicationError> {
+        let tags = qiita_tags(&article.categories);
+        if tags.is_empty() {
+            return Err(SyndicationError::NoTags);
+        }
+        Ok(QiitaItemInput {
+            title: article.article.title.clone(),
+            body: self
+                .qiita_body(&article.article.slug, &article.article.body)
+                .await?,
+            tags,
+            private,
+        })
+    }
+
+    /// Qiitaに投稿する本文（末尾にこのブログの記事へのリンクを付ける）
+    async fn qiita_body(&self, slug: &str, body: &str) -> Result<String, CmsError> {
+        let wiki_link_titles: HashMap<String, String> = self
+            .published_article_service
+            .fetch_linked_articles(&extract_wiki_link_slugs(body))
+            .await?
+            .into_iter()
+            .map(|linked| (linked.slug, linked.title))
+            .collect();
+
+        let mut qiita_body = to_qiita_markdown(body, &self.site_url, &wiki_link_titles);
+        qiita_body.push_str(&format!(
+            "\n\n---\n\nこの記事は {}/articles/{slug} からの転載です。\n",
+            self.site_url
+        ));
+        Ok(qiita_body)
+    }
+}
+
+/// カテゴリを記事のタグにする（Qiitaのタグは空白を含められないため `-` にし、上限までにする）
+fn qiita_tags(categories: &[Category]) -> Vec<Tag> {
+    let mut tags: Vec<Tag> = Vec::new();
+    for category in categories {
+        let name = category
+            .name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-");
+        if name.is_empty() || tags.iter().any(|tag| tag.name.eq_ignore_ascii_case(&name)) {
+            continue;
+        }
+        tags.push(Tag {
+            name,
+            versions: vec![],
+        });
+        if tags.len() >= QIITA_MAX_TAGS {
+            break;
+        }
+    }
+    tags
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str) -> Category {
+        Category {
+            id: Uuid::now_v7(),
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+        }
+    }
+
+    #[test]
+    fn test_カテゴリの空白をハイフンにして上限までタグにすること() {
+        let categories: Vec<Category> = [
+            "Rust",
+            "Web Assembly",
+            "rust",
+            "Leptos",
+            "Axum",
+            "sqlx",
+            "Docker",
+        ]
+        .into_iter()
+        .map(category)
+        .collect();
+
+        let names: Vec<String> = qiita_tags(&categories)
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["Rust", "Web-Assembly", "Leptos", "Axum", "sqlx"]
+        );
+    }
+}
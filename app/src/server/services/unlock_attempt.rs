@@ -0,0 +1,193 @@
+use axum::http::HeaderMap;
+use fred::prelude::{KeysInterface, Pool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+/// Valkeyに保存するキーの接頭辞（後ろに `{クライアントのIP}:{slug}` が付く）
+const VALKEY_KEY_PREFIX: &str = "unlock_attempts:";
+/// 期間内に許可するパスワードの試行回数
+const MAX_UNLOCK_ATTEMPTS: i64 = 10;
+/// 試行回数を数える期間（15分）
+const UNLOCK_ATTEMPT_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// パスワード保護記事のパスワード試行回数をクライアントのIPと記事ごとに制限する
+///
+/// 試行回数はValkeyに保存してインスタンス間で共有する（Valkeyがなければメモリ上で数える）。
+/// Valkeyの読み書きに失敗した場合も試行を素通りさせず、warnログを出してメモリ上で数える。
+#[derive(Debug, Clone)]
+pub(crate) struct UnlockAttemptLimiter {
+    store: Option<Pool>,
+    attempts: Arc<Mutex<HashMap<String, (i64, Instant)>>>,
+    trust_cf_connecting_ip: bool,
+}
+
+impl UnlockAttemptLimiter {
+    /// `trust_cf_connecting_ip` はnginxの前にCloudflareがある場合のみ `true` にする
+    pub(crate) fn new(store: Option<Pool>, trust_cf_connecting_ip: bool) -> Self {
+        Self {
+            store,
+            attempts: Arc::default(),
+            trust_cf_connecting_ip,
+        }
+    }
+
+    /// 試行回数を数えるクライアントのIP
+    pub(crate) fn client_ip(&self, headers: &HeaderMap) -> String {
+        client_ip(headers, self.trust_cf_connecting_ip)
+    }
+
+    /// 試行を1回記録し、期間内の試行回数が上限以内なら `true` を返す
+    #[instrument(skip(self))]
+    pub(crate) async fn try_attempt(&self, client: &str, slug: &str) -> bool {
+        let key = format!("{VALKEY_KEY_PREFIX}{client}:{slug}");
+        let count = match &self.store {
+            Some(pool) => match Self::increment_in_valkey(pool, &key).await {
+                Ok(count) => count,
+                Err(err) => {
+                    tracing::warn!(
+                        error = err,
+                        "Counted unlock attempts in memory instead of Valkey"
+                    );
+                    self.increment_in_memory(key, Instant::now())
+                }
+            },
+            None => self.increment_in_memory(key, Instant::now()),
+        };
+        count <= MAX_UNLOCK_ATTEMPTS
+    }
+
+    async fn increment_in_valkey(pool: &Pool, key: &str) -> Result<i64, String> {
+        let count: i64 = pool.incr(key).await.map_err(|err| err.to_string())?;
+        if count == 1 {
+            pool.expire::<(), _>(key, UNLOCK_ATTEMPT_WINDOW.as_secs() as i64, None)
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(count)
+    }
+
+    fn increment_in_memory(&self, key: String, now: Instant) -> i64 {
+        let mut attempts = self.attempts.lock().unwrap_or_else(|e| e.into_inner());
+        attempts
+            .retain(|_, (_, started_at)| now.duration_since(*started_at) < UNLOCK_ATTEMPT_WINDOW);
+        let (count, _) = attempts.entry(key).or_insert((0, now));
+        *count += 1;
+        *count
+    }
+}
+
+/// リクエスト元のIPアドレス
+///
+/// クライアントが送った値をそのまま使わないよう、nginxが `$remote_addr` で上書きする `X-Real-IP`、
+/// なければnginxが `X-Forwarded-For` の末尾に追加した接続元を使う。
+/// `CF-Connecting-IP` は誰でも送れるため、Cloudflareを信頼する設定の場合のみ使う。
+fn client_ip(headers: &HeaderMap, trust_cf_connecting_ip: bool) -> String {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    trust_cf_connecting_ip
+        .then(|| header("cf-connecting-ip"))
+        .flatten()
+        .or_else(|| header("x-real-ip"))
+        .or_else(|| header("x-forwarded-for").and_then(|value| value.rsplit(',').next()))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_期間内の試行回数が上限を超えると拒否し期間が過ぎると許可すること() {
+        let limiter = UnlockAttemptLimiter::new(None, false);
+        let started_at = Instant::now();
+        for _ in 0..MAX_UNLOCK_ATTEMPTS {
+            limiter.increment_in_memory("1.2.3.4:slug".to_string(), started_at);
+        }
+
+        assert_eq!(
+            limiter.increment_in_memory("1.2.3.4:slug".to_string(), started_at),
+            MAX_UNLOCK_ATTEMPTS + 1
+        );
+        // 別の記事・別のクライアントは数えない
+        assert_eq!(
+            limiter.increment_in_memory("1.2.3.4:other".to_string(), started_at),
+            1
+        );
+        assert_eq!(
+            limiter.increment_in_memory("5.6.7.8:slug".to_string(), started_at),
+            1
+        );
+        assert_eq!(
+            limiter.increment_in_memory(
+                "1.2.3.4:slug".to_string(),
+                started_at + UNLOCK_ATTEMPT_WINDOW
+            ),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_valkeyに接続できない場合はメモリ上で数えて上限を超えると拒否すること() {
+        use fred::prelude::{Builder, Config};
+
+        let config = Config::from_url("redis://127.0.0.1:1").unwrap();
+        let pool = Builder::from_config(config)
+            .with_performance_config(|config| {
+                config.default_command_timeout = Duration::from_millis(100);
+            })
+            .build_pool(1)
+            .unwrap();
+        let limiter = UnlockAttemptLimiter::new(Some(pool), false);
+        for _ in 0..MAX_UNLOCK_ATTEMPTS {
+            assert!(limiter.try_attempt("1.2.3.4", "slug").await);
+        }
+
+        assert!(!limiter.try_attempt("1.2.3.4", "slug").await);
+    }
+
+    #[test]
+    fn test_nginxが設定したヘッダーからクライアントのipを取得すること() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip(&headers, false), "unknown");
+
+        // 先頭はクライアントが偽装できるため、nginxが末尾に追加した接続元を使う
+        headers.insert("x-forwarded-for", "10.0.0.1, 10.0.0.2".parse().unwrap());
+        assert_eq!(client_ip(&headers, false), "10.0.0.2");
+
+        headers.insert("x-real-ip", "10.0.0.3".parse().unwrap());
+        assert_eq!(client_ip(&headers, false), "10.0.0.3");
+    }
+
+    #[test]
+    fn test_偽装したx_forwarded_forの先頭をクライアントのipにしないこと() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "198.51.100.1, 203.0.113.9".parse().unwrap(),
+        );
+        assert_eq!(client_ip(&headers, false), "203.0.113.9");
+
+        headers.insert(
+            "x-forwarded-for",
+            "198.51.100.2, 203.0.113.9".parse().unwrap(),
+        );
+        assert_eq!(client_ip(&headers, false), "203.0.113.9");
+    }
+
+    #[test]
+    fn test_cloudflareを信頼する設定の場合のみcf_connecting_ipを使うこと() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", "10.0.0.3".parse().unwrap());
+        headers.insert("cf-connecting-ip", "203.0.113.1".parse().unwrap());
+        assert_eq!(client_ip(&headers, false), "10.0.0.3");
+        assert_eq!(client_ip(&headers, true), "203.0.113.1");
+
+        let limiter = UnlockAttemptLimiter::new(None, true);
+        assert_eq!(limiter.client_ip(&headers), "203.0.113.1");
+    }
+}
@@ -1,23 +1,49 @@
-use crate::constants::PRTIMES_WORD_PRESS_AUTHOR_ID;
 use crate::error::WordPressArticleServiceError;
+use crate::server::models::external_article::ExternalArticle;
 use crate::server::models::word_press_article::WordPressArticle;
 use crate::server::models::word_press_category::Category;
+use crate::server::services::external_source::{ExternalArticleSource, ExternalArticlesFuture};
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tracing::instrument;
 
+/// 1回のリクエストで取得するカテゴリの最大数（WordPress REST APIの `per_page` の上限）
+const WORD_PRESS_CATEGORIES_PER_PAGE: usize = 100;
+
+/// 取得したカテゴリを再取得するまでの期間（カテゴリ名の変更や削除はこの期間が過ぎると反映される）
+const WORD_PRESS_CATEGORY_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 取得済みのカテゴリ
+#[derive(Debug, Clone)]
+struct CachedCategory {
+    /// 存在しないカテゴリは `None`（毎回問い合わせないよう、見つからなかったことも覚えておく）
+    category: Option<Category>,
+    fetched_at: Instant,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct WordPressArticleService {
     client: reqwest::Client,
     base_url: Arc<String>,
+    author_id: u64,
+    /// 取得済みのカテゴリ（IDごと。カテゴリ名はほとんど変わらないため期限まで再取得しない）
+    categories: Arc<RwLock<HashMap<u64, CachedCategory>>>,
 }
 
 impl WordPressArticleService {
     #[instrument]
-    pub(crate) fn new(client: reqwest::Client, base_url: impl ToString + Debug) -> Self {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        base_url: impl ToString + Debug,
+        author_id: u64,
+    ) -> Self {
         Self {
             client,
             base_url: Arc::new(base_url.to_string()),
+            author_id,
+            categories: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -29,7 +55,7 @@ impl WordPressArticleService {
             .client
             .get(format!(
                 "{}/wp-json/wp/v2/posts?author={}",
-                self.base_url, PRTIMES_WORD_PRESS_AUTHOR_ID
+                self.base_url, self.author_id
             ))
             .send()
             .await?;
@@ -41,35 +67,104 @@ impl WordPressArticleService {
         }
 
         let mut articles: Vec<WordPressArticle> = response.json().await?;
-        for article in &mut articles {
-            let mut category = vec![];
-            for id in article.categories.iter() {
-                category.push(self.fetch_categories(*id).await?);
-            }
+        let ids: Vec<u64> = articles
+            .iter()
+            .flat_map(|article| article.categories.iter().copied())
+            .collect();
+        self.fetch_missing_categories(&ids, Instant::now()).await?;
 
-            article.category_names = category;
+        let categories = self.categories.read().unwrap();
+        for article in &mut articles {
+            article.category_names = article
+                .categories
+                .iter()
+                .filter_map(|id| categories.get(id)?.category.clone())
+                .collect();
         }
 
         Ok(articles)
     }
 
+    /// 未取得・期限切れのカテゴリを `?include=` でまとめて取得してキャッシュに追加
+    ///
+    /// 削除されたカテゴリは返ってこないため、見つからなかったカテゴリとして覚え、記事のカテゴリから外す。
     #[instrument]
-    async fn fetch_categories(&self, id: u64) -> Result<Category, WordPressArticleServiceError> {
-        let response = self
-            .client
-            .get(format!("{}/wp-json/wp/v2/categories/{}", self.base_url, id))
-            .send()
-            .await?;
+    async fn fetch_missing_categories(
+        &self,
+        ids: &[u64],
+        now: Instant,
+    ) -> Result<(), WordPressArticleServiceError> {
+        let mut missing: Vec<u64> = {
+            let mut categories = self.categories.write().unwrap();
+            categories.retain(|_, cached| {
+                now.duration_since(cached.fetched_at) < WORD_PRESS_CATEGORY_CACHE_TTL
+            });
+            ids.iter()
+                .copied()
+                .filter(|id| !categories.contains_key(id))
+                .collect()
+        };
+        missing.sort_unstable();
+        missing.dedup();
 
-        if !response.status().is_success() {
-            return Err(WordPressArticleServiceError::UnexpectedStatusCode(
-                response.status(),
-            ));
+        for chunk in missing.chunks(WORD_PRESS_CATEGORIES_PER_PAGE) {
+            let include = chunk
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let response = self
+                .client
+                .get(format!("{}/wp-json/wp/v2/categories", self.base_url))
+                .query(&[
+                    ("include", include),
+                    ("per_page", WORD_PRESS_CATEGORIES_PER_PAGE.to_string()),
+                ])
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(WordPressArticleServiceError::UnexpectedStatusCode(
+                    response.status(),
+                ));
+            }
+
+            let fetched: Vec<Category> = response.json().await?;
+            let mut categories = self.categories.write().unwrap();
+            for id in chunk {
+                categories.insert(
+                    *id,
+                    CachedCategory {
+                        category: None,
+                        fetched_at: now,
+                    },
+                );
+            }
+            for category in fetched {
+                categories.insert(
+                    category.id,
+                    CachedCategory {
+                        category: Some(category),
+                        fetched_at: now,
+                    },
+                );
+            }
         }
 
-        let categories: Category = response.json().await?;
+        Ok(())
+    }
+}
+
+impl ExternalArticleSource for WordPressArticleService {
+    fn name(&self) -> &str {
+        "WordPress"
+    }
 
-        Ok(categories)
+    fn fetch_articles(&self) -> ExternalArticlesFuture<'_> {
+        Box::pin(async move {
+            let articles = WordPressArticleService::fetch_articles(self).await?;
+            Ok(articles.into_iter().map(ExternalArticle::from).collect())
+        })
     }
 }
 
@@ -78,8 +173,50 @@ impl WordPressArticleService {
 mod tests {
     use crate::constants::PRTIMES_WORD_PRESS_AUTHOR_ID;
     use crate::error::WordPressArticleServiceError;
+    use crate::server::models::word_press_article::WordPressArticle;
+    use crate::server::models::word_press_category::Category;
     use crate::server::services::word_press::WordPressArticleService;
     use axum::http::StatusCode;
+    use mockito::Matcher;
+    use std::time::{Duration, Instant};
+
+    fn posts_json(categories: &[&[u64]]) -> String {
+        let posts: Vec<WordPressArticle> = categories
+            .iter()
+            .enumerate()
+            .map(|(i, categories)| WordPressArticle {
+                id: i as u64,
+                categories: categories.to_vec(),
+                ..WordPressArticle::default()
+            })
+            .collect();
+        serde_json::to_string(&posts).unwrap()
+    }
+
+    fn categories_json(ids: &[u64]) -> String {
+        let categories: Vec<Category> = ids
+            .iter()
+            .map(|id| Category {
+                id: *id,
+                name: format!("カテゴリ{id}"),
+                ..Category::default()
+            })
+            .collect();
+        serde_json::to_string(&categories).unwrap()
+    }
+
+    fn category_names(articles: &[WordPressArticle]) -> Vec<Vec<String>> {
+        articles
+            .iter()
+            .map(|article| {
+                article
+                    .category_names
+                    .iter()
+                    .map(|category| category.name.clone())
+                    .collect()
+            })
+            .collect()
+    }
 
     #[tokio::test]
     async fn test_正常系() {
@@ -95,7 +232,7 @@ mod tests {
             .create();
 
         let client = reqwest::Client::new();
-        let service = WordPressArticleService::new(client, &url);
+        let service = WordPressArticleService::new(client, &url, PRTIMES_WORD_PRESS_AUTHOR_ID);
 
         let result = service.fetch_articles().await;
 
@@ -117,7 +254,7 @@ mod tests {
             .create();
 
         let client = reqwest::Client::new();
-        let service = WordPressArticleService::new(client, &url);
+        let service = WordPressArticleService::new(client, &url, PRTIMES_WORD_PRESS_AUTHOR_ID);
 
         let result = service.fetch_articles().await;
 
@@ -142,7 +279,7 @@ mod tests {
             .create();
 
         let client = reqwest::Client::new();
-        let service = WordPressArticleService::new(client, &url);
+        let service = WordPressArticleService::new(client, &url, PRTIMES_WORD_PRESS_AUTHOR_ID);
 
         let result = service.fetch_articles().await;
 
@@ -155,4 +292,189 @@ mod tests {
             StatusCode::INTERNAL_SERVER_ERROR
         );
     }
+
+    #[tokio::test]
+    async fn test_カテゴリは重複を除いて1回でまとめて取得し記事のカテゴリ順に設定すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock(
+                "GET",
+                format!("/wp-json/wp/v2/posts?author={PRTIMES_WORD_PRESS_AUTHOR_ID}").as_str(),
+            )
+            .with_body(posts_json(&[&[3, 1], &[1, 2], &[]]))
+            .create();
+        // 存在しないカテゴリ（2）は返ってこない
+        let categories_mock = server
+            .mock("GET", "/wp-json/wp/v2/categories")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("include".into(), "1,2,3".into()),
+                Matcher::UrlEncoded("per_page".into(), "100".into()),
+            ]))
+            .with_body(categories_json(&[1, 3]))
+            .expect(1)
+            .create();
+
+        let service = WordPressArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            PRTIMES_WORD_PRESS_AUTHOR_ID,
+        );
+
+        let articles = service.fetch_articles().await.unwrap();
+
+        categories_mock.assert();
+        assert_eq!(
+            category_names(&articles),
+            vec![
+                vec!["カテゴリ3".to_string(), "カテゴリ1".to_string()],
+                vec!["カテゴリ1".to_string()],
+                vec![],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_取得済みのカテゴリは再取得しないこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let posts_mock = server
+            .mock(
+                "GET",
+                format!("/wp-json/wp/v2/posts?author={PRTIMES_WORD_PRESS_AUTHOR_ID}").as_str(),
+            )
+            .with_body(posts_json(&[&[1, 2]]))
+            .expect(2)
+            .create();
+        let categories_mock = server
+            .mock("GET", "/wp-json/wp/v2/categories")
+            .match_query(Matcher::UrlEncoded("include".into(), "1,2".into()))
+            .with_body(categories_json(&[1, 2]))
+            .expect(1)
+            .create();
+
+        let service = WordPressArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            PRTIMES_WORD_PRESS_AUTHOR_ID,
+        );
+
+        service.fetch_articles().await.unwrap();
+        let articles = service.fetch_articles().await.unwrap();
+
+        posts_mock.assert();
+        categories_mock.assert();
+        assert_eq!(
+            category_names(&articles),
+            vec![vec!["カテゴリ1".to_string(), "カテゴリ2".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_見つからなかったカテゴリも再取得しないこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock(
+                "GET",
+                format!("/wp-json/wp/v2/posts?author={PRTIMES_WORD_PRESS_AUTHOR_ID}").as_str(),
+            )
+            .with_body(posts_json(&[&[1, 2]]))
+            .expect(2)
+            .create();
+        // 削除されたカテゴリ（2）は返ってこない
+        let categories_mock = server
+            .mock("GET", "/wp-json/wp/v2/categories")
+            .match_query(Matcher::UrlEncoded("include".into(), "1,2".into()))
+            .with_body(categories_json(&[1]))
+            .expect(1)
+            .create();
+
+        let service = WordPressArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            PRTIMES_WORD_PRESS_AUTHOR_ID,
+        );
+
+        service.fetch_articles().await.unwrap();
+        let articles = service.fetch_articles().await.unwrap();
+
+        categories_mock.assert();
+        assert_eq!(
+            category_names(&articles),
+            vec![vec!["カテゴリ1".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_期限が過ぎたカテゴリは再取得すること() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock(
+                "GET",
+                format!("/wp-json/wp/v2/posts?author={PRTIMES_WORD_PRESS_AUTHOR_ID}").as_str(),
+            )
+            .with_body(posts_json(&[&[1]]))
+            .create();
+        let categories_mock = server
+            .mock("GET", "/wp-json/wp/v2/categories")
+            .match_query(Matcher::UrlEncoded("include".into(), "1".into()))
+            .with_body(categories_json(&[1]))
+            .expect(2)
+            .create();
+
+        let service = WordPressArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            PRTIMES_WORD_PRESS_AUTHOR_ID,
+        );
+
+        service.fetch_articles().await.unwrap();
+        service
+            .fetch_missing_categories(
+                &[1],
+                Instant::now() + super::WORD_PRESS_CATEGORY_CACHE_TTL + Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+
+        categories_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_カテゴリの取得に失敗した場合はエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock(
+                "GET",
+                format!("/wp-json/wp/v2/posts?author={PRTIMES_WORD_PRESS_AUTHOR_ID}").as_str(),
+            )
+            .with_body(posts_json(&[&[1]]))
+            .create();
+        server
+            .mock("GET", "/wp-json/wp/v2/categories")
+            .match_query(Matcher::Any)
+            .with_status(500)
+            .create();
+
+        let service = WordPressArticleService::new(
+            reqwest::Client::new(),
+            &url,
+            PRTIMES_WORD_PRESS_AUTHOR_ID,
+        );
+
+        let result = service.fetch_articles().await;
+
+        assert!(matches!(
+            result.unwrap_err(),
+            WordPressArticleServiceError::UnexpectedStatusCode(StatusCode::INTERNAL_SERVER_ERROR)
+        ));
+    }
 }
@@ -0,0 +1,416 @@
+use crate::constants::THUMBNAIL_NO_IMAGE_URL;
+use crate::error::ZennArticleServiceError;
+use crate::server::models::external_article::ExternalArticle;
+use crate::server::models::zenn_article::{
+    ZennArticle, ZennArticleDetail, ZennArticleDetailResponse, ZennArticleList,
+};
+use crate::server::services::external_source::{ExternalArticleSource, ExternalArticlesFuture};
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::instrument;
+
+/// 記事一覧を辿る最大ページ数（`next_page` が返り続けた場合の打ち切り）
+const ZENN_MAX_PAGES: u32 = 10;
+
+/// 記事の詳細を同時に取得する最大数
+const ZENN_DETAIL_FETCH_CONCURRENCY: usize = 4;
+
+/// 前回取得した記事の詳細（取得時の本文の更新日時つき）
+#[derive(Debug, Clone)]
+struct CachedZennDetail {
+    body_updated_at: DateTime<FixedOffset>,
+    detail: ZennArticleDetail,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ZennArticleService {
+    client: reqwest::Client,
+    base_url: Arc<String>,
+    user_name: Arc<String>,
+    /// 前回取得した記事の詳細（slugごと）
+    details: Arc<Mutex<HashMap<String, CachedZennDetail>>>,
+}
+
+impl ZennArticleService {
+    #[instrument]
+    pub(crate) fn new(
+        client: reqwest::Client,
+        base_url: impl ToString + Debug,
+        user_name: impl ToString + Debug,
+    ) -> Self {
+        Self {
+            client,
+            base_url: Arc::new(base_url.to_string()),
+            user_name: Arc::new(user_name.to_string()),
+            details: Arc::default(),
+        }
+    }
+
+    /// ユーザーの記事を全ページ取得し、記事ごとにトピックとOG画像を補う
+    ///
+    /// 詳細は前回の取得から本文が更新された記事（と新しい記事）だけ取得し直す。
+    #[instrument]
+    pub(crate) async fn fetch_articles(&self) -> Result<Vec<ZennArticle>, ZennArticleServiceError> {
+        let mut articles = Vec::new();
+        let mut page = Some(1);
+        while let Some(current) = page.filter(|page| *page <= ZENN_MAX_PAGES) {
+            let list = self.fetch_article_list(current).await?;
+            articles.extend(list.articles);
+            page = list.next_page;
+        }
+
+        let mut details = self.fetch_article_details(&articles).await;
+        for article in articles.iter_mut() {
+            article.url = format!("{}{}", self.base_url, article.path);
+            let detail = details.remove(&article.slug).unwrap_or_default();
+            article.topics = detail.topics;
+            article.og_image_url = detail
+                .og_image_url
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| THUMBNAIL_NO_IMAGE_URL.to_string());
+        }
+
+        Ok(articles)
+    }
+
+    /// 記事ごとの詳細（slugごと）を返す
+    ///
+    /// 本文が前回から更新されていない記事は前回の詳細を使い、それ以外を並行して取得する。
+    /// 取得できなかった記事は含めない（次回に取得し直す）。
+    async fn fetch_article_details(
+        &self,
+        articles: &[ZennArticle],
+    ) -> HashMap<String, ZennArticleDetail> {
+        let mut details = HashMap::new();
+        let mut stale = Vec::new();
+        {
+            let cache = self.details.lock().unwrap();
+            for article in articles {
+                match cache.get(&article.slug) {
+                    Some(cached) if Some(cached.body_updated_at) == article.body_updated_at => {
+                        details.insert(article.slug.clone(), cached.detail.clone());
+                    }
+                    _ => stale.push((article.slug.clone(), article.body_updated_at)),
+                }
+            }
+        }
+
+        let semaphore = Arc::new(Semaphore::new(ZENN_DETAIL_FETCH_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (slug, body_updated_at) in stale {
+            let service = self.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.unwrap();
+                let detail = service.fetch_article_detail(&slug).await;
+                (slug, body_updated_at, detail)
+            });
+        }
+
+        let mut fetched = Vec::new();
+        for (slug, body_updated_at, detail) in tasks.join_all().await {
+            match detail {
+                Ok(detail) => {
+                    if let Some(body_updated_at) = body_updated_at {
+                        fetched.push((
+                            slug.clone(),
+                            CachedZennDetail {
+                                body_updated_at,
+                                detail: detail.clone(),
+                            },
+                        ));
+                    }
+                    details.insert(slug, detail);
+                }
+                Err(err) => tracing::warn!(
+                    error = err.to_string(),
+                    slug,
+                    "Failed to fetch detail of Zenn article"
+                ),
+            }
+        }
+
+        let mut cache = self.details.lock().unwrap();
+        cache.retain(|slug, _| details.contains_key(slug));
+        cache.extend(fetched);
+        details
+    }
+
+    #[instrument]
+    async fn fetch_article_list(
+        &self,
+        page: u32,
+    ) -> Result<ZennArticleList, ZennArticleServiceError> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/api/articles?username={}&order=latest&page={}",
+                self.base_url, self.user_name, page
+            ))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ZennArticleServiceError::UnexpectedStatusCode(
+                response.status(),
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    #[instrument]
+    async fn fetch_article_detail(
+        &self,
+        slug: &str,
+    ) -> Result<ZennArticleDetail, ZennArticleServiceError> {
+        let response = self
+            .client
+            .get(format!("{}/api/articles/{}", self.base_url, slug))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ZennArticleServiceError::UnexpectedStatusCode(
+                response.status(),
+            ));
+        }
+
+        let detail: ZennArticleDetailResponse = response.json().await?;
+
+        Ok(detail.article)
+    }
+}
+
+impl ExternalArticleSource for ZennArticleService {
+    fn name(&self) -> &str {
+        "Zenn"
+    }
+
+    fn fetch_articles(&self) -> ExternalArticlesFuture<'_> {
+        Box::pin(async move {
+            let articles = ZennArticleService::fetch_articles(self).await?;
+            Ok(articles.into_iter().map(ExternalArticle::from).collect())
+        })
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use crate::common::dto::ArticleSource;
+    use crate::constants::THUMBNAIL_NO_IMAGE_URL;
+    use crate::error::ZennArticleServiceError;
+    use crate::server::models::external_article::ExternalArticle;
+    use crate::server::services::zenn::ZennArticleService;
+    use axum::http::StatusCode;
+    use chrono::{TimeZone, Utc};
+
+    fn article_json(slug: &str) -> String {
+        article_json_updated_at(slug, "2026-10-01T09:00:00.000+09:00")
+    }
+
+    fn article_json_updated_at(slug: &str, body_updated_at: &str) -> String {
+        format!(
+            r#"{{
+                "id": 1,
+                "post_type": "Article",
+                "title": "{slug}のタイトル",
+                "slug": "{slug}",
+                "emoji": "🦀",
+                "published_at": "2026-10-01T09:00:00.000+09:00",
+                "body_updated_at": "{body_updated_at}",
+                "path": "/romira/articles/{slug}",
+                "liked_count": 3
+            }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_正常系() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let first_page = server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_body(format!(
+                r#"{{"articles": [{}], "next_page": 2}}"#,
+                article_json("first")
+            ))
+            .create();
+        let second_page = server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=2")
+            .with_body(format!(
+                r#"{{"articles": [{}], "next_page": null}}"#,
+                article_json("second")
+            ))
+            .create();
+        server
+            .mock("GET", "/api/articles/first")
+            .with_body(
+                r#"{"article": {
+                    "topics": [{"name": "rust", "display_name": "Rust"}],
+                    "og_image_url": "https://res.cloudinary.com/zenn/first.png"
+                }}"#,
+            )
+            .create();
+        server
+            .mock("GET", "/api/articles/second")
+            .with_body(r#"{"article": {"topics": [], "og_image_url": null}}"#)
+            .create();
+
+        let service = ZennArticleService::new(reqwest::Client::new(), &url, "romira");
+
+        let articles = service.fetch_articles().await.unwrap();
+
+        first_page.assert();
+        second_page.assert();
+        let articles: Vec<ExternalArticle> =
+            articles.into_iter().map(ExternalArticle::from).collect();
+        assert_eq!(
+            articles,
+            vec![
+                ExternalArticle {
+                    title: "firstのタイトル".to_string(),
+                    url: format!("{url}/romira/articles/first"),
+                    thumbnail_url: "https://res.cloudinary.com/zenn/first.png".to_string(),
+                    categories: vec!["Rust".to_string()],
+                    published_at: Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(),
+                    source: ArticleSource::Zenn,
+                    emoji: Some("🦀".to_string()),
+                },
+                ExternalArticle {
+                    title: "secondのタイトル".to_string(),
+                    url: format!("{url}/romira/articles/second"),
+                    thumbnail_url: THUMBNAIL_NO_IMAGE_URL.to_string(),
+                    categories: vec![],
+                    published_at: Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(),
+                    source: ArticleSource::Zenn,
+                    emoji: Some("🦀".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_記事詳細が取得できない場合はトピックなしで代替画像を使うこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_body(format!(
+                r#"{{"articles": [{}], "next_page": null}}"#,
+                article_json("first")
+            ))
+            .create();
+        server
+            .mock("GET", "/api/articles/first")
+            .with_status(404)
+            .create();
+
+        let service = ZennArticleService::new(reqwest::Client::new(), &url, "romira");
+
+        let articles = service.fetch_articles().await.unwrap();
+
+        assert_eq!(articles.len(), 1);
+        assert!(articles[0].topics.is_empty());
+        assert_eq!(articles[0].og_image_url, THUMBNAIL_NO_IMAGE_URL);
+    }
+
+    #[tokio::test]
+    async fn test_本文が更新されていない記事の詳細は前回の取得結果を使うこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        let first_list = server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_body(format!(
+                r#"{{"articles": [{}, {}], "next_page": null}}"#,
+                article_json("first"),
+                article_json("second")
+            ))
+            .create();
+        let first_detail = server
+            .mock("GET", "/api/articles/first")
+            .with_body(
+                r#"{"article": {"topics": [], "og_image_url": "https://example.com/first.png"}}"#,
+            )
+            .expect(1)
+            .create();
+        let second_detail = server
+            .mock("GET", "/api/articles/second")
+            .with_body(
+                r#"{"article": {"topics": [], "og_image_url": "https://example.com/second.png"}}"#,
+            )
+            .expect(2)
+            .create();
+
+        let service = ZennArticleService::new(reqwest::Client::new(), &url, "romira");
+        service.fetch_articles().await.unwrap();
+        first_list.remove();
+
+        server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_body(format!(
+                r#"{{"articles": [{}, {}], "next_page": null}}"#,
+                article_json("first"),
+                article_json_updated_at("second", "2026-10-02T09:00:00.000+09:00")
+            ))
+            .create();
+
+        let articles = service.fetch_articles().await.unwrap();
+
+        first_detail.assert();
+        second_detail.assert();
+        assert_eq!(articles[0].og_image_url, "https://example.com/first.png");
+        assert_eq!(articles[1].og_image_url, "https://example.com/second.png");
+    }
+
+    #[tokio::test]
+    async fn test_response_jsonのスキーマが異なる場合エラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_body(r#"{"id": 1}"#)
+            .create();
+
+        let service = ZennArticleService::new(reqwest::Client::new(), &url, "romira");
+
+        let result = service.fetch_articles().await;
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ZennArticleServiceError::FailedReqwestSend(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_200_299以外のステータスコードが返された場合はエラーを返すこと() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock("GET", "/api/articles?username=romira&order=latest&page=1")
+            .with_status(500)
+            .create();
+
+        let service = ZennArticleService::new(reqwest::Client::new(), &url, "romira");
+
+        let result = service.fetch_articles().await;
+
+        assert_eq!(
+            match result.unwrap_err() {
+                ZennArticleServiceError::UnexpectedStatusCode(status) => status,
+                _ => panic!("Unexpected error type"),
+            },
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
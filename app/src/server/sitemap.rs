@@ -0,0 +1,216 @@
+//! sitemap.xml（サイトマップインデックスと各サイトマップ）の生成
+
+use chrono::NaiveDateTime;
+
+use crate::server::utils::xml::escape_xml;
+
+/// サイトマップの1URL
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SitemapUrl {
+    pub(crate) loc: String,
+    /// 最終更新日時（UTC）
+    pub(crate) lastmod: Option<NaiveDateTime>,
+    pub(crate) changefreq: Option<&'static str>,
+    pub(crate) priority: Option<&'static str>,
+    /// ページに含まれる画像のURL
+    pub(crate) images: Vec<String>,
+}
+
+/// サイトマップインデックスに載せるサイトマップ
+#[derive(Debug, Clone)]
+pub(crate) struct SitemapRef {
+    pub(crate) loc: String,
+    /// サイトマップ内で最も新しい更新日時（UTC）
+    pub(crate) lastmod: Option<NaiveDateTime>,
+}
+
+/// W3C Datetime 形式（UTC）
+fn to_w3c_datetime(datetime: NaiveDateTime) -> String {
+    datetime
+        .and_utc()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// `<urlset>` を生成（画像がある場合は `image:image` 拡張を付ける）
+pub(crate) fn render_urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" \
+         xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\">\n",
+    );
+    for url in urls {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                to_w3c_datetime(lastmod)
+            ));
+        }
+        if let Some(changefreq) = url.changefreq {
+            xml.push_str(&format!("    <changefreq>{}</changefreq>\n", changefreq));
+        }
+        if let Some(priority) = url.priority {
+            xml.push_str(&format!("    <priority>{}</priority>\n", priority));
+        }
+        for image in &url.images {
+            xml.push_str(&format!(
+                "    <image:image>\n\
+                 \x20     <image:loc>{}</image:loc>\n\
+                 \x20   </image:image>\n",
+                escape_xml(image)
+            ));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// `<sitemapindex>` を生成
+pub(crate) fn render_sitemap_index(sitemaps: &[SitemapRef]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for sitemap in sitemaps {
+        xml.push_str("  <sitemap>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&sitemap.loc)));
+        if let Some(lastmod) = sitemap.lastmod {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                to_w3c_datetime(lastmod)
+            ));
+        }
+        xml.push_str("  </sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    /// 開始タグと終了タグが正しく対応しているかを検証し、要素名を出現順に返す
+    fn assert_well_formed(xml: &str) -> Vec<String> {
+        let body = xml
+            .strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")
+            .expect("XML宣言がない");
+        let mut stack: Vec<String> = Vec::new();
+        let mut elements = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("タグが閉じていない") + start;
+            let tag = &rest[start + 1..end];
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop().as_deref(), Some(name), "{xml}");
+            } else {
+                let name = tag.split_whitespace().next().unwrap().to_string();
+                elements.push(name.clone());
+                if !tag.ends_with('/') {
+                    stack.push(name);
+                }
+            }
+            let text = &rest[end + 1..];
+            let next = text.find('<').unwrap_or(text.len());
+            assert!(!text[..next].contains(['<', '>', '"']), "{xml}");
+            rest = text;
+        }
+        assert!(stack.is_empty(), "{xml}");
+        elements
+    }
+
+    #[test]
+    fn urlsetが正しい構造で画像拡張を含むこと() {
+        let xml = render_urlset(&[
+            SitemapUrl {
+                loc: "https://blog.romira.dev/articles/a&b".to_string(),
+                lastmod: Some(parse_datetime("2025-01-02 03:04:05")),
+                changefreq: Some("monthly"),
+                priority: Some("0.8"),
+                images: vec![
+                    "https://blog-romira.imgix.net/cover.png".to_string(),
+                    "https://blog-romira.imgix.net/body.png?x=1&y=2".to_string(),
+                ],
+            },
+            SitemapUrl {
+                loc: "https://blog.romira.dev/".to_string(),
+                ..Default::default()
+            },
+        ]);
+
+        let elements = assert_well_formed(&xml);
+        assert_eq!(
+            elements,
+            [
+                "urlset",
+                "url",
+                "loc",
+                "lastmod",
+                "changefreq",
+                "priority",
+                "image:image",
+                "image:loc",
+                "image:image",
+                "image:loc",
+                "url",
+                "loc",
+            ]
+        );
+        assert!(
+            xml.contains("xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\""),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<loc>https://blog.romira.dev/articles/a&amp;b</loc>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<lastmod>2025-01-02T03:04:05Z</lastmod>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains(
+                "<image:loc>https://blog-romira.imgix.net/body.png?x=1&amp;y=2</image:loc>"
+            ),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn sitemapindexが正しい構造になること() {
+        let xml = render_sitemap_index(&[
+            SitemapRef {
+                loc: "https://blog.romira.dev/sitemaps/pages.xml".to_string(),
+                lastmod: Some(parse_datetime("2025-01-02 03:04:05")),
+            },
+            SitemapRef {
+                loc: "https://blog.romira.dev/sitemaps/articles.xml".to_string(),
+                lastmod: None,
+            },
+        ]);
+
+        let elements = assert_well_formed(&xml);
+        assert_eq!(
+            elements,
+            [
+                "sitemapindex",
+                "sitemap",
+                "loc",
+                "lastmod",
+                "sitemap",
+                "loc"
+            ]
+        );
+        assert!(
+            xml.contains("<lastmod>2025-01-02T03:04:05Z</lastmod>"),
+            "{xml}"
+        );
+    }
+}
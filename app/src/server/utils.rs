@@ -1,2 +1,6 @@
+pub(crate) mod feed_parser;
 pub(crate) mod html;
+pub(crate) mod oembed;
+pub(crate) mod qiita_markdown;
 pub(crate) mod url;
+pub(crate) mod xml;
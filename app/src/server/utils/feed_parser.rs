@@ -0,0 +1,260 @@
+//! RSS 2.0 / Atom フィードの解析
+
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+const MEDIA_NAMESPACE: &str = "http://search.yahoo.com/mrss/";
+const DUBLIN_CORE_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// フィードの1記事
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FeedEntry {
+    pub(crate) title: String,
+    pub(crate) link: String,
+    /// 公開日時（なければ更新日時）
+    pub(crate) published_at: Option<DateTime<Utc>>,
+    pub(crate) categories: Vec<String>,
+    pub(crate) author: Option<String>,
+    /// enclosure / `media:thumbnail` / `media:content` の画像URL
+    pub(crate) image_url: Option<String>,
+}
+
+/// RSS 2.0 または Atom のフィードを解析する
+///
+/// タイトルかリンクがない記事は読み飛ばす。
+pub(crate) fn parse_feed(xml: &str) -> Result<Vec<FeedEntry>, String> {
+    let document = Document::parse(xml).map_err(|err| err.to_string())?;
+    let root = document.root_element();
+    let entries = match root.tag_name().name() {
+        "rss" => root
+            .children()
+            .filter(|node| is_element(node, None, "channel"))
+            .flat_map(|channel| channel.children())
+            .filter(|node| is_element(node, None, "item"))
+            .filter_map(|item| parse_rss_item(&item))
+            .collect(),
+        "feed" if root.tag_name().namespace() == Some(ATOM_NAMESPACE) => root
+            .children()
+            .filter(|node| is_element(node, Some(ATOM_NAMESPACE), "entry"))
+            .filter_map(|entry| parse_atom_entry(&entry))
+            .collect(),
+        other => return Err(format!("Unsupported feed root element: {other}")),
+    };
+    Ok(entries)
+}
+
+fn parse_rss_item(item: &Node) -> Option<FeedEntry> {
+    let title = child_text(item, None, "title")?;
+    let link = child_text(item, None, "link")?;
+    let published_at = child_text(item, None, "pubDate")
+        .and_then(|date| DateTime::parse_from_rfc2822(&date).ok())
+        .or_else(|| {
+            child_text(item, Some(DUBLIN_CORE_NAMESPACE), "date")
+                .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+        })
+        .map(|date| date.with_timezone(&Utc));
+    let categories = item
+        .children()
+        .filter(|node| is_element(node, None, "category"))
+        .filter_map(|node| non_empty_text(&node))
+        .collect();
+    let author = child_text(item, Some(DUBLIN_CORE_NAMESPACE), "creator")
+        .or_else(|| child_text(item, None, "author"));
+    let image_url = item
+        .children()
+        .find(|node| is_element(node, None, "enclosure") && is_image(node))
+        .and_then(|node| node.attribute("url"))
+        .map(str::to_string)
+        .or_else(|| media_image_url(item));
+
+    Some(FeedEntry {
+        title,
+        link,
+        published_at,
+        categories,
+        author,
+        image_url,
+    })
+}
+
+fn parse_atom_entry(entry: &Node) -> Option<FeedEntry> {
+    let title = child_text(entry, Some(ATOM_NAMESPACE), "title")?;
+    let links: Vec<Node> = entry
+        .children()
+        .filter(|node| is_element(node, Some(ATOM_NAMESPACE), "link"))
+        .collect();
+    let link = links
+        .iter()
+        .find(|node| node.attribute("rel").is_none_or(|rel| rel == "alternate"))
+        .and_then(|node| node.attribute("href"))?
+        .to_string();
+    let published_at = child_text(entry, Some(ATOM_NAMESPACE), "published")
+        .or_else(|| child_text(entry, Some(ATOM_NAMESPACE), "updated"))
+        .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+        .map(|date| date.with_timezone(&Utc));
+    let categories = entry
+        .children()
+        .filter(|node| is_element(node, Some(ATOM_NAMESPACE), "category"))
+        .filter_map(|node| {
+            node.attribute("label")
+                .or_else(|| node.attribute("term"))
+                .map(str::trim)
+                .filter(|term| !term.is_empty())
+                .map(str::to_string)
+        })
+        .collect();
+    let author = entry
+        .children()
+        .find(|node| is_element(node, Some(ATOM_NAMESPACE), "author"))
+        .and_then(|author| child_text(&author, Some(ATOM_NAMESPACE), "name"));
+    let image_url = links
+        .iter()
+        .find(|node| node.attribute("rel") == Some("enclosure") && is_image(node))
+        .and_then(|node| node.attribute("href"))
+        .map(str::to_string)
+        .or_else(|| media_image_url(entry));
+
+    Some(FeedEntry {
+        title,
+        link,
+        published_at,
+        categories,
+        author,
+        image_url,
+    })
+}
+
+/// `media:thumbnail`、なければ画像の `media:content` のURL
+fn media_image_url(node: &Node) -> Option<String> {
+    let media = |name: &'static str| {
+        node.descendants()
+            .filter(move |child| is_element(child, Some(MEDIA_NAMESPACE), name))
+    };
+    media("thumbnail")
+        .chain(
+            media("content").filter(|content| {
+                content.attribute("medium") == Some("image") || is_image(content)
+            }),
+        )
+        .find_map(|child| child.attribute("url"))
+        .map(str::to_string)
+}
+
+fn is_image(node: &Node) -> bool {
+    node.attribute("type")
+        .is_some_and(|content_type| content_type.starts_with("image/"))
+}
+
+fn is_element(node: &Node, namespace: Option<&str>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name && node.tag_name().namespace() == namespace
+}
+
+fn child_text(node: &Node, namespace: Option<&str>, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| is_element(child, namespace, name))
+        .and_then(|child| non_empty_text(&child))
+}
+
+/// 要素内のテキスト（CDATAを含む）を結合して前後の空白を除去
+fn non_empty_text(node: &Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const RSS_FIXTURE: &str = include_str!("../../../tests/fixtures/feeds/rss2.xml");
+    const ATOM_FIXTURE: &str = include_str!("../../../tests/fixtures/feeds/atom.xml");
+
+    #[test]
+    fn rss2のフィードを解析できること() {
+        let entries = parse_feed(RSS_FIXTURE).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                FeedEntry {
+                    title: "RustでRSSを読む".to_string(),
+                    link: "https://note.com/romira/n/n0001".to_string(),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 10, 1, 3, 0, 0).unwrap()),
+                    categories: vec!["rust".to_string(), "個人開発".to_string()],
+                    author: Some("Romira".to_string()),
+                    image_url: Some("https://assets.note.com/n0001.png".to_string()),
+                },
+                FeedEntry {
+                    title: "サムネイル付きの記事".to_string(),
+                    link: "https://note.com/romira/n/n0002".to_string(),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 9, 20, 0, 0, 0).unwrap()),
+                    categories: vec![],
+                    author: Some("Guest".to_string()),
+                    image_url: Some("https://assets.note.com/n0002-thumb.png".to_string()),
+                },
+                FeedEntry {
+                    title: "画像なしの記事".to_string(),
+                    link: "https://note.com/romira/n/n0003".to_string(),
+                    published_at: None,
+                    categories: vec![],
+                    author: None,
+                    image_url: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn atomのフィードを解析できること() {
+        let entries = parse_feed(ATOM_FIXTURE).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                FeedEntry {
+                    title: "Atomの記事".to_string(),
+                    link: "https://tech.example.com/entry/1".to_string(),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 10, 2, 1, 0, 0).unwrap()),
+                    categories: vec!["Rust".to_string(), "leptos".to_string()],
+                    author: Some("Romira".to_string()),
+                    image_url: Some("https://tech.example.com/images/1.png".to_string()),
+                },
+                FeedEntry {
+                    title: "更新日時だけの記事".to_string(),
+                    link: "https://tech.example.com/entry/2".to_string(),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap()),
+                    categories: vec![],
+                    author: Some("Other".to_string()),
+                    image_url: Some("https://tech.example.com/images/2-thumb.png".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn タイトルかリンクがない記事は読み飛ばすこと() {
+        let xml = r#"<rss version="2.0"><channel>
+            <item><title>リンクなし</title></item>
+            <item><link>https://example.com/no-title</link></item>
+            <item><title>OK</title><link>https://example.com/ok</link></item>
+        </channel></rss>"#;
+
+        let entries = parse_feed(xml).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].link, "https://example.com/ok");
+    }
+
+    #[test]
+    fn フィード以外のxmlはエラーになること() {
+        assert!(parse_feed("<html><body/></html>").is_err());
+        assert!(parse_feed("not xml").is_err());
+    }
+}
@@ -1,33 +1,176 @@
+use crate::error::OgpFetchError;
 use select::document::Document;
 use select::predicate::{Attr, Name, Predicate};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tracing::instrument;
+use url::Url;
 
-#[instrument]
-pub(crate) async fn get_og_image_url(
+/// OGP取得のタイムアウト
+const OGP_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// OGP取得時に読み込む本文の上限（metaタグは`<head>`にあるため先頭だけで十分）
+const OGP_MAX_BODY_BYTES: usize = 512 * 1024;
+
+/// 保存するテキストの最大文字数
+const OGP_MAX_TEXT_CHARS: usize = 300;
+
+/// ページから取得したOGPメタデータ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OgpMetadata {
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) image_url: Option<String>,
+    pub(crate) favicon_url: Option<String>,
+    pub(crate) site_name: Option<String>,
+}
+
+/// URLのページを取得してOGPメタデータを抽出
+///
+/// タイムアウトと読み込みサイズの上限付きで取得し、上限を超えた分は読み捨てる。
+#[instrument(skip(client))]
+pub(crate) async fn fetch_ogp_metadata(
     client: &reqwest::Client,
     url: &str,
-) -> Result<Option<String>, reqwest::Error> {
-    let response = client.get(url).send().await?;
+) -> Result<OgpMetadata, OgpFetchError> {
+    let mut response = client
+        .get(url)
+        .timeout(OGP_FETCH_TIMEOUT)
+        .header(reqwest::header::ACCEPT, "text/html,application/xhtml+xml")
+        .send()
+        .await?;
 
-    let document = Document::from(response.text().await?.as_str());
+    if !response.status().is_success() {
+        return Err(OgpFetchError::UnexpectedStatusCode(response.status()));
+    }
 
-    if let Some(meta_tag) = document
-        .find(Name("meta").and(Attr("property", "og:image")))
-        .next()
-        && let Some(content) = meta_tag.attr("content")
+    if let Some(content_type) = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        && !content_type.contains("html")
     {
-        return Ok(Some(content.to_string()));
+        return Err(OgpFetchError::UnsupportedContentType(
+            content_type.to_string(),
+        ));
+    }
+
+    let base_url = response.url().clone();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() >= OGP_MAX_BODY_BYTES {
+            body.truncate(OGP_MAX_BODY_BYTES);
+            break;
+        }
     }
 
-    Ok(None)
+    Ok(parse_ogp_metadata(
+        &String::from_utf8_lossy(&body),
+        &base_url,
+    ))
+}
+
+/// 複数のページのOGP画像URLを、同時に `concurrency` 件までに制限して取得
+///
+/// 結果は `urls` と同じ順に並ぶ。取得に失敗したページや画像のないページは `None` にする。
+pub(crate) async fn fetch_og_image_urls(
+    client: &reqwest::Client,
+    urls: &[String],
+    concurrency: usize,
+) -> Vec<Option<String>> {
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, url) in urls.iter().cloned().enumerate() {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.unwrap();
+            let image_url = match fetch_ogp_metadata(&client, &url).await {
+                Ok(metadata) => metadata.image_url,
+                Err(err) => {
+                    tracing::warn!(error = err.to_string(), url, "Failed to fetch OGP image");
+                    None
+                }
+            };
+            (index, image_url)
+        });
+    }
+
+    let mut image_urls = vec![None; urls.len()];
+    for (index, image_url) in tasks.join_all().await {
+        image_urls[index] = image_url;
+    }
+    image_urls
 }
 
+/// HTMLからOGPメタデータを抽出（相対URLは `base_url` で解決）
+pub(crate) fn parse_ogp_metadata(html: &str, base_url: &Url) -> OgpMetadata {
+    let document = Document::from(html);
+
+    let meta = |attr: &'static str, name: &'static str| {
+        document
+            .find(Name("meta").and(Attr(attr, name)))
+            .filter_map(|node| node.attr("content"))
+            .map(str::trim)
+            .find(|content| !content.is_empty())
+            .map(str::to_string)
+    };
+    let resolve = |href: String| base_url.join(&href).ok().map(String::from);
+
+    let title = meta("property", "og:title")
+        .or_else(|| meta("name", "twitter:title"))
+        .or_else(|| {
+            document
+                .find(Name("title"))
+                .next()
+                .map(|node| node.text().trim().to_string())
+                .filter(|title| !title.is_empty())
+        });
+    let description = meta("property", "og:description").or_else(|| meta("name", "description"));
+    let image_url = meta("property", "og:image")
+        .or_else(|| meta("name", "twitter:image"))
+        .and_then(resolve);
+    let favicon_url = document
+        .find(Name("link"))
+        .filter(|node| {
+            node.attr("rel").is_some_and(|rel| {
+                rel.split_ascii_whitespace()
+                    .any(|r| r.eq_ignore_ascii_case("icon"))
+            })
+        })
+        .filter_map(|node| node.attr("href"))
+        .map(str::to_string)
+        .find_map(resolve)
+        .or_else(|| resolve("/favicon.ico".to_string()));
+    let site_name = meta("property", "og:site_name");
+
+    OgpMetadata {
+        title: title.map(truncate_text),
+        description: description.map(truncate_text),
+        image_url,
+        favicon_url,
+        site_name: site_name.map(truncate_text),
+    }
+}
+
+/// 長すぎるテキストを切り詰める
+fn truncate_text(text: String) -> String {
+    match text.char_indices().nth(OGP_MAX_TEXT_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text,
+    }
+}
+
+//noinspection NonAsciiCharacters
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
-    async fn test_get_og_image_url() {
+    async fn test_fetch_ogp_metadata() {
         let mut server = mockito::Server::new_async().await;
         let url = server.url();
 
@@ -38,7 +181,12 @@ mod tests {
                 r#"
                     <html>
                         <head>
+                            <title>Fallback</title>
+                            <meta property="og:title" content="OGタイトル">
+                            <meta property="og:description" content="OG説明">
                             <meta property="og:image" content="https://example.com/image.jpg">
+                            <meta property="og:site_name" content="Example">
+                            <link rel="shortcut icon" href="/icon.png">
                         </head>
                     </html>
                 "#,
@@ -46,12 +194,94 @@ mod tests {
             .create();
 
         let client = reqwest::Client::new();
-        let result = get_og_image_url(&client, &url).await;
+        let result = fetch_ogp_metadata(&client, &url).await.unwrap();
 
-        assert!(result.is_ok());
         assert_eq!(
-            result.unwrap(),
-            Some("https://example.com/image.jpg".to_string())
+            result,
+            OgpMetadata {
+                title: Some("OGタイトル".to_string()),
+                description: Some("OG説明".to_string()),
+                image_url: Some("https://example.com/image.jpg".to_string()),
+                favicon_url: Some(format!("{url}/icon.png")),
+                site_name: Some("Example".to_string()),
+            }
         );
     }
+
+    #[tokio::test]
+    async fn test_fetch_ogp_metadataでhtml以外はエラーになること() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("GET", "/image.png")
+            .with_header("content-type", "image/png")
+            .with_body("png")
+            .create();
+
+        let client = reqwest::Client::new();
+        let result = fetch_ogp_metadata(&client, &format!("{}/image.png", server.url())).await;
+
+        assert!(matches!(
+            result,
+            Err(OgpFetchError::UnsupportedContentType(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ogp_metadataでエラーステータスはエラーになること() {
+        let mut server = mockito::Server::new_async().await;
+
+        server.mock("GET", "/").with_status(404).create();
+
+        let client = reqwest::Client::new();
+        let result = fetch_ogp_metadata(&client, &server.url()).await;
+
+        assert!(matches!(
+            result,
+            Err(OgpFetchError::UnexpectedStatusCode(
+                reqwest::StatusCode::NOT_FOUND
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_ogp_metadataでogがない場合titleとdescriptionにフォールバックすること() {
+        let html = r#"
+            <html>
+                <head>
+                    <title> ページタイトル </title>
+                    <meta name="description" content="ページ説明">
+                    <meta property="og:image" content="/og.png">
+                </head>
+            </html>
+        "#;
+        let base_url = Url::parse("https://example.com/posts/1").unwrap();
+
+        let result = parse_ogp_metadata(html, &base_url);
+
+        assert_eq!(result.title.as_deref(), Some("ページタイトル"));
+        assert_eq!(result.description.as_deref(), Some("ページ説明"));
+        assert_eq!(
+            result.image_url.as_deref(),
+            Some("https://example.com/og.png")
+        );
+        assert_eq!(
+            result.favicon_url.as_deref(),
+            Some("https://example.com/favicon.ico")
+        );
+        assert!(result.site_name.is_none());
+    }
+
+    #[test]
+    fn test_parse_ogp_metadataで長い説明が切り詰められること() {
+        let long = "あ".repeat(OGP_MAX_TEXT_CHARS + 10);
+        let html = format!(r#"<meta property="og:description" content="{long}">"#);
+        let base_url = Url::parse("https://example.com/").unwrap();
+
+        let result = parse_ogp_metadata(&html, &base_url);
+
+        let description = result.description.unwrap();
+        assert_eq!(description.chars().count(), OGP_MAX_TEXT_CHARS + 1);
+        assert!(description.ends_with('…'));
+    }
 }
@@ -0,0 +1,141 @@
+use crate::common::embed_provider::EmbedProvider;
+use crate::error::OEmbedFetchError;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::instrument;
+
+/// oEmbed取得のタイムアウト
+const OEMBED_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 埋め込みの最大幅（本文の表示幅に合わせる）
+const OEMBED_MAX_WIDTH: u32 = 800;
+
+#[derive(Debug, Deserialize)]
+struct OEmbedResponse {
+    html: Option<String>,
+}
+
+/// プロバイダーのoEmbedエンドポイントからURLの埋め込みHTMLを取得
+#[instrument(skip(client))]
+pub(crate) async fn fetch_oembed_html(
+    client: &reqwest::Client,
+    provider: &EmbedProvider,
+    url: &str,
+) -> Result<String, OEmbedFetchError> {
+    fetch_oembed_html_from(client, &provider.oembed_endpoint, url).await
+}
+
+async fn fetch_oembed_html_from(
+    client: &reqwest::Client,
+    endpoint: &str,
+    url: &str,
+) -> Result<String, OEmbedFetchError> {
+    let response = client
+        .get(endpoint)
+        .query(&[
+            ("url", url),
+            ("format", "json"),
+            ("maxwidth", &OEMBED_MAX_WIDTH.to_string()),
+        ])
+        .timeout(OEMBED_FETCH_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(OEmbedFetchError::UnexpectedStatusCode(response.status()));
+    }
+
+    response
+        .json::<OEmbedResponse>()
+        .await?
+        .html
+        .filter(|html| !html.trim().is_empty())
+        .ok_or(OEmbedFetchError::MissingHtml)
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[tokio::test]
+    async fn test_fetch_oembed_htmlで埋め込みhtmlが取得できること() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/oembed")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded(
+                    "url".into(),
+                    "https://www.youtube.com/watch?v=abc".into(),
+                ),
+                Matcher::UrlEncoded("format".into(), "json".into()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"type":"video","html":"<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>"}"#,
+            )
+            .create();
+
+        let client = reqwest::Client::new();
+        let result = fetch_oembed_html_from(
+            &client,
+            &format!("{}/oembed", server.url()),
+            "https://www.youtube.com/watch?v=abc",
+        )
+        .await
+        .unwrap();
+
+        mock.assert();
+        assert_eq!(
+            result,
+            r#"<iframe src="https://www.youtube.com/embed/abc"></iframe>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_oembed_htmlでhtmlがない場合エラーになること() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/oembed")
+            .match_query(Matcher::Any)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"type":"link"}"#)
+            .create();
+
+        let client = reqwest::Client::new();
+        let result = fetch_oembed_html_from(
+            &client,
+            &format!("{}/oembed", server.url()),
+            "https://example.com/",
+        )
+        .await;
+
+        assert!(matches!(result, Err(OEmbedFetchError::MissingHtml)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_oembed_htmlでエラーステータスはエラーになること() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/oembed")
+            .match_query(Matcher::Any)
+            .with_status(404)
+            .create();
+
+        let client = reqwest::Client::new();
+        let result = fetch_oembed_html_from(
+            &client,
+            &format!("{}/oembed", server.url()),
+            "https://example.com/",
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(OEmbedFetchError::UnexpectedStatusCode(
+                reqwest::StatusCode::NOT_FOUND
+            ))
+        ));
+    }
+}
@@ -0,0 +1,439 @@
+//! Qiita独自のMarkdown記法とこのブログの記法を相互に変換する
+
+use crate::common::markdown::markdown_options;
+use comrak::nodes::{LineColumn, NodeValue};
+use comrak::{Arena, parse_document};
+use std::collections::{HashMap, HashSet};
+
+/// Qiita独自の記法をこのブログのMarkdownに変換する
+///
+/// - `:::note info|warn|alert` 〜 `:::` → `> [!NOTE]` / `> [!WARNING]` / `> [!CAUTION]` のアラート
+/// - ```` ```lang:file ```` → ファイル名をインラインコードにした行 + ```` ```lang ````
+///
+/// コードブロックの中は変換しない。
+pub(crate) fn convert_qiita_markdown(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_note = false;
+    let mut fence: Option<Fence> = None;
+
+    for line in markdown.lines() {
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            }
+            lines.push(quote(in_note, line));
+            continue;
+        }
+
+        if let Some((open, info)) = Fence::open(line) {
+            fence = Some(open);
+            match info.split_once(':') {
+                Some((lang, file)) if !file.trim().is_empty() => {
+                    lines.push(quote(in_note, &format!("`{}`", file.trim())));
+                    lines.push(quote(in_note, &format!("{}{}", open.marker(), lang.trim())));
+                }
+                _ => lines.push(quote(in_note, line)),
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if !in_note && let Some(kind) = note_alert_kind(trimmed) {
+            lines.push(format!("> [!{kind}]"));
+            in_note = true;
+            continue;
+        }
+        if in_note && trimmed == ":::" {
+            // 後続の行が引用に続けて解釈されないよう空行にする
+            lines.push(String::new());
+            in_note = false;
+            continue;
+        }
+
+        lines.push(quote(in_note, line));
+    }
+
+    let mut converted = lines.join("\n");
+    if markdown.ends_with('\n') {
+        converted.push('\n');
+    }
+    converted
+}
+
+/// このブログのMarkdownをQiitaに投稿する本文に変換する
+///
+/// - `> [!NOTE]` などのアラート → `:::note info|warn|alert` 〜 `:::`
+/// - ウィキリンク `[[slug]]` / `[[slug|ラベル]]` → 記事ページの絶対URLへのリンク（ラベル省略時は `wiki_link_titles` の記事タイトル）
+/// - ルート相対のリンク・画像（`](/...)` と参照定義 `[id]: /...`）→ `site_url` を付けた絶対URL
+///
+/// コードブロック・インラインコード・HTMLの中は変換しない。
+pub(crate) fn to_qiita_markdown(
+    markdown: &str,
+    site_url: &str,
+    wiki_link_titles: &HashMap<String, String>,
+) -> String {
+    let markdown = absolutize_links(markdown, site_url.trim_end_matches('/'), wiki_link_titles);
+    let mut lines = Vec::new();
+    let mut in_note = false;
+    let mut fence: Option<Fence> = None;
+
+    for line in markdown.lines() {
+        let line = if in_note {
+            match unquote(line) {
+                Some(inner) => inner,
+                None => {
+                    lines.push(":::".to_string());
+                    in_note = false;
+                    fence = None;
+                    line
+                }
+            }
+        } else {
+            line
+        };
+
+        if let Some(open) = fence {
+            if open.is_closed_by(line) {
+                fence = None;
+            }
+            lines.push(line.to_string());
+            continue;
+        }
+        if let Some((open, _)) = Fence::open(line) {
+            fence = Some(open);
+            lines.push(line.to_string());
+            continue;
+        }
+
+        if !in_note && let Some(kind) = alert_note_kind(line) {
+            lines.push(format!(":::note {kind}"));
+            in_note = true;
+            continue;
+        }
+
+        lines.push(line.to_string());
+    }
+    if in_note {
+        lines.push(":::".to_string());
+    }
+
+    let mut converted = lines.join("\n");
+    if markdown.ends_with('\n') {
+        converted.push('\n');
+    }
+    converted
+}
+
+/// アラートの開始行（`> [!NOTE]` など）なら対応する `:::note` の種別を返す
+fn alert_note_kind(line: &str) -> Option<&'static str> {
+    let kind = unquote(line)?
+        .trim()
+        .strip_prefix("[!")?
+        .strip_suffix(']')?;
+    Some(match kind.to_ascii_uppercase().as_str() {
+        "NOTE" | "TIP" | "IMPORTANT" => "info",
+        "WARNING" => "warn",
+        "CAUTION" => "alert",
+        _ => return None,
+    })
+}
+
+/// 引用の行なら `>` を外した中身を返す
+fn unquote(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('>')?;
+    Some(rest.strip_prefix(' ').unwrap_or(rest))
+}
+
+/// ウィキリンクとルート相対のURLを絶対URLのリンクにする
+///
+/// 本文を構文木にして、リンク・画像・ウィキリンクのノードの位置だけを書き換える。
+/// 参照定義（`[id]: /path`）は構文木に残らないため、コードブロック・HTMLブロックの外の行から探す。
+fn absolutize_links(
+    markdown: &str,
+    site_url: &str,
+    wiki_link_titles: &HashMap<String, String>,
+) -> String {
+    let options = markdown_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(markdown.match_indices('\n').map(|(pos, _)| pos + 1))
+        .collect();
+    let offset = |pos: LineColumn| line_starts[pos.line - 1] + pos.column - 1;
+
+    // (置き換える範囲の開始, 終了, 置き換える文字列)
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut literal_lines = HashSet::new();
+    for node in root.descendants() {
+        let sourcepos = node.data().sourcepos;
+        match node.data().value {
+            NodeValue::CodeBlock(_) | NodeValue::HtmlBlock(_) => {
+                literal_lines.extend(sourcepos.start.line..=sourcepos.end.line);
+            }
+            NodeValue::Link(ref link) | NodeValue::Image(ref link)
+                if is_root_relative(&link.url) =>
+            {
+                // リンクテキストの後ろの `](` から宛先を探す（参照リンクは参照定義の方を書き換える）
+                let text_end = match node.last_child() {
+                    Some(child) => offset(child.data().sourcepos.end) + 1,
+                    None => offset(sourcepos.start),
+                };
+                if let Some(destination) = inline_destination(markdown, text_end) {
+                    edits.push((destination, destination, site_url.to_string()));
+                }
+            }
+            NodeValue::WikiLink(_) => {
+                let (start, end) = (offset(sourcepos.start), offset(sourcepos.end) + 1);
+                if let Some(link) =
+                    wiki_link_markdown(&markdown[start..end], site_url, wiki_link_titles)
+                {
+                    edits.push((start, end, link));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (index, line) in markdown.split('\n').enumerate() {
+        if literal_lines.contains(&(index + 1)) {
+            continue;
+        }
+        let content = line.trim_start_matches(['>', ' ']);
+        if content.starts_with('[')
+            && let Some(pos) = content.find("]: /")
+            && !content[pos + 4..].starts_with('/')
+        {
+            let destination = line_starts[index] + line.len() - content.len() + pos + 3;
+            edits.push((destination, destination, site_url.to_string()));
+        }
+    }
+
+    let mut result = markdown.to_string();
+    edits.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    for (start, end, replacement) in edits {
+        result.replace_range(start..end, &replacement);
+    }
+    result
+}
+
+/// `//` で始まるプロトコル相対URLを除く、`/` で始まるURLか
+fn is_root_relative(url: &str) -> bool {
+    url.starts_with('/') && !url.starts_with("//")
+}
+
+/// リンクテキストの終わり（`]` の位置）に続くインラインの宛先が `/` で始まるなら、その位置を返す
+fn inline_destination(markdown: &str, text_end: usize) -> Option<usize> {
+    let rest = &markdown[text_end..];
+    let close = rest.find(']')?;
+    let after = rest[close + 1..].strip_prefix('(')?;
+    let trimmed = after.trim_start();
+    let trimmed = trimmed.strip_prefix('<').unwrap_or(trimmed);
+    is_root_relative(trimmed).then(|| markdown.len() - trimmed.len())
+}
+
+/// `[[slug]]` / `[[slug|ラベル]]` を記事ページへのリンクにする
+fn wiki_link_markdown(
+    source: &str,
+    site_url: &str,
+    wiki_link_titles: &HashMap<String, String>,
+) -> Option<String> {
+    let inner = source.strip_prefix("[[")?.strip_suffix("]]")?;
+    let (slug, label) = match inner.split_once('|') {
+        Some((slug, label)) => (slug.trim(), Some(label.trim())),
+        None => (inner.trim(), None),
+    };
+    if slug.is_empty() {
+        return None;
+    }
+    let label = label
+        .filter(|label| !label.is_empty())
+        .or_else(|| wiki_link_titles.get(slug).map(String::as_str))
+        .unwrap_or(slug);
+    Some(format!("[{label}]({site_url}/articles/{slug})"))
+}
+
+/// `:::note` の種別に対応するアラート（`:::note` 以外の行は `None`）
+fn note_alert_kind(line: &str) -> Option<&'static str> {
+    let rest = line.strip_prefix(":::note")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(match rest.trim() {
+        "warn" => "WARNING",
+        "alert" => "CAUTION",
+        _ => "NOTE",
+    })
+}
+
+/// メモ（`:::note`）の中の行は引用にする
+fn quote(in_note: bool, line: &str) -> String {
+    match (in_note, line.is_empty()) {
+        (false, _) => line.to_string(),
+        (true, true) => ">".to_string(),
+        (true, false) => format!("> {line}"),
+    }
+}
+
+/// 開いているコードフェンス
+#[derive(Debug, Clone, Copy)]
+struct Fence {
+    /// `` ` `` または `~`
+    char: char,
+    len: usize,
+}
+
+impl Fence {
+    /// コードフェンスの開始行なら、フェンスと情報文字列を返す
+    fn open(line: &str) -> Option<(Self, &str)> {
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            return None;
+        }
+        let char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == char).count();
+        if len < 3 {
+            return None;
+        }
+        let info = trimmed[len..].trim();
+        if char == '`' && info.contains('`') {
+            return None;
+        }
+        Some((Self { char, len }, info))
+    }
+
+    /// 開始と同じ文字で同じ長さ以上の、情報文字列のない行で閉じる
+    fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.len() >= self.len && trimmed.chars().all(|c| c == self.char)
+    }
+
+    fn marker(&self) -> String {
+        self.char.to_string().repeat(self.len)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noteの種別ごとにアラートに変換すること() {
+        let markdown = ":::note info\n補足です\n:::\n\n:::note warn\n注意です\n:::\n\n:::note alert\n警告です\n:::\n";
+
+        assert_eq!(
+            convert_qiita_markdown(markdown),
+            "> [!NOTE]\n> 補足です\n\n\n> [!WARNING]\n> 注意です\n\n\n> [!CAUTION]\n> 警告です\n\n"
+        );
+    }
+
+    #[test]
+    fn test_種別のないnoteは補足のアラートにすること() {
+        assert_eq!(
+            convert_qiita_markdown(":::note\n一行目\n\n二行目\n:::"),
+            "> [!NOTE]\n> 一行目\n>\n> 二行目\n"
+        );
+    }
+
+    #[test]
+    fn test_コードブロックのファイル名を直前の行に出すこと() {
+        assert_eq!(
+            convert_qiita_markdown("```ruby:app/main.rb\nputs 1\n```\n"),
+            "`app/main.rb`\n```ruby\nputs 1\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_言語のないファイル名だけのコードブロックも変換すること() {
+        assert_eq!(
+            convert_qiita_markdown("```:Gemfile\ngem 'rails'\n```"),
+            "`Gemfile`\n```\ngem 'rails'\n```"
+        );
+    }
+
+    #[test]
+    fn test_コードブロックの中は変換しないこと() {
+        let markdown = "````md\n:::note info\n```rust:main.rs\n```\n:::\n````\n";
+
+        assert_eq!(convert_qiita_markdown(markdown), markdown);
+    }
+
+    #[test]
+    fn test_noteの中のコードブロックも引用にすること() {
+        assert_eq!(
+            convert_qiita_markdown(":::note warn\n```sh:run.sh\nrm -rf tmp\n```\n:::\n"),
+            "> [!WARNING]\n> `run.sh`\n> ```sh\n> rm -rf tmp\n> ```\n\n"
+        );
+    }
+
+    #[test]
+    fn test_qiita独自の記法がない本文はそのまま返すこと() {
+        let markdown = "# 見出し\n\n```rust\nfn main() {}\n```\n\n:::notebook\n";
+
+        assert_eq!(convert_qiita_markdown(markdown), markdown);
+    }
+
+    const SITE_URL: &str = "https://blog.romira.dev";
+
+    #[test]
+    fn test_アラートをqiitaのnoteに変換すること() {
+        let markdown = "> [!TIP]\n> 補足です\n>\n> 二行目\n\n> [!WARNING]\n> 注意です\n\n> [!CAUTION]\n> 警告です\n";
+
+        assert_eq!(
+            to_qiita_markdown(markdown, SITE_URL, &HashMap::new()),
+            ":::note info\n補足です\n\n二行目\n:::\n\n:::note warn\n注意です\n:::\n\n:::note alert\n警告です\n:::\n"
+        );
+    }
+
+    #[test]
+    fn test_ルート相対の画像とリンクを絶対urlにすること() {
+        let markdown = "![図](/images/a.png) と [記事](/articles/foo) と [外部](https://example.com) と [cdn](//cdn.example.com/a.js)\n\n[ref]: /articles/bar\n";
+
+        assert_eq!(
+            to_qiita_markdown(markdown, "https://blog.romira.dev/", &HashMap::new()),
+            "![図](https://blog.romira.dev/images/a.png) と [記事](https://blog.romira.dev/articles/foo) と [外部](https://example.com) と [cdn](//cdn.example.com/a.js)\n\n[ref]: https://blog.romira.dev/articles/bar\n"
+        );
+    }
+
+    #[test]
+    fn test_インラインコードとhtmlの中のリンクは変換しないこと() {
+        let markdown = "`[記事](/articles/foo)` と `[[foo]]` と [記事](/articles/foo)\n\n<div>\n[記事](/articles/bar)\n</div>\n";
+
+        assert_eq!(
+            to_qiita_markdown(markdown, SITE_URL, &HashMap::new()),
+            "`[記事](/articles/foo)` と `[[foo]]` と [記事](https://blog.romira.dev/articles/foo)\n\n<div>\n[記事](/articles/bar)\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn test_リンクテキストの中の画像や装飾があっても宛先を絶対urlにすること() {
+        let markdown = "> [![図](/images/a.png) と **太字**](/articles/foo \"タイトル\") と [参照][ref]\n\n[ref]: /articles/bar\n";
+
+        assert_eq!(
+            to_qiita_markdown(markdown, SITE_URL, &HashMap::new()),
+            "> [![図](https://blog.romira.dev/images/a.png) と **太字**](https://blog.romira.dev/articles/foo \"タイトル\") と [参照][ref]\n\n[ref]: https://blog.romira.dev/articles/bar\n"
+        );
+    }
+
+    #[test]
+    fn test_ウィキリンクを記事タイトルのリンクにすること() {
+        let titles = HashMap::from([("foo".to_string(), "Fooの記事".to_string())]);
+
+        assert_eq!(
+            to_qiita_markdown("[[foo]] と [[bar|ラベル]] と [[baz]]", SITE_URL, &titles),
+            "[Fooの記事](https://blog.romira.dev/articles/foo) と [ラベル](https://blog.romira.dev/articles/bar) と [baz](https://blog.romira.dev/articles/baz)"
+        );
+    }
+
+    #[test]
+    fn test_qiitaに投稿する本文のコードブロックの中は変換しないこと() {
+        let markdown =
+            "> [!NOTE]\n> ```md\n> ![図](/images/a.png)\n> ```\n\n```md\n> [!NOTE]\n[[foo]]\n```\n";
+
+        assert_eq!(
+            to_qiita_markdown(markdown, SITE_URL, &HashMap::new()),
+            ":::note info\n```md\n![図](/images/a.png)\n```\n:::\n\n```md\n> [!NOTE]\n[[foo]]\n```\n"
+        );
+    }
+}
@@ -191,6 +191,17 @@ async fn insert_published_article(pool: &PgPool, title: &str, slug: &str, body:
     .expect("Failed to insert published article")
 }
 
+async fn set_draft_description(pool: &PgPool, draft_id: Uuid, description: &str) {
+    sqlx::query!(
+        "UPDATE draft_articles SET description = $1 WHERE id = $2",
+        description,
+        draft_id
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to update draft description");
+}
+
 // =====================================
 // get_admin_articles_handler のテスト
 // =====================================
@@ -485,6 +496,57 @@ async fn test_save_published_正常系_記事が更新されること(pool: PgPo
     assert_eq!(article.slug, "updated-slug");
 }
 
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_同じカテゴリの記事が関連記事として表示されること(
+    pool: PgPool,
+) {
+    let rust_id = insert_published_article(&pool, "Rust入門", "rust-intro", "Body").await;
+    let async_id = insert_published_article(&pool, "非同期Rust", "rust-async", "Body").await;
+    insert_published_article(&pool, "旅行記", "travel", "京都に行った").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    for (id, title, slug) in [
+        (rust_id, "Rust入門", "rust-intro"),
+        (async_id, "非同期Rust", "rust-async"),
+    ] {
+        let input = json!({
+            "input": {
+                "id": id.to_string(),
+                "title": title,
+                "slug": slug,
+                "body": "Body",
+                "description": "Description",
+                "category_names": ["Rust"]
+            }
+        });
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/admin/save_published")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&input).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/get_article_handler?id=rust-intro")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let related = json["Found"]["related_articles"].as_array().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0]["title"], "非同期Rust");
+    assert_eq!(related[0]["src"], "/articles/rust-async");
+}
+
 #[sqlx::test(migrations = "../migrations")]
 async fn test_save_published_空タイトルの場合バリデーションエラーを返すこと(
     pool: PgPool,
@@ -549,6 +611,408 @@ async fn test_save_published_空スラッグの場合バリデーションエラ
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_seo設定が記事ページのメタ情報とサイトマップに反映されること(
+    pool: PgPool,
+) {
+    let published_id =
+        insert_published_article(&pool, "Original Title", "seo-slug", "Original Body").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state.clone());
+
+    let input = json!({
+        "input": {
+            "id": published_id.to_string(),
+            "title": "記事タイトル",
+            "slug": "seo-slug",
+            "body": "本文",
+            "description": "記事の説明",
+            "seo": {
+                "canonical_url": "https://qiita.com/Romira/items/abc",
+                "robots": "NoIndex, nofollow",
+                "og_title": "共有用のタイトル",
+                "og_description": " ",
+                "twitter_card": "summary"
+            }
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_published")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 編集画面には正規化した値が返る
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/admin/get_article?id={}", published_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let article: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        article["seo"],
+        json!({
+            "canonical_url": "https://qiita.com/Romira/items/abc",
+            "robots": "noindex, nofollow",
+            "og_title": "共有用のタイトル",
+            "og_description": null,
+            "og_image_url": null,
+            "twitter_card": "summary"
+        })
+    );
+
+    // 未設定の項目は記事の内容から決まる
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/get_article_handler?id=seo-slug")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let meta = &json["Found"]["article_meta_dto"];
+    assert_eq!(meta["canonical_url"], "https://qiita.com/Romira/items/abc");
+    assert_eq!(meta["robots"], "noindex, nofollow");
+    assert_eq!(meta["og_title"], "共有用のタイトル");
+    assert_eq!(meta["og_description"], "記事の説明");
+    assert_eq!(
+        meta["og_image_url"],
+        "https://blog.romira.dev/og/seo-slug.png"
+    );
+    assert_eq!(meta["twitter_card"], "summary");
+
+    // noindexの記事はサイトマップに含めない
+    let seo_app = blog_romira_dev_app::seo_routes().with_state(app_state);
+    let (status, body) = get_body(seo_app, "/sitemaps/articles.xml").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains("seo-slug"), "{body}");
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_不正なseo設定の場合バリデーションエラーを返すこと(
+    pool: PgPool,
+) {
+    let published_id =
+        insert_published_article(&pool, "Original Title", "original-slug", "Original Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "id": published_id.to_string(),
+            "title": "Title",
+            "slug": "original-slug",
+            "body": "Body",
+            "description": "Description",
+            "seo": { "canonical_url": "qiita.com/Romira/items/abc" }
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_published")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+async fn save_published_visibility(
+    app: &Router,
+    id: Uuid,
+    slug: &str,
+    visibility: &str,
+    password: Option<&str>,
+) -> StatusCode {
+    let input = json!({
+        "input": {
+            "id": id.to_string(),
+            "title": "限定記事",
+            "slug": slug,
+            "body": "本文",
+            "description": "記事の説明",
+            "visibility": visibility,
+            "password": password
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_published")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_限定公開の記事はurlで閲覧できるがサイトマップとフィードに含まれないこと(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Original", "unlisted-slug", "Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state.clone());
+
+    let status =
+        save_published_visibility(&app, published_id, "unlisted-slug", "unlisted", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/get_article_handler?id=unlisted-slug")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers().clone();
+    assert!(
+        headers
+            .get("cache-control")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("private")
+    );
+    assert_eq!(headers.get("x-robots-tag").unwrap(), "noindex");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["Found"]["article_meta_dto"]["robots"], "noindex");
+
+    let seo_app = blog_romira_dev_app::seo_routes().with_state(app_state);
+    for uri in ["/sitemaps/articles.xml", "/feed.xml"] {
+        let (_, body) = get_body(seo_app.clone(), uri).await;
+        assert!(!body.contains("unlisted-slug"), "{uri}: {body}");
+    }
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_パスワードなしでパスワード保護にするとバリデーションエラーを返すこと(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Original", "protected-slug", "Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let status =
+        save_published_visibility(&app, published_id, "protected-slug", "protected", None).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_スラッグが重複する場合は公開範囲も更新しないこと(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Original", "original-slug", "Body").await;
+    insert_published_article(&pool, "Other", "taken-slug", "Body").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    let status = save_published_visibility(
+        &app,
+        published_id,
+        "taken-slug",
+        "protected",
+        Some("secret-pass"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let article = sqlx::query!(
+        r#"SELECT slug, visibility::text as "visibility!", password_hash FROM published_articles WHERE id = $1"#,
+        published_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Article should exist");
+    assert_eq!(article.slug, "original-slug");
+    assert_eq!(article.visibility, "public");
+    assert!(article.password_hash.is_none());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_パスワード保護の記事はパスワードを入力すると閲覧できること(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Original", "protected-slug", "Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let status = save_published_visibility(
+        &app,
+        published_id,
+        "protected-slug",
+        "protected",
+        Some("secret-pass"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Cookieがなければ本文を返さない
+    let (status, body) = get_body(app.clone(), "/api/get_article_handler?id=protected-slug").await;
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json, json!({ "Protected": "protected-slug" }));
+
+    let unlock = |password: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/unlock_article")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from(format!(
+                "slug=protected-slug&password={}",
+                password
+            )))
+            .unwrap()
+    };
+
+    // パスワードが違う場合はCookieを発行しない
+    let response = app.clone().oneshot(unlock("wrong-pass")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("set-cookie").is_none());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"false");
+
+    let response = app.clone().oneshot(unlock("secret-pass")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let set_cookie = response
+        .headers()
+        .get("set-cookie")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(set_cookie.starts_with("article_access_protected-slug="));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"true");
+
+    // 発行されたCookieがあれば本文を返す
+    let cookie = set_cookie.split(';').next().unwrap();
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/get_article_handler?id=protected-slug")
+        .header("cookie", cookie)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["Found"]["article_detail_dto"]["title"], "限定記事");
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_パスワードの試行回数が上限を超えると429を返すこと(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Original", "protected-slug", "Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let status = save_published_visibility(
+        &app,
+        published_id,
+        "protected-slug",
+        "protected",
+        Some("secret-pass"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let unlock = |ip: &'static str, password: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/unlock_article")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("x-real-ip", ip)
+            .body(Body::from(format!(
+                "slug=protected-slug&password={}",
+                password
+            )))
+            .unwrap()
+    };
+
+    for _ in 0..10 {
+        let response = app
+            .clone()
+            .oneshot(unlock("203.0.113.1", "wrong-pass"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // 上限を超えると正しいパスワードでも照合しない
+    let response = app
+        .clone()
+        .oneshot(unlock("203.0.113.1", "secret-pass"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().get("set-cookie").is_none());
+
+    // 別のクライアントは試行できる
+    let response = app
+        .oneshot(unlock("203.0.113.2", "secret-pass"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("set-cookie").is_some());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_説明文が空の場合構造チェックでバリデーションエラーを返すこと(
+    pool: PgPool,
+) {
+    let published_id =
+        insert_published_article(&pool, "Original Title", "original-slug", "Original Body").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "id": published_id.to_string(),
+            "title": "Updated Title",
+            "slug": "original-slug",
+            "body": "Updated Body",
+            "description": null
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_published")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 記事は更新されていないことを確認
+    let title = sqlx::query_scalar!(
+        "SELECT title FROM published_articles WHERE id = $1",
+        published_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(title, "Original Title");
+}
+
 // =====================================
 // publish_article_handler のテスト
 // =====================================
@@ -556,6 +1020,7 @@ async fn test_save_published_空スラッグの場合バリデーションエラ
 #[sqlx::test(migrations = "../migrations")]
 async fn test_publish_article_正常系_下書きが公開記事になること(pool: PgPool) {
     let draft_id = insert_draft_article(&pool, "Draft Title", "draft-slug", "Draft Body").await;
+    set_draft_description(&pool, draft_id, "Draft Description").await;
 
     let app_state = create_test_app_state(pool.clone());
     let app = build_test_router(app_state);
@@ -568,102 +1033,1084 @@ async fn test_publish_article_正常系_下書きが公開記事になること(
 
     let request = Request::builder()
         .method("POST")
-        .uri("/api/admin/publish_article")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let published_id_str: String = serde_json::from_slice(&body).unwrap();
+    let published_id = Uuid::parse_str(&published_id_str).expect("Valid UUID should be returned");
+
+    // 公開記事が作成されていることを確認
+    let published = sqlx::query!(
+        "SELECT title, slug FROM published_articles WHERE id = $1",
+        published_id
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Published article should exist");
+    assert_eq!(published.title, "Draft Title");
+    assert_eq!(published.slug, "draft-slug");
+
+    // 下書きが削除されていることを確認
+    let draft_count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM draft_articles WHERE id = $1",
+        draft_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(draft_count, Some(0));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_ウィキリンク先の記事にバックリンクが表示されること(
+    pool: PgPool,
+) {
+    insert_published_article(&pool, "Target Title", "target-slug", "Target Body").await;
+    let draft_id = insert_draft_article(
+        &pool,
+        "Source Title",
+        "source-slug",
+        "[[target-slug]]と[[source-slug|自分自身]]",
+    )
+    .await;
+    set_draft_description(&pool, draft_id, "Source Description").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    // 自分自身へのリンクは公開前のため構造チェックのエラーになるので無視する
+    let input = json!({
+        "input": {
+            "id": draft_id.to_string(),
+            "ignore_lint_errors": true
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 自分自身へのリンクは保存されない
+    let targets = sqlx::query_scalar!("SELECT target_slug FROM article_links")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(targets, vec!["target-slug".to_string()]);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/get_article_handler?id=target-slug")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json["Found"]["article_detail_dto"]["backlinks"],
+        json!([{ "title": "Source Title", "src": "/articles/source-slug" }])
+    );
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_save_published_ウィキリンクを外すとリンクが削除されること(
+    pool: PgPool,
+) {
+    insert_published_article(&pool, "Target Title", "target-slug", "Target Body").await;
+    let source_id =
+        insert_published_article(&pool, "Source Title", "source-slug", "Source Body").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    for (body, expected) in [
+        (
+            "[前回](/articles/target-slug)",
+            vec!["target-slug".to_string()],
+        ),
+        ("リンクなし", vec![]),
+    ] {
+        let input = json!({
+            "input": {
+                "id": source_id.to_string(),
+                "title": "Source Title",
+                "slug": "source-slug",
+                "body": body,
+                "description": "Source Description"
+            }
+        });
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/admin/save_published")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&input).unwrap()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let targets = sqlx::query_scalar!(
+            "SELECT target_slug FROM article_links WHERE source_article_id = $1",
+            source_id
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(targets, expected);
+    }
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_存在しない下書きの場合notfoundエラーを返すこと(
+    pool: PgPool,
+) {
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let nonexistent_id = Uuid::now_v7();
+    let input = json!({
+        "input": {
+            "id": nonexistent_id.to_string()
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_下書きが取得できない場合は公開せずエラーを返すこと(
+    pool: PgPool,
+) {
+    let draft_id = insert_draft_article(&pool, "Title", "fetch-error-slug", "Body").await;
+    // アプリだけが使うプールを閉じて、下書きの取得を失敗させる
+    let app_pool = sqlx::postgres::PgPoolOptions::new()
+        .connect_with((*pool.connect_options()).clone())
+        .await
+        .unwrap();
+    app_pool.close().await;
+    let app = build_test_router(create_test_app_state(app_pool));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_vec(&json!({ "input": { "id": draft_id.to_string() } })).unwrap(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let draft_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM draft_articles WHERE id = $1")
+        .bind(draft_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(draft_count, 1);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_空スラッグの下書きの場合バリデーションエラーを返すこと(
+    pool: PgPool,
+) {
+    let draft_id = insert_draft_article(&pool, "Draft Title", "", "Draft Body").await;
+    set_draft_description(&pool, draft_id, "Draft Description").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "id": draft_id.to_string()
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 下書きは削除されていないことを確認
+    let draft_count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM draft_articles WHERE id = $1",
+        draft_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(draft_count, Some(1));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_構造チェックでエラーがある場合公開されないこと(
+    pool: PgPool,
+) {
+    let draft_id = insert_draft_article(
+        &pool,
+        "Draft Title",
+        "draft-slug",
+        "[壊れたリンク](/articles/missing-article)",
+    )
+    .await;
+    set_draft_description(&pool, draft_id, "Draft Description").await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "id": draft_id.to_string()
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains("/articles/missing-article"));
+
+    // 下書きは削除されていないことを確認
+    let draft_count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM draft_articles WHERE id = $1",
+        draft_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(draft_count, Some(1));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_article_構造チェックのエラーを無視すると公開されること(
+    pool: PgPool,
+) {
+    let draft_id = insert_draft_article(
+        &pool,
+        "Draft Title",
+        "draft-slug",
+        "[壊れたリンク](/articles/missing-article)",
+    )
+    .await;
+
+    let app_state = create_test_app_state(pool.clone());
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "id": draft_id.to_string(),
+            "ignore_lint_errors": true
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/publish_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// =====================================
+// lint_article_handler のテスト
+// =====================================
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_lint_article_問題一覧を返すこと(pool: PgPool) {
+    insert_published_article(&pool, "Existing", "existing-article", "Body").await;
+
+    let app_state = create_test_app_state(pool);
+    let app = build_test_router(app_state);
+
+    let input = json!({
+        "input": {
+            "body": "[ok](/articles/existing-article)\n\n[ng](/articles/missing-article)\n\n![](https://example.com/a.png)",
+            "description": null
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/lint_article")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let issues: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let rules: Vec<&str> = issues
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["rule"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "EmptyDescription",
+            "BrokenInternalLink",
+            "MissingAltText",
+            "ExternalImageHost"
+        ]
+    );
+}
+
+// =====================================
+// 文章チェック設定のテスト
+// =====================================
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_prose_check_settings_保存した設定が取得できること(pool: PgPool) {
+    let app_state = create_test_app_state(pool);
+
+    // 未保存なら全ルール有効
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/admin/get_prose_check_settings")
+        .body(Body::empty())
+        .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let settings: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let rules = settings["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 5);
+    assert!(rules.iter().all(|rule| rule["enabled"] == true));
+
+    let input = json!({
+        "input": {
+            "rules": [
+                { "rule": "RaNuki", "enabled": false },
+                { "rule": "LongSentence", "enabled": true }
+            ]
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_prose_check_settings")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/admin/get_prose_check_settings")
+        .body(Body::empty())
+        .unwrap();
+    let response = build_test_router(app_state).oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let settings: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let disabled: Vec<&str> = settings["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|rule| rule["enabled"] == false)
+        .map(|rule| rule["rule"].as_str().unwrap())
+        .collect();
+    assert_eq!(disabled, vec!["RaNuki"]);
+}
+
+// =====================================
+// featured_items のテスト
+// =====================================
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_featured_items_保存したおすすめが表示順に取得できること(
+    pool: PgPool,
+) {
+    let published_id = insert_published_article(&pool, "Pinned", "pinned", "Body").await;
+    let app_state = create_test_app_state(pool);
+
+    let input = json!({
+        "input": {
+            "items": [
+                {
+                    "external_url": " https://qiita.com/Romira/items/abc ",
+                    "expires_on": "2099-12-31"
+                },
+                { "article_id": published_id.to_string() }
+            ]
+        }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_featured_items")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (status, body) = get_body(
+        build_test_router(app_state),
+        "/api/admin/get_featured_items",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(
+        items,
+        json!([
+            {
+                "article_id": null,
+                "external_url": "https://qiita.com/Romira/items/abc",
+                "expires_on": "2099-12-31"
+            },
+            {
+                "article_id": published_id.to_string(),
+                "external_url": null,
+                "expires_on": null
+            }
+        ])
+    );
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_featured_items_不正なurlの場合バリデーションエラーを返すこと(
+    pool: PgPool,
+) {
+    let app_state = create_test_app_state(pool);
+
+    let input = json!({
+        "input": { "items": [{ "external_url": "qiita.com/Romira/items/abc" }] }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/save_featured_items")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .unwrap();
+    let response = build_test_router(app_state).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// =====================================
+// 外部記事ソースのテスト
+// =====================================
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_外部記事ソースの更新状況_ソースがない場合は空配列を返すこと(
+    pool: PgPool,
+) {
+    let app_state = create_test_app_state(pool);
+
+    let (status, body) = get_body(
+        build_test_router(app_state),
+        "/api/admin/get_external_source_statuses",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let statuses: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(statuses, json!([]));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_トップページの記事一覧_外部記事を取得せずに公開記事を返すこと(
+    pool: PgPool,
+) {
+    insert_published_article(&pool, "ローカルの記事", "local-article", "本文").await;
+    let app_state = create_test_app_state(pool);
+
+    let (status, body) = get_body(
+        build_test_router_with_auth(app_state),
+        "/api/get_articles_handler",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let dto: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(dto["articles"][0]["title"], "ローカルの記事");
+    assert_eq!(dto["degraded_sources"], json!([]));
+}
+
+// =====================================
+// Qiita記事の取り込みのテスト
+// =====================================
+
+/// Qiita APIの記事一覧の1件分
+fn qiita_item_json(
+    base_url: &str,
+    id: &str,
+    title: &str,
+    body: &str,
+    tags: &[&str],
+) -> serde_json::Value {
+    json!({
+        "rendered_body": "",
+        "body": body,
+        "coediting": false,
+        "comments_count": 0,
+        "created_at": "2020-04-01T12:00:00+09:00",
+        "group": null,
+        "id": id,
+        "likes_count": 0,
+        "private": false,
+        "reactions_count": 0,
+        "stocks_count": 0,
+        "tags": tags.iter().map(|tag| json!({ "name": tag, "versions": [] })).collect::<Vec<_>>(),
+        "title": title,
+        "updated_at": "2020-04-01T12:00:00+09:00",
+        "url": format!("{base_url}/Romira/items/{id}"),
+        "user": {
+            "description": null,
+            "facebook_id": null,
+            "followees_count": 0,
+            "followers_count": 0,
+            "github_login_name": null,
+            "id": "Romira",
+            "items_count": 2,
+            "linkedin_id": null,
+            "location": null,
+            "name": null,
+            "organization": null,
+            "permanent_id": 1,
+            "profile_image_url": "https://example.com/avatar.png",
+            "team_only": false,
+            "twitter_screen_name": null,
+            "website_url": null
+        },
+        "page_views_count": null,
+        "team_membership": null,
+        "organization_url_name": null,
+        "slide": false
+    })
+}
+
+/// 認証ユーザーの記事一覧のモック（1ページのみ）
+async fn mock_qiita_items(server: &mut mockito::Server, items: serde_json::Value) -> mockito::Mock {
+    server
+        .mock("GET", "/api/v2/authenticated_user/items")
+        .match_query(mockito::Matcher::Any)
+        .with_header("content-type", "application/json")
+        .with_body(items.to_string())
+        .create_async()
+        .await
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_qiita取り込み候補_取り込み済みの記事に印を付けること(
+    pool: PgPool,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let _mock = mock_qiita_items(
+        &mut server,
+        json!([
+            qiita_item_json(&base_url, "aaa111", "取り込み済みの記事", "本文", &["Rust"]),
+            qiita_item_json(&base_url, "bbb222", "新しい記事", "本文", &["Leptos"]),
+        ]),
+    )
+    .await;
+    let draft_id = insert_draft_article(&pool, "取り込み済みの記事", "qiita-aaa111", "本文").await;
+    sqlx::query!(
+        "UPDATE draft_articles SET origin_url = $1 WHERE id = $2",
+        format!("{base_url}/Romira/items/aaa111"),
+        draft_id
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let app_state = create_test_app_state(pool).with_qiita_base_url(&base_url);
+
+    let (status, body) = get_body(
+        build_test_router(app_state),
+        "/api/admin/get_qiita_import_candidates",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let candidates: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(
+        candidates,
+        json!([
+            {
+                "id": "aaa111",
+                "title": "取り込み済みの記事",
+                "url": format!("{base_url}/Romira/items/aaa111"),
+                "tags": ["Rust"],
+                "created_at": "2020年04月01日",
+                "imported": true
+            },
+            {
+                "id": "bbb222",
+                "title": "新しい記事",
+                "url": format!("{base_url}/Romira/items/bbb222"),
+                "tags": ["Leptos"],
+                "created_at": "2020年04月01日",
+                "imported": false
+            }
+        ])
+    );
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_qiita記事の取り込み_記法を変換しタグをカテゴリにして下書きを作成すること(
+    pool: PgPool,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let _mock = mock_qiita_items(
+        &mut server,
+        json!([
+            qiita_item_json(
+                &base_url,
+                "ccc333",
+                "Qiitaの記事",
+                ":::note warn\n注意\n:::\n\n```rust:main.rs\nfn main() {}\n```\n",
+                &["Rust", "Leptos"],
+            ),
+            qiita_item_json(&base_url, "ddd444", "選ばない記事", "本文", &[]),
+        ]),
+    )
+    .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/import_qiita_items")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_vec(&json!({ "input": { "ids": ["ccc333"] } })).unwrap(),
+        ))
+        .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let drafts: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(drafts.as_array().unwrap().len(), 1);
+    assert_eq!(drafts[0]["title"], "Qiitaの記事");
+
+    let draft_id = Uuid::parse_str(drafts[0]["id"].as_str().unwrap()).unwrap();
+    let draft = sqlx::query!(
+        "SELECT slug, body, origin_url FROM draft_articles WHERE id = $1",
+        draft_id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(draft.slug, "qiita-ccc333");
+    assert_eq!(
+        draft.body,
+        "> [!WARNING]\n> 注意\n\n\n`main.rs`\n```rust\nfn main() {}\n```\n"
+    );
+    assert_eq!(
+        draft.origin_url,
+        Some(format!("{base_url}/Romira/items/ccc333"))
+    );
+    let mut categories = sqlx::query_scalar!(
+        r#"SELECT c.name FROM categories c
+           INNER JOIN draft_article_categories dac ON dac.category_id = c.id
+           WHERE dac.article_id = $1"#,
+        draft_id
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    categories.sort();
+    assert_eq!(categories, vec!["Leptos".to_string(), "Rust".to_string()]);
+
+    // 取り込み済みの記事は取り込み直さない
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/import_qiita_items")
         .header("content-type", "application/json")
-        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .body(Body::from(
+            serde_json::to_vec(&json!({ "input": { "ids": ["ccc333"] } })).unwrap(),
+        ))
         .unwrap();
-
-    let response = app.oneshot(request).await.unwrap();
-
+    let response = build_test_router(app_state).oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let drafts: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(drafts, json!([]));
+}
+
+// =====================================
+// Qiitaへの転載のテスト
+// =====================================
 
+/// 管理APIにJSONをPOSTし、ステータスとレスポンスのJSONを返す
+async fn post_admin_json(
+    app_state: &AppState,
+    endpoint: &str,
+    input: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/admin/{endpoint}"))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_vec(&json!({ "input": input })).unwrap(),
+        ))
+        .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
     let body = response.into_body().collect().await.unwrap().to_bytes();
-    let published_id_str: String = serde_json::from_slice(&body).unwrap();
-    let published_id = Uuid::parse_str(&published_id_str).expect("Valid UUID should be returned");
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
 
-    // 公開記事が作成されていることを確認
-    let published = sqlx::query!(
-        "SELECT title, slug FROM published_articles WHERE id = $1",
-        published_id
+/// 転載状況（`article_syndications`）のQiitaの行
+async fn fetch_qiita_syndication(
+    pool: &PgPool,
+    article_id: Uuid,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let row = sqlx::query!(
+        "SELECT remote_id, remote_url, last_error FROM article_syndications WHERE article_id = $1 AND target = 'qiita'",
+        article_id
     )
-    .fetch_one(&pool)
+    .fetch_one(pool)
     .await
-    .expect("Published article should exist");
-    assert_eq!(published.title, "Draft Title");
-    assert_eq!(published.slug, "draft-slug");
+    .expect("Syndication should exist");
+    (row.remote_id, row.remote_url, row.last_error)
+}
 
-    // 下書きが削除されていることを確認
-    let draft_count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM draft_articles WHERE id = $1",
-        draft_id
+#[sqlx::test(migrations = "../migrations")]
+async fn test_qiitaへの転載_公開時に投稿し保存時は同じ記事を更新すること(
+    pool: PgPool,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let created = server
+        .mock("POST", "/api/v2/items")
+        .match_body(mockito::Matcher::PartialJson(json!({
+            "title": "転載する記事",
+            "body": "![図](http://localhost:3000/images/a.png)\n\n---\n\nこの記事は http://localhost:3000/articles/syndicated-slug からの転載です。\n",
+            "tags": [{ "name": "Web-Assembly", "versions": [] }],
+            "private": false,
+        })))
+        .with_status(201)
+        .with_body(json!({ "id": "abc123", "url": format!("{base_url}/Romira/items/abc123") }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let updated = server
+        .mock("PATCH", "/api/v2/items/abc123")
+        .match_body(mockito::Matcher::PartialJson(
+            json!({ "title": "更新した記事" }),
+        ))
+        .with_body(
+            json!({ "id": "abc123", "url": format!("{base_url}/Romira/items/abc123") }).to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+
+    let (status, draft_id) = post_admin_json(
+        &app_state,
+        "save_draft",
+        json!({
+            "id": Uuid::now_v7().to_string(),
+            "title": "転載する記事",
+            "slug": "syndicated-slug",
+            "body": "![図](/images/a.png)",
+            "description": "説明",
+            "category_names": ["Web Assembly"],
+        }),
     )
-    .fetch_one(&pool)
-    .await
-    .unwrap();
-    assert_eq!(draft_count, Some(0));
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, published_id) = post_admin_json(
+        &app_state,
+        "publish_article",
+        json!({ "id": draft_id.as_str().unwrap() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let published_id = Uuid::parse_str(published_id.as_str().unwrap()).unwrap();
+
+    created.assert_async().await;
+    let (remote_id, remote_url, last_error) = fetch_qiita_syndication(&pool, published_id).await;
+    assert_eq!(remote_id.as_deref(), Some("abc123"));
+    assert_eq!(remote_url, Some(format!("{base_url}/Romira/items/abc123")));
+    assert_eq!(last_error, None);
+
+    // 2回目以降は投稿済みの記事を更新する
+    let (status, _) = post_admin_json(
+        &app_state,
+        "save_published",
+        json!({
+            "id": published_id.to_string(),
+            "title": "更新した記事",
+            "slug": "syndicated-slug",
+            "body": "本文",
+            "description": "説明",
+            "category_names": ["Web Assembly"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    updated.assert_async().await;
+    let (remote_id, _, last_error) = fetch_qiita_syndication(&pool, published_id).await;
+    assert_eq!(remote_id.as_deref(), Some("abc123"));
+    assert_eq!(last_error, None);
 }
 
 #[sqlx::test(migrations = "../migrations")]
-async fn test_publish_article_存在しない下書きの場合notfoundエラーを返すこと(
+async fn test_qiitaへの転載_失敗しても保存は成功し状況に記録して再試行できること(
     pool: PgPool,
 ) {
-    let app_state = create_test_app_state(pool);
-    let app = build_test_router(app_state);
-
-    let nonexistent_id = Uuid::now_v7();
-    let input = json!({
-        "input": {
-            "id": nonexistent_id.to_string()
-        }
-    });
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let failed = server
+        .mock("POST", "/api/v2/items")
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+    let created = server
+        .mock("POST", "/api/v2/items")
+        .with_status(201)
+        .with_body(
+            json!({ "id": "def456", "url": format!("{base_url}/Romira/items/def456") }).to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+    let published_id = insert_published_article(&pool, "Title", "retry-slug", "Body").await;
+
+    let (status, _) = post_admin_json(
+        &app_state,
+        "save_published",
+        json!({
+            "id": published_id.to_string(),
+            "title": "Title",
+            "slug": "retry-slug",
+            "body": "Body",
+            "description": "説明",
+            "category_names": ["Rust"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    failed.assert_async().await;
 
     let request = Request::builder()
-        .method("POST")
-        .uri("/api/admin/publish_article")
-        .header("content-type", "application/json")
-        .body(Body::from(serde_json::to_vec(&input).unwrap()))
+        .method("GET")
+        .uri(format!(
+            "/api/admin/get_syndications?article_id={published_id}"
+        ))
+        .body(Body::empty())
         .unwrap();
+    let response = build_test_router(app_state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let statuses: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(statuses[0]["target"], "qiita");
+    assert_eq!(statuses[0]["remote_url"], serde_json::Value::Null);
+    assert_eq!(
+        statuses[0]["last_error"],
+        "Failed to api response status code: 500 Internal Server Error"
+    );
 
-    let response = app.oneshot(request).await.unwrap();
-
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let (status, statuses) = post_admin_json(
+        &app_state,
+        "retry_syndication",
+        json!({ "article_id": published_id.to_string() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    created.assert_async().await;
+    assert_eq!(
+        statuses[0]["remote_url"],
+        format!("{base_url}/Romira/items/def456")
+    );
+    assert_eq!(statuses[0]["last_error"], serde_json::Value::Null);
+    assert!(statuses[0]["synced_at"].is_string());
 }
 
 #[sqlx::test(migrations = "../migrations")]
-async fn test_publish_article_空スラッグの下書きの場合バリデーションエラーを返すこと(
+async fn test_qiitaへの転載_同時に反映しても記事を重複して投稿しないこと(
     pool: PgPool,
 ) {
-    let draft_id = insert_draft_article(&pool, "Draft Title", "", "Draft Body").await;
-
-    let app_state = create_test_app_state(pool.clone());
-    let app = build_test_router(app_state);
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let created = server
+        .mock("POST", "/api/v2/items")
+        .with_status(201)
+        .with_body(
+            json!({ "id": "jkl012", "url": format!("{base_url}/Romira/items/jkl012") }).to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("PATCH", "/api/v2/items/jkl012")
+        .with_body(
+            json!({ "id": "jkl012", "url": format!("{base_url}/Romira/items/jkl012") }).to_string(),
+        )
+        .expect_at_most(1)
+        .create_async()
+        .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+    let published_id = insert_published_article(&pool, "Title", "concurrent-slug", "Body").await;
+    app_state
+        .category_service()
+        .save_for_published(published_id, &["Rust".to_string()])
+        .await
+        .unwrap();
 
-    let input = json!({
-        "input": {
-            "id": draft_id.to_string()
-        }
-    });
+    let retry = || {
+        post_admin_json(
+            &app_state,
+            "retry_syndication",
+            json!({ "article_id": published_id.to_string() }),
+        )
+    };
+    let ((first, _), (second, _)) = tokio::join!(retry(), retry());
+    assert_eq!(first, StatusCode::OK);
+    assert_eq!(second, StatusCode::OK);
+
+    created.assert_async().await;
+    let (remote_id, _, last_error) = fetch_qiita_syndication(&pool, published_id).await;
+    assert_eq!(remote_id.as_deref(), Some("jkl012"));
+    assert_eq!(last_error, None);
+}
 
-    let request = Request::builder()
-        .method("POST")
-        .uri("/api/admin/publish_article")
-        .header("content-type", "application/json")
-        .body(Body::from(serde_json::to_vec(&input).unwrap()))
-        .unwrap();
+#[sqlx::test(migrations = "../migrations")]
+async fn test_qiitaへの転載_公開でなくなった記事は限定共有にし失敗を状況に記録すること(
+    pool: PgPool,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    server
+        .mock("POST", "/api/v2/items")
+        .with_status(201)
+        .with_body(
+            json!({ "id": "ghi789", "url": format!("{base_url}/Romira/items/ghi789") }).to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let made_private = server
+        .mock("PATCH", "/api/v2/items/ghi789")
+        .match_body(mockito::Matcher::PartialJson(json!({ "private": true })))
+        .with_status(403)
+        .expect(1)
+        .create_async()
+        .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+    let published_id = insert_published_article(&pool, "Title", "unlisted-later", "Body").await;
+    let input = |visibility: &str| {
+        json!({
+            "id": published_id.to_string(),
+            "title": "Title",
+            "slug": "unlisted-later",
+            "body": "Body",
+            "description": "説明",
+            "category_names": ["Rust"],
+            "visibility": visibility,
+        })
+    };
 
-    let response = app.oneshot(request).await.unwrap();
+    let (status, _) = post_admin_json(&app_state, "save_published", input("public")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = post_admin_json(&app_state, "save_published", input("unlisted")).await;
+    assert_eq!(status, StatusCode::OK);
 
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    made_private.assert_async().await;
+    let (remote_id, _, last_error) = fetch_qiita_syndication(&pool, published_id).await;
+    assert_eq!(remote_id.as_deref(), Some("ghi789"));
+    assert_eq!(
+        last_error.as_deref(),
+        Some(
+            "The article is no longer public but the Qiita item could not be made private: Failed to api response status code: 403 Forbidden"
+        )
+    );
+}
 
-    // 下書きは削除されていないことを確認
-    let draft_count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM draft_articles WHERE id = $1",
-        draft_id
+#[sqlx::test(migrations = "../migrations")]
+async fn test_qiitaへの転載_カテゴリのない記事は投稿せず状況に記録すること(
+    pool: PgPool,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+    let created = server
+        .mock("POST", "/api/v2/items")
+        .expect(0)
+        .create_async()
+        .await;
+    let app_state = create_test_app_state(pool.clone()).with_qiita_base_url(&base_url);
+    let published_id = insert_published_article(&pool, "Title", "no-tag-slug", "Body").await;
+
+    let (status, statuses) = post_admin_json(
+        &app_state,
+        "retry_syndication",
+        json!({ "article_id": published_id.to_string() }),
     )
-    .fetch_one(&pool)
-    .await
-    .unwrap();
-    assert_eq!(draft_count, Some(1));
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    created.assert_async().await;
+    assert_eq!(
+        statuses[0]["last_error"],
+        "At least one category is required to post to Qiita"
+    );
 }
 
 // =====================================
@@ -1146,3 +2593,235 @@ async fn test_admin接頭辞だが管理パスでないパスは認証不要で
     assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
     assert_ne!(response.status(), StatusCode::FORBIDDEN);
 }
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_feed_xml_公開記事がrssフィードに含まれること(pool: PgPool) {
+    insert_published_article(&pool, "フィード記事", "feed-article", "# 見出し\n\n本文").await;
+    let app_state = create_test_app_state(pool);
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/feed.xml")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get("cache-tag")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        "feed"
+    );
+    assert!(
+        response
+            .headers()
+            .get("content-type")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("application/rss+xml")
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<title>フィード記事</title>"), "{body}");
+    assert!(body.contains("/articles/feed-article</link>"), "{body}");
+    assert!(body.contains("&lt;h1"), "{body}");
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_カテゴリ別フィード_記事のないカテゴリは404を返すこと(
+    pool: PgPool,
+) {
+    let app_state = create_test_app_state(pool);
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/categories/unknown/atom.xml")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn insert_category_for_published(pool: &PgPool, article_id: Uuid, name: &str, slug: &str) {
+    let category_id = sqlx::query_scalar!(
+        r#"INSERT INTO categories (name, slug) VALUES ($1, $2) RETURNING id"#,
+        name,
+        slug
+    )
+    .fetch_one(pool)
+    .await
+    .expect("Failed to insert category");
+    sqlx::query!(
+        r#"INSERT INTO published_article_categories (article_id, category_id) VALUES ($1, $2)"#,
+        article_id,
+        category_id
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to link category");
+}
+
+async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_sitemap_xml_各サイトマップを参照するインデックスを返すこと(
+    pool: PgPool,
+) {
+    insert_published_article(&pool, "記事", "sitemap-article", "本文").await;
+    let app_state = create_test_app_state(pool);
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let (status, body) = get_body(app, "/sitemap.xml").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("<sitemapindex"), "{body}");
+    for name in ["pages", "articles", "categories"] {
+        assert!(
+            body.contains(&format!("/sitemaps/{name}.xml</loc>")),
+            "{body}"
+        );
+    }
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_記事サイトマップにカバー画像と登録済みの本文画像が含まれること(
+    pool: PgPool,
+) {
+    let article_id = insert_published_article(
+        &pool,
+        "画像つき記事",
+        "image-article",
+        "![a](https://blog-romira.imgix.net/dev/images/registered.png)\n\n\
+         ![b](https://blog-romira.imgix.net/dev/images/unknown.png)",
+    )
+    .await;
+    sqlx::query!(
+        r#"UPDATE published_articles SET cover_image_url = $1 WHERE id = $2"#,
+        "https://blog-romira.imgix.net/dev/images/cover.png",
+        article_id
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        r#"INSERT INTO images (filename, gcs_path, mime_type, size_bytes) VALUES ($1, $2, $3, $4)"#,
+        "registered.png",
+        "dev/images/registered.png",
+        "image/png",
+        100_i64
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let app_state = create_test_app_state(pool);
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let (status, body) = get_body(app, "/sitemaps/articles.xml").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("/articles/image-article</loc>"), "{body}");
+    assert!(
+        body.contains("<image:loc>https://blog-romira.imgix.net/dev/images/cover.png</image:loc>"),
+        "{body}"
+    );
+    assert!(
+        body.contains(
+            "<image:loc>https://blog-romira.imgix.net/dev/images/registered.png</image:loc>"
+        ),
+        "{body}"
+    );
+    assert!(!body.contains("unknown.png"), "{body}");
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_カテゴリサイトマップとカテゴリページに記事のあるカテゴリが表示されること(
+    pool: PgPool,
+) {
+    let article_id = insert_published_article(&pool, "Rust入門", "rust-intro", "本文").await;
+    insert_category_for_published(&pool, article_id, "Rust", "rust").await;
+    let app_state = create_test_app_state(pool);
+
+    let seo_app = blog_romira_dev_app::seo_routes().with_state(app_state.clone());
+    let (status, body) = get_body(seo_app, "/sitemaps/categories.xml").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("/categories/rust</loc>"), "{body}");
+
+    // カテゴリページはトップページと同じキャッシュ設定を使うため CacheControlSet が必要
+    let app = build_test_router_with_auth(app_state);
+    let (status, body) =
+        get_body(app.clone(), "/api/get_category_articles_handler?slug=rust").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Rust入門"), "{body}");
+
+    let (status, _) = get_body(app, "/api/get_category_articles_handler?slug=unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_sitemap_xml_db障害時は500を返すこと(pool: PgPool) {
+    let app_state = create_test_app_state(pool.clone());
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+    pool.close().await;
+
+    let (status, _) = get_body(app, "/sitemap.xml").await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_og画像_公開記事のpng画像を生成してキャッシュすること(
+    pool: PgPool,
+) {
+    insert_published_article(&pool, "OGP画像のテスト記事", "og-article", "本文").await;
+    let app_state = create_test_app_state(pool.clone());
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/og/og-article.png")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert!(response.headers().contains_key("etag"));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(body.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let cached = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM og_image_caches"#)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(cached, 1);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_og画像_存在しない記事は404を返すこと(pool: PgPool) {
+    let app_state = create_test_app_state(pool);
+    let app = blog_romira_dev_app::seo_routes().with_state(app_state);
+
+    let (status, _) = get_body(app, "/og/not-found.png").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -10,17 +10,28 @@ pub mod value_objects;
 
 pub use error::CmsError;
 pub use models::{
-    ArticleContent, ArticleListItem, Category, DraftArticle, DraftArticleWithCategories, Image,
-    PublishedArticle, PublishedArticleWithCategories,
+    ArticleContent, ArticleListItem, ArticleSeo, ArticleSyndication, ArticleVisibility, Category,
+    DraftArticle, DraftArticleWithCategories, FeaturedItem, Image, LinkedArticle, OgpCache,
+    OgpContent, ProseCheckRule, PublishedArticle, PublishedArticleWithCategories, RelatedArticle,
+    RelatedArticleCandidate,
 };
 pub use queries::{
-    AdminArticleQuery, CategoryQuery, DraftArticleQuery, ImageQuery, PublishedArticleQuery,
+    AdminArticleQuery, ArticleLinkQuery, ArticleSyndicationQuery, CategoryQuery, DraftArticleQuery,
+    FeaturedItemQuery, ImageQuery, OgImageCacheQuery, OgpCacheQuery, ProseCheckRuleQuery,
+    PublishedArticleQuery, RelatedArticleQuery,
 };
 pub use repositories::{
-    CategoryRepository, DraftArticleRepository, ImageRepository, PublishedArticleRepository,
+    ArticleLinkRepository, ArticleSyndicationRepository, CategoryRepository,
+    DraftArticleRepository, FeaturedItemRepository, ImageRepository, OgImageCacheRepository,
+    OgpCacheRepository, ProseCheckRuleRepository, PublishedArticleRepository,
+    RelatedArticleRepository,
 };
 pub use services::{
-    AdminArticleService, CategoryService, DraftArticleService, ImageService,
-    PublishedArticleService,
+    AdminArticleService, ArticleLinkService, ArticleSyndicationService, CategoryService,
+    DraftArticleService, FeaturedItemService, ImageService, OgImageCacheService, OgpCacheService,
+    ProseCheckRuleService, PublishedArticleService, RelatedArticleService,
+};
+pub use value_objects::{
+    FeaturedItemEntry, FeaturedItemTarget, PublishedArticleSeo, PublishedArticleSlug,
+    PublishedArticleTitle, PublishedArticleVisibility,
 };
-pub use value_objects::{PublishedArticleSlug, PublishedArticleTitle};
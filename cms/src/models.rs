@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use sqlx::types::Json;
 use uuid::Uuid;
 
 /// Repository層で記事コンテンツの受け渡しに使用する構造体
@@ -11,6 +12,73 @@ pub struct ArticleContent<'a> {
     pub body: &'a str,
     pub description: Option<&'a str>,
     pub cover_image_url: Option<&'a str>,
+    pub seo: &'a ArticleSeo,
+}
+
+/// 記事ごとのSEO設定（未設定の項目は記事の内容から決める）
+///
+/// `published_articles.seo` / `draft_articles.seo` にJSONBで保存する。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArticleSeo {
+    /// 転載記事などで別のURLを正規URLにする場合に指定
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    /// robotsメタタグの値（例: `noindex, nofollow`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub robots: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub og_image_url: Option<String>,
+    /// `summary` または `summary_large_image`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twitter_card: Option<String>,
+}
+
+impl ArticleSeo {
+    /// 検索エンジンにインデックスさせない設定か（`noindex` または `none`）
+    pub fn is_noindex(&self) -> bool {
+        self.robots.as_deref().is_some_and(|robots| {
+            robots.split(',').map(str::trim).any(|directive| {
+                directive.eq_ignore_ascii_case("noindex") || directive.eq_ignore_ascii_case("none")
+            })
+        })
+    }
+}
+
+/// 公開記事の公開範囲
+///
+/// `unlisted` と `protected` の記事はURLを知っていれば閲覧できるが、
+/// 記事一覧・フィード・サイトマップ・関連記事・バックリンクには表示しない。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "article_visibility", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ArticleVisibility {
+    /// 一覧に表示する
+    #[default]
+    Public,
+    /// URLを知っている人のみ閲覧できる
+    Unlisted,
+    /// パスワードを入力した人のみ閲覧できる
+    Protected,
+}
+
+impl ArticleVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unlisted => "unlisted",
+            Self::Protected => "protected",
+        }
+    }
+
+    /// 一覧に表示する記事か
+    pub fn is_public(&self) -> bool {
+        *self == Self::Public
+    }
 }
 
 /// 画像ライブラリ
@@ -28,6 +96,97 @@ pub struct Image {
     pub created_at: NaiveDateTime,
 }
 
+/// Repository層でOGPメタデータの受け渡しに使用する構造体
+#[derive(Debug, Default)]
+pub struct OgpContent<'a> {
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub image_url: Option<&'a str>,
+    pub favicon_url: Option<&'a str>,
+    pub site_name: Option<&'a str>,
+    pub embed_html: Option<&'a str>,
+}
+
+/// リンクカード用OGPメタデータのキャッシュ
+/// タイムスタンプはUTCで保存
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct OgpCache {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub site_name: Option<String>,
+    pub embed_html: Option<String>,
+    pub fetched_at: NaiveDateTime,
+}
+
+/// 文章チェックのルール設定
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProseCheckRule {
+    pub rule: String,
+    pub enabled: bool,
+}
+
+/// 記事間リンクの参照先・参照元として表示する公開記事
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize, Deserialize)]
+pub struct LinkedArticle {
+    pub slug: String,
+    pub title: String,
+}
+
+/// 関連記事の計算対象となる公開記事（公開範囲が `public` のもの。公開日時に関わらずすべて）
+#[derive(Debug, Clone, FromRow)]
+pub struct RelatedArticleCandidate {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    pub category_ids: Vec<Uuid>,
+}
+
+/// トップページの「おすすめ」に固定表示する項目
+///
+/// ローカル記事（`article_id`）か外部記事のURL（`external_url`）のどちらか一方を持つ。
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize, Deserialize)]
+pub struct FeaturedItem {
+    pub id: Uuid,
+    pub article_id: Option<Uuid>,
+    /// ローカル記事のslug（外部記事の場合は `None`）
+    pub article_slug: Option<String>,
+    pub external_url: Option<String>,
+    /// 1始まりの表示順
+    pub position: i32,
+    /// この日時を過ぎたら表示しない（UTC）
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+/// 公開記事の転載先への反映状況
+/// タイムスタンプはUTCで保存
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize, Deserialize)]
+pub struct ArticleSyndication {
+    pub article_id: Uuid,
+    /// 転載先（例: `qiita`）
+    pub target: String,
+    /// 転載先で作成した記事のID
+    pub remote_id: Option<String>,
+    /// 転載先で作成した記事のURL
+    pub remote_url: Option<String>,
+    /// 直近の反映が失敗した場合のエラー
+    pub last_error: Option<String>,
+    /// 最後に反映に成功した日時
+    pub synced_at: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// 事前計算した関連記事（`rank` は1始まりの表示順）
+#[derive(Debug, Clone, FromRow)]
+pub struct RelatedArticle {
+    pub article_id: Uuid,
+    pub related_article_id: Uuid,
+    pub rank: i32,
+}
+
 /// 公開済み記事
 /// タイムスタンプはUTCで保存
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -38,6 +197,10 @@ pub struct PublishedArticle {
     pub body: String,
     pub description: Option<String>,
     pub cover_image_url: Option<String>,
+    /// 外部サービスから取り込んだ記事の取り込み元URL
+    pub origin_url: Option<String>,
+    pub seo: Json<ArticleSeo>,
+    pub visibility: ArticleVisibility,
     pub published_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
@@ -53,6 +216,9 @@ pub struct DraftArticle {
     pub body: String,
     pub description: Option<String>,
     pub cover_image_url: Option<String>,
+    /// 外部サービスから取り込んだ記事の取り込み元URL
+    pub origin_url: Option<String>,
+    pub seo: Json<ArticleSeo>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -1,11 +1,25 @@
 mod admin_article;
+mod article_link;
+mod article_syndication;
 mod category;
 mod draft_article;
+mod featured_item;
 mod image;
+mod og_image_cache;
+mod ogp_cache;
+mod prose_check_rule;
 mod published_article;
+mod related_article;
 
 pub use admin_article::AdminArticleQuery;
+pub use article_link::ArticleLinkQuery;
+pub use article_syndication::ArticleSyndicationQuery;
 pub use category::CategoryQuery;
 pub use draft_article::DraftArticleQuery;
+pub use featured_item::FeaturedItemQuery;
 pub use image::ImageQuery;
+pub use og_image_cache::OgImageCacheQuery;
+pub use ogp_cache::OgpCacheQuery;
+pub use prose_check_rule::ProseCheckRuleQuery;
 pub use published_article::PublishedArticleQuery;
+pub use related_article::RelatedArticleQuery;
@@ -1,10 +1,11 @@
 use super::CategoryQuery;
 use crate::error::CmsError;
 use crate::models::{
-    ArticleListItem, DraftArticle, DraftArticleWithCategories, PublishedArticle,
-    PublishedArticleWithCategories,
+    ArticleListItem, ArticleSeo, ArticleVisibility, DraftArticle, DraftArticleWithCategories,
+    PublishedArticle, PublishedArticleWithCategories,
 };
 use sqlx::PgPool;
+use sqlx::types::Json;
 use tracing::instrument;
 
 /// 管理画面用記事クエリサービス（SELECT操作）
@@ -18,8 +19,8 @@ impl AdminArticleQuery {
         let published = sqlx::query_as!(
             PublishedArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
-                   published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
+                   visibility as "visibility: ArticleVisibility", published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
             FROM published_articles
             ORDER BY published_at DESC
             "#
@@ -31,7 +32,7 @@ impl AdminArticleQuery {
         let drafts = sqlx::query_as!(
             DraftArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
                    created_at as "created_at: _", updated_at as "updated_at: _"
             FROM draft_articles
             ORDER BY updated_at DESC
@@ -65,6 +66,22 @@ impl AdminArticleQuery {
 
         Ok(result)
     }
+
+    /// 公開記事と下書き記事に記録された取り込み元URLをすべて取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_origin_urls(pool: &PgPool) -> Result<Vec<String>, CmsError> {
+        let urls = sqlx::query_scalar!(
+            r#"
+            SELECT origin_url as "origin_url!" FROM published_articles WHERE origin_url IS NOT NULL
+            UNION
+            SELECT origin_url as "origin_url!" FROM draft_articles WHERE origin_url IS NOT NULL
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(urls)
+    }
 }
 
 //noinspection NonAsciiCharacters
@@ -318,4 +335,48 @@ mod tests {
         assert_eq!(result[2].id(), id_t2);
         assert_eq!(result[3].id(), id_t1);
     }
+
+    #[sqlx::test]
+    async fn test_fetch_origin_urlsで公開記事と下書き記事の取り込み元urlが取得されること(
+        pool: PgPool,
+    ) {
+        let published_id = insert_published_article(
+            &pool,
+            "pub",
+            "Published",
+            "Body",
+            None,
+            parse_datetime("2020-01-01 10:00:00"),
+        )
+        .await;
+        let draft_id = insert_draft_article(&pool, "draft", "Draft", "Body", None).await;
+        insert_draft_article(&pool, "no-origin", "No Origin", "Body", None).await;
+        sqlx::query!(
+            "UPDATE published_articles SET origin_url = 'https://qiita.com/romira/items/pub' WHERE id = $1",
+            published_id
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to update published article");
+        sqlx::query!(
+            "UPDATE draft_articles SET origin_url = 'https://qiita.com/romira/items/draft' WHERE id = $1",
+            draft_id
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to update draft article");
+
+        let mut urls = AdminArticleQuery::fetch_origin_urls(&pool)
+            .await
+            .expect("Failed to fetch origin urls");
+        urls.sort();
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://qiita.com/romira/items/draft".to_string(),
+                "https://qiita.com/romira/items/pub".to_string(),
+            ]
+        );
+    }
 }
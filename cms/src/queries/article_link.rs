@@ -0,0 +1,134 @@
+use crate::error::CmsError;
+use crate::models::LinkedArticle;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// 記事間リンククエリサービス（SELECT操作）
+pub struct ArticleLinkQuery;
+
+impl ArticleLinkQuery {
+    /// 指定したslugの記事を参照している公開済み記事を取得（公開範囲が `public` の記事のみ、公開日時の新しい順）
+    #[instrument(skip(pool))]
+    pub async fn fetch_backlinks(
+        pool: &PgPool,
+        target_slug: &str,
+        now: NaiveDateTime,
+    ) -> Result<Vec<LinkedArticle>, CmsError> {
+        let backlinks = sqlx::query_as!(
+            LinkedArticle,
+            r#"
+            SELECT pa.slug, pa.title
+            FROM article_links al
+            INNER JOIN published_articles pa ON pa.id = al.source_article_id
+            WHERE al.target_slug = $1 AND pa.published_at <= $2 AND pa.visibility = 'public'
+            ORDER BY pa.published_at DESC
+            "#,
+            target_slug,
+            now as _
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(backlinks)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::ArticleLinkRepository;
+    use crate::test_utils::*;
+
+    fn parse_datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_backlinksで公開済みの参照元のみ新しい順に返ること(
+        pool: PgPool,
+    ) {
+        let old_id = insert_published_article(
+            &pool,
+            "old-article",
+            "Old Article",
+            "Body",
+            None,
+            parse_datetime("2024-01-01 10:00:00"),
+        )
+        .await;
+        let new_id = insert_published_article(
+            &pool,
+            "new-article",
+            "New Article",
+            "Body",
+            None,
+            parse_datetime("2024-06-01 10:00:00"),
+        )
+        .await;
+        let future_id = insert_published_article(
+            &pool,
+            "future-article",
+            "Future Article",
+            "Body",
+            None,
+            parse_datetime("2099-01-01 10:00:00"),
+        )
+        .await;
+        for id in [old_id, new_id, future_id] {
+            ArticleLinkRepository::replace_for_source(&pool, id, &["target".to_string()])
+                .await
+                .expect("Failed to save links");
+        }
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let result = ArticleLinkQuery::fetch_backlinks(&pool, "target", now)
+            .await
+            .expect("Failed to fetch backlinks");
+
+        assert_eq!(
+            result,
+            vec![
+                LinkedArticle {
+                    slug: "new-article".to_string(),
+                    title: "New Article".to_string(),
+                },
+                LinkedArticle {
+                    slug: "old-article".to_string(),
+                    title: "Old Article".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_backlinksで公開範囲がpublic以外の参照元は除外されること(
+        pool: PgPool,
+    ) {
+        let past = parse_datetime("2024-01-01 10:00:00");
+        let public_id =
+            insert_published_article(&pool, "public", "Public", "Body", None, past).await;
+        let protected_id =
+            insert_published_article(&pool, "protected", "Protected", "Body", None, past).await;
+        set_published_article_visibility(&pool, protected_id, "protected", Some("hash")).await;
+        for id in [public_id, protected_id] {
+            ArticleLinkRepository::replace_for_source(&pool, id, &["target".to_string()])
+                .await
+                .expect("Failed to save links");
+        }
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let result = ArticleLinkQuery::fetch_backlinks(&pool, "target", now)
+            .await
+            .expect("Failed to fetch backlinks");
+
+        assert_eq!(
+            result,
+            vec![LinkedArticle {
+                slug: "public".to_string(),
+                title: "Public".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::error::CmsError;
+use crate::models::ArticleSyndication;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 転載状況クエリサービス（SELECT操作）
+pub struct ArticleSyndicationQuery;
+
+impl ArticleSyndicationQuery {
+    /// 公開記事の転載先ごとの反映状況を転載先の名前順に取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_for_article(
+        pool: &PgPool,
+        article_id: Uuid,
+    ) -> Result<Vec<ArticleSyndication>, CmsError> {
+        let syndications = sqlx::query_as!(
+            ArticleSyndication,
+            r#"
+            SELECT article_id, target, remote_id, remote_url, last_error,
+                   synced_at as "synced_at: _", updated_at as "updated_at: _"
+            FROM article_syndications
+            WHERE article_id = $1
+            ORDER BY target
+            "#,
+            article_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(syndications)
+    }
+
+    /// 転載先の記事がある（一度でも反映に成功した）転載状況をすべて取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_synced(pool: &PgPool) -> Result<Vec<ArticleSyndication>, CmsError> {
+        let syndications = sqlx::query_as!(
+            ArticleSyndication,
+            r#"
+            SELECT article_id, target, remote_id, remote_url, last_error,
+                   synced_at as "synced_at: _", updated_at as "updated_at: _"
+            FROM article_syndications
+            WHERE remote_url IS NOT NULL
+            ORDER BY article_id, target
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(syndications)
+    }
+}
@@ -1,7 +1,8 @@
 use super::CategoryQuery;
 use crate::error::CmsError;
-use crate::models::{DraftArticle, DraftArticleWithCategories};
+use crate::models::{ArticleSeo, DraftArticle, DraftArticleWithCategories};
 use sqlx::PgPool;
+use sqlx::types::Json;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -15,7 +16,7 @@ impl DraftArticleQuery {
         let articles = sqlx::query_as!(
             DraftArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
                    created_at as "created_at: _", updated_at as "updated_at: _"
             FROM draft_articles
             ORDER BY updated_at DESC
@@ -45,7 +46,7 @@ impl DraftArticleQuery {
         let article = sqlx::query_as!(
             DraftArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
                    created_at as "created_at: _", updated_at as "updated_at: _"
             FROM draft_articles
             WHERE id = $1
@@ -0,0 +1,159 @@
+use crate::error::CmsError;
+use crate::models::FeaturedItem;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// おすすめ記事クエリ（SELECT操作）
+pub struct FeaturedItemQuery;
+
+impl FeaturedItemQuery {
+    /// 期限切れを含むすべての項目を表示順に取得（管理者用）
+    #[instrument(skip(pool))]
+    pub async fn fetch_all(pool: &PgPool) -> Result<Vec<FeaturedItem>, CmsError> {
+        let items = sqlx::query_as!(
+            FeaturedItem,
+            r#"
+            SELECT fi.id, fi.article_id, pa.slug as "article_slug?", fi.external_url, fi.position,
+                   fi.expires_at as "expires_at: _"
+            FROM featured_items fi
+            LEFT JOIN published_articles pa ON pa.id = fi.article_id
+            ORDER BY fi.position
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// 期限切れでない項目を表示順に取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_active(
+        pool: &PgPool,
+        now: NaiveDateTime,
+    ) -> Result<Vec<FeaturedItem>, CmsError> {
+        let items = sqlx::query_as!(
+            FeaturedItem,
+            r#"
+            SELECT fi.id, fi.article_id, pa.slug as "article_slug?", fi.external_url, fi.position,
+                   fi.expires_at as "expires_at: _"
+            FROM featured_items fi
+            LEFT JOIN published_articles pa ON pa.id = fi.article_id
+            WHERE fi.expires_at IS NULL OR fi.expires_at > $1
+            ORDER BY fi.position
+            "#,
+            now as _
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// `since` より後、`until` 以前に表示期限が来た項目があるか
+    #[instrument(skip(pool))]
+    pub async fn exists_expired_between(
+        pool: &PgPool,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Result<bool, CmsError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM featured_items WHERE expires_at > $1 AND expires_at <= $2) as "exists!: bool""#,
+            since as _,
+            until as _
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(exists)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use chrono::Duration;
+
+    #[sqlx::test]
+    async fn test_fetch_activeで期限切れの項目が除外されること(pool: PgPool) {
+        let now = utc_now();
+        let article_id =
+            insert_published_article(&pool, "pinned", "Pinned", "Body", None, now).await;
+        insert_featured_item(&pool, Some(article_id), None, 1, None).await;
+        insert_featured_item(
+            &pool,
+            None,
+            Some("https://qiita.com/Romira/items/expired"),
+            2,
+            Some(now - Duration::days(1)),
+        )
+        .await;
+        insert_featured_item(
+            &pool,
+            None,
+            Some("https://qiita.com/Romira/items/active"),
+            3,
+            Some(now + Duration::days(1)),
+        )
+        .await;
+
+        let active = FeaturedItemQuery::fetch_active(&pool, now)
+            .await
+            .expect("Failed to fetch");
+        let all = FeaturedItemQuery::fetch_all(&pool)
+            .await
+            .expect("Failed to fetch");
+
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].article_slug.as_deref(), Some("pinned"));
+        assert_eq!(
+            active[1].external_url.as_deref(),
+            Some("https://qiita.com/Romira/items/active")
+        );
+        assert_eq!(all.len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_exists_expired_betweenで期間内に期限が来た項目だけを判定すること(
+        pool: PgPool,
+    ) {
+        let now = utc_now();
+        insert_featured_item(
+            &pool,
+            None,
+            Some("https://qiita.com/Romira/items/a"),
+            1,
+            None,
+        )
+        .await;
+        insert_featured_item(
+            &pool,
+            None,
+            Some("https://qiita.com/Romira/items/b"),
+            2,
+            Some(now - Duration::minutes(5)),
+        )
+        .await;
+
+        let expired = |since, until| FeaturedItemQuery::exists_expired_between(&pool, since, until);
+
+        assert!(
+            expired(now - Duration::minutes(10), now)
+                .await
+                .expect("Failed to check")
+        );
+        assert!(
+            !expired(now - Duration::minutes(5), now)
+                .await
+                .expect("Failed to check")
+        );
+        assert!(
+            !expired(now - Duration::minutes(20), now - Duration::minutes(10))
+                .await
+                .expect("Failed to check")
+        );
+    }
+}
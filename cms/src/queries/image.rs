@@ -45,6 +45,28 @@ impl ImageQuery {
         Ok(image)
     }
 
+    /// 指定したGCSパスの画像をまとめて取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_by_gcs_paths(
+        pool: &PgPool,
+        gcs_paths: &[String],
+    ) -> Result<Vec<Image>, CmsError> {
+        let images = sqlx::query_as!(
+            Image,
+            r#"
+            SELECT id, filename, gcs_path, mime_type, size_bytes, width, height, alt_text,
+                   created_at as "created_at: _"
+            FROM images
+            WHERE gcs_path = ANY($1)
+            "#,
+            gcs_paths
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(images)
+    }
+
     /// 指定したGCSパスが既に存在するかチェック
     #[instrument(skip(pool))]
     pub async fn exists_by_gcs_path(pool: &PgPool, gcs_path: &str) -> Result<bool, CmsError> {
@@ -103,6 +125,24 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[sqlx::test]
+    async fn test_fetch_by_gcs_pathsで指定したパスの画像のみ取得されること(
+        pool: PgPool,
+    ) {
+        insert_test_image(&pool, "a.jpg", "images/a.jpg", "image/jpeg", 1024).await;
+        insert_test_image(&pool, "b.jpg", "images/b.jpg", "image/jpeg", 1024).await;
+
+        let result = ImageQuery::fetch_by_gcs_paths(
+            &pool,
+            &["images/a.jpg".to_string(), "images/c.jpg".to_string()],
+        )
+        .await
+        .expect("Failed to fetch by gcs paths");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].gcs_path, "images/a.jpg");
+    }
+
     #[sqlx::test]
     async fn test_exists_by_gcs_pathで存在確認ができること(pool: PgPool) {
         insert_test_image(&pool, "test.jpg", "images/test.jpg", "image/jpeg", 1024).await;
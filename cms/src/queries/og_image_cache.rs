@@ -0,0 +1,21 @@
+use crate::error::CmsError;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// OGP画像キャッシュクエリサービス（SELECT操作）
+pub struct OgImageCacheQuery;
+
+impl OgImageCacheQuery {
+    /// 内容ハッシュに対応する画像を取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_png(pool: &PgPool, content_hash: &str) -> Result<Option<Vec<u8>>, CmsError> {
+        let png = sqlx::query_scalar!(
+            r#"SELECT png FROM og_image_caches WHERE content_hash = $1"#,
+            content_hash
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(png)
+    }
+}
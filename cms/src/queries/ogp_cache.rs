@@ -0,0 +1,68 @@
+use crate::error::CmsError;
+use crate::models::OgpCache;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// OGPキャッシュクエリサービス（SELECT操作）
+pub struct OgpCacheQuery;
+
+impl OgpCacheQuery {
+    /// 指定したURLのキャッシュをまとめて取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_by_urls(pool: &PgPool, urls: &[String]) -> Result<Vec<OgpCache>, CmsError> {
+        let caches = sqlx::query_as!(
+            OgpCache,
+            r#"
+            SELECT url, title, description, image_url, favicon_url, site_name, embed_html,
+                   fetched_at as "fetched_at: _"
+            FROM ogp_caches
+            WHERE url = ANY($1)
+            "#,
+            urls
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(caches)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_fetch_by_urlsで指定したurlのキャッシュのみ取得されること(
+        pool: PgPool,
+    ) {
+        insert_test_ogp_cache(&pool, "https://example.com/a", Some("A")).await;
+        insert_test_ogp_cache(&pool, "https://example.com/b", Some("B")).await;
+
+        let result = OgpCacheQuery::fetch_by_urls(
+            &pool,
+            &[
+                "https://example.com/a".to_string(),
+                "https://example.com/c".to_string(),
+            ],
+        )
+        .await
+        .expect("Failed to fetch by urls");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].url, "https://example.com/a");
+        assert_eq!(result[0].title.as_deref(), Some("A"));
+    }
+
+    #[sqlx::test]
+    async fn test_空のurl一覧でfetch_by_urlsすると空が返ること(pool: PgPool) {
+        insert_test_ogp_cache(&pool, "https://example.com/a", Some("A")).await;
+
+        let result = OgpCacheQuery::fetch_by_urls(&pool, &[])
+            .await
+            .expect("Failed to fetch by urls");
+
+        assert!(result.is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error::CmsError;
+use crate::models::ProseCheckRule;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// 文章チェックのルール設定クエリ（SELECT操作）
+pub struct ProseCheckRuleQuery;
+
+impl ProseCheckRuleQuery {
+    /// 保存済みのルール設定を全件取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_all(pool: &PgPool) -> Result<Vec<ProseCheckRule>, CmsError> {
+        let rules = sqlx::query_as!(
+            ProseCheckRule,
+            r#"SELECT rule, enabled FROM prose_check_rules ORDER BY rule"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_fetch_allで設定が無い場合空リストが返ること(pool: PgPool) {
+        let rules = ProseCheckRuleQuery::fetch_all(&pool)
+            .await
+            .expect("Failed to fetch");
+
+        assert!(rules.is_empty());
+    }
+}
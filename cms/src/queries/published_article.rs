@@ -1,8 +1,11 @@
 use super::CategoryQuery;
 use crate::error::CmsError;
-use crate::models::{PublishedArticle, PublishedArticleWithCategories};
+use crate::models::{
+    ArticleSeo, ArticleVisibility, LinkedArticle, PublishedArticle, PublishedArticleWithCategories,
+};
 use chrono::NaiveDateTime;
 use sqlx::PgPool;
+use sqlx::types::Json;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -10,7 +13,7 @@ use uuid::Uuid;
 pub struct PublishedArticleQuery;
 
 impl PublishedArticleQuery {
-    /// 公開済み記事一覧を取得
+    /// 公開済み記事一覧を取得（公開範囲が `public` の記事のみ）
     #[instrument(skip(pool))]
     pub async fn fetch_all(
         pool: &PgPool,
@@ -19,10 +22,10 @@ impl PublishedArticleQuery {
         let articles = sqlx::query_as!(
             PublishedArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
-                   published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
+                   visibility as "visibility: ArticleVisibility", published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
             FROM published_articles
-            WHERE published_at <= $1
+            WHERE published_at <= $1 AND visibility = 'public'
             ORDER BY published_at DESC
             "#,
             now as _
@@ -52,8 +55,8 @@ impl PublishedArticleQuery {
         let article = sqlx::query_as!(
             PublishedArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
-                   published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
+                   visibility as "visibility: ArticleVisibility", published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
             FROM published_articles
             WHERE id = $1 AND published_at <= $2
             "#,
@@ -85,8 +88,8 @@ impl PublishedArticleQuery {
         let article = sqlx::query_as!(
             PublishedArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
-                   published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
+                   visibility as "visibility: ArticleVisibility", published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
             FROM published_articles
             WHERE slug = $1 AND published_at <= $2
             "#,
@@ -138,6 +141,78 @@ impl PublishedArticleQuery {
         Ok(exists)
     }
 
+    /// 指定したslugのうち公開済み（公開日時が現在以前）で一般公開の記事のslugとタイトルを取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_linked_articles(
+        pool: &PgPool,
+        slugs: &[String],
+        now: NaiveDateTime,
+    ) -> Result<Vec<LinkedArticle>, CmsError> {
+        let articles = sqlx::query_as!(
+            LinkedArticle,
+            r#"SELECT slug, title FROM published_articles WHERE slug = ANY($1) AND published_at <= $2 AND visibility = 'public'"#,
+            slugs,
+            now as _
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(articles)
+    }
+
+    /// 指定したslugのうち公開済み（公開日時が現在以前）の記事のslugを取得（公開範囲は問わない）
+    #[instrument(skip(pool))]
+    pub async fn fetch_existing_slugs(
+        pool: &PgPool,
+        slugs: &[String],
+        now: NaiveDateTime,
+    ) -> Result<Vec<String>, CmsError> {
+        let existing = sqlx::query_scalar!(
+            r#"SELECT slug FROM published_articles WHERE slug = ANY($1) AND published_at <= $2"#,
+            slugs,
+            now as _
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(existing)
+    }
+
+    /// パスワード保護された公開済み記事のパスワードハッシュをslugで取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_password_hash(
+        pool: &PgPool,
+        slug: &str,
+        now: NaiveDateTime,
+    ) -> Result<Option<String>, CmsError> {
+        let password_hash = sqlx::query_scalar!(
+            r#"
+            SELECT password_hash as "password_hash!"
+            FROM published_articles
+            WHERE slug = $1 AND published_at <= $2 AND visibility = 'protected' AND password_hash IS NOT NULL
+            "#,
+            slug,
+            now as _
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(password_hash)
+    }
+
+    /// 公開記事にパスワードが設定されているかチェック
+    #[instrument(skip(pool))]
+    pub async fn has_password(pool: &PgPool, article_id: Uuid) -> Result<bool, CmsError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM published_articles WHERE id = $1 AND password_hash IS NOT NULL) as "exists!: bool""#,
+            article_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(exists)
+    }
+
     /// 公開済み記事をIDで取得（管理者用、公開日時フィルタなし）
     #[instrument(skip(pool))]
     pub async fn fetch_by_id_for_admin(
@@ -147,8 +222,8 @@ impl PublishedArticleQuery {
         let article = sqlx::query_as!(
             PublishedArticle,
             r#"
-            SELECT id, slug, title, body, description, cover_image_url,
-                   published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id, slug, title, body, description, cover_image_url, origin_url, seo as "seo: Json<ArticleSeo>",
+                   visibility as "visibility: ArticleVisibility", published_at as "published_at: _", created_at as "created_at: _", updated_at as "updated_at: _"
             FROM published_articles
             WHERE id = $1
             "#,
@@ -213,6 +288,61 @@ mod tests {
         assert_eq!(result[0].article.id, past_id);
     }
 
+    #[sqlx::test]
+    async fn test_fetch_allで公開範囲がpublic以外の記事が除外されること(
+        pool: PgPool,
+    ) {
+        let published_at = parse_datetime("2020-01-01 10:00:00");
+        let public_id =
+            insert_published_article(&pool, "public", "Public", "Body", None, published_at).await;
+        let unlisted_id =
+            insert_published_article(&pool, "unlisted", "Unlisted", "Body", None, published_at)
+                .await;
+        set_published_article_visibility(&pool, unlisted_id, "unlisted", None).await;
+        let protected_id =
+            insert_published_article(&pool, "protected", "Protected", "Body", None, published_at)
+                .await;
+        set_published_article_visibility(&pool, protected_id, "protected", Some("hash")).await;
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let result = PublishedArticleQuery::fetch_all(&pool, now)
+            .await
+            .expect("Failed to fetch all");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].article.id, public_id);
+
+        // URLを知っていれば取得できる
+        let unlisted = PublishedArticleQuery::fetch_by_slug(&pool, "unlisted", now)
+            .await
+            .expect("Failed to fetch")
+            .expect("Article not found");
+        assert_eq!(unlisted.article.visibility, ArticleVisibility::Unlisted);
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_password_hashでパスワード保護された記事のハッシュのみ返ること(
+        pool: PgPool,
+    ) {
+        let published_at = parse_datetime("2020-01-01 10:00:00");
+        let protected_id =
+            insert_published_article(&pool, "protected", "Protected", "Body", None, published_at)
+                .await;
+        set_published_article_visibility(&pool, protected_id, "protected", Some("hash")).await;
+        insert_published_article(&pool, "public", "Public", "Body", None, published_at).await;
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let protected = PublishedArticleQuery::fetch_password_hash(&pool, "protected", now)
+            .await
+            .expect("Failed to fetch");
+        let public = PublishedArticleQuery::fetch_password_hash(&pool, "public", now)
+            .await
+            .expect("Failed to fetch");
+
+        assert_eq!(protected.as_deref(), Some("hash"));
+        assert!(public.is_none());
+    }
+
     #[sqlx::test]
     async fn test_fetch_allでカテゴリも取得されること(pool: PgPool) {
         let cat_id = create_test_category(&pool, "TestCategory", "testcategory").await;
@@ -375,4 +505,118 @@ mod tests {
 
         assert!(result.is_none());
     }
+
+    #[sqlx::test]
+    async fn test_fetch_linked_articlesで公開済みの記事のみ返ること(pool: PgPool) {
+        insert_published_article(
+            &pool,
+            "past-article",
+            "Past Article",
+            "Body",
+            None,
+            parse_datetime("2020-01-01 10:00:00"),
+        )
+        .await;
+        insert_published_article(
+            &pool,
+            "future-article",
+            "Future Article",
+            "Body",
+            None,
+            parse_datetime("2099-01-20 10:00:00"),
+        )
+        .await;
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let slugs = vec![
+            "past-article".to_string(),
+            "future-article".to_string(),
+            "missing-article".to_string(),
+        ];
+        let result = PublishedArticleQuery::fetch_linked_articles(&pool, &slugs, now)
+            .await
+            .expect("Failed to fetch linked articles");
+
+        assert_eq!(
+            result,
+            vec![LinkedArticle {
+                slug: "past-article".to_string(),
+                title: "Past Article".to_string(),
+            }]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_linked_articlesで一般公開でない記事を返さないこと(
+        pool: PgPool,
+    ) {
+        let published_at = parse_datetime("2020-01-01 10:00:00");
+        insert_published_article(&pool, "public", "Public", "Body", None, published_at).await;
+        let protected_id = insert_published_article(
+            &pool,
+            "protected",
+            "Secret Title",
+            "Body",
+            None,
+            published_at,
+        )
+        .await;
+        set_published_article_visibility(&pool, protected_id, "protected", Some("hash")).await;
+        let unlisted_id = insert_published_article(
+            &pool,
+            "unlisted",
+            "Unlisted Title",
+            "Body",
+            None,
+            published_at,
+        )
+        .await;
+        set_published_article_visibility(&pool, unlisted_id, "unlisted", None).await;
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let slugs = vec![
+            "public".to_string(),
+            "protected".to_string(),
+            "unlisted".to_string(),
+        ];
+        let result = PublishedArticleQuery::fetch_linked_articles(&pool, &slugs, now)
+            .await
+            .expect("Failed to fetch linked articles");
+
+        assert_eq!(
+            result,
+            vec![LinkedArticle {
+                slug: "public".to_string(),
+                title: "Public".to_string(),
+            }]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_existing_slugsで公開範囲を問わず公開済みの記事のslugを返すこと(
+        pool: PgPool,
+    ) {
+        let published_at = parse_datetime("2020-01-01 10:00:00");
+        let unlisted_id =
+            insert_published_article(&pool, "unlisted", "Unlisted", "Body", None, published_at)
+                .await;
+        set_published_article_visibility(&pool, unlisted_id, "unlisted", None).await;
+        insert_published_article(
+            &pool,
+            "future",
+            "Future",
+            "Body",
+            None,
+            parse_datetime("2099-01-20 10:00:00"),
+        )
+        .await;
+
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let slugs = vec!["unlisted".to_string(), "future".to_string()];
+        let result = PublishedArticleQuery::fetch_existing_slugs(&pool, &slugs, now)
+            .await
+            .expect("Failed to fetch existing slugs");
+
+        assert_eq!(result, vec!["unlisted".to_string()]);
+    }
 }
@@ -0,0 +1,238 @@
+use super::CategoryQuery;
+use crate::error::CmsError;
+use crate::models::{
+    ArticleSeo, ArticleVisibility, PublishedArticle, PublishedArticleWithCategories,
+    RelatedArticle, RelatedArticleCandidate,
+};
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use sqlx::types::Json;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 関連記事クエリサービス（SELECT操作）
+pub struct RelatedArticleQuery;
+
+impl RelatedArticleQuery {
+    /// 指定した記事の関連記事のうち公開済みかつ公開範囲が `public` のものを表示順に取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_for_article(
+        pool: &PgPool,
+        article_id: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<Vec<PublishedArticleWithCategories>, CmsError> {
+        let articles = sqlx::query_as!(
+            PublishedArticle,
+            r#"
+            SELECT pa.id, pa.slug, pa.title, pa.body, pa.description, pa.cover_image_url, pa.origin_url, pa.seo as "seo: Json<ArticleSeo>",
+                   pa.visibility as "visibility: ArticleVisibility", pa.published_at as "published_at: _", pa.created_at as "created_at: _", pa.updated_at as "updated_at: _"
+            FROM related_articles ra
+            INNER JOIN published_articles pa ON pa.id = ra.related_article_id
+            WHERE ra.article_id = $1 AND pa.published_at <= $2 AND pa.visibility = 'public'
+            ORDER BY ra.rank
+            "#,
+            article_id,
+            now as _
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut result = Vec::with_capacity(articles.len());
+        for article in articles {
+            let categories = CategoryQuery::fetch_for_published(pool, article.id).await?;
+            result.push(PublishedArticleWithCategories {
+                article,
+                categories,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// 保存済みの関連記事をすべて取得（記事ごとに表示順）
+    #[instrument(skip(pool))]
+    pub async fn fetch_all(pool: &PgPool) -> Result<Vec<RelatedArticle>, CmsError> {
+        let related = sqlx::query_as!(
+            RelatedArticle,
+            r#"
+            SELECT article_id as "article_id!", related_article_id as "related_article_id!", rank
+            FROM related_articles
+            ORDER BY article_id, rank
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(related)
+    }
+
+    /// 指定した記事を関連記事に含む記事のslugを取得
+    #[instrument(skip(pool))]
+    pub async fn fetch_referring_slugs(
+        pool: &PgPool,
+        related_article_id: Uuid,
+    ) -> Result<Vec<String>, CmsError> {
+        let slugs = sqlx::query_scalar!(
+            r#"
+            SELECT pa.slug
+            FROM related_articles ra
+            INNER JOIN published_articles pa ON pa.id = ra.article_id
+            WHERE ra.related_article_id = $1
+            ORDER BY pa.slug
+            "#,
+            related_article_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(slugs)
+    }
+
+    /// 関連記事の計算対象となる公開記事をカテゴリIDとともにすべて取得（予約投稿を含む）
+    ///
+    /// 公開範囲が `public` でない記事は関連記事として表示しないため対象外にする。
+    #[instrument(skip(pool))]
+    pub async fn fetch_candidates(pool: &PgPool) -> Result<Vec<RelatedArticleCandidate>, CmsError> {
+        let candidates = sqlx::query_as!(
+            RelatedArticleCandidate,
+            r#"
+            SELECT pa.id, pa.slug, pa.title, pa.body,
+                   COALESCE(
+                       array_agg(pac.category_id) FILTER (WHERE pac.category_id IS NOT NULL),
+                       '{}'
+                   ) as "category_ids!: Vec<Uuid>"
+            FROM published_articles pa
+            LEFT JOIN published_article_categories pac ON pac.article_id = pa.id
+            WHERE pa.visibility = 'public'
+            GROUP BY pa.id
+            ORDER BY pa.published_at DESC
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(candidates)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::RelatedArticleRepository;
+    use crate::test_utils::*;
+
+    fn parse_datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_for_articleで公開済みの関連記事のみ表示順に返ること(
+        pool: PgPool,
+    ) {
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let past = parse_datetime("2024-01-01 10:00:00");
+        let article_id = insert_published_article(&pool, "base", "Base", "Body", None, past).await;
+        let first_id = insert_published_article(&pool, "first", "First", "Body", None, past).await;
+        let second_id =
+            insert_published_article(&pool, "second", "Second", "Body", None, past).await;
+        let future_id = insert_published_article(
+            &pool,
+            "future",
+            "Future",
+            "Body",
+            None,
+            parse_datetime("2099-01-01 10:00:00"),
+        )
+        .await;
+        RelatedArticleRepository::replace_for_article(
+            &pool,
+            article_id,
+            &[future_id, second_id, first_id],
+        )
+        .await
+        .expect("Failed to save related articles");
+
+        let result = RelatedArticleQuery::fetch_for_article(&pool, article_id, now)
+            .await
+            .expect("Failed to fetch related articles");
+
+        let slugs: Vec<&str> = result.iter().map(|a| a.article.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["second", "first"]);
+    }
+
+    #[sqlx::test]
+    async fn test_公開範囲がpublic以外の記事は関連記事に含まれないこと(
+        pool: PgPool,
+    ) {
+        let now = parse_datetime("2025-01-15 12:00:00");
+        let past = parse_datetime("2024-01-01 10:00:00");
+        let article_id = insert_published_article(&pool, "base", "Base", "Body", None, past).await;
+        let public_id =
+            insert_published_article(&pool, "public", "Public", "Body", None, past).await;
+        let unlisted_id =
+            insert_published_article(&pool, "unlisted", "Unlisted", "Body", None, past).await;
+        set_published_article_visibility(&pool, unlisted_id, "unlisted", None).await;
+        RelatedArticleRepository::replace_for_article(&pool, article_id, &[unlisted_id, public_id])
+            .await
+            .expect("Failed to save related articles");
+
+        let result = RelatedArticleQuery::fetch_for_article(&pool, article_id, now)
+            .await
+            .expect("Failed to fetch related articles");
+        let candidates = RelatedArticleQuery::fetch_candidates(&pool)
+            .await
+            .expect("Failed to fetch candidates");
+
+        let slugs: Vec<&str> = result.iter().map(|a| a.article.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["public"]);
+        assert!(candidates.iter().all(|c| c.id != unlisted_id));
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_candidatesでカテゴリidが集約されること(pool: PgPool) {
+        let article_id =
+            insert_published_article(&pool, "with-category", "A", "Body", None, utc_now()).await;
+        insert_published_article(&pool, "no-category", "B", "Body", None, utc_now()).await;
+        let category_id = create_test_category(&pool, "Rust", "rust").await;
+        link_published_article_category(&pool, article_id, category_id).await;
+
+        let candidates = RelatedArticleQuery::fetch_candidates(&pool)
+            .await
+            .expect("Failed to fetch candidates");
+
+        assert_eq!(candidates.len(), 2);
+        let with_category = candidates.iter().find(|c| c.id == article_id).unwrap();
+        assert_eq!(with_category.category_ids, vec![category_id]);
+        let no_category = candidates.iter().find(|c| c.id != article_id).unwrap();
+        assert!(no_category.category_ids.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_referring_slugsで指定した記事を関連記事に含む記事のslugが返ること(
+        pool: PgPool,
+    ) {
+        let past = parse_datetime("2024-01-01 10:00:00");
+        let target_id =
+            insert_published_article(&pool, "target", "Target", "Body", None, past).await;
+        let first_id = insert_published_article(&pool, "first", "First", "Body", None, past).await;
+        let second_id =
+            insert_published_article(&pool, "second", "Second", "Body", None, past).await;
+        let other_id = insert_published_article(&pool, "other", "Other", "Body", None, past).await;
+        RelatedArticleRepository::replace_for_article(&pool, second_id, &[target_id])
+            .await
+            .expect("Failed to save related articles");
+        RelatedArticleRepository::replace_for_article(&pool, first_id, &[other_id, target_id])
+            .await
+            .expect("Failed to save related articles");
+        RelatedArticleRepository::replace_for_article(&pool, target_id, &[other_id])
+            .await
+            .expect("Failed to save related articles");
+
+        let slugs = RelatedArticleQuery::fetch_referring_slugs(&pool, target_id)
+            .await
+            .expect("Failed to fetch referring slugs");
+
+        assert_eq!(slugs, vec!["first", "second"]);
+    }
+}
@@ -1,9 +1,23 @@
+mod article_link;
+mod article_syndication;
 mod category;
 mod draft_article;
+mod featured_item;
 mod image;
+mod og_image_cache;
+mod ogp_cache;
+mod prose_check_rule;
 mod published_article;
+mod related_article;
 
+pub use article_link::ArticleLinkRepository;
+pub use article_syndication::ArticleSyndicationRepository;
 pub use category::CategoryRepository;
 pub use draft_article::DraftArticleRepository;
+pub use featured_item::FeaturedItemRepository;
 pub use image::ImageRepository;
+pub use og_image_cache::OgImageCacheRepository;
+pub use ogp_cache::OgpCacheRepository;
+pub use prose_check_rule::ProseCheckRuleRepository;
 pub use published_article::PublishedArticleRepository;
+pub use related_article::RelatedArticleRepository;
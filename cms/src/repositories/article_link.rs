@@ -0,0 +1,74 @@
+use crate::error::CmsError;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 記事間リンクリポジトリ（CUD操作）
+pub struct ArticleLinkRepository;
+
+impl ArticleLinkRepository {
+    /// 参照元の記事のリンク先を指定したslugで置き換え、置き換え前のリンク先を返す
+    #[instrument(skip(pool))]
+    pub async fn replace_for_source(
+        pool: &PgPool,
+        source_article_id: Uuid,
+        target_slugs: &[String],
+    ) -> Result<Vec<String>, CmsError> {
+        let previous = sqlx::query_scalar!(
+            "DELETE FROM article_links WHERE source_article_id = $1 RETURNING target_slug",
+            source_article_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO article_links (source_article_id, target_slug)
+            SELECT $1, target_slug FROM UNNEST($2::text[]) AS t(target_slug)
+            ON CONFLICT DO NOTHING
+            "#,
+            source_article_id,
+            target_slugs
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(previous)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::ArticleLinkQuery;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_replace_for_sourceで以前のリンク先が置き換わること(pool: PgPool) {
+        let source_id =
+            insert_published_article(&pool, "source", "Source", "Body", None, utc_now()).await;
+        ArticleLinkRepository::replace_for_source(&pool, source_id, &["old-target".to_string()])
+            .await
+            .expect("Failed to save links");
+
+        let previous = ArticleLinkRepository::replace_for_source(
+            &pool,
+            source_id,
+            &["new-target".to_string(), "new-target".to_string()],
+        )
+        .await
+        .expect("Failed to replace links");
+
+        assert_eq!(previous, vec!["old-target".to_string()]);
+        let old = ArticleLinkQuery::fetch_backlinks(&pool, "old-target", utc_now())
+            .await
+            .expect("Failed to fetch backlinks");
+        let new = ArticleLinkQuery::fetch_backlinks(&pool, "new-target", utc_now())
+            .await
+            .expect("Failed to fetch backlinks");
+        assert!(old.is_empty());
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].slug, "source");
+    }
+}
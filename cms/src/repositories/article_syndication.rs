@@ -0,0 +1,125 @@
+use crate::error::CmsError;
+use crate::models::ArticleSyndication;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 転載状況リポジトリ（CUD操作）
+pub struct ArticleSyndicationRepository;
+
+impl ArticleSyndicationRepository {
+    /// 反映中の印を立てて反映状況を返す（なければ作る）
+    ///
+    /// 他で反映中（`claimed_at` が `stale_before` より後）なら印を立てずに `None` を返す。
+    #[instrument(skip(pool))]
+    pub async fn claim(
+        pool: &PgPool,
+        article_id: Uuid,
+        target: &str,
+        now: NaiveDateTime,
+        stale_before: NaiveDateTime,
+    ) -> Result<Option<ArticleSyndication>, CmsError> {
+        let syndication = sqlx::query_as!(
+            ArticleSyndication,
+            r#"
+            INSERT INTO article_syndications (article_id, target, claimed_at, updated_at)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT (article_id, target) DO UPDATE SET
+                claimed_at = EXCLUDED.claimed_at
+            WHERE article_syndications.claimed_at IS NULL
+               OR article_syndications.claimed_at < $4
+            RETURNING article_id, target, remote_id, remote_url, last_error,
+                      synced_at as "synced_at: _", updated_at as "updated_at: _"
+            "#,
+            article_id,
+            target,
+            now as _,
+            stale_before as _
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(syndication)
+    }
+
+    /// 反映せずに反映中の印を消す
+    #[instrument(skip(pool))]
+    pub async fn release(pool: &PgPool, article_id: Uuid, target: &str) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            UPDATE article_syndications SET claimed_at = NULL
+            WHERE article_id = $1 AND target = $2
+            "#,
+            article_id,
+            target
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 反映に成功したことを記録（転載先の記事を保存し、エラーと反映中の印を消す）
+    #[instrument(skip(pool))]
+    pub async fn save_success(
+        pool: &PgPool,
+        article_id: Uuid,
+        target: &str,
+        remote_id: &str,
+        remote_url: &str,
+        now: NaiveDateTime,
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO article_syndications (article_id, target, remote_id, remote_url, last_error, synced_at, updated_at)
+            VALUES ($1, $2, $3, $4, NULL, $5, $5)
+            ON CONFLICT (article_id, target) DO UPDATE SET
+                remote_id = EXCLUDED.remote_id,
+                remote_url = EXCLUDED.remote_url,
+                last_error = NULL,
+                synced_at = EXCLUDED.synced_at,
+                updated_at = EXCLUDED.updated_at,
+                claimed_at = NULL
+            "#,
+            article_id,
+            target,
+            remote_id,
+            remote_url,
+            now as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 反映に失敗したことを記録（転載先の記事は前回のものを残し、反映中の印を消す）
+    #[instrument(skip(pool))]
+    pub async fn save_failure(
+        pool: &PgPool,
+        article_id: Uuid,
+        target: &str,
+        error: &str,
+        now: NaiveDateTime,
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO article_syndications (article_id, target, last_error, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (article_id, target) DO UPDATE SET
+                last_error = EXCLUDED.last_error,
+                updated_at = EXCLUDED.updated_at,
+                claimed_at = NULL
+            "#,
+            article_id,
+            target,
+            error,
+            now as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
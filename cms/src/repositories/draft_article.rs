@@ -2,6 +2,7 @@ use crate::error::CmsError;
 use crate::models::ArticleContent;
 use chrono::NaiveDateTime;
 use sqlx::PgPool;
+use sqlx::types::Json;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -34,14 +35,15 @@ impl DraftArticleRepository {
     ) -> Result<(), CmsError> {
         sqlx::query!(
             r#"
-            INSERT INTO draft_articles (id, slug, title, body, description, cover_image_url, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+            INSERT INTO draft_articles (id, slug, title, body, description, cover_image_url, seo, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
             ON CONFLICT (id) DO UPDATE SET
                 slug = EXCLUDED.slug,
                 title = EXCLUDED.title,
                 body = EXCLUDED.body,
                 description = EXCLUDED.description,
                 cover_image_url = EXCLUDED.cover_image_url,
+                seo = EXCLUDED.seo,
                 updated_at = EXCLUDED.updated_at
             "#,
             id,
@@ -50,6 +52,7 @@ impl DraftArticleRepository {
             content.body,
             content.description,
             content.cover_image_url,
+            Json(content.seo) as _,
             now as _
         )
         .execute(pool)
@@ -57,13 +60,36 @@ impl DraftArticleRepository {
 
         Ok(())
     }
+
+    /// 下書き記事の取り込み元URLを設定
+    #[instrument(skip(pool))]
+    pub async fn set_origin_url(
+        pool: &PgPool,
+        article_id: Uuid,
+        origin_url: Option<&str>,
+    ) -> Result<(), CmsError> {
+        let rows = sqlx::query!(
+            "UPDATE draft_articles SET origin_url = $1 WHERE id = $2",
+            origin_url,
+            article_id
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if rows == 0 {
+            return Err(CmsError::NotFound);
+        }
+
+        Ok(())
+    }
 }
 
 //noinspection NonAsciiCharacters
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ArticleContent;
+    use crate::models::{ArticleContent, ArticleSeo};
     use crate::test_utils::utc_now;
 
     #[sqlx::test]
@@ -76,6 +102,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, now)
             .await
@@ -125,6 +152,7 @@ mod tests {
             body: "これはテスト記事の本文です。",
             description: Some("テスト記事の説明"),
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, now)
             .await
@@ -156,6 +184,7 @@ mod tests {
             body: "元の本文",
             description: None,
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, now)
             .await
@@ -168,6 +197,7 @@ mod tests {
             body: "更新後の本文",
             description: Some("更新後の説明"),
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, utc_now())
             .await
@@ -201,6 +231,7 @@ mod tests {
                 body: "本文",
                 description: None,
                 cover_image_url: None,
+                seo: &ArticleSeo::default(),
             };
             DraftArticleRepository::upsert(&pool, id, &content, now)
                 .await
@@ -237,6 +268,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: Some("https://example.com/image.jpg"),
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, now)
             .await
@@ -262,6 +294,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         DraftArticleRepository::upsert(&pool, id, &content, utc_now())
             .await
@@ -277,4 +310,58 @@ mod tests {
 
         assert_eq!(article.cover_image_url, None);
     }
+
+    #[sqlx::test]
+    async fn test_set_origin_urlで取り込み元urlが保存されupsertで上書きされないこと(
+        pool: PgPool,
+    ) {
+        let now = utc_now();
+        let id = Uuid::now_v7();
+        let content = ArticleContent {
+            title: "取り込み記事",
+            slug: "qiita-abc",
+            body: "本文",
+            description: None,
+            cover_image_url: None,
+            seo: &ArticleSeo::default(),
+        };
+        DraftArticleRepository::upsert(&pool, id, &content, now)
+            .await
+            .expect("Failed to create draft article");
+
+        DraftArticleRepository::set_origin_url(
+            &pool,
+            id,
+            Some("https://qiita.com/romira/items/abc"),
+        )
+        .await
+        .expect("Failed to set origin url");
+        DraftArticleRepository::upsert(&pool, id, &content, utc_now())
+            .await
+            .expect("Failed to update draft article");
+
+        let origin_url =
+            sqlx::query_scalar!("SELECT origin_url FROM draft_articles WHERE id = $1", id)
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to fetch origin url");
+        assert_eq!(
+            origin_url.as_deref(),
+            Some("https://qiita.com/romira/items/abc")
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_存在しない記事にset_origin_urlするとnotfoundエラーになること(
+        pool: PgPool,
+    ) {
+        let result = DraftArticleRepository::set_origin_url(
+            &pool,
+            Uuid::now_v7(),
+            Some("https://example.com"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(CmsError::NotFound)));
+    }
 }
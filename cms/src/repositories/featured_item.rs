@@ -0,0 +1,80 @@
+use crate::error::CmsError;
+use crate::value_objects::FeaturedItemEntry;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// おすすめ記事リポジトリ（CUD操作）
+pub struct FeaturedItemRepository;
+
+impl FeaturedItemRepository {
+    /// すべての項目を指定した順で置き換える
+    #[instrument(skip(pool))]
+    pub async fn replace_all(pool: &PgPool, entries: &[FeaturedItemEntry]) -> Result<(), CmsError> {
+        let article_ids: Vec<Option<Uuid>> = entries.iter().map(|e| e.article_id()).collect();
+        let external_urls: Vec<Option<String>> = entries
+            .iter()
+            .map(|e| e.external_url().map(str::to_string))
+            .collect();
+        let expires_ats: Vec<Option<NaiveDateTime>> =
+            entries.iter().map(|e| e.expires_at()).collect();
+
+        sqlx::query!("DELETE FROM featured_items")
+            .execute(pool)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO featured_items (article_id, external_url, expires_at, position)
+            SELECT article_id, external_url, expires_at, position::INTEGER
+            FROM UNNEST($1::uuid[], $2::varchar[], $3::timestamp[])
+                WITH ORDINALITY AS t(article_id, external_url, expires_at, position)
+            "#,
+            &article_ids as _,
+            &external_urls as _,
+            &expires_ats as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::FeaturedItemQuery;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_replace_allで以前の項目が置き換わること(pool: PgPool) {
+        let article_id =
+            insert_published_article(&pool, "pinned", "Pinned", "Body", None, utc_now()).await;
+        insert_featured_item(&pool, None, Some("https://example.com/old"), 1, None).await;
+        let entries = vec![
+            FeaturedItemEntry::new(None, Some("https://example.com/new".to_string()), None)
+                .unwrap(),
+            FeaturedItemEntry::new(Some(article_id), None, Some(utc_now())).unwrap(),
+        ];
+
+        FeaturedItemRepository::replace_all(&pool, &entries)
+            .await
+            .expect("Failed to replace");
+
+        let items = FeaturedItemQuery::fetch_all(&pool)
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].external_url.as_deref(),
+            Some("https://example.com/new")
+        );
+        assert_eq!(items[0].position, 1);
+        assert_eq!(items[1].article_id, Some(article_id));
+        assert_eq!(items[1].position, 2);
+        assert!(items[1].expires_at.is_some());
+    }
+}
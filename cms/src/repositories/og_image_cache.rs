@@ -0,0 +1,33 @@
+use crate::error::CmsError;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// OGP画像キャッシュリポジトリ（CUD操作）
+pub struct OgImageCacheRepository;
+
+impl OgImageCacheRepository {
+    /// 画像を保存（同じ内容ハッシュが既にあれば何もしない）
+    #[instrument(skip(pool, png))]
+    pub async fn insert(
+        pool: &PgPool,
+        content_hash: &str,
+        png: &[u8],
+        now: NaiveDateTime,
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO og_image_caches (content_hash, png, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (content_hash) DO NOTHING
+            "#,
+            content_hash,
+            png,
+            now as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::error::CmsError;
+use crate::models::OgpContent;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// OGPキャッシュリポジトリ（CUD操作）
+pub struct OgpCacheRepository;
+
+impl OgpCacheRepository {
+    /// OGPキャッシュを作成または更新
+    #[instrument(skip(pool))]
+    pub async fn upsert(
+        pool: &PgPool,
+        url: &str,
+        content: &OgpContent<'_>,
+        now: NaiveDateTime,
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO ogp_caches (url, title, description, image_url, favicon_url, site_name, embed_html, fetched_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (url) DO UPDATE
+            SET title = EXCLUDED.title,
+                description = EXCLUDED.description,
+                image_url = EXCLUDED.image_url,
+                favicon_url = EXCLUDED.favicon_url,
+                site_name = EXCLUDED.site_name,
+                embed_html = EXCLUDED.embed_html,
+                fetched_at = EXCLUDED.fetched_at
+            "#,
+            url,
+            content.title,
+            content.description,
+            content.image_url,
+            content.favicon_url,
+            content.site_name,
+            content.embed_html,
+            now as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::OgpCacheQuery;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_upsertで新規にキャッシュが作成されること(pool: PgPool) {
+        let content = OgpContent {
+            title: Some("タイトル"),
+            description: Some("説明"),
+            image_url: Some("https://example.com/og.png"),
+            favicon_url: Some("https://example.com/favicon.ico"),
+            site_name: Some("Example"),
+            embed_html: None,
+        };
+
+        OgpCacheRepository::upsert(&pool, "https://example.com/", &content, utc_now())
+            .await
+            .expect("Failed to upsert");
+
+        let caches = OgpCacheQuery::fetch_by_urls(&pool, &["https://example.com/".to_string()])
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(caches.len(), 1);
+        assert_eq!(caches[0].title.as_deref(), Some("タイトル"));
+        assert_eq!(caches[0].site_name.as_deref(), Some("Example"));
+    }
+
+    #[sqlx::test]
+    async fn test_upsertで既存のキャッシュが上書きされること(pool: PgPool) {
+        insert_test_ogp_cache(&pool, "https://example.com/", Some("古いタイトル")).await;
+
+        let content = OgpContent {
+            title: Some("新しいタイトル"),
+            ..Default::default()
+        };
+        OgpCacheRepository::upsert(&pool, "https://example.com/", &content, utc_now())
+            .await
+            .expect("Failed to upsert");
+
+        let caches = OgpCacheQuery::fetch_by_urls(&pool, &["https://example.com/".to_string()])
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(caches.len(), 1);
+        assert_eq!(caches[0].title.as_deref(), Some("新しいタイトル"));
+        assert!(caches[0].description.is_none());
+    }
+}
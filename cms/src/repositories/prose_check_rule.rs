@@ -0,0 +1,60 @@
+use crate::error::CmsError;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// 文章チェックのルール設定リポジトリ（CUD操作）
+pub struct ProseCheckRuleRepository;
+
+impl ProseCheckRuleRepository {
+    /// ルール設定を作成または更新
+    #[instrument(skip(pool))]
+    pub async fn upsert(
+        pool: &PgPool,
+        rule: &str,
+        enabled: bool,
+        now: NaiveDateTime,
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO prose_check_rules (rule, enabled, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (rule) DO UPDATE
+            SET enabled = EXCLUDED.enabled,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            rule,
+            enabled,
+            now as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::ProseCheckRuleQuery;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_upsertで既存の設定が上書きされること(pool: PgPool) {
+        ProseCheckRuleRepository::upsert(&pool, "long_sentence", true, utc_now())
+            .await
+            .expect("Failed to upsert");
+        ProseCheckRuleRepository::upsert(&pool, "long_sentence", false, utc_now())
+            .await
+            .expect("Failed to upsert");
+
+        let rules = ProseCheckRuleQuery::fetch_all(&pool)
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].rule, "long_sentence");
+        assert!(!rules[0].enabled);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::error::CmsError;
-use crate::models::{ArticleContent, DraftArticleWithCategories};
+use crate::models::{ArticleContent, ArticleVisibility, DraftArticleWithCategories};
 use chrono::NaiveDateTime;
 use sqlx::PgPool;
+use sqlx::types::Json;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -18,8 +19,8 @@ impl PublishedArticleRepository {
     ) -> Result<Uuid, CmsError> {
         let published_id = sqlx::query_scalar!(
             r#"
-            INSERT INTO published_articles (slug, title, body, description, cover_image_url, published_at, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $6, $6)
+            INSERT INTO published_articles (slug, title, body, description, cover_image_url, origin_url, seo, published_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8, $8)
             RETURNING id
             "#,
             &draft.article.slug,
@@ -27,6 +28,8 @@ impl PublishedArticleRepository {
             &draft.article.body,
             draft.article.description.as_deref(),
             draft.article.cover_image_url.as_deref(),
+            draft.article.origin_url.as_deref(),
+            &draft.article.seo as _,
             now as _
         )
         .fetch_one(pool)
@@ -72,14 +75,15 @@ impl PublishedArticleRepository {
         let rows = sqlx::query!(
             r#"
             UPDATE published_articles
-            SET title = $1, slug = $2, body = $3, description = $4, cover_image_url = $5, updated_at = $6
-            WHERE id = $7
+            SET title = $1, slug = $2, body = $3, description = $4, cover_image_url = $5, seo = $6, updated_at = $7
+            WHERE id = $8
             "#,
             content.title,
             content.slug,
             content.body,
             content.description,
             content.cover_image_url,
+            Json(content.seo) as _,
             now as _,
             article_id
         )
@@ -93,13 +97,46 @@ impl PublishedArticleRepository {
 
         Ok(())
     }
+
+    /// 公開記事の公開範囲を更新
+    ///
+    /// `password_hash` が `None` の場合、`protected` なら既存のパスワードを引き継ぎ、
+    /// それ以外ならパスワードを削除する。
+    #[instrument(skip(pool, password_hash))]
+    pub async fn update_visibility(
+        pool: &PgPool,
+        article_id: Uuid,
+        visibility: ArticleVisibility,
+        password_hash: Option<&str>,
+    ) -> Result<(), CmsError> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE published_articles
+            SET visibility = $1::article_visibility,
+                password_hash = CASE WHEN $1::article_visibility = 'protected' THEN COALESCE($2, password_hash) ELSE NULL END
+            WHERE id = $3
+            "#,
+            visibility as _,
+            password_hash,
+            article_id
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if rows == 0 {
+            return Err(CmsError::NotFound);
+        }
+
+        Ok(())
+    }
 }
 
 //noinspection NonAsciiCharacters
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{ArticleContent, Category, DraftArticle};
+    use crate::models::{ArticleContent, ArticleSeo, Category, DraftArticle};
     use crate::test_utils::*;
 
     #[sqlx::test]
@@ -117,6 +154,8 @@ mod tests {
                 body: "下書き本文".to_string(),
                 description: Some("下書き説明".to_string()),
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
@@ -142,6 +181,45 @@ mod tests {
         assert_eq!(published.description, Some("下書き説明".to_string()));
     }
 
+    #[sqlx::test]
+    async fn test_公開記事作成時に取り込み元urlが引き継がれること(
+        pool: PgPool,
+    ) {
+        let draft_id = insert_draft_article(&pool, "qiita-abc", "取り込み記事", "本文", None).await;
+
+        let draft = DraftArticleWithCategories {
+            article: DraftArticle {
+                id: draft_id,
+                slug: "qiita-abc".to_string(),
+                title: "取り込み記事".to_string(),
+                body: "本文".to_string(),
+                description: None,
+                cover_image_url: None,
+                origin_url: Some("https://qiita.com/romira/items/abc".to_string()),
+                seo: Default::default(),
+                created_at: utc_now(),
+                updated_at: utc_now(),
+            },
+            categories: vec![],
+        };
+
+        let published_id = PublishedArticleRepository::create_from_draft(&pool, &draft, utc_now())
+            .await
+            .expect("Failed to create published article from draft");
+
+        let origin_url = sqlx::query_scalar!(
+            "SELECT origin_url FROM published_articles WHERE id = $1",
+            published_id
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to fetch origin url");
+        assert_eq!(
+            origin_url.as_deref(),
+            Some("https://qiita.com/romira/items/abc")
+        );
+    }
+
     #[sqlx::test]
     async fn test_カテゴリ付き下書きから公開記事とカテゴリが作成されること(
         pool: PgPool,
@@ -159,6 +237,8 @@ mod tests {
                 body: "本文".to_string(),
                 description: None,
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
@@ -204,6 +284,8 @@ mod tests {
                 body: "本文".to_string(),
                 description: None,
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
@@ -248,6 +330,8 @@ mod tests {
                 body: "元の本文".to_string(),
                 description: Some("元の説明".to_string()),
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
@@ -267,6 +351,7 @@ mod tests {
             body: "更新後の本文",
             description: Some("更新後の説明"),
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         PublishedArticleRepository::update(&pool, published_id, &content, update_time)
             .await
@@ -297,6 +382,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         let result =
             PublishedArticleRepository::update(&pool, nonexistent_id, &content, utc_now()).await;
@@ -317,6 +403,8 @@ mod tests {
                 body: "本文".to_string(),
                 description: None,
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
@@ -336,6 +424,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: Some("https://example.com/cover.jpg"),
+            seo: &ArticleSeo::default(),
         };
         PublishedArticleRepository::update(&pool, published_id, &content, utc_now())
             .await
@@ -361,6 +450,7 @@ mod tests {
             body: "本文",
             description: None,
             cover_image_url: None,
+            seo: &ArticleSeo::default(),
         };
         PublishedArticleRepository::update(&pool, published_id, &content, utc_now())
             .await
@@ -389,6 +479,8 @@ mod tests {
                 body: "本文".to_string(),
                 description: None,
                 cover_image_url: None,
+                origin_url: None,
+                seo: Default::default(),
                 created_at: utc_now(),
                 updated_at: utc_now(),
             },
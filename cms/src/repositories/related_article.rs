@@ -0,0 +1,73 @@
+use crate::error::CmsError;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// 関連記事リポジトリ（CUD操作）
+pub struct RelatedArticleRepository;
+
+impl RelatedArticleRepository {
+    /// 記事の関連記事を指定した順で置き換える
+    #[instrument(skip(pool))]
+    pub async fn replace_for_article(
+        pool: &PgPool,
+        article_id: Uuid,
+        related_article_ids: &[Uuid],
+    ) -> Result<(), CmsError> {
+        sqlx::query!(
+            "DELETE FROM related_articles WHERE article_id = $1",
+            article_id
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO related_articles (article_id, related_article_id, rank)
+            SELECT $1, related_article_id, rank::INTEGER
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS t(related_article_id, rank)
+            "#,
+            article_id,
+            related_article_ids
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::RelatedArticleQuery;
+    use crate::test_utils::*;
+
+    #[sqlx::test]
+    async fn test_replace_for_articleで以前の関連記事が置き換わること(
+        pool: PgPool,
+    ) {
+        let article_id =
+            insert_published_article(&pool, "base", "Base", "Body", None, utc_now()).await;
+        let old_id = insert_published_article(&pool, "old", "Old", "Body", None, utc_now()).await;
+        let a_id = insert_published_article(&pool, "a", "A", "Body", None, utc_now()).await;
+        let b_id = insert_published_article(&pool, "b", "B", "Body", None, utc_now()).await;
+        RelatedArticleRepository::replace_for_article(&pool, article_id, &[old_id])
+            .await
+            .expect("Failed to save related articles");
+
+        RelatedArticleRepository::replace_for_article(&pool, article_id, &[b_id, a_id])
+            .await
+            .expect("Failed to replace related articles");
+
+        let related = RelatedArticleQuery::fetch_all(&pool)
+            .await
+            .expect("Failed to fetch related articles");
+        let saved: Vec<(Uuid, i32)> = related
+            .iter()
+            .map(|r| (r.related_article_id, r.rank))
+            .collect();
+        assert_eq!(saved, vec![(b_id, 1), (a_id, 2)]);
+    }
+}
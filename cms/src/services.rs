@@ -1,14 +1,28 @@
 mod admin_article;
+mod article_link;
+mod article_syndication;
 mod category;
 mod draft_article;
+mod featured_item;
 mod image;
+mod og_image_cache;
+mod ogp_cache;
+mod prose_check_rule;
 mod published_article;
+mod related_article;
 
 pub use admin_article::AdminArticleService;
+pub use article_link::ArticleLinkService;
+pub use article_syndication::ArticleSyndicationService;
 pub use category::CategoryService;
 pub use draft_article::DraftArticleService;
+pub use featured_item::FeaturedItemService;
 pub use image::ImageService;
+pub use og_image_cache::OgImageCacheService;
+pub use ogp_cache::OgpCacheService;
+pub use prose_check_rule::ProseCheckRuleService;
 pub use published_article::PublishedArticleService;
+pub use related_article::RelatedArticleService;
 
 use chrono::{NaiveDateTime, Utc};
 
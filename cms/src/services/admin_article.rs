@@ -21,4 +21,10 @@ impl AdminArticleService {
     pub async fn fetch_all(&self) -> Result<Vec<ArticleListItem>, CmsError> {
         AdminArticleQuery::fetch_all(&self.pool).await
     }
+
+    /// 公開記事と下書き記事に記録された取り込み元URLをすべて取得
+    #[instrument(skip(self))]
+    pub async fn fetch_origin_urls(&self) -> Result<Vec<String>, CmsError> {
+        AdminArticleQuery::fetch_origin_urls(&self.pool).await
+    }
 }
@@ -0,0 +1,80 @@
+use crate::error::CmsError;
+use crate::models::LinkedArticle;
+use crate::queries::ArticleLinkQuery;
+use crate::repositories::ArticleLinkRepository;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::utc_now;
+
+/// 記事間リンク（バックリンク）サービス
+#[derive(Debug, Clone)]
+pub struct ArticleLinkService {
+    pool: PgPool,
+}
+
+impl ArticleLinkService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定したslugの記事を参照している公開済み記事を取得
+    #[instrument(skip(self))]
+    pub async fn fetch_backlinks(&self, target_slug: &str) -> Result<Vec<LinkedArticle>, CmsError> {
+        ArticleLinkQuery::fetch_backlinks(&self.pool, target_slug, utc_now()).await
+    }
+
+    /// 公開記事の本文中のリンク先を保存（以前のリンク先は置き換える）
+    ///
+    /// 戻り値は保存前後のいずれかでリンク先だったslug（バックリンクの表示が変わりうる記事）。
+    #[instrument(skip(self))]
+    pub async fn save_links(
+        &self,
+        source_article_id: Uuid,
+        target_slugs: &[String],
+    ) -> Result<Vec<String>, CmsError> {
+        let mut affected =
+            ArticleLinkRepository::replace_for_source(&self.pool, source_article_id, target_slugs)
+                .await?;
+        for slug in target_slugs {
+            if !affected.contains(slug) {
+                affected.push(slug.clone());
+            }
+        }
+        Ok(affected)
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::insert_published_article;
+
+    #[sqlx::test]
+    async fn test_save_linksで保存前後のリンク先が返ること(pool: PgPool) {
+        let source_id =
+            insert_published_article(&pool, "source", "Source", "Body", None, utc_now()).await;
+        let service = ArticleLinkService::new(pool);
+        service
+            .save_links(source_id, &["a".to_string(), "b".to_string()])
+            .await
+            .expect("Failed to save links");
+
+        let mut affected = service
+            .save_links(source_id, &["b".to_string(), "c".to_string()])
+            .await
+            .expect("Failed to save links");
+        affected.sort();
+
+        assert_eq!(affected, vec!["a", "b", "c"]);
+        let backlinks = service
+            .fetch_backlinks("c")
+            .await
+            .expect("Failed to fetch backlinks");
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].slug, "source");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::error::CmsError;
+use crate::models::ArticleSyndication;
+use crate::queries::ArticleSyndicationQuery;
+use crate::repositories::ArticleSyndicationRepository;
+use chrono::TimeDelta;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::utc_now;
+
+/// 反映中の印を有効とみなす期間（これを過ぎた印は反映中のプロセスが止まったとみなす）
+const CLAIM_TIMEOUT: TimeDelta = TimeDelta::minutes(10);
+
+/// 公開記事の転載先への反映状況サービス
+#[derive(Debug, Clone)]
+pub struct ArticleSyndicationService {
+    pool: PgPool,
+}
+
+impl ArticleSyndicationService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 公開記事の転載先ごとの反映状況を取得
+    #[instrument(skip(self))]
+    pub async fn fetch_for_article(
+        &self,
+        article_id: Uuid,
+    ) -> Result<Vec<ArticleSyndication>, CmsError> {
+        ArticleSyndicationQuery::fetch_for_article(&self.pool, article_id).await
+    }
+
+    /// 公開記事の指定した転載先への反映状況を取得
+    #[instrument(skip(self))]
+    pub async fn fetch(
+        &self,
+        article_id: Uuid,
+        target: &str,
+    ) -> Result<Option<ArticleSyndication>, CmsError> {
+        Ok(self
+            .fetch_for_article(article_id)
+            .await?
+            .into_iter()
+            .find(|syndication| syndication.target == target))
+    }
+
+    /// 転載先の記事があるすべての転載状況を取得
+    #[instrument(skip(self))]
+    pub async fn fetch_synced(&self) -> Result<Vec<ArticleSyndication>, CmsError> {
+        ArticleSyndicationQuery::fetch_synced(&self.pool).await
+    }
+
+    /// 転載先への反映を始める前に反映中の印を立て、反映状況を返す
+    ///
+    /// 同じ記事を同時に転載先へ投稿しないよう、他で反映中なら `None` を返す。
+    /// 印は `record_success` / `record_failure` / `release` で消す。
+    #[instrument(skip(self))]
+    pub async fn claim(
+        &self,
+        article_id: Uuid,
+        target: &str,
+    ) -> Result<Option<ArticleSyndication>, CmsError> {
+        let now = utc_now();
+        ArticleSyndicationRepository::claim(
+            &self.pool,
+            article_id,
+            target,
+            now,
+            now - CLAIM_TIMEOUT,
+        )
+        .await
+    }
+
+    /// 反映せずに反映中の印を消す
+    #[instrument(skip(self))]
+    pub async fn release(&self, article_id: Uuid, target: &str) -> Result<(), CmsError> {
+        ArticleSyndicationRepository::release(&self.pool, article_id, target).await
+    }
+
+    /// 反映に成功したことを記録
+    #[instrument(skip(self))]
+    pub async fn record_success(
+        &self,
+        article_id: Uuid,
+        target: &str,
+        remote_id: &str,
+        remote_url: &str,
+    ) -> Result<(), CmsError> {
+        ArticleSyndicationRepository::save_success(
+            &self.pool,
+            article_id,
+            target,
+            remote_id,
+            remote_url,
+            utc_now(),
+        )
+        .await
+    }
+
+    /// 反映に失敗したことを記録
+    #[instrument(skip(self))]
+    pub async fn record_failure(
+        &self,
+        article_id: Uuid,
+        target: &str,
+        error: &str,
+    ) -> Result<(), CmsError> {
+        ArticleSyndicationRepository::save_failure(&self.pool, article_id, target, error, utc_now())
+            .await
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::insert_published_article;
+
+    #[sqlx::test]
+    async fn test_失敗を記録しても前回の転載先の記事が残ること(pool: PgPool) {
+        let article_id =
+            insert_published_article(&pool, "slug", "Title", "Body", None, utc_now()).await;
+        let service = ArticleSyndicationService::new(pool);
+
+        service
+            .record_success(
+                article_id,
+                "qiita",
+                "abc123",
+                "https://qiita.com/romira/items/abc123",
+            )
+            .await
+            .expect("Failed to record success");
+        service
+            .record_failure(
+                article_id,
+                "qiita",
+                "Failed to api response status code: 500",
+            )
+            .await
+            .expect("Failed to record failure");
+
+        let syndication = service
+            .fetch(article_id, "qiita")
+            .await
+            .expect("Failed to fetch")
+            .expect("Syndication not found");
+        assert_eq!(syndication.remote_id.as_deref(), Some("abc123"));
+        assert_eq!(
+            syndication.last_error.as_deref(),
+            Some("Failed to api response status code: 500")
+        );
+        assert!(syndication.synced_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_成功を記録すると前回のエラーが消えること(pool: PgPool) {
+        let article_id =
+            insert_published_article(&pool, "slug", "Title", "Body", None, utc_now()).await;
+        let service = ArticleSyndicationService::new(pool);
+
+        service
+            .record_failure(article_id, "qiita", "Rate limited until None")
+            .await
+            .expect("Failed to record failure");
+        let failed = service
+            .fetch(article_id, "qiita")
+            .await
+            .expect("Failed to fetch")
+            .expect("Syndication not found");
+        assert!(failed.remote_id.is_none());
+        assert!(failed.synced_at.is_none());
+
+        service
+            .record_success(
+                article_id,
+                "qiita",
+                "abc123",
+                "https://qiita.com/romira/items/abc123",
+            )
+            .await
+            .expect("Failed to record success");
+
+        let syndications = service
+            .fetch_for_article(article_id)
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(syndications.len(), 1);
+        assert_eq!(
+            syndications[0].remote_url.as_deref(),
+            Some("https://qiita.com/romira/items/abc123")
+        );
+        assert!(syndications[0].last_error.is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_転載先の記事がある転載状況だけを取得すること(pool: PgPool) {
+        let synced_id =
+            insert_published_article(&pool, "synced", "Synced", "Body", None, utc_now()).await;
+        let failed_id =
+            insert_published_article(&pool, "failed", "Failed", "Body", None, utc_now()).await;
+        let service = ArticleSyndicationService::new(pool);
+        service
+            .record_success(
+                synced_id,
+                "qiita",
+                "abc123",
+                "https://qiita.com/romira/items/abc123",
+            )
+            .await
+            .expect("Failed to record success");
+        service
+            .record_failure(failed_id, "qiita", "Rate limited until None")
+            .await
+            .expect("Failed to record failure");
+
+        let syndications = service.fetch_synced().await.expect("Failed to fetch");
+
+        assert_eq!(syndications.len(), 1);
+        assert_eq!(syndications[0].article_id, synced_id);
+    }
+
+    #[sqlx::test]
+    async fn test_反映中は他で反映を始められず記録すると再び始められること(
+        pool: PgPool,
+    ) {
+        let article_id =
+            insert_published_article(&pool, "slug", "Title", "Body", None, utc_now()).await;
+        let service = ArticleSyndicationService::new(pool);
+
+        let claimed = service
+            .claim(article_id, "qiita")
+            .await
+            .expect("Failed to claim")
+            .expect("Not claimed");
+        assert_eq!(claimed.remote_id, None);
+        assert!(
+            service
+                .claim(article_id, "qiita")
+                .await
+                .expect("Failed to claim")
+                .is_none()
+        );
+
+        service
+            .record_success(
+                article_id,
+                "qiita",
+                "abc123",
+                "https://qiita.com/romira/items/abc123",
+            )
+            .await
+            .expect("Failed to record success");
+        let claimed = service
+            .claim(article_id, "qiita")
+            .await
+            .expect("Failed to claim")
+            .expect("Not claimed");
+        assert_eq!(claimed.remote_id.as_deref(), Some("abc123"));
+
+        service
+            .release(article_id, "qiita")
+            .await
+            .expect("Failed to release");
+        assert!(
+            service
+                .claim(article_id, "qiita")
+                .await
+                .expect("Failed to claim")
+                .is_some()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_期限を過ぎた反映中の印は無視して反映を始められること(
+        pool: PgPool,
+    ) {
+        let article_id =
+            insert_published_article(&pool, "slug", "Title", "Body", None, utc_now()).await;
+        let stale = utc_now() - CLAIM_TIMEOUT - TimeDelta::minutes(1);
+        ArticleSyndicationRepository::claim(&pool, article_id, "qiita", stale, stale)
+            .await
+            .expect("Failed to claim")
+            .expect("Not claimed");
+        let service = ArticleSyndicationService::new(pool);
+
+        assert!(
+            service
+                .claim(article_id, "qiita")
+                .await
+                .expect("Failed to claim")
+                .is_some()
+        );
+    }
+}
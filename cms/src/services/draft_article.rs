@@ -2,7 +2,7 @@ use crate::error::CmsError;
 use crate::models::{ArticleContent, DraftArticleWithCategories};
 use crate::queries::{DraftArticleQuery, PublishedArticleQuery};
 use crate::repositories::{DraftArticleRepository, PublishedArticleRepository};
-use crate::value_objects::PublishedArticleSlug;
+use crate::value_objects::{PublishedArticleSeo, PublishedArticleSlug};
 use sqlx::PgPool;
 use tracing::instrument;
 use uuid::Uuid;
@@ -46,15 +46,28 @@ impl DraftArticleService {
         DraftArticleRepository::upsert(&self.pool, article_id, content, utc_now()).await
     }
 
+    /// 下書き記事の取り込み元URLを設定（`save` では変更されない）
+    #[instrument(skip(self))]
+    pub async fn set_origin_url(
+        &self,
+        article_id: Uuid,
+        origin_url: Option<&str>,
+    ) -> Result<(), CmsError> {
+        DraftArticleRepository::set_origin_url(&self.pool, article_id, origin_url).await
+    }
+
     /// 下書きを公開（draft_articles → published_articles に移動）
     #[instrument(skip(self))]
     pub async fn publish(&self, draft_id: Uuid) -> Result<Uuid, CmsError> {
-        let draft = DraftArticleQuery::fetch_by_id(&self.pool, draft_id)
+        let mut draft = DraftArticleQuery::fetch_by_id(&self.pool, draft_id)
             .await?
             .ok_or(CmsError::NotFound)?;
 
         // スラッグのバリデーション
         let slug = PublishedArticleSlug::new(draft.article.slug.clone())?;
+        // SEO設定のバリデーション（下書き保存時は検証しないため公開時に行い、正規化した値で公開する）
+        let seo = PublishedArticleSeo::new(draft.article.seo.0.clone())?;
+        draft.article.seo.0 = seo.as_seo().clone();
 
         // スラッグ重複チェック（新規公開なので除外IDなし）
         if PublishedArticleQuery::exists_by_slug(&self.pool, slug.as_str(), None).await? {
@@ -0,0 +1,131 @@
+use crate::error::CmsError;
+use crate::models::FeaturedItem;
+use crate::queries::{FeaturedItemQuery, PublishedArticleQuery};
+use crate::repositories::FeaturedItemRepository;
+use crate::value_objects::{FeaturedItemEntry, FeaturedItemTarget};
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use tracing::instrument;
+
+use super::utc_now;
+
+/// トップページの「おすすめ」サービス（管理設定）
+#[derive(Debug, Clone)]
+pub struct FeaturedItemService {
+    pool: PgPool,
+}
+
+impl FeaturedItemService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 期限切れを含むすべての項目を表示順に取得（管理者用）
+    #[instrument(skip(self))]
+    pub async fn fetch_all(&self) -> Result<Vec<FeaturedItem>, CmsError> {
+        FeaturedItemQuery::fetch_all(&self.pool).await
+    }
+
+    /// 表示中（期限切れでない）の項目を表示順に取得
+    #[instrument(skip(self))]
+    pub async fn fetch_active(&self) -> Result<Vec<FeaturedItem>, CmsError> {
+        FeaturedItemQuery::fetch_active(&self.pool, utc_now()).await
+    }
+
+    /// `since` より後、`until` 以前に表示期限が来た項目があるか
+    #[instrument(skip(self))]
+    pub async fn has_expired_between(
+        &self,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Result<bool, CmsError> {
+        FeaturedItemQuery::exists_expired_between(&self.pool, since, until).await
+    }
+
+    /// すべての項目を指定した順で保存
+    #[instrument(skip(self))]
+    pub async fn save(&self, entries: &[FeaturedItemEntry]) -> Result<(), CmsError> {
+        let mut seen = HashSet::new();
+        for entry in entries {
+            if !seen.insert(entry.target()) {
+                return Err(CmsError::ValidationError(
+                    "同じ記事がおすすめに複数指定されています".to_string(),
+                ));
+            }
+            if let FeaturedItemTarget::Article(article_id) = entry.target()
+                && PublishedArticleQuery::fetch_by_id_for_admin(&self.pool, *article_id)
+                    .await?
+                    .is_none()
+            {
+                return Err(CmsError::ValidationError(
+                    "おすすめに指定した記事が見つかりません".to_string(),
+                ));
+            }
+        }
+        FeaturedItemRepository::replace_all(&self.pool, entries).await
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::insert_published_article;
+    use uuid::Uuid;
+
+    #[sqlx::test]
+    async fn test_saveした項目が表示順に取得できること(pool: PgPool) {
+        let article_id =
+            insert_published_article(&pool, "pinned", "Pinned", "Body", None, utc_now()).await;
+        let service = FeaturedItemService::new(pool);
+        let entries = vec![
+            FeaturedItemEntry::new(
+                None,
+                Some("https://qiita.com/Romira/items/abc".to_string()),
+                None,
+            )
+            .unwrap(),
+            FeaturedItemEntry::new(Some(article_id), None, None).unwrap(),
+        ];
+
+        service.save(&entries).await.expect("Failed to save");
+
+        let items = service.fetch_active().await.expect("Failed to fetch");
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].external_url.as_deref(),
+            Some("https://qiita.com/Romira/items/abc")
+        );
+        assert_eq!(items[1].article_slug.as_deref(), Some("pinned"));
+    }
+
+    #[sqlx::test]
+    async fn test_saveで同じ記事を重複して指定するとバリデーションエラーになること(
+        pool: PgPool,
+    ) {
+        let service = FeaturedItemService::new(pool);
+        let url = "https://example.com/a".to_string();
+        let entries = vec![
+            FeaturedItemEntry::new(None, Some(url.clone()), None).unwrap(),
+            FeaturedItemEntry::new(None, Some(url), None).unwrap(),
+        ];
+
+        let result = service.save(&entries).await;
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+
+    #[sqlx::test]
+    async fn test_saveで存在しない記事を指定するとバリデーションエラーになること(
+        pool: PgPool,
+    ) {
+        let service = FeaturedItemService::new(pool);
+        let entries = vec![FeaturedItemEntry::new(Some(Uuid::nil()), None, None).unwrap()];
+
+        let result = service.save(&entries).await;
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+}
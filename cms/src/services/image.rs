@@ -39,6 +39,15 @@ impl ImageService {
         ImageQuery::fetch_by_id(&self.pool, image_id).await
     }
 
+    /// 指定したGCSパスの画像をまとめて取得
+    #[instrument(skip(self))]
+    pub async fn fetch_by_gcs_paths(&self, gcs_paths: &[String]) -> Result<Vec<Image>, CmsError> {
+        if gcs_paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        ImageQuery::fetch_by_gcs_paths(&self.pool, gcs_paths).await
+    }
+
     /// MIMEタイプをバリデーション
     #[instrument]
     pub fn validate_mime_type(mime_type: &str) -> Result<(), CmsError> {
@@ -0,0 +1,58 @@
+use crate::error::CmsError;
+use crate::queries::OgImageCacheQuery;
+use crate::repositories::OgImageCacheRepository;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use super::utc_now;
+
+/// OGP画像キャッシュサービス
+#[derive(Debug, Clone)]
+pub struct OgImageCacheService {
+    pool: PgPool,
+}
+
+impl OgImageCacheService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 内容ハッシュに対応する画像を取得
+    #[instrument(skip(self))]
+    pub async fn fetch_png(&self, content_hash: &str) -> Result<Option<Vec<u8>>, CmsError> {
+        OgImageCacheQuery::fetch_png(&self.pool, content_hash).await
+    }
+
+    /// 生成した画像を保存
+    #[instrument(skip(self, png))]
+    pub async fn save(&self, content_hash: &str, png: &[u8]) -> Result<(), CmsError> {
+        OgImageCacheRepository::insert(&self.pool, content_hash, png, utc_now()).await
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_saveした画像がfetch_pngで取得できること(pool: PgPool) {
+        let service = OgImageCacheService::new(pool);
+
+        service
+            .save("hash-a", b"png-a")
+            .await
+            .expect("Failed to save");
+        // 同じハッシュは上書きしない
+        service
+            .save("hash-a", b"png-b")
+            .await
+            .expect("Failed to save");
+
+        let png = service.fetch_png("hash-a").await.expect("Failed to fetch");
+        assert_eq!(png.as_deref(), Some(&b"png-a"[..]));
+        let missing = service.fetch_png("hash-b").await.expect("Failed to fetch");
+        assert!(missing.is_none());
+    }
+}
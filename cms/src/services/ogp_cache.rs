@@ -0,0 +1,67 @@
+use crate::error::CmsError;
+use crate::models::{OgpCache, OgpContent};
+use crate::queries::OgpCacheQuery;
+use crate::repositories::OgpCacheRepository;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use super::utc_now;
+
+/// OGPキャッシュサービス
+#[derive(Debug, Clone)]
+pub struct OgpCacheService {
+    pool: PgPool,
+}
+
+impl OgpCacheService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定したURLのキャッシュをまとめて取得
+    #[instrument(skip(self))]
+    pub async fn fetch_by_urls(&self, urls: &[String]) -> Result<Vec<OgpCache>, CmsError> {
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+        OgpCacheQuery::fetch_by_urls(&self.pool, urls).await
+    }
+
+    /// 取得したOGPメタデータを保存（取得日時は現在時刻）
+    #[instrument(skip(self))]
+    pub async fn save(&self, url: &str, content: &OgpContent<'_>) -> Result<(), CmsError> {
+        OgpCacheRepository::upsert(&self.pool, url, content, utc_now()).await
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_saveしたメタデータがfetch_by_urlsで取得できること(pool: PgPool) {
+        let service = OgpCacheService::new(pool);
+        let content = OgpContent {
+            title: Some("タイトル"),
+            image_url: Some("https://example.com/og.png"),
+            ..Default::default()
+        };
+
+        service
+            .save("https://example.com/", &content)
+            .await
+            .expect("Failed to save");
+
+        let caches = service
+            .fetch_by_urls(&["https://example.com/".to_string()])
+            .await
+            .expect("Failed to fetch");
+        assert_eq!(caches.len(), 1);
+        assert_eq!(
+            caches[0].image_url.as_deref(),
+            Some("https://example.com/og.png")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::CmsError;
+use crate::models::ProseCheckRule;
+use crate::queries::ProseCheckRuleQuery;
+use crate::repositories::ProseCheckRuleRepository;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use super::utc_now;
+
+/// 文章チェックのルール設定サービス（管理設定）
+#[derive(Debug, Clone)]
+pub struct ProseCheckRuleService {
+    pool: PgPool,
+}
+
+impl ProseCheckRuleService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 保存済みのルール設定を取得（未保存のルールは含まない）
+    #[instrument(skip(self))]
+    pub async fn fetch_all(&self) -> Result<Vec<ProseCheckRule>, CmsError> {
+        ProseCheckRuleQuery::fetch_all(&self.pool).await
+    }
+
+    /// ルール設定をまとめて保存
+    #[instrument(skip(self))]
+    pub async fn save(&self, rules: &[ProseCheckRule]) -> Result<(), CmsError> {
+        let now = utc_now();
+        for rule in rules {
+            if rule.rule.trim().is_empty() {
+                return Err(CmsError::ValidationError("ルール名は必須です".to_string()));
+            }
+            ProseCheckRuleRepository::upsert(&self.pool, &rule.rule, rule.enabled, now).await?;
+        }
+        Ok(())
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_saveした設定がfetch_allで取得できること(pool: PgPool) {
+        let service = ProseCheckRuleService::new(pool);
+        let rules = vec![
+            ProseCheckRule {
+                rule: "ra_nuki".to_string(),
+                enabled: false,
+            },
+            ProseCheckRule {
+                rule: "long_sentence".to_string(),
+                enabled: true,
+            },
+        ];
+
+        service.save(&rules).await.expect("Failed to save");
+
+        let saved = service.fetch_all().await.expect("Failed to fetch");
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0].rule, "long_sentence");
+        assert!(saved[0].enabled);
+        assert_eq!(saved[1].rule, "ra_nuki");
+        assert!(!saved[1].enabled);
+    }
+
+    #[sqlx::test]
+    async fn test_saveで空のルール名はバリデーションエラーになること(
+        pool: PgPool,
+    ) {
+        let service = ProseCheckRuleService::new(pool);
+        let rules = vec![ProseCheckRule {
+            rule: " ".to_string(),
+            enabled: true,
+        }];
+
+        let result = service.save(&rules).await;
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+}
@@ -1,8 +1,11 @@
 use crate::error::CmsError;
-use crate::models::{ArticleContent, PublishedArticleWithCategories};
+use crate::models::ArticleVisibility;
+use crate::models::{ArticleContent, LinkedArticle, PublishedArticleWithCategories};
 use crate::queries::PublishedArticleQuery;
 use crate::repositories::PublishedArticleRepository;
-use crate::value_objects::{PublishedArticleSlug, PublishedArticleTitle};
+use crate::value_objects::{
+    PublishedArticleSeo, PublishedArticleSlug, PublishedArticleTitle, PublishedArticleVisibility,
+};
 use sqlx::PgPool;
 use tracing::instrument;
 use uuid::Uuid;
@@ -45,6 +48,39 @@ impl PublishedArticleService {
         PublishedArticleQuery::fetch_by_slug(&self.pool, slug, utc_now()).await
     }
 
+    /// 指定したslugのうち公開済みで一般公開の記事のslugとタイトルを取得
+    #[instrument(skip(self))]
+    pub async fn fetch_linked_articles(
+        &self,
+        slugs: &[String],
+    ) -> Result<Vec<LinkedArticle>, CmsError> {
+        if slugs.is_empty() {
+            return Ok(Vec::new());
+        }
+        PublishedArticleQuery::fetch_linked_articles(&self.pool, slugs, utc_now()).await
+    }
+
+    /// 指定したslugのうち公開済みの記事のslugを取得（公開範囲は問わない）
+    #[instrument(skip(self))]
+    pub async fn fetch_existing_slugs(&self, slugs: &[String]) -> Result<Vec<String>, CmsError> {
+        if slugs.is_empty() {
+            return Ok(Vec::new());
+        }
+        PublishedArticleQuery::fetch_existing_slugs(&self.pool, slugs, utc_now()).await
+    }
+
+    /// パスワード保護された公開済み記事のパスワードハッシュをslugで取得
+    #[instrument(skip(self))]
+    pub async fn fetch_password_hash(&self, slug: &str) -> Result<Option<String>, CmsError> {
+        PublishedArticleQuery::fetch_password_hash(&self.pool, slug, utc_now()).await
+    }
+
+    /// 公開記事にパスワードが設定されているかチェック（管理者用）
+    #[instrument(skip(self))]
+    pub async fn has_password(&self, article_id: Uuid) -> Result<bool, CmsError> {
+        PublishedArticleQuery::has_password(&self.pool, article_id).await
+    }
+
     /// 公開済み記事をIDで取得（管理者用、公開日時フィルタなし）
     #[instrument(skip(self))]
     pub async fn fetch_by_id_for_admin(
@@ -60,8 +96,12 @@ impl PublishedArticleService {
         PublishedArticleRepository::delete(&self.pool, article_id).await
     }
 
-    /// 公開記事を更新
+    /// 公開記事の内容と公開範囲を更新
+    ///
+    /// スラッグの重複と、パスワードなしでの `protected` への変更（新しいパスワードも設定済みの
+    /// パスワードもない場合）はどちらも書き込む前に検証し、エラーなら何も更新しない。
     #[instrument(skip(self))]
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         &self,
         article_id: Uuid,
@@ -70,6 +110,8 @@ impl PublishedArticleService {
         body: &str,
         description: Option<&str>,
         cover_image_url: Option<&str>,
+        seo: &PublishedArticleSeo,
+        visibility: &PublishedArticleVisibility,
     ) -> Result<(), CmsError> {
         // スラッグ重複チェック（自分自身は除外）
         if PublishedArticleQuery::exists_by_slug(&self.pool, slug.as_str(), Some(article_id))
@@ -79,6 +121,7 @@ impl PublishedArticleService {
                 "このスラッグは既に使用されています".to_string(),
             ));
         }
+        self.ensure_password(article_id, visibility).await?;
 
         let content = ArticleContent {
             title: title.as_str(),
@@ -86,8 +129,54 @@ impl PublishedArticleService {
             body,
             description,
             cover_image_url,
+            seo: seo.as_seo(),
         };
 
-        PublishedArticleRepository::update(&self.pool, article_id, &content, utc_now()).await
+        // 内容の更新に成功してから公開範囲を書き込む
+        PublishedArticleRepository::update(&self.pool, article_id, &content, utc_now()).await?;
+        PublishedArticleRepository::update_visibility(
+            &self.pool,
+            article_id,
+            visibility.visibility(),
+            visibility.password_hash(),
+        )
+        .await
+    }
+
+    /// 公開記事の公開範囲を更新
+    ///
+    /// `protected` にする場合、新しいパスワードも設定済みのパスワードもなければエラーにする。
+    #[instrument(skip(self))]
+    pub async fn update_visibility(
+        &self,
+        article_id: Uuid,
+        visibility: &PublishedArticleVisibility,
+    ) -> Result<(), CmsError> {
+        self.ensure_password(article_id, visibility).await?;
+
+        PublishedArticleRepository::update_visibility(
+            &self.pool,
+            article_id,
+            visibility.visibility(),
+            visibility.password_hash(),
+        )
+        .await
+    }
+
+    /// `protected` にする場合に、新しいパスワードか設定済みのパスワードがあるか検証
+    async fn ensure_password(
+        &self,
+        article_id: Uuid,
+        visibility: &PublishedArticleVisibility,
+    ) -> Result<(), CmsError> {
+        if visibility.visibility() == ArticleVisibility::Protected
+            && visibility.password_hash().is_none()
+            && !PublishedArticleQuery::has_password(&self.pool, article_id).await?
+        {
+            return Err(CmsError::ValidationError(
+                "パスワード保護する記事にはパスワードを設定してください".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,296 @@
+use crate::error::CmsError;
+use crate::models::{PublishedArticleWithCategories, RelatedArticleCandidate};
+use crate::queries::RelatedArticleQuery;
+use crate::repositories::RelatedArticleRepository;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::utc_now;
+
+/// 1記事あたりの関連記事の最大件数
+const RELATED_ARTICLE_LIMIT: usize = 4;
+/// 関連記事とみなす最低スコア
+const MIN_RELATED_SCORE: f64 = 0.1;
+/// 共通カテゴリ1件あたりのスコア
+const CATEGORY_WEIGHT: f64 = 1.0;
+/// タイトルの語の重なり（Jaccard係数）の重み
+const TITLE_WEIGHT: f64 = 1.5;
+/// 本文の語の重なり（Jaccard係数）の重み
+const BODY_WEIGHT: f64 = 2.0;
+/// 語の重なりの計算から除外する英単語（URLの断片など）
+const STOP_WORDS: &[&str] = &[
+    "http", "https", "www", "com", "org", "net", "jp", "io", "png", "jpg", "jpeg", "gif", "webp",
+    "the", "and", "for", "with", "from", "this", "that",
+];
+
+/// 関連記事サービス
+#[derive(Debug, Clone)]
+pub struct RelatedArticleService {
+    pool: PgPool,
+}
+
+impl RelatedArticleService {
+    #[instrument(skip(pool))]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定した記事の関連記事（公開済みのみ）を表示順に取得
+    #[instrument(skip(self))]
+    pub async fn fetch_for_article(
+        &self,
+        article_id: Uuid,
+    ) -> Result<Vec<PublishedArticleWithCategories>, CmsError> {
+        RelatedArticleQuery::fetch_for_article(&self.pool, article_id, utc_now()).await
+    }
+
+    /// 指定した記事を関連記事に含む記事のslugを取得
+    ///
+    /// 記事の削除や非公開化の前に呼び、関連記事の表示が変わる記事を知るために使う。
+    #[instrument(skip(self))]
+    pub async fn fetch_referring_slugs(&self, article_id: Uuid) -> Result<Vec<String>, CmsError> {
+        RelatedArticleQuery::fetch_referring_slugs(&self.pool, article_id).await
+    }
+
+    /// 全公開記事の関連記事を再計算して保存
+    ///
+    /// 戻り値は関連記事の表示が変わる記事のslug。関連記事の一覧が変わった記事に加えて、
+    /// `updated_article_id` の記事（タイトル等が変わりうる）を関連記事に含む記事も対象になる。
+    #[instrument(skip(self))]
+    pub async fn refresh(&self, updated_article_id: Uuid) -> Result<Vec<String>, CmsError> {
+        let candidates = RelatedArticleQuery::fetch_candidates(&self.pool).await?;
+        let rankings = rank_related_articles(&candidates);
+
+        let mut saved: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for related in RelatedArticleQuery::fetch_all(&self.pool).await? {
+            saved
+                .entry(related.article_id)
+                .or_default()
+                .push(related.related_article_id);
+        }
+
+        let mut affected_slugs = Vec::new();
+        for (candidate, ranked) in candidates.iter().zip(&rankings) {
+            let previous = saved
+                .get(&candidate.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if ranked.as_slice() != previous {
+                RelatedArticleRepository::replace_for_article(&self.pool, candidate.id, ranked)
+                    .await?;
+                affected_slugs.push(candidate.slug.clone());
+            } else if ranked.contains(&updated_article_id) {
+                affected_slugs.push(candidate.slug.clone());
+            }
+        }
+        Ok(affected_slugs)
+    }
+}
+
+/// スコア計算用に前処理した記事
+struct ArticleTerms {
+    category_ids: HashSet<Uuid>,
+    title: HashSet<String>,
+    body: HashSet<String>,
+}
+
+/// 記事ごとの関連記事IDをスコアの高い順に計算（戻り値は `candidates` と同じ並び）
+///
+/// スコアは共通カテゴリ数と、タイトル・本文それぞれの語の重なり（Jaccard係数）の重み付き和。
+/// 同点の場合は `candidates` の並び（公開日時の新しい順）を優先する。
+fn rank_related_articles(candidates: &[RelatedArticleCandidate]) -> Vec<Vec<Uuid>> {
+    let terms: Vec<ArticleTerms> = candidates
+        .iter()
+        .map(|candidate| ArticleTerms {
+            category_ids: candidate.category_ids.iter().copied().collect(),
+            title: extract_terms(&candidate.title),
+            body: extract_terms(&candidate.body),
+        })
+        .collect();
+
+    terms
+        .iter()
+        .enumerate()
+        .map(|(i, article)| {
+            let mut scored: Vec<(usize, f64)> = terms
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, other)| (j, related_score(article, other)))
+                .filter(|(_, score)| *score >= MIN_RELATED_SCORE)
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scored
+                .into_iter()
+                .take(RELATED_ARTICLE_LIMIT)
+                .map(|(j, _)| candidates[j].id)
+                .collect()
+        })
+        .collect()
+}
+
+/// 2記事間の関連スコア
+fn related_score(a: &ArticleTerms, b: &ArticleTerms) -> f64 {
+    let shared_categories = a.category_ids.intersection(&b.category_ids).count();
+    CATEGORY_WEIGHT * shared_categories as f64
+        + TITLE_WEIGHT * jaccard(&a.title, &b.title)
+        + BODY_WEIGHT * jaccard(&a.body, &b.body)
+}
+
+/// 2つの語集合のJaccard係数
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        return 0.0;
+    }
+    intersection as f64 / union as f64
+}
+
+/// 語の種類（連続する同種の文字を1つの語として扱う）
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TermKind {
+    /// 英数字（単語単位）
+    Ascii,
+    /// 漢字・カタカナ（文字bigram単位）
+    Cjk,
+}
+
+fn term_kind(c: char) -> Option<TermKind> {
+    match c {
+        c if c.is_ascii_alphanumeric() => Some(TermKind::Ascii),
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{30A0}'..='\u{30FF}' => {
+            Some(TermKind::Cjk)
+        }
+        _ => None,
+    }
+}
+
+/// テキストから語を抽出
+///
+/// 英数字は小文字化した2文字以上の単語（数字のみ・ストップワードを除く）、
+/// 漢字・カタカナは連続部分の文字bigramを語とする。ひらがなや記号は区切りとして扱う。
+fn extract_terms(text: &str) -> HashSet<String> {
+    let mut terms = HashSet::new();
+    let mut run = String::new();
+    let mut run_kind = None;
+
+    for c in text.chars().chain(std::iter::once(' ')) {
+        let kind = term_kind(c);
+        if kind != run_kind {
+            if let Some(run_kind) = run_kind {
+                push_terms(&run, run_kind, &mut terms);
+            }
+            run.clear();
+            run_kind = kind;
+        }
+        if kind.is_some() {
+            run.push(c);
+        }
+    }
+    terms
+}
+
+fn push_terms(run: &str, kind: TermKind, terms: &mut HashSet<String>) {
+    match kind {
+        TermKind::Ascii => {
+            let word = run.to_ascii_lowercase();
+            if word.len() >= 2
+                && !word.chars().all(|c| c.is_ascii_digit())
+                && !STOP_WORDS.contains(&word.as_str())
+            {
+                terms.insert(word);
+            }
+        }
+        TermKind::Cjk => {
+            let chars: Vec<char> = run.chars().collect();
+            for pair in chars.windows(2) {
+                terms.insert(pair.iter().collect());
+            }
+        }
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        create_test_category, insert_published_article, link_published_article_category,
+    };
+
+    fn candidate(title: &str, body: &str, category_ids: Vec<Uuid>) -> RelatedArticleCandidate {
+        RelatedArticleCandidate {
+            id: Uuid::now_v7(),
+            slug: title.to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
+            category_ids,
+        }
+    }
+
+    #[test]
+    fn extract_termsで英単語と漢字カタカナのbigramが抽出されること() {
+        let terms = extract_terms("Rustの非同期処理とTokio 2024 https://example.com");
+        let expected: HashSet<String> =
+            ["rust", "tokio", "example", "非同", "同期", "期処", "処理"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+        assert_eq!(terms, expected);
+    }
+
+    #[test]
+    fn rank_related_articlesで共通カテゴリと語の重なりが多い記事が上位になること() {
+        let category = Uuid::now_v7();
+        let base = candidate(
+            "Rustの非同期処理",
+            "Tokioで非同期処理を書く",
+            vec![category],
+        );
+        let same_topic = candidate("Rustの非同期ランタイム", "Tokioの使い方", vec![category]);
+        let same_category = candidate("料理の記録", "カレーを作った", vec![category]);
+        let unrelated = candidate("旅行記", "京都に行った", vec![]);
+        let candidates = vec![
+            base.clone(),
+            unrelated.clone(),
+            same_category.clone(),
+            same_topic.clone(),
+        ];
+
+        let rankings = rank_related_articles(&candidates);
+
+        assert_eq!(rankings[0], vec![same_topic.id, same_category.id]);
+        assert!(rankings[1].is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_refreshで関連記事が保存され表示が変わる記事のslugが返ること(
+        pool: PgPool,
+    ) {
+        let category_id = create_test_category(&pool, "Rust", "rust").await;
+        let a_id = insert_published_article(&pool, "a", "Rust入門", "Body", None, utc_now()).await;
+        let b_id = insert_published_article(&pool, "b", "Rust応用", "Body", None, utc_now()).await;
+        insert_published_article(&pool, "c", "旅行記", "京都", None, utc_now()).await;
+        link_published_article_category(&pool, a_id, category_id).await;
+        link_published_article_category(&pool, b_id, category_id).await;
+        let service = RelatedArticleService::new(pool);
+
+        let mut affected = service.refresh(a_id).await.expect("Failed to refresh");
+        affected.sort();
+        assert_eq!(affected, vec!["a", "b"]);
+
+        let related = service
+            .fetch_for_article(a_id)
+            .await
+            .expect("Failed to fetch related articles");
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].article.slug, "b");
+
+        // 一覧が変わらなくても、更新した記事を関連記事に含む記事は対象になる
+        let affected = service.refresh(a_id).await.expect("Failed to refresh");
+        assert_eq!(affected, vec!["b"]);
+    }
+}
@@ -102,6 +102,44 @@ pub async fn insert_published_article_with_created_at(
     .expect("Failed to insert published article")
 }
 
+/// テスト用公開記事の公開範囲を設定（`visibility` は `public` / `unlisted` / `protected`）
+pub async fn set_published_article_visibility(
+    pool: &PgPool,
+    article_id: Uuid,
+    visibility: &str,
+    password_hash: Option<&str>,
+) {
+    sqlx::query!(
+        r#"UPDATE published_articles SET visibility = $1::text::article_visibility, password_hash = $2 WHERE id = $3"#,
+        visibility,
+        password_hash,
+        article_id
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to set published article visibility");
+}
+
+/// テスト用おすすめ項目を作成
+pub async fn insert_featured_item(
+    pool: &PgPool,
+    article_id: Option<Uuid>,
+    external_url: Option<&str>,
+    position: i32,
+    expires_at: Option<NaiveDateTime>,
+) -> Uuid {
+    sqlx::query_scalar!(
+        r#"INSERT INTO featured_items (article_id, external_url, position, expires_at) VALUES ($1, $2, $3, $4) RETURNING id"#,
+        article_id,
+        external_url,
+        position,
+        expires_at as _
+    )
+    .fetch_one(pool)
+    .await
+    .expect("Failed to insert featured item")
+}
+
 /// 下書き記事とカテゴリを紐付け
 pub async fn link_draft_article_category(pool: &PgPool, article_id: Uuid, category_id: Uuid) {
     sqlx::query!(
@@ -173,3 +211,17 @@ pub async fn insert_test_image_with_dimensions(
     .await
     .expect("Failed to insert test image with dimensions")
 }
+
+/// テスト用OGPキャッシュを作成
+pub async fn insert_test_ogp_cache(pool: &PgPool, url: &str, title: Option<&str>) {
+    let now = utc_now();
+    sqlx::query!(
+        r#"INSERT INTO ogp_caches (url, title, fetched_at) VALUES ($1, $2, $3)"#,
+        url,
+        title,
+        now as _
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to insert test ogp cache");
+}
@@ -1,5 +1,11 @@
+mod featured_item_entry;
+mod published_article_seo;
 mod published_article_slug;
 mod published_article_title;
+mod published_article_visibility;
 
+pub use featured_item_entry::{FeaturedItemEntry, FeaturedItemTarget};
+pub use published_article_seo::PublishedArticleSeo;
 pub use published_article_slug::PublishedArticleSlug;
 pub use published_article_title::PublishedArticleTitle;
+pub use published_article_visibility::PublishedArticleVisibility;
@@ -0,0 +1,151 @@
+use chrono::NaiveDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::error::CmsError;
+
+/// 外部記事URLの最大長（`featured_items.external_url` の列長）
+const EXTERNAL_URL_MAX_LENGTH: usize = 512;
+
+/// おすすめに表示する記事
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FeaturedItemTarget {
+    /// ローカル記事（公開記事ID）
+    Article(Uuid),
+    /// 外部記事（Qiita / WordPress / Zenn / フィード）のURL
+    ExternalUrl(String),
+}
+
+/// 保存するおすすめの1項目（表示順は並び順で決まる）
+#[derive(Debug, Clone)]
+pub struct FeaturedItemEntry {
+    target: FeaturedItemTarget,
+    expires_at: Option<NaiveDateTime>,
+}
+
+impl FeaturedItemEntry {
+    /// ローカル記事かURLのどちらか一方を指定する。URLは前後の空白を除去する
+    #[instrument]
+    pub fn new(
+        article_id: Option<Uuid>,
+        external_url: Option<String>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<Self, CmsError> {
+        let external_url = external_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        let target = match (article_id, external_url) {
+            (Some(article_id), None) => FeaturedItemTarget::Article(article_id),
+            (None, Some(url)) => {
+                let valid = ["https://", "http://"].iter().any(|scheme| {
+                    url.strip_prefix(scheme)
+                        .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+                });
+                if !valid {
+                    return Err(CmsError::ValidationError(
+                        "おすすめの記事URLはhttp(s)から始まるURLを入力してください".to_string(),
+                    ));
+                }
+                if url.chars().count() > EXTERNAL_URL_MAX_LENGTH {
+                    return Err(CmsError::ValidationError(format!(
+                        "おすすめの記事URLは{}文字以内で入力してください",
+                        EXTERNAL_URL_MAX_LENGTH
+                    )));
+                }
+                FeaturedItemTarget::ExternalUrl(url)
+            }
+            _ => {
+                return Err(CmsError::ValidationError(
+                    "おすすめには記事かURLのどちらか一方を指定してください".to_string(),
+                ));
+            }
+        };
+        Ok(Self { target, expires_at })
+    }
+
+    pub fn target(&self) -> &FeaturedItemTarget {
+        &self.target
+    }
+
+    pub fn article_id(&self) -> Option<Uuid> {
+        match &self.target {
+            FeaturedItemTarget::Article(article_id) => Some(*article_id),
+            FeaturedItemTarget::ExternalUrl(_) => None,
+        }
+    }
+
+    pub fn external_url(&self) -> Option<&str> {
+        match &self.target {
+            FeaturedItemTarget::Article(_) => None,
+            FeaturedItemTarget::ExternalUrl(url) => Some(url),
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<NaiveDateTime> {
+        self.expires_at
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_記事idを指定して作成できること() {
+        let article_id = Uuid::from_u128(1);
+
+        let entry = FeaturedItemEntry::new(Some(article_id), Some(" ".to_string()), None).unwrap();
+
+        assert_eq!(entry.target(), &FeaturedItemTarget::Article(article_id));
+        assert_eq!(entry.article_id(), Some(article_id));
+        assert_eq!(entry.external_url(), None);
+    }
+
+    #[test]
+    fn test_urlは前後の空白を除去して保持すること() {
+        let entry = FeaturedItemEntry::new(
+            None,
+            Some(" https://qiita.com/Romira/items/abc ".to_string()),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            entry.external_url(),
+            Some("https://qiita.com/Romira/items/abc")
+        );
+    }
+
+    #[test]
+    fn test_記事とurlの両方または両方なしはエラーになること() {
+        let both = FeaturedItemEntry::new(
+            Some(Uuid::from_u128(1)),
+            Some("https://example.com".to_string()),
+            None,
+        );
+        let neither = FeaturedItemEntry::new(None, None, None);
+
+        assert!(matches!(both, Err(CmsError::ValidationError(_))));
+        assert!(matches!(neither, Err(CmsError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_http以外のurlはエラーになること() {
+        let result = FeaturedItemEntry::new(None, Some("qiita.com/items/abc".to_string()), None);
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_長すぎるurlはエラーになること() {
+        let url = format!(
+            "https://example.com/{}",
+            "a".repeat(EXTERNAL_URL_MAX_LENGTH)
+        );
+
+        let result = FeaturedItemEntry::new(None, Some(url), None);
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+}
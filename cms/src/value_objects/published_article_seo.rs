@@ -0,0 +1,213 @@
+use tracing::instrument;
+
+use crate::error::CmsError;
+use crate::models::ArticleSeo;
+
+/// robotsメタタグに指定できるディレクティブ
+const ROBOTS_DIRECTIVES: [&str; 7] = [
+    "index",
+    "noindex",
+    "follow",
+    "nofollow",
+    "none",
+    "noarchive",
+    "nosnippet",
+];
+
+/// Twitter Cardの種類
+const TWITTER_CARDS: [&str; 2] = ["summary", "summary_large_image"];
+
+/// 記事のSEO設定（公開時に検証する）
+///
+/// 前後の空白を除去し、空の項目は未設定として扱う。
+#[derive(Debug, Clone)]
+pub struct PublishedArticleSeo(ArticleSeo);
+
+impl PublishedArticleSeo {
+    #[instrument]
+    pub fn new(value: ArticleSeo) -> Result<Self, CmsError> {
+        const OG_TITLE_MAX_LENGTH: usize = 200;
+        const OG_DESCRIPTION_MAX_LENGTH: usize = 300;
+
+        let canonical_url = normalize(value.canonical_url);
+        if let Some(url) = &canonical_url {
+            validate_url(url, "canonical URL")?;
+        }
+        let og_image_url = normalize(value.og_image_url);
+        if let Some(url) = &og_image_url {
+            validate_url(url, "OGP画像URL")?;
+        }
+
+        let robots = normalize(value.robots)
+            .map(|robots| {
+                let directives = robots
+                    .split(',')
+                    .map(|directive| directive.trim().to_ascii_lowercase())
+                    .filter(|directive| !directive.is_empty())
+                    .collect::<Vec<_>>();
+                match directives
+                    .iter()
+                    .find(|directive| !ROBOTS_DIRECTIVES.contains(&directive.as_str()))
+                {
+                    Some(invalid) => Err(CmsError::ValidationError(format!(
+                        "robotsに指定できない値です: {}",
+                        invalid
+                    ))),
+                    None => Ok(directives.join(", ")),
+                }
+            })
+            .transpose()?
+            .filter(|robots| !robots.is_empty());
+
+        let og_title = normalize(value.og_title);
+        if og_title
+            .as_ref()
+            .is_some_and(|title| title.chars().count() > OG_TITLE_MAX_LENGTH)
+        {
+            return Err(CmsError::ValidationError(format!(
+                "OGPタイトルは{}文字以内で入力してください",
+                OG_TITLE_MAX_LENGTH
+            )));
+        }
+        let og_description = normalize(value.og_description);
+        if og_description
+            .as_ref()
+            .is_some_and(|description| description.chars().count() > OG_DESCRIPTION_MAX_LENGTH)
+        {
+            return Err(CmsError::ValidationError(format!(
+                "OGP説明は{}文字以内で入力してください",
+                OG_DESCRIPTION_MAX_LENGTH
+            )));
+        }
+
+        let twitter_card = normalize(value.twitter_card);
+        if let Some(card) = &twitter_card
+            && !TWITTER_CARDS.contains(&card.as_str())
+        {
+            return Err(CmsError::ValidationError(format!(
+                "Twitter Cardは {} のいずれかを指定してください",
+                TWITTER_CARDS.join(" / ")
+            )));
+        }
+
+        Ok(Self(ArticleSeo {
+            canonical_url,
+            robots,
+            og_title,
+            og_description,
+            og_image_url,
+            twitter_card,
+        }))
+    }
+
+    pub fn as_seo(&self) -> &ArticleSeo {
+        &self.0
+    }
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn validate_url(url: &str, label: &str) -> Result<(), CmsError> {
+    let valid = ["https://", "http://"].iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(CmsError::ValidationError(format!(
+            "{}はhttp(s)から始まるURLを入力してください",
+            label
+        )))
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_空白のみの項目は未設定になること() {
+        let seo = PublishedArticleSeo::new(ArticleSeo {
+            canonical_url: Some("  ".to_string()),
+            og_title: Some("".to_string()),
+            robots: Some(" , ".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(seo.as_seo(), &ArticleSeo::default());
+    }
+
+    #[test]
+    fn test_robotsは小文字に揃えて区切りを正規化すること() {
+        let seo = PublishedArticleSeo::new(ArticleSeo {
+            robots: Some("NoIndex,nofollow ".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(seo.as_seo().robots.as_deref(), Some("noindex, nofollow"));
+        assert!(seo.as_seo().is_noindex());
+    }
+
+    #[test]
+    fn test_不明なrobotsディレクティブはエラーになること() {
+        let result = PublishedArticleSeo::new(ArticleSeo {
+            robots: Some("noindex, hoge".to_string()),
+            ..Default::default()
+        });
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_httpから始まらないurlはエラーになること() {
+        let canonical = PublishedArticleSeo::new(ArticleSeo {
+            canonical_url: Some("qiita.com/Romira/items/abc".to_string()),
+            ..Default::default()
+        });
+        let og_image = PublishedArticleSeo::new(ArticleSeo {
+            og_image_url: Some("javascript:alert(1)".to_string()),
+            ..Default::default()
+        });
+
+        assert!(canonical.is_err());
+        assert!(og_image.is_err());
+    }
+
+    #[test]
+    fn test_未対応のtwitter_cardはエラーになること() {
+        let valid = PublishedArticleSeo::new(ArticleSeo {
+            twitter_card: Some("summary".to_string()),
+            ..Default::default()
+        });
+        let invalid = PublishedArticleSeo::new(ArticleSeo {
+            twitter_card: Some("player".to_string()),
+            ..Default::default()
+        });
+
+        assert!(valid.is_ok());
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_ogpタイトルは200文字まで有効() {
+        let valid = PublishedArticleSeo::new(ArticleSeo {
+            og_title: Some("あ".repeat(200)),
+            ..Default::default()
+        });
+        let invalid = PublishedArticleSeo::new(ArticleSeo {
+            og_title: Some("あ".repeat(201)),
+            ..Default::default()
+        });
+
+        assert!(valid.is_ok());
+        assert!(invalid.is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use tracing::instrument;
+
+use crate::error::CmsError;
+use crate::models::ArticleVisibility;
+
+/// 公開記事の公開範囲（保存時に検証する）
+///
+/// `protected` で新しいパスワードが指定された場合はArgon2でハッシュ化して保持する。
+/// パスワードを指定しない場合は既存のパスワードを引き継ぐ。
+#[derive(Clone)]
+pub struct PublishedArticleVisibility {
+    visibility: ArticleVisibility,
+    password_hash: Option<String>,
+}
+
+impl std::fmt::Debug for PublishedArticleVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishedArticleVisibility")
+            .field("visibility", &self.visibility)
+            .field("password_changed", &self.password_hash.is_some())
+            .finish()
+    }
+}
+
+impl PublishedArticleVisibility {
+    #[instrument(skip(password))]
+    pub fn new(visibility: ArticleVisibility, password: Option<String>) -> Result<Self, CmsError> {
+        const PASSWORD_MIN_LENGTH: usize = 4;
+        const PASSWORD_MAX_LENGTH: usize = 128;
+
+        let password = password.filter(|password| !password.is_empty());
+        let password_hash = match (visibility, password) {
+            (ArticleVisibility::Protected, Some(password)) => {
+                let length = password.chars().count();
+                if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+                    return Err(CmsError::ValidationError(format!(
+                        "パスワードは{}文字以上{}文字以内で入力してください",
+                        PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH
+                    )));
+                }
+                let salt = SaltString::generate(&mut OsRng);
+                let hash = Argon2::default()
+                    .hash_password(password.as_bytes(), &salt)
+                    .map_err(|e| CmsError::ValidationError(e.to_string()))?;
+                Some(hash.to_string())
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            visibility,
+            password_hash,
+        })
+    }
+
+    pub fn visibility(&self) -> ArticleVisibility {
+        self.visibility
+    }
+
+    /// 新しく設定するパスワードのハッシュ（変更しない場合は `None`）
+    pub fn password_hash(&self) -> Option<&str> {
+        self.password_hash.as_deref()
+    }
+
+    /// パスワードが保存済みのハッシュと一致するか
+    pub fn verify_password(password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    }
+}
+
+//noinspection NonAsciiCharacters
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protectedでパスワードを指定するとハッシュ化されること() {
+        let visibility = PublishedArticleVisibility::new(
+            ArticleVisibility::Protected,
+            Some("event-2026".to_string()),
+        )
+        .unwrap();
+
+        let hash = visibility.password_hash().unwrap();
+        assert_ne!(hash, "event-2026");
+        assert!(PublishedArticleVisibility::verify_password(
+            "event-2026",
+            hash
+        ));
+        assert!(!PublishedArticleVisibility::verify_password("wrong", hash));
+    }
+
+    #[test]
+    fn test_protected以外ではパスワードを保持しないこと() {
+        let visibility = PublishedArticleVisibility::new(
+            ArticleVisibility::Unlisted,
+            Some("event-2026".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(visibility.visibility(), ArticleVisibility::Unlisted);
+        assert!(visibility.password_hash().is_none());
+    }
+
+    #[test]
+    fn test_空のパスワードは変更なしとして扱うこと() {
+        let visibility =
+            PublishedArticleVisibility::new(ArticleVisibility::Protected, Some(String::new()))
+                .unwrap();
+
+        assert!(visibility.password_hash().is_none());
+    }
+
+    #[test]
+    fn test_短すぎるパスワードはエラーになること() {
+        let result =
+            PublishedArticleVisibility::new(ArticleVisibility::Protected, Some("abc".to_string()));
+
+        assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_不正な形式のハッシュでは一致しないこと() {
+        assert!(!PublishedArticleVisibility::verify_password(
+            "password",
+            "not-a-hash"
+        ));
+    }
+}
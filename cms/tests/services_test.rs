@@ -5,7 +5,10 @@
 use blog_romira_dev_cms::error::CmsError;
 use blog_romira_dev_cms::services::{DraftArticleService, PublishedArticleService};
 use blog_romira_dev_cms::test_utils::*;
-use blog_romira_dev_cms::{ArticleContent, PublishedArticleSlug, PublishedArticleTitle};
+use blog_romira_dev_cms::{
+    ArticleContent, ArticleSeo, ArticleVisibility, PublishedArticleSeo, PublishedArticleSlug,
+    PublishedArticleTitle, PublishedArticleVisibility,
+};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -28,6 +31,7 @@ async fn test_下書き公開で公開記事が作成されカテゴリがコピ
         body: "Draft Body",
         description: Some("Draft Desc"),
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     service
         .save(draft_id, &content)
@@ -71,6 +75,82 @@ async fn test_下書き公開で公開記事が作成されカテゴリがコピ
     assert!(draft_after.is_none());
 }
 
+//noinspection NonAsciiCharacters
+/// 下書きのSEO設定は公開時に検証・正規化されて公開記事に引き継がれることを確認
+#[sqlx::test]
+async fn test_下書き公開でseo設定が正規化されて引き継がれること(
+    pool: PgPool,
+) {
+    let service = DraftArticleService::new(pool.clone());
+
+    let draft_id = Uuid::now_v7();
+    let seo = ArticleSeo {
+        canonical_url: Some(" https://qiita.com/Romira/items/abc ".to_string()),
+        robots: Some("NoIndex".to_string()),
+        og_title: Some("".to_string()),
+        ..Default::default()
+    };
+    let content = ArticleContent {
+        title: "SEO",
+        slug: "seo-article",
+        body: "Body",
+        description: None,
+        cover_image_url: None,
+        seo: &seo,
+    };
+    service
+        .save(draft_id, &content)
+        .await
+        .expect("Failed to create draft");
+
+    let published_id = service.publish(draft_id).await.expect("Failed to publish");
+
+    let published = PublishedArticleService::new(pool)
+        .fetch_by_id_for_admin(published_id)
+        .await
+        .expect("Failed to fetch")
+        .expect("Article not found");
+    assert_eq!(
+        published.article.seo.0,
+        ArticleSeo {
+            canonical_url: Some("https://qiita.com/Romira/items/abc".to_string()),
+            robots: Some("noindex".to_string()),
+            ..Default::default()
+        }
+    );
+    assert!(published.article.seo.is_noindex());
+}
+
+//noinspection NonAsciiCharacters
+/// 下書き保存では検証しないが、不正なSEO設定のままでは公開できないことを確認
+#[sqlx::test]
+async fn test_不正なseo設定の下書きは公開できないこと(pool: PgPool) {
+    let service = DraftArticleService::new(pool);
+
+    let draft_id = Uuid::now_v7();
+    let seo = ArticleSeo {
+        twitter_card: Some("player".to_string()),
+        ..Default::default()
+    };
+    let content = ArticleContent {
+        title: "SEO",
+        slug: "invalid-seo",
+        body: "Body",
+        description: None,
+        cover_image_url: None,
+        seo: &seo,
+    };
+    service
+        .save(draft_id, &content)
+        .await
+        .expect("下書き保存は検証しない");
+
+    let result = service.publish(draft_id).await;
+
+    assert!(matches!(result, Err(CmsError::ValidationError(_))));
+    assert!(service.fetch_by_id(draft_id).await.unwrap().is_some());
+}
+
 //noinspection NonAsciiCharacters
 #[sqlx::test]
 async fn test_存在しない下書きを公開するとnotfoundエラーになること(
@@ -107,6 +187,7 @@ async fn test_空スラッグの下書きを公開するとvalidationエラー
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     service
         .save(draft_id, &content)
@@ -139,6 +220,7 @@ async fn test_重複スラッグの下書きを公開するとvalidationエラ
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     service
         .save(first_draft_id, &content)
@@ -157,6 +239,7 @@ async fn test_重複スラッグの下書きを公開するとvalidationエラ
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     service
         .save(second_draft_id, &content)
@@ -188,6 +271,7 @@ async fn test_公開記事を同じスラッグで更新すると成功するこ
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     draft_service
         .save(draft_id, &content)
@@ -201,8 +285,19 @@ async fn test_公開記事を同じスラッグで更新すると成功するこ
     // 同じスラッグで更新
     let title = PublishedArticleTitle::new("Updated Title".to_string()).unwrap();
     let slug = PublishedArticleSlug::new("same-slug".to_string()).unwrap();
+    let seo = PublishedArticleSeo::new(ArticleSeo::default()).unwrap();
+    let visibility = PublishedArticleVisibility::new(ArticleVisibility::Public, None).unwrap();
     let result = published_service
-        .update(published_id, &title, &slug, "Updated Body", None, None)
+        .update(
+            published_id,
+            &title,
+            &slug,
+            "Updated Body",
+            None,
+            None,
+            &seo,
+            &visibility,
+        )
         .await;
 
     assert!(result.is_ok());
@@ -224,6 +319,7 @@ async fn test_公開記事を他の記事と重複するスラッグで更新す
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     draft_service
         .save(draft1_id, &content)
@@ -241,6 +337,7 @@ async fn test_公開記事を他の記事と重複するスラッグで更新す
         body: "Body",
         description: None,
         cover_image_url: None,
+        seo: &ArticleSeo::default(),
     };
     draft_service
         .save(draft2_id, &content)
@@ -254,9 +351,100 @@ async fn test_公開記事を他の記事と重複するスラッグで更新す
     // 2番目の記事を1番目と同じスラッグで更新しようとする
     let title = PublishedArticleTitle::new("Updated".to_string()).unwrap();
     let slug = PublishedArticleSlug::new("first-slug".to_string()).unwrap();
+    let seo = PublishedArticleSeo::new(ArticleSeo::default()).unwrap();
+    let visibility = PublishedArticleVisibility::new(ArticleVisibility::Public, None).unwrap();
     let result = published_service
-        .update(second_published_id, &title, &slug, "Body", None, None)
+        .update(
+            second_published_id,
+            &title,
+            &slug,
+            "Body",
+            None,
+            None,
+            &seo,
+            &visibility,
+        )
         .await;
 
     assert!(matches!(result, Err(CmsError::ValidationError(_))));
 }
+
+//noinspection NonAsciiCharacters
+/// 公開範囲を変更するワークフローのテスト
+/// - パスワードなしでは保護できない → パスワードを設定して保護 → パスワードを変えずに再保存 → 公開に戻す
+#[sqlx::test]
+async fn test_公開範囲の変更でパスワードが設定済みの場合のみ保護でき公開に戻すと削除されること(
+    pool: PgPool,
+) {
+    let published_service = PublishedArticleService::new(pool.clone());
+    let article_id = insert_published_article(
+        &pool,
+        "event-article",
+        "Event",
+        "Body",
+        None,
+        utc_now() - chrono::Duration::hours(1),
+    )
+    .await;
+
+    // パスワード未設定のまま保護しようとする
+    let without_password =
+        PublishedArticleVisibility::new(ArticleVisibility::Protected, None).unwrap();
+    let result = published_service
+        .update_visibility(article_id, &without_password)
+        .await;
+    assert!(matches!(result, Err(CmsError::ValidationError(_))));
+
+    // パスワードを設定して保護
+    let with_password = PublishedArticleVisibility::new(
+        ArticleVisibility::Protected,
+        Some("event-2026".to_string()),
+    )
+    .unwrap();
+    published_service
+        .update_visibility(article_id, &with_password)
+        .await
+        .expect("Failed to protect article");
+
+    // パスワードを指定せずに再保存しても既存のパスワードが引き継がれる
+    published_service
+        .update_visibility(article_id, &without_password)
+        .await
+        .expect("Failed to keep password");
+    let hash = published_service
+        .fetch_password_hash("event-article")
+        .await
+        .expect("Failed to fetch password hash")
+        .expect("Password hash not found");
+    assert!(PublishedArticleVisibility::verify_password(
+        "event-2026",
+        &hash
+    ));
+    assert!(
+        published_service
+            .fetch_all()
+            .await
+            .expect("Failed to fetch all")
+            .is_empty()
+    );
+
+    // 公開に戻すとパスワードは削除される
+    let public = PublishedArticleVisibility::new(ArticleVisibility::Public, None).unwrap();
+    published_service
+        .update_visibility(article_id, &public)
+        .await
+        .expect("Failed to make article public");
+    let article = published_service
+        .fetch_by_slug("event-article")
+        .await
+        .expect("Failed to fetch")
+        .expect("Article not found");
+    assert_eq!(article.article.visibility, ArticleVisibility::Public);
+    assert!(
+        published_service
+            .fetch_password_hash("event-article")
+            .await
+            .expect("Failed to fetch password hash")
+            .is_none()
+    );
+}
@@ -0,0 +1,13 @@
+-- リンクカード用OGPメタデータのキャッシュテーブル
+-- 記事の描画時はこのテーブルのみを参照し、外部サイトへの取得はバックグラウンドで行う
+-- 取得に失敗したURLもメタデータ空で保存し、fetched_at を基準に再取得する
+-- タイムスタンプはUTCで保存
+CREATE TABLE ogp_caches (
+    url TEXT PRIMARY KEY,
+    title TEXT,
+    description TEXT,
+    image_url TEXT,
+    favicon_url TEXT,
+    site_name TEXT,
+    fetched_at TIMESTAMP DEFAULT (now() AT TIME ZONE 'UTC') NOT NULL
+);
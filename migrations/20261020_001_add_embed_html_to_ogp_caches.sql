@@ -0,0 +1,3 @@
+-- oEmbedで取得した埋め込みHTMLを保存するカラムを追加
+-- 埋め込みプロバイダーに該当するURLのみ値を持つ
+ALTER TABLE ogp_caches ADD COLUMN embed_html TEXT;
@@ -0,0 +1,8 @@
+-- 管理設定: 文章チェックのルールごとの有効/無効
+-- 行が無いルールは有効として扱う
+-- タイムスタンプはUTCで保存
+CREATE TABLE prose_check_rules (
+    rule TEXT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMP DEFAULT (now() AT TIME ZONE 'UTC') NOT NULL
+);
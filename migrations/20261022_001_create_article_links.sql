@@ -0,0 +1,10 @@
+-- 記事間リンク（本文中の内部リンク・ウィキリンクの参照先）
+-- 公開記事の保存・公開のたびに参照元ごとに洗い替える
+-- 参照先は未公開・未作成の記事も指せるようslugで保持する
+CREATE TABLE article_links (
+    source_article_id UUID REFERENCES published_articles(id) ON DELETE CASCADE,
+    target_slug VARCHAR(255) NOT NULL,
+    PRIMARY KEY (source_article_id, target_slug)
+);
+
+CREATE INDEX idx_article_links_target_slug ON article_links(target_slug);
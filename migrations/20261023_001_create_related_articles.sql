@@ -0,0 +1,8 @@
+-- 関連記事（公開記事の保存・公開時に事前計算する）
+-- rank は1始まりの表示順
+CREATE TABLE related_articles (
+    article_id UUID REFERENCES published_articles(id) ON DELETE CASCADE,
+    related_article_id UUID REFERENCES published_articles(id) ON DELETE CASCADE,
+    rank INTEGER NOT NULL,
+    PRIMARY KEY (article_id, related_article_id)
+);
@@ -0,0 +1,8 @@
+-- 自動生成したOGP画像（PNG）のキャッシュテーブル
+-- タイトル・カテゴリなど画像の内容から計算したハッシュをキーにし、内容が変わらない限り再生成しない
+-- タイムスタンプはUTCで保存
+CREATE TABLE og_image_caches (
+    content_hash VARCHAR(64) PRIMARY KEY,
+    png BYTEA NOT NULL,
+    created_at TIMESTAMP DEFAULT (now() AT TIME ZONE 'UTC') NOT NULL
+);
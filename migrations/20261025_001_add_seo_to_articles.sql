@@ -0,0 +1,4 @@
+-- 記事ごとのSEO設定（canonical URL・robots・OGP/Twitter Cardの上書き）を保存するカラムを追加
+-- 未設定の項目はキーごと省略し、記事の内容から決める
+ALTER TABLE published_articles ADD COLUMN seo JSONB NOT NULL DEFAULT '{}'::jsonb;
+ALTER TABLE draft_articles ADD COLUMN seo JSONB NOT NULL DEFAULT '{}'::jsonb;
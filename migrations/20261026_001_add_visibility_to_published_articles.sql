@@ -0,0 +1,7 @@
+-- 公開記事の公開範囲（public: 一覧に表示 / unlisted: URLを知っている人のみ / protected: パスワード保護）
+-- password_hash は protected のときのみ設定する（Argon2のPHC文字列）
+CREATE TYPE article_visibility AS ENUM ('public', 'unlisted', 'protected');
+
+ALTER TABLE published_articles
+    ADD COLUMN visibility article_visibility NOT NULL DEFAULT 'public',
+    ADD COLUMN password_hash VARCHAR(255);
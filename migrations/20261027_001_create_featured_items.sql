@@ -0,0 +1,15 @@
+-- トップページの「おすすめ」に固定表示する記事
+-- ローカル記事（article_id）か外部記事のURL（external_url）のどちらか一方を持つ
+-- positionは1始まりの表示順、expires_atを過ぎた項目は表示しない
+-- タイムスタンプはUTCで保存
+CREATE TABLE featured_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    article_id UUID REFERENCES published_articles(id) ON DELETE CASCADE,
+    external_url VARCHAR(512),
+    position INTEGER NOT NULL,
+    expires_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (now() AT TIME ZONE 'UTC') NOT NULL,
+    CONSTRAINT featured_items_target CHECK ((article_id IS NULL) <> (external_url IS NULL))
+);
+
+CREATE INDEX idx_featured_items_position ON featured_items(position);
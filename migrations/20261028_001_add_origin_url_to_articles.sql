@@ -0,0 +1,7 @@
+-- 外部サービス（Qiitaなど）から取り込んだ記事の取り込み元URL
+-- 正規URLの設定や、外部記事一覧との重複排除に使う
+ALTER TABLE draft_articles ADD COLUMN origin_url VARCHAR(512);
+ALTER TABLE published_articles ADD COLUMN origin_url VARCHAR(512);
+
+CREATE UNIQUE INDEX idx_draft_articles_origin_url ON draft_articles (origin_url);
+CREATE UNIQUE INDEX idx_published_articles_origin_url ON published_articles (origin_url);
@@ -0,0 +1,14 @@
+-- 公開記事の転載先（Qiitaなど）への反映状況
+-- remote_id / remote_url は転載先で作成した記事（2回目以降はこの記事を更新する）
+-- last_error は直近の反映が失敗した場合のみ設定する
+-- タイムスタンプはUTCで保存
+CREATE TABLE article_syndications (
+    article_id UUID NOT NULL REFERENCES published_articles(id) ON DELETE CASCADE,
+    target VARCHAR(32) NOT NULL,
+    remote_id VARCHAR(255),
+    remote_url VARCHAR(512),
+    last_error TEXT,
+    synced_at TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT (now() AT TIME ZONE 'UTC') NOT NULL,
+    PRIMARY KEY (article_id, target)
+);
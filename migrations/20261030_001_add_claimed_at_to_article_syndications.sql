@@ -0,0 +1,3 @@
+-- 転載先への反映中の印（同じ記事を同時に転載先へ投稿しないよう、反映の前に立てて反映の記録で消す）
+-- 反映中にプロセスが止まった場合に備え、一定時間を過ぎた印は無視する
+ALTER TABLE article_syndications ADD COLUMN claimed_at TIMESTAMP;
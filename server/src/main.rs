@@ -57,7 +57,8 @@ async fn main() {
         .wait_for_connect()
         .await
         .expect("Failed to connect to Valkey");
-    let session_store = RedisStore::new(valkey_pool);
+    // セッションストアと外部記事キャッシュで同じプールを使う
+    let session_store = RedisStore::new(valkey_pool.clone());
     let session_layer = SessionManagerLayer::new(session_store)
         .with_secure(true)
         .with_same_site(tower_sessions::cookie::SameSite::Lax)
@@ -68,7 +69,8 @@ async fn main() {
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
-    let app_state = AppState::new(leptos_options.clone(), db_pool);
+    let app_state = AppState::new(leptos_options.clone(), db_pool, valkey_pool);
+    app_state.spawn_background_tasks();
     // Generate the list of routes in your Leptos App
     let routes = generate_route_list(App);
 